@@ -0,0 +1,160 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![warn(clippy::all)]
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use criterion::{
+    black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput,
+};
+use sqlparser::dialect::GenericDialect;
+use sqlparser::parser::Parser;
+use sqlparser::tokenizer::Tokenizer;
+
+/// Counts the allocations made, so that the benchmarks can report them along
+/// with the time taken
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Return the number of allocations made by `f`
+fn count_allocations<T>(f: impl FnOnce() -> T) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    black_box(f());
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+const SIMPLE_SELECT: &str = "SELECT * FROM table WHERE 1 = 1";
+
+const COMPLEX_SELECT: &str = "
+    WITH derived AS (
+        SELECT MAX(a) AS max_a,
+               COUNT(b) AS b_num,
+               user_id
+        FROM TABLE
+        GROUP BY user_id
+    )
+    SELECT * FROM table
+    LEFT JOIN derived USING (user_id)
+    -- Only the interesting rows
+    WHERE name = 'O''Brien' AND kind IN ('a', 'b', 'c')
+    ORDER BY created_at DESC
+    LIMIT 100
+";
+
+/// Generate a query with `n` columns, joins, conditions and list items, which
+/// exercises the lookahead of the parser
+fn large_query(n: usize) -> String {
+    let columns: Vec<_> = (0..n)
+        .map(|i| format!("t{}.col_{} AS c{}", i % 10, i, i))
+        .collect();
+    let joins: Vec<_> = (1..10)
+        .map(|i| format!("LEFT JOIN table_{} AS t{} ON t{}.id = t0.id", i, i, i))
+        .collect();
+    let conditions: Vec<_> = (0..n)
+        .map(|i| {
+            format!(
+                "(t{}.col_{} + {} * 2 >= {} OR t0.name LIKE 'x{}%')",
+                i % 10,
+                i,
+                i,
+                i,
+                i
+            )
+        })
+        .collect();
+    let items: Vec<_> = (0..n).map(|i| format!("'item_{}'", i)).collect();
+    format!(
+        "SELECT {} FROM table_0 AS t0 {} WHERE {} AND t0.kind IN ({}) ORDER BY c0, c1 DESC LIMIT 10",
+        columns.join(", "),
+        joins.join(" "),
+        conditions.join(" AND "),
+        items.join(", "),
+    )
+}
+
+fn tokenize(c: &mut Criterion) {
+    let dialect = GenericDialect {};
+    for (name, sql) in &[("simple", SIMPLE_SELECT), ("complex", COMPLEX_SELECT)] {
+        let tokenize = || Tokenizer::new(&dialect, sql).tokenize_with_span().unwrap();
+        println!(
+            "tokenize {}: {} tokens, {} allocations",
+            name,
+            tokenize().len(),
+            count_allocations(tokenize)
+        );
+        c.bench_function(&format!("tokenize {}", name), |b| b.iter(tokenize));
+    }
+}
+
+fn parse(c: &mut Criterion) {
+    let dialect = GenericDialect {};
+    for (name, sql) in &[("simple", SIMPLE_SELECT), ("complex", COMPLEX_SELECT)] {
+        let parse = || Parser::parse_sql(&dialect, sql.to_string()).unwrap();
+        println!("parse {}: {} allocations", name, count_allocations(parse));
+        c.bench_function(&format!("parse {}", name), |b| b.iter(parse));
+    }
+}
+
+fn parse_large(c: &mut Criterion) {
+    let dialect = GenericDialect {};
+    let mut group = c.benchmark_group("parse large");
+    for n in &[100, 1000] {
+        let sql = large_query(*n);
+        let tokens = Tokenizer::new(&dialect, &sql).tokenize_with_span().unwrap();
+        // Only count the allocations made by the parser itself
+        let parse = || {
+            Parser::new_with_spans(tokens.clone(), &dialect)
+                .parse_statements()
+                .unwrap()
+        };
+        println!(
+            "parse large {}: {} tokens, {} allocations",
+            n,
+            tokens.len(),
+            count_allocations(parse) - count_allocations(|| tokens.clone())
+        );
+        group.throughput(Throughput::Bytes(sql.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(n), &tokens, |b, tokens| {
+            b.iter_batched(
+                || tokens.clone(),
+                |tokens| Parser::new_with_spans(tokens, &dialect).parse_statements(),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, tokenize, parse, parse_large);
+criterion_main!(benches);
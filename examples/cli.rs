@@ -12,10 +12,9 @@
 
 #![warn(clippy::all)]
 
-use simple_logger;
+//! A small command-line app to run the parser.
+//! Run with `cargo run --example cli`
 
-///! A small command-line app to run the parser.
-/// Run with `cargo run --example cli`
 use std::fs;
 
 use sqlparser::dialect::*;
@@ -40,7 +39,7 @@ fn main() {
     println!("Parsing from file '{}' using {:?}", &filename, dialect);
     let contents = fs::read_to_string(&filename)
         .unwrap_or_else(|_| panic!("Unable to read the file {}", &filename));
-    let without_bom = if contents.chars().nth(0).unwrap() as u64 != 0xfeff {
+    let without_bom = if contents.chars().next().unwrap() as u64 != 0xfeff {
         contents.as_str()
     } else {
         let mut chars = contents.chars();
@@ -0,0 +1,1727 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An in-memory catalog of the objects created by DDL statements
+//!
+//! A [`Catalog`] starts empty, and [`Catalog::apply`] updates it with each
+//! `CREATE`, `ALTER TABLE` and `DROP` statement, checking the statement
+//! against the objects that exist as a database would, e.g. that a view only
+//! refers to existing tables and columns, or that a table that other objects
+//! depend on is only dropped with `CASCADE`. This allows checking a migration
+//! script without a database, and the catalog is a [`TableCatalog`] that
+//! provides the columns of its tables and views to [`resolve`].
+//!
+//! The constraints of a table are all kept as `TableConstraint`s, including
+//! those declared as options of its columns, and are named as PostgreSQL names
+//! them when the statement that creates them does not (see
+//! [`default_constraint_name`]).
+//!
+//! [`resolve`]: super::resolve
+
+use std::error::Error;
+use std::fmt;
+
+use super::{ident_eq, object_name_eq, references, resolve, Relation, TableCatalog};
+use crate::ast::visit::{self, Visit};
+use crate::ast::{
+    AlterColumnOperation, AlterTableOperation, ColumnDef, ColumnOption, ColumnOptionDef, Expr,
+    Ident, ObjectName, ObjectType, Query, Select, SelectItem, SourceSchema, SqlOption, Statement,
+    TableConstraint, TableFactor,
+};
+
+/// The objects of a database, as created by DDL statements
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Catalog {
+    /// The objects, in the order in which they were created
+    objects: Vec<CatalogObject>,
+}
+
+/// An object of a [`Catalog`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CatalogObject {
+    /// The name of the object, as it was created
+    pub name: ObjectName,
+    pub kind: ObjectKind,
+    /// The names of the other objects that this object refers to, which
+    /// cannot be dropped without `CASCADE` while it exists
+    pub dependencies: Vec<ObjectName>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ObjectKind {
+    Table(Table),
+    View(View),
+    Source(Source),
+    Sink(Sink),
+    Index(Index),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Table {
+    /// The columns of the table, whose options are only `NULL`, `NOT NULL`
+    /// and `DEFAULT`, the others being constraints
+    pub columns: Vec<ColumnDef>,
+    /// The constraints of the table, which all have a name
+    pub constraints: Vec<TableConstraint>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct View {
+    /// The names of the columns of the view, if they are known
+    pub columns: Option<Vec<Ident>>,
+    pub query: Box<Query>,
+    pub materialized: bool,
+    /// The columns of tables that the `*` and `t.*` of the query expanded to
+    /// when the view was created, qualified with the name of their table
+    pub wildcard_columns: Vec<ObjectName>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Source {
+    pub url: String,
+    pub schema: Option<SourceSchema>,
+    pub with_options: Vec<SqlOption>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sink {
+    pub from: ObjectName,
+    pub url: String,
+    pub with_options: Vec<SqlOption>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Index {
+    /// The table (or view or source) that the index is on
+    pub on_name: ObjectName,
+    pub key_parts: Vec<Expr>,
+}
+
+impl CatalogObject {
+    pub fn object_type(&self) -> ObjectType {
+        match self.kind {
+            ObjectKind::Table(_) => ObjectType::Table,
+            ObjectKind::View(_) => ObjectType::View,
+            ObjectKind::Source(_) => ObjectType::Source,
+            ObjectKind::Sink(_) => ObjectType::Sink,
+            ObjectKind::Index(_) => ObjectType::Index,
+        }
+    }
+
+    /// The name of the schema that the object is in, e.g. `s` for `s.t`,
+    /// which is empty if the name of the object is not qualified
+    pub fn schema(&self) -> &[Ident] {
+        &self.name.0[..self.name.0.len() - 1]
+    }
+}
+
+/// Why a statement cannot be applied to a [`Catalog`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CatalogError {
+    /// An object of the same name exists
+    AlreadyExists(ObjectType, ObjectName),
+    /// The object that `ALTER` or `DROP` refers to does not exist
+    UnknownObject(ObjectType, ObjectName),
+    /// A table, view or source that a statement refers to does not exist
+    UnknownRelation(ObjectName),
+    /// The object is not of the type that the statement expects
+    WrongObjectType {
+        name: ObjectName,
+        expected: ObjectType,
+    },
+    DuplicateColumn {
+        table: ObjectName,
+        column: Ident,
+    },
+    UnknownColumn {
+        table: ObjectName,
+        column: Ident,
+    },
+    DuplicateConstraint {
+        table: ObjectName,
+        name: Ident,
+    },
+    UnknownConstraint {
+        table: ObjectName,
+        name: Ident,
+    },
+    /// A second primary key for the table `table`
+    MultiplePrimaryKeys(ObjectName),
+    /// `DROP` without `CASCADE` of an object that another one depends on
+    DependentObject {
+        name: ObjectName,
+        dependent: ObjectName,
+    },
+    /// Dropping, or changing the type of, a column that a view or the foreign
+    /// key of another table refers to
+    DependentColumn {
+        /// The name of the column, qualified with the name of its table
+        column: ObjectName,
+        dependent: ObjectName,
+    },
+    /// The query of the view `name` refers to columns that do not exist, or
+    /// ambiguously
+    InvalidQuery {
+        name: ObjectName,
+        message: String,
+    },
+}
+
+impl fmt::Display for CatalogError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CatalogError::AlreadyExists(object_type, name) => write!(
+                f,
+                "{} {} already exists",
+                object_type.to_string().to_lowercase(),
+                name
+            ),
+            CatalogError::UnknownObject(object_type, name) => write!(
+                f,
+                "{} {} does not exist",
+                object_type.to_string().to_lowercase(),
+                name
+            ),
+            CatalogError::UnknownRelation(name) => write!(f, "relation {} does not exist", name),
+            CatalogError::WrongObjectType { name, expected } => write!(
+                f,
+                "{} is not a {}",
+                name,
+                expected.to_string().to_lowercase()
+            ),
+            CatalogError::DuplicateColumn { table, column } => write!(
+                f,
+                "column {} of relation {} specified more than once",
+                column, table
+            ),
+            CatalogError::UnknownColumn { table, column } => {
+                write!(f, "column {} of relation {} does not exist", column, table)
+            }
+            CatalogError::DuplicateConstraint { table, name } => write!(
+                f,
+                "constraint {} for relation {} already exists",
+                name, table
+            ),
+            CatalogError::UnknownConstraint { table, name } => write!(
+                f,
+                "constraint {} of relation {} does not exist",
+                name, table
+            ),
+            CatalogError::MultiplePrimaryKeys(table) => write!(
+                f,
+                "multiple primary keys for table {} are not allowed",
+                table
+            ),
+            CatalogError::DependentObject { name, dependent } => write!(
+                f,
+                "cannot drop {} because {} depends on it",
+                name, dependent
+            ),
+            CatalogError::DependentColumn { column, dependent } => {
+                let (name, table) = column.0.split_last().unwrap();
+                write!(
+                    f,
+                    "column {} of relation {} is used by {}",
+                    name,
+                    ObjectName(table.to_vec()),
+                    dependent
+                )
+            }
+            CatalogError::InvalidQuery { name, message } => {
+                write!(f, "invalid query for {}: {}", name, message)
+            }
+        }
+    }
+}
+
+impl Error for CatalogError {}
+
+impl Catalog {
+    pub fn new() -> Self {
+        Catalog::default()
+    }
+
+    /// The object named `name`, if any
+    pub fn get(&self, name: &ObjectName) -> Option<&CatalogObject> {
+        self.objects
+            .iter()
+            .find(|object| object_name_eq(&object.name, name))
+    }
+
+    /// The objects of the catalog, in the order in which they were created
+    pub fn objects(&self) -> impl DoubleEndedIterator<Item = &CatalogObject> {
+        self.objects.iter()
+    }
+
+    /// The names of the schemas that have objects, in the order in which
+    /// their first object was created
+    pub fn schemas(&self) -> Vec<ObjectName> {
+        let mut schemas: Vec<ObjectName> = vec![];
+        for object in &self.objects {
+            let schema = ObjectName(object.schema().to_vec());
+            if !schema.0.is_empty() && !schemas.iter().any(|s| object_name_eq(s, &schema)) {
+                schemas.push(schema);
+            }
+        }
+        schemas
+    }
+
+    /// The objects that depend on the object named `name`
+    pub fn dependents<'a>(
+        &'a self,
+        name: &'a ObjectName,
+    ) -> impl Iterator<Item = &'a CatalogObject> + 'a {
+        self.objects.iter().filter(move |object| {
+            !object_name_eq(&object.name, name)
+                && object
+                    .dependencies
+                    .iter()
+                    .any(|dependency| object_name_eq(dependency, name))
+        })
+    }
+
+    /// Update the catalog with the DDL statement `statement`, or return why
+    /// it cannot be applied, in which case the catalog is left unchanged.
+    /// Statements that do not create, alter or drop objects are ignored.
+    pub fn apply(&mut self, statement: &Statement) -> Result<(), CatalogError> {
+        match statement {
+            Statement::CreateTable {
+                name,
+                columns,
+                constraints,
+                ..
+            } => self.create_table(name, columns, constraints),
+            Statement::CreateView {
+                name,
+                columns,
+                query,
+                materialized,
+                ..
+            } => self.create_view(statement, name, columns, query, *materialized),
+            Statement::CreateSource {
+                name,
+                url,
+                schema,
+                with_options,
+            } => {
+                self.check_new(ObjectType::Source, name)?;
+                self.objects.push(CatalogObject {
+                    name: name.clone(),
+                    kind: ObjectKind::Source(Source {
+                        url: url.clone(),
+                        schema: schema.clone(),
+                        with_options: with_options.clone(),
+                    }),
+                    dependencies: vec![],
+                });
+                Ok(())
+            }
+            Statement::CreateSink {
+                name,
+                from,
+                url,
+                with_options,
+            } => {
+                self.check_new(ObjectType::Sink, name)?;
+                let from = self.relation(from)?.name.clone();
+                self.objects.push(CatalogObject {
+                    name: name.clone(),
+                    kind: ObjectKind::Sink(Sink {
+                        from: from.clone(),
+                        url: url.clone(),
+                        with_options: with_options.clone(),
+                    }),
+                    dependencies: vec![from],
+                });
+                Ok(())
+            }
+            Statement::CreateIndex {
+                name,
+                on_name,
+                key_parts,
+            } => self.create_index(statement, name, on_name, key_parts),
+            Statement::AlterTable { name, operation } => self.alter_table(name, operation),
+            Statement::Drop {
+                object_type,
+                if_exists,
+                names,
+                cascade,
+            } => self.drop(*object_type, *if_exists, names, *cascade),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check that no object is named `name`
+    fn check_new(&self, object_type: ObjectType, name: &ObjectName) -> Result<(), CatalogError> {
+        match self.get(name) {
+            Some(object) => Err(CatalogError::AlreadyExists(
+                object_type,
+                object.name.clone(),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// The table, view or source named `name`
+    fn relation(&self, name: &ObjectName) -> Result<&CatalogObject, CatalogError> {
+        match self.get(name) {
+            Some(object) => match object.kind {
+                ObjectKind::Table(_) | ObjectKind::View(_) | ObjectKind::Source(_) => Ok(object),
+                _ => Err(CatalogError::UnknownRelation(name.clone())),
+            },
+            None => Err(CatalogError::UnknownRelation(name.clone())),
+        }
+    }
+
+    /// The table named `name`
+    fn table(&self, name: &ObjectName) -> Result<(&ObjectName, &Table), CatalogError> {
+        match self.get(name) {
+            Some(CatalogObject {
+                name,
+                kind: ObjectKind::Table(table),
+                ..
+            }) => Ok((name, table)),
+            Some(object) => Err(CatalogError::WrongObjectType {
+                name: object.name.clone(),
+                expected: ObjectType::Table,
+            }),
+            None => Err(CatalogError::UnknownObject(ObjectType::Table, name.clone())),
+        }
+    }
+
+    /// Whether `name` is the name of a constraint of a table other than
+    /// `table` in the schema of `table`, or if `is_index`, of an object of
+    /// the schema, as PostgreSQL checks the name of the index of a unique
+    /// constraint
+    fn is_name_taken(&self, table: &ObjectName, name: &Ident, is_index: bool) -> bool {
+        let schema = &table.0[..table.0.len() - 1];
+        self.objects.iter().any(|object| {
+            let in_schema = object.schema().len() == schema.len()
+                && object
+                    .schema()
+                    .iter()
+                    .zip(schema)
+                    .all(|(a, b)| ident_eq(a, b));
+            if !in_schema {
+                return false;
+            }
+            if is_index && ident_eq(object.name.0.last().unwrap(), name) {
+                return true;
+            }
+            match &object.kind {
+                ObjectKind::Table(other) if !object_name_eq(&object.name, table) => {
+                    other.constraint(name).is_some()
+                }
+                _ => false,
+            }
+        })
+    }
+
+    fn table_mut(&mut self, name: &ObjectName) -> &mut Table {
+        match self
+            .objects
+            .iter_mut()
+            .find(|o| object_name_eq(&o.name, name))
+        {
+            Some(CatalogObject {
+                kind: ObjectKind::Table(table),
+                ..
+            }) => table,
+            _ => panic!("no table {}", name),
+        }
+    }
+
+    fn create_table(
+        &mut self,
+        name: &ObjectName,
+        columns: &[ColumnDef],
+        constraints: &[TableConstraint],
+    ) -> Result<(), CatalogError> {
+        self.check_new(ObjectType::Table, name)?;
+        let mut table = Table {
+            columns: vec![],
+            constraints: vec![],
+        };
+        let mut new_constraints = vec![];
+        for column in columns {
+            if table.column(&column.name).is_some() {
+                return Err(CatalogError::DuplicateColumn {
+                    table: name.clone(),
+                    column: column.name.clone(),
+                });
+            }
+            let (options, constraints) = split_column_options(column);
+            table.columns.push(ColumnDef {
+                options,
+                ..column.clone()
+            });
+            new_constraints.extend(constraints);
+        }
+        new_constraints.extend(constraints.iter().cloned());
+        let mut dependencies: Vec<ObjectName> = vec![];
+        for constraint in new_constraints {
+            let foreign_table = self.check_constraint(name, &table, &constraint)?;
+            if let Some(foreign_table) = foreign_table {
+                if !dependencies
+                    .iter()
+                    .any(|d| object_name_eq(d, &foreign_table))
+                {
+                    dependencies.push(foreign_table);
+                }
+            }
+            table.add_constraint(self, name, constraint)?;
+        }
+        self.objects.push(CatalogObject {
+            name: name.clone(),
+            kind: ObjectKind::Table(table),
+            dependencies,
+        });
+        Ok(())
+    }
+
+    /// Check that the columns of `constraint` of `table` exist, and those of
+    /// the table that it references if it is a foreign key, whose name is
+    /// returned unless it is `table` itself.
+    fn check_constraint(
+        &self,
+        name: &ObjectName,
+        table: &Table,
+        constraint: &TableConstraint,
+    ) -> Result<Option<ObjectName>, CatalogError> {
+        table.check_columns(name, constraint_columns(constraint))?;
+        if let TableConstraint::ForeignKey {
+            foreign_table,
+            referred_columns,
+            ..
+        } = constraint
+        {
+            if object_name_eq(foreign_table, name) {
+                table.check_columns(name, referred_columns)?;
+            } else {
+                let (foreign_table, foreign) = self.table(foreign_table)?;
+                foreign.check_columns(foreign_table, referred_columns)?;
+                return Ok(Some(foreign_table.clone()));
+            }
+        }
+        Ok(None)
+    }
+
+    fn create_view(
+        &mut self,
+        statement: &Statement,
+        name: &ObjectName,
+        columns: &[Ident],
+        query: &Query,
+        materialized: bool,
+    ) -> Result<(), CatalogError> {
+        self.check_new(ObjectType::View, name)?;
+        let mut dependencies = vec![];
+        for table in references(statement).read_tables {
+            dependencies.push(self.relation(&table)?.name.clone());
+        }
+        let resolution = resolve(query, Some(&*self));
+        if let Some(error) = resolution.errors.first() {
+            return Err(CatalogError::InvalidQuery {
+                name: name.clone(),
+                message: error.to_string(),
+            });
+        }
+        let view_columns = resolution.columns.map(|mut view_columns| {
+            for (view_column, column) in view_columns.iter_mut().zip(columns) {
+                *view_column = column.clone();
+            }
+            view_columns
+        });
+        self.objects.push(CatalogObject {
+            name: name.clone(),
+            kind: ObjectKind::View(View {
+                columns: view_columns,
+                query: Box::new(query.clone()),
+                materialized,
+                wildcard_columns: wildcard_columns(self, query),
+            }),
+            dependencies,
+        });
+        Ok(())
+    }
+
+    fn create_index(
+        &mut self,
+        statement: &Statement,
+        name: &Ident,
+        on_name: &ObjectName,
+        key_parts: &[Expr],
+    ) -> Result<(), CatalogError> {
+        // Indexes are in the schema of their table.
+        let on = self.relation(on_name)?;
+        let mut index_name = on.schema().to_vec();
+        index_name.push(name.clone());
+        let index_name = ObjectName(index_name);
+        self.check_new(ObjectType::Index, &index_name)?;
+        if let Some(columns) = self.table_columns(on_name) {
+            for column in references(statement).columns {
+                if !columns.iter().any(|c| ident_eq(c, &column.column)) {
+                    return Err(CatalogError::UnknownColumn {
+                        table: on.name.clone(),
+                        column: column.column,
+                    });
+                }
+            }
+        }
+        let on_name = on.name.clone();
+        self.objects.push(CatalogObject {
+            name: index_name,
+            kind: ObjectKind::Index(Index {
+                on_name: on_name.clone(),
+                key_parts: key_parts.to_vec(),
+            }),
+            dependencies: vec![on_name],
+        });
+        Ok(())
+    }
+
+    fn alter_table(
+        &mut self,
+        name: &ObjectName,
+        operation: &AlterTableOperation,
+    ) -> Result<(), CatalogError> {
+        let (name, table) = self.table(name)?;
+        let name = name.clone();
+        match operation {
+            AlterTableOperation::AddConstraint(constraint) => {
+                let foreign_table = self.check_constraint(&name, table, constraint)?;
+                let mut table = table.clone();
+                table.add_constraint(self, &name, constraint.clone())?;
+                *self.table_mut(&name) = table;
+                if let Some(foreign_table) = foreign_table {
+                    self.add_dependency(&name, foreign_table);
+                }
+            }
+            AlterTableOperation::DropConstraint { name: constraint } => {
+                let i = table
+                    .constraints
+                    .iter()
+                    .position(|c| ident_eq(constraint_name(c).unwrap(), constraint))
+                    .ok_or_else(|| CatalogError::UnknownConstraint {
+                        table: name.clone(),
+                        name: constraint.clone(),
+                    })?;
+                self.table_mut(&name).constraints.remove(i);
+                self.update_foreign_key_dependencies(&name);
+            }
+            AlterTableOperation::AddColumn(column) => {
+                if table.column(&column.name).is_some() {
+                    return Err(CatalogError::DuplicateColumn {
+                        table: name,
+                        column: column.name.clone(),
+                    });
+                }
+                let (options, constraints) = split_column_options(column);
+                let mut table = table.clone();
+                table.columns.push(ColumnDef {
+                    options,
+                    ..column.clone()
+                });
+                let mut foreign_tables = vec![];
+                for constraint in constraints {
+                    foreign_tables.extend(self.check_constraint(&name, &table, &constraint)?);
+                    table.add_constraint(self, &name, constraint)?;
+                }
+                *self.table_mut(&name) = table;
+                for foreign_table in foreign_tables {
+                    self.add_dependency(&name, foreign_table);
+                }
+            }
+            AlterTableOperation::DropColumn { name: column } => {
+                let column = table.existing_column(&name, column)?.name.clone();
+                self.check_unused_column(&name, &column)?;
+                // Like PostgreSQL, drop the constraints and indexes of the
+                // table that involve the column.
+                let table = self.table_mut(&name);
+                table.columns.retain(|c| !ident_eq(&c.name, &column));
+                table.constraints.retain(|constraint| {
+                    let self_reference = match constraint {
+                        TableConstraint::ForeignKey {
+                            foreign_table,
+                            referred_columns,
+                            ..
+                        } if object_name_eq(foreign_table, &name) => {
+                            referred_columns.iter().collect()
+                        }
+                        _ => vec![],
+                    };
+                    !constraint_columns(constraint)
+                        .into_iter()
+                        .chain(self_reference)
+                        .any(|c| ident_eq(c, &column))
+                });
+                self.update_foreign_key_dependencies(&name);
+                self.objects.retain(|object| match &object.kind {
+                    ObjectKind::Index(index) => {
+                        !object_name_eq(&index.on_name, &name)
+                            || !index
+                                .key_parts
+                                .iter()
+                                .flat_map(expr_columns)
+                                .any(|c| ident_eq(c, &column))
+                    }
+                    _ => true,
+                });
+            }
+            AlterTableOperation::AlterColumn {
+                name: column,
+                operation,
+            } => {
+                let column = table.existing_column(&name, column)?.name.clone();
+                if let AlterColumnOperation::SetDataType(_) = operation {
+                    self.check_unused_column(&name, &column)?;
+                }
+                let column = self
+                    .table_mut(&name)
+                    .columns
+                    .iter_mut()
+                    .find(|c| ident_eq(&c.name, &column))
+                    .unwrap();
+                let options = &mut column.options;
+                match operation {
+                    AlterColumnOperation::SetDataType(data_type) => {
+                        column.data_type = data_type.clone()
+                    }
+                    AlterColumnOperation::SetDefault(_) | AlterColumnOperation::DropDefault => {
+                        options.retain(|o| !matches!(o.option, ColumnOption::Default(_)))
+                    }
+                    AlterColumnOperation::SetNotNull | AlterColumnOperation::DropNotNull => options
+                        .retain(|o| {
+                            !matches!(o.option, ColumnOption::Null | ColumnOption::NotNull)
+                        }),
+                }
+                let option = match operation {
+                    AlterColumnOperation::SetDefault(expr) => ColumnOption::Default(expr.clone()),
+                    AlterColumnOperation::SetNotNull => ColumnOption::NotNull,
+                    _ => return Ok(()),
+                };
+                options.push(ColumnOptionDef { name: None, option });
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that no view and no foreign key of another table refers to the
+    /// column `column` of the table `table`
+    fn check_unused_column(&self, table: &ObjectName, column: &Ident) -> Result<(), CatalogError> {
+        let error = |dependent: &CatalogObject| {
+            let mut column_name = table.clone();
+            column_name.0.push(column.clone());
+            Err(CatalogError::DependentColumn {
+                column: column_name,
+                dependent: dependent.name.clone(),
+            })
+        };
+        for dependent in self.dependents(table) {
+            match &dependent.kind {
+                ObjectKind::Table(other) => {
+                    for constraint in &other.constraints {
+                        if let TableConstraint::ForeignKey {
+                            foreign_table,
+                            referred_columns,
+                            ..
+                        } = constraint
+                        {
+                            if object_name_eq(foreign_table, table)
+                                && referred_columns.iter().any(|c| ident_eq(c, column))
+                            {
+                                return error(dependent);
+                            }
+                        }
+                    }
+                }
+                ObjectKind::View(view) => {
+                    if view.wildcard_columns.iter().any(|c| {
+                        let (name, table_name) = c.0.split_last().unwrap();
+                        object_name_eq(&ObjectName(table_name.to_vec()), table)
+                            && ident_eq(name, column)
+                    }) {
+                        return error(dependent);
+                    }
+                    let resolution = resolve(&view.query, Some(self));
+                    for binding in resolution.bindings {
+                        let binding_column = match binding.expr {
+                            Expr::Identifier(ident) => ident,
+                            Expr::CompoundIdentifier(idents) => idents.last().unwrap(),
+                            _ => continue,
+                        };
+                        if let Relation::Table(TableFactor::Table { name, .. }) = binding.relation {
+                            if object_name_eq(name, table) && ident_eq(binding_column, column) {
+                                return error(dependent);
+                            }
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+
+    fn add_dependency(&mut self, name: &ObjectName, dependency: ObjectName) {
+        let object = self
+            .objects
+            .iter_mut()
+            .find(|o| object_name_eq(&o.name, name))
+            .unwrap();
+        if !object
+            .dependencies
+            .iter()
+            .any(|d| object_name_eq(d, &dependency))
+        {
+            object.dependencies.push(dependency);
+        }
+    }
+
+    /// Remove the dependencies of the table `name` on the tables that none of
+    /// its foreign keys reference anymore
+    fn update_foreign_key_dependencies(&mut self, name: &ObjectName) {
+        let object = self
+            .objects
+            .iter_mut()
+            .find(|o| object_name_eq(&o.name, name))
+            .unwrap();
+        if let ObjectKind::Table(table) = &object.kind {
+            let constraints = &table.constraints;
+            object.dependencies.retain(|dependency| {
+                constraints.iter().any(|constraint| match constraint {
+                    TableConstraint::ForeignKey { foreign_table, .. } => {
+                        object_name_eq(foreign_table, dependency)
+                    }
+                    _ => false,
+                })
+            });
+        }
+    }
+
+    fn drop(
+        &mut self,
+        object_type: ObjectType,
+        if_exists: bool,
+        names: &[ObjectName],
+        cascade: bool,
+    ) -> Result<(), CatalogError> {
+        let mut dropped: Vec<ObjectName> = vec![];
+        for name in names {
+            match self.get(name) {
+                None if if_exists => (),
+                None => return Err(CatalogError::UnknownObject(object_type, name.clone())),
+                Some(object) if object.object_type() != object_type => {
+                    return Err(CatalogError::WrongObjectType {
+                        name: object.name.clone(),
+                        expected: object_type,
+                    })
+                }
+                Some(object) => {
+                    if !dropped.iter().any(|d| object_name_eq(d, &object.name)) {
+                        dropped.push(object.name.clone());
+                    }
+                }
+            }
+        }
+        // Drop the objects that depend on the dropped ones, which requires
+        // `CASCADE` except for indexes, but only drop the foreign keys of the
+        // tables that reference a dropped table.
+        let mut referencing_tables = vec![];
+        let mut i = 0;
+        while i < dropped.len() {
+            let name = dropped[i].clone();
+            for dependent in self.dependents(&name) {
+                if dropped.iter().any(|d| object_name_eq(d, &dependent.name)) {
+                    continue;
+                }
+                let is_index = matches!(dependent.kind, ObjectKind::Index(_));
+                if !cascade && !is_index {
+                    return Err(CatalogError::DependentObject {
+                        name: name.clone(),
+                        dependent: dependent.name.clone(),
+                    });
+                }
+                match dependent.kind {
+                    ObjectKind::Table(_) => referencing_tables.push(dependent.name.clone()),
+                    _ => dropped.push(dependent.name.clone()),
+                }
+            }
+            i += 1;
+        }
+        self.objects
+            .retain(|object| !dropped.iter().any(|d| object_name_eq(d, &object.name)));
+        for name in referencing_tables {
+            if self.get(&name).is_none() {
+                continue;
+            }
+            self.table_mut(&name)
+                .constraints
+                .retain(|constraint| match constraint {
+                    TableConstraint::ForeignKey { foreign_table, .. } => {
+                        !dropped.iter().any(|d| object_name_eq(d, foreign_table))
+                    }
+                    _ => true,
+                });
+            self.update_foreign_key_dependencies(&name);
+        }
+        Ok(())
+    }
+}
+
+impl TableCatalog for Catalog {
+    fn table_columns(&self, name: &ObjectName) -> Option<Vec<Ident>> {
+        match &self.get(name)?.kind {
+            ObjectKind::Table(table) => {
+                Some(table.columns.iter().map(|c| c.name.clone()).collect())
+            }
+            ObjectKind::View(view) => view.columns.clone(),
+            _ => None,
+        }
+    }
+}
+
+impl Table {
+    /// The column named `name`, if any
+    pub fn column(&self, name: &Ident) -> Option<&ColumnDef> {
+        self.columns
+            .iter()
+            .find(|column| ident_eq(&column.name, name))
+    }
+
+    /// The constraint named `name`, if any
+    pub fn constraint(&self, name: &Ident) -> Option<&TableConstraint> {
+        self.constraints
+            .iter()
+            .find(|constraint| ident_eq(constraint_name(constraint).unwrap(), name))
+    }
+
+    /// The column named `column`, or an error if there is none
+    fn existing_column(
+        &self,
+        table: &ObjectName,
+        column: &Ident,
+    ) -> Result<&ColumnDef, CatalogError> {
+        self.column(column)
+            .ok_or_else(|| CatalogError::UnknownColumn {
+                table: table.clone(),
+                column: column.clone(),
+            })
+    }
+
+    fn check_columns<'a>(
+        &self,
+        table: &ObjectName,
+        columns: impl IntoIterator<Item = &'a Ident>,
+    ) -> Result<(), CatalogError> {
+        for column in columns {
+            if self.column(column).is_none() {
+                return Err(CatalogError::UnknownColumn {
+                    table: table.clone(),
+                    column: column.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Add `constraint` to the table `table` of `catalog`, naming it if it
+    /// has no name
+    fn add_constraint(
+        &mut self,
+        catalog: &Catalog,
+        table: &ObjectName,
+        mut constraint: TableConstraint,
+    ) -> Result<(), CatalogError> {
+        if is_primary_key(&constraint) && self.constraints.iter().any(is_primary_key) {
+            return Err(CatalogError::MultiplePrimaryKeys(table.clone()));
+        }
+        let name = match constraint_name(&constraint) {
+            Some(name) => {
+                if self.constraint(name).is_some() {
+                    return Err(CatalogError::DuplicateConstraint {
+                        table: table.clone(),
+                        name: name.clone(),
+                    });
+                }
+                name.clone()
+            }
+            None => {
+                // Like PostgreSQL, number the default name if it is taken.
+                let is_index = matches!(constraint, TableConstraint::Unique { .. });
+                let mut i = 0;
+                loop {
+                    let name = numbered_constraint_name(table, &constraint, i);
+                    if self.constraint(&name).is_none()
+                        && !catalog.is_name_taken(table, &name, is_index)
+                    {
+                        break name;
+                    }
+                    i += 1;
+                }
+            }
+        };
+        match &mut constraint {
+            TableConstraint::Unique { name: n, .. }
+            | TableConstraint::ForeignKey { name: n, .. }
+            | TableConstraint::Check { name: n, .. } => *n = Some(name),
+        }
+        self.constraints.push(constraint);
+        Ok(())
+    }
+}
+
+/// The name of `constraint`, if it has one
+pub fn constraint_name(constraint: &TableConstraint) -> Option<&Ident> {
+    match constraint {
+        TableConstraint::Unique { name, .. }
+        | TableConstraint::ForeignKey { name, .. }
+        | TableConstraint::Check { name, .. } => name.as_ref(),
+    }
+}
+
+/// Whether `constraint` is a primary key
+fn is_primary_key(constraint: &TableConstraint) -> bool {
+    matches!(
+        constraint,
+        TableConstraint::Unique {
+            is_primary: true,
+            ..
+        }
+    )
+}
+
+/// The columns of the table that `constraint` involves
+fn constraint_columns(constraint: &TableConstraint) -> Vec<&Ident> {
+    match constraint {
+        TableConstraint::Unique { columns, .. } | TableConstraint::ForeignKey { columns, .. } => {
+            columns.iter().collect()
+        }
+        TableConstraint::Check { expr, .. } => expr_columns(expr),
+    }
+}
+
+/// The name that PostgreSQL gives to `constraint` of the table `table` when
+/// it is not named: `<table>_pkey` for a primary key, and
+/// `<table>_<columns>_key`, `<table>_<columns>_fkey` and
+/// `<table>_<column>_check` for `UNIQUE`, foreign key and `CHECK` constraints,
+/// where `<column>` is the column that the check refers to. A check that
+/// refers to no column or to several ones is named `<table>_check`.
+/// Unquoted names are folded to lower case first, and the name is quoted if
+/// it is not in lower case. Like PostgreSQL, the longer of the table and
+/// column parts is shortened until the name fits in 63 bytes, and a number
+/// is added to the suffix if the name is already taken in the schema.
+pub fn default_constraint_name(table: &ObjectName, constraint: &TableConstraint) -> Ident {
+    numbered_constraint_name(table, constraint, 0)
+}
+
+/// The longest name that PostgreSQL keeps, in bytes (`NAMEDATALEN - 1`)
+const MAX_NAME_LENGTH: usize = 63;
+
+/// The default name of `constraint`, whose suffix is numbered `n` unless `n`
+/// is 0, e.g. `t_x_key1`
+fn numbered_constraint_name(table: &ObjectName, constraint: &TableConstraint, n: usize) -> Ident {
+    let (columns, suffix) = match constraint {
+        TableConstraint::Unique {
+            is_primary: true, ..
+        } => (vec![], "pkey"),
+        TableConstraint::Unique { columns, .. } => (columns.iter().collect(), "key"),
+        TableConstraint::ForeignKey { columns, .. } => (columns.iter().collect(), "fkey"),
+        TableConstraint::Check { expr, .. } => {
+            let columns = expr_columns(expr);
+            let column = columns
+                .first()
+                .filter(|first| columns.iter().all(|c| ident_eq(c, first)));
+            (column.into_iter().copied().collect(), "check")
+        }
+    };
+    let columns: Vec<String> = columns.into_iter().map(folded).collect();
+    let columns = columns.join("_");
+    let suffix = match n {
+        0 => suffix.to_string(),
+        n => format!("{}{}", suffix, n),
+    };
+    let name = make_object_name(
+        &table.0.last().map_or(String::new(), folded),
+        Some(truncated(&columns, MAX_NAME_LENGTH)).filter(|columns| !columns.is_empty()),
+        &suffix,
+    );
+    if name.chars().any(char::is_uppercase) {
+        Ident::with_quote('"', name)
+    } else {
+        Ident::new(name)
+    }
+}
+
+/// Join `name1`, `name2` and `label` with underscores, as PostgreSQL's
+/// `makeObjectName` does: the longer of `name1` and `name2` is shortened
+/// until the name fits in `MAX_NAME_LENGTH` bytes, and `label` is kept
+fn make_object_name(name1: &str, name2: Option<&str>, label: &str) -> String {
+    let overhead = label.len() + 1 + name2.map_or(0, |_| 1);
+    let available = MAX_NAME_LENGTH.saturating_sub(overhead);
+    let mut length1 = name1.len();
+    let mut length2 = name2.map_or(0, str::len);
+    while length1 + length2 > available {
+        if length1 > length2 {
+            length1 -= 1;
+        } else {
+            length2 -= 1;
+        }
+    }
+    let mut name = truncated(name1, length1).to_string();
+    if let Some(name2) = name2 {
+        name.push('_');
+        name.push_str(truncated(name2, length2));
+    }
+    name.push('_');
+    name.push_str(label);
+    name
+}
+
+/// The longest prefix of `s` that has at most `length` bytes, without
+/// splitting a character
+fn truncated(s: &str, mut length: usize) -> &str {
+    if length >= s.len() {
+        return s;
+    }
+    while !s.is_char_boundary(length) {
+        length -= 1;
+    }
+    &s[..length]
+}
+
+/// The value of `ident` as the database stores it, in lower case unless it is
+/// quoted
+fn folded(ident: &Ident) -> String {
+    match ident.quote_style {
+        None => ident.value.to_lowercase(),
+        Some(_) => ident.value.clone(),
+    }
+}
+
+/// Split the options of `column` into those that are kept with the column,
+/// and its constraints, as table constraints
+fn split_column_options(column: &ColumnDef) -> (Vec<ColumnOptionDef>, Vec<TableConstraint>) {
+    let mut options = vec![];
+    let mut constraints = vec![];
+    for option in &column.options {
+        let name = option.name.clone();
+        let columns = vec![column.name.clone()];
+        match &option.option {
+            ColumnOption::Null | ColumnOption::NotNull | ColumnOption::Default(_) => {
+                options.push(option.clone())
+            }
+            ColumnOption::Unique { is_primary } => constraints.push(TableConstraint::Unique {
+                name,
+                columns,
+                is_primary: *is_primary,
+            }),
+            ColumnOption::ForeignKey {
+                foreign_table,
+                referred_columns,
+            } => constraints.push(TableConstraint::ForeignKey {
+                name,
+                columns,
+                foreign_table: foreign_table.clone(),
+                referred_columns: referred_columns.clone(),
+            }),
+            ColumnOption::Check(expr) => constraints.push(TableConstraint::Check {
+                name,
+                expr: Box::new(expr.clone()),
+            }),
+        }
+    }
+    (options, constraints)
+}
+
+/// The columns that `expr` refers to, in order
+fn expr_columns(expr: &Expr) -> Vec<&Ident> {
+    struct Columns<'ast>(Vec<&'ast Ident>);
+
+    impl<'ast> Visit<'ast> for Columns<'ast> {
+        fn visit_expr(&mut self, expr: &'ast Expr) {
+            match expr {
+                Expr::Identifier(ident) => self.0.push(ident),
+                Expr::CompoundIdentifier(idents) => self.0.extend(idents.last()),
+                _ => visit::visit_expr(self, expr),
+            }
+        }
+    }
+
+    let mut columns = Columns(vec![]);
+    columns.visit_expr(expr);
+    columns.0
+}
+
+/// The columns of the tables of `catalog` that the `*` and `t.*` of the
+/// `SELECT`s of `query` expand to, qualified with the name of their table
+fn wildcard_columns(catalog: &Catalog, query: &Query) -> Vec<ObjectName> {
+    struct Wildcards<'a, 'ast> {
+        catalog: &'a Catalog,
+        /// The names of the CTEs in scope, which hide tables of the same name
+        ctes: Vec<&'ast Ident>,
+        columns: Vec<ObjectName>,
+    }
+
+    impl<'a, 'ast> Visit<'ast> for Wildcards<'a, 'ast> {
+        fn visit_query(&mut self, query: &'ast Query) {
+            let len = self.ctes.len();
+            self.ctes
+                .extend(query.ctes.iter().map(|cte| &cte.alias.name));
+            visit::visit_query(self, query);
+            self.ctes.truncate(len);
+        }
+
+        fn visit_select(&mut self, select: &'ast Select) {
+            // The tables of the `FROM` clause, and the names they are
+            // referred to by
+            let mut tables = vec![];
+            let mut factors: Vec<&TableFactor> = vec![];
+            for table in &select.from {
+                factors.push(&table.relation);
+                factors.extend(table.joins.iter().map(|join| &join.relation));
+            }
+            while let Some(factor) = factors.pop() {
+                match factor {
+                    TableFactor::Table { name, alias, .. } => {
+                        let is_cte = name.0.len() == 1
+                            && self.ctes.iter().any(|cte| ident_eq(cte, &name.0[0]));
+                        if !is_cte {
+                            let alias = match alias {
+                                Some(alias) => vec![alias.name.clone()],
+                                None => name.0.clone(),
+                            };
+                            tables.push((name, alias));
+                        }
+                    }
+                    TableFactor::NestedJoin(table) => {
+                        factors.push(&table.relation);
+                        factors.extend(table.joins.iter().map(|join| &join.relation));
+                    }
+                    TableFactor::Derived { .. } => (),
+                }
+            }
+            for item in &select.projection {
+                let prefix = match item {
+                    SelectItem::Wildcard => None,
+                    SelectItem::QualifiedWildcard(prefix) => Some(prefix),
+                    _ => continue,
+                };
+                for (name, alias) in &tables {
+                    if let Some(prefix) = prefix {
+                        if alias.len() != prefix.0.len()
+                            || !alias.iter().zip(&prefix.0).all(|(a, b)| ident_eq(a, b))
+                        {
+                            continue;
+                        }
+                    }
+                    if let Ok((name, table)) = self.catalog.table(name) {
+                        for column in &table.columns {
+                            let mut column_name = name.clone();
+                            column_name.0.push(column.name.clone());
+                            self.columns.push(column_name);
+                        }
+                    }
+                }
+            }
+            visit::visit_select(self, select);
+        }
+    }
+
+    let mut wildcards = Wildcards {
+        catalog,
+        ctes: vec![],
+        columns: vec![],
+    };
+    wildcards.visit_query(query);
+    wildcards.columns
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::GenericDialect;
+    use crate::parser::Parser;
+
+    /// Apply the statements of `script` to `catalog`, stopping at the first
+    /// error
+    fn apply(catalog: &mut Catalog, script: &str) -> Result<(), CatalogError> {
+        for statement in Parser::parse_sql(&GenericDialect {}, script.to_string()).unwrap() {
+            catalog.apply(&statement)?;
+        }
+        Ok(())
+    }
+
+    fn name(name: &str) -> ObjectName {
+        ObjectName(name.split('.').map(Ident::new).collect())
+    }
+
+    fn table<'a>(catalog: &'a Catalog, name: &str) -> &'a Table {
+        match &catalog.get(&self::name(name)).unwrap().kind {
+            ObjectKind::Table(table) => table,
+            kind => panic!("not a table: {:?}", kind),
+        }
+    }
+
+    fn constraint_names(table: &Table) -> Vec<String> {
+        let names = table.constraints.iter().map(constraint_name);
+        names.map(|name| name.unwrap().to_string()).collect()
+    }
+
+    fn dependencies(catalog: &Catalog, name: &str) -> Vec<String> {
+        let object = catalog.get(&self::name(name)).unwrap();
+        object.dependencies.iter().map(|d| d.to_string()).collect()
+    }
+
+    fn object_names(catalog: &Catalog) -> Vec<String> {
+        catalog.objects().map(|o| o.name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_create() {
+        let mut catalog = Catalog::new();
+        apply(
+            &mut catalog,
+            "CREATE TABLE a (
+                id INT PRIMARY KEY,
+                x INT NOT NULL UNIQUE,
+                y INT DEFAULT 1 CHECK (y > 0),
+                CHECK (x > y),
+                CONSTRAINT named UNIQUE (x, y)
+            );
+            CREATE TABLE b (
+                id INT,
+                a_id INT REFERENCES a (id),
+                FOREIGN KEY (a_id) REFERENCES a (id),
+                UNIQUE (id, a_id),
+                CHECK (1 > 0)
+            );
+            CREATE VIEW v (a_id) AS SELECT a.id, b.id AS b_id FROM a JOIN b ON a.id = b.a_id;
+            CREATE TABLE s.t (w INT);
+            CREATE INDEX i ON s.t (w);
+            CREATE SOURCE src FROM 'kafka://host/topic' USING SCHEMA 'schema';
+            CREATE SINK snk FROM v INTO 'file:///sink'",
+        )
+        .unwrap();
+
+        assert_eq!(
+            object_names(&catalog),
+            vec!["a", "b", "v", "s.t", "s.i", "src", "snk"]
+        );
+        assert_eq!(catalog.schemas(), vec![name("s")]);
+
+        let a = table(&catalog, "a");
+        let options: Vec<String> = a
+            .columns
+            .iter()
+            .map(|column| {
+                let options = column.options.iter().map(|o| o.to_string());
+                options.collect::<Vec<_>>().join(" ")
+            })
+            .collect();
+        assert_eq!(options, vec!["", "NOT NULL", "DEFAULT 1"]);
+        assert_eq!(
+            constraint_names(a),
+            vec!["a_pkey", "a_x_key", "a_y_check", "a_check", "named"]
+        );
+        assert_eq!(
+            constraint_names(table(&catalog, "b")),
+            vec!["b_a_id_fkey", "b_a_id_fkey1", "b_id_a_id_key", "b_check"]
+        );
+
+        assert_eq!(dependencies(&catalog, "a"), Vec::<String>::new());
+        assert_eq!(dependencies(&catalog, "b"), vec!["a"]);
+        assert_eq!(dependencies(&catalog, "v"), vec!["a", "b"]);
+        assert_eq!(dependencies(&catalog, "s.i"), vec!["s.t"]);
+        assert_eq!(dependencies(&catalog, "snk"), vec!["v"]);
+        let dependents: Vec<String> = catalog
+            .dependents(&name("a"))
+            .map(|o| o.name.to_string())
+            .collect();
+        assert_eq!(dependents, vec!["b", "v"]);
+
+        // The catalog provides the columns of tables and views.
+        let columns = |name: &str| {
+            let columns = catalog.table_columns(&self::name(name))?;
+            Some(columns.iter().map(|c| c.to_string()).collect::<Vec<_>>())
+        };
+        assert_eq!(
+            columns("A"),
+            Some(vec!["id".into(), "x".into(), "y".into()])
+        );
+        assert_eq!(columns("v"), Some(vec!["a_id".into(), "b_id".into()]));
+        assert_eq!(columns("src"), None);
+        assert_eq!(columns("c"), None);
+
+        // `GROUP BY` may refer to the columns of the result of a view.
+        apply(
+            &mut catalog,
+            "CREATE VIEW g AS SELECT x AS v, count(*) FROM a GROUP BY v",
+        )
+        .unwrap();
+        assert_eq!(
+            catalog.table_columns(&name("g")).unwrap(),
+            vec![Ident::new("v"), Ident::new("count")]
+        );
+        assert_eq!(dependencies(&catalog, "g"), vec!["a"]);
+
+        // Unquoted names are folded to lower case in default names.
+        let mut catalog = Catalog::new();
+        apply(
+            &mut catalog,
+            r#"CREATE TABLE C (X INT UNIQUE, "Y" INT UNIQUE, UNIQUE ("Y"))"#,
+        )
+        .unwrap();
+        assert_eq!(
+            constraint_names(table(&catalog, "c")),
+            vec!["c_x_key", r#""c_Y_key""#, r#""c_Y_key1""#]
+        );
+
+        // Long default names are shortened as PostgreSQL does.
+        let mut catalog = Catalog::new();
+        apply(
+            &mut catalog,
+            "CREATE TABLE a_very_long_table_name_that_goes_on_and_on_forever (
+                a_very_long_column_name_that_also_goes_on INT PRIMARY KEY UNIQUE UNIQUE
+            );
+            CREATE TABLE \"ééééééééééééééééééééééééééééééééééééééé\" (x INT UNIQUE)",
+        )
+        .unwrap();
+        assert_eq!(
+            constraint_names(table(
+                &catalog,
+                "a_very_long_table_name_that_goes_on_and_on_forever"
+            )),
+            vec![
+                "a_very_long_table_name_that_goes_on_and_on_forever_pkey",
+                "a_very_long_table_name_that_g_a_very_long_column_name_that__key",
+                "a_very_long_table_name_that_g_a_very_long_column_name_that_key1",
+            ]
+        );
+        assert_eq!(
+            constraint_names(table(&catalog, "ééééééééééééééééééééééééééééééééééééééé")),
+            vec!["éééééééééééééééééééééééééééé_x_key"]
+        );
+
+        // Default names are unique in the schema, and those of unique
+        // constraints are distinct from the names of its objects.
+        let mut catalog = Catalog::new();
+        apply(
+            &mut catalog,
+            "CREATE TABLE t (a_x INT CHECK (a_x > 0), x INT);
+            CREATE INDEX t_x_key ON t (x);
+            ALTER TABLE t ADD UNIQUE (x);
+            CREATE TABLE t_a (x INT CHECK (x > 0));
+            CREATE TABLE s.t_a (x INT CHECK (x > 0))",
+        )
+        .unwrap();
+        assert_eq!(
+            constraint_names(table(&catalog, "t")),
+            vec!["t_a_x_check", "t_x_key1"]
+        );
+        assert_eq!(
+            constraint_names(table(&catalog, "t_a")),
+            vec!["t_a_x_check1"]
+        );
+        assert_eq!(
+            constraint_names(table(&catalog, "s.t_a")),
+            vec!["t_a_x_check"]
+        );
+    }
+
+    #[test]
+    fn test_alter_and_drop() {
+        let mut catalog = Catalog::new();
+        apply(
+            &mut catalog,
+            "CREATE TABLE a (x INT UNIQUE);
+            CREATE TABLE b (x INT);
+            ALTER TABLE b ADD FOREIGN KEY (x) REFERENCES a (x);
+            ALTER TABLE b ADD CONSTRAINT b_x_check CHECK (x > 0);
+            ALTER TABLE b ADD CHECK (x < 10)",
+        )
+        .unwrap();
+        assert_eq!(
+            constraint_names(table(&catalog, "b")),
+            vec!["b_x_fkey", "b_x_check", "b_x_check1"]
+        );
+        assert_eq!(dependencies(&catalog, "b"), vec!["a"]);
+
+        // The parser does not support `DROP CONSTRAINT` yet.
+        let drop_constraint = |name: &str| Statement::AlterTable {
+            name: self::name("b"),
+            operation: AlterTableOperation::DropConstraint {
+                name: Ident::new(name),
+            },
+        };
+        catalog.apply(&drop_constraint("B_X_FKEY")).unwrap();
+        assert_eq!(
+            constraint_names(table(&catalog, "b")),
+            vec!["b_x_check", "b_x_check1"]
+        );
+        assert_eq!(dependencies(&catalog, "b"), Vec::<String>::new());
+
+        // Indexes are dropped with their table, other objects only with
+        // `CASCADE`, which only drops the foreign keys of other tables.
+        apply(
+            &mut catalog,
+            "ALTER TABLE b ADD FOREIGN KEY (x) REFERENCES a (x);
+            CREATE VIEW v AS SELECT x FROM a;
+            CREATE VIEW w AS SELECT x FROM v;
+            CREATE INDEX i ON a (x);
+            CREATE INDEX j ON b (x);
+            DROP INDEX j;
+            DROP TABLE a CASCADE",
+        )
+        .unwrap();
+        assert_eq!(object_names(&catalog), vec!["b"]);
+        assert_eq!(
+            constraint_names(table(&catalog, "b")),
+            vec!["b_x_check", "b_x_check1"]
+        );
+        assert_eq!(dependencies(&catalog, "b"), Vec::<String>::new());
+
+        apply(
+            &mut catalog,
+            "CREATE INDEX i ON b (x);
+            DROP TABLE IF EXISTS a, b",
+        )
+        .unwrap();
+        assert_eq!(object_names(&catalog), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_alter_columns() {
+        let mut catalog = Catalog::new();
+        apply(
+            &mut catalog,
+            "CREATE TABLE a (x INT UNIQUE, y INT, z INT);
+            CREATE TABLE b (a_x INT REFERENCES a (x));
+            CREATE VIEW v AS SELECT y FROM a;
+            CREATE INDEX i ON a (z);
+            ALTER TABLE a ADD COLUMN w INT NOT NULL CHECK (w > 0);
+            ALTER TABLE a DROP COLUMN z;
+            ALTER TABLE a ALTER COLUMN w SET DATA TYPE bigint;
+            ALTER TABLE a ALTER COLUMN w SET DEFAULT 1;
+            ALTER TABLE a ALTER COLUMN w DROP NOT NULL",
+        )
+        .unwrap();
+        // The index on the dropped column is dropped with it.
+        assert_eq!(object_names(&catalog), vec!["a", "b", "v"]);
+        let a = table(&catalog, "a");
+        let columns: Vec<String> = a.columns.iter().map(|c| c.to_string()).collect();
+        assert_eq!(columns, vec!["x int", "y int", "w bigint DEFAULT 1"]);
+        assert_eq!(constraint_names(a), vec!["a_x_key", "a_w_check"]);
+
+        apply(&mut catalog, "ALTER TABLE a DROP COLUMN w").unwrap();
+        assert_eq!(constraint_names(table(&catalog, "a")), vec!["a_x_key"]);
+
+        for (sql, message) in &[
+            (
+                "ALTER TABLE a ADD COLUMN Y INT",
+                "column Y of relation a specified more than once",
+            ),
+            (
+                "ALTER TABLE a DROP COLUMN w",
+                "column w of relation a does not exist",
+            ),
+            (
+                "ALTER TABLE a ALTER COLUMN w SET NOT NULL",
+                "column w of relation a does not exist",
+            ),
+            (
+                "ALTER TABLE a DROP COLUMN x",
+                "column x of relation a is used by b",
+            ),
+            (
+                "ALTER TABLE a ALTER COLUMN y TYPE text",
+                "column y of relation a is used by v",
+            ),
+        ] {
+            let mut changed = catalog.clone();
+            let error = apply(&mut changed, sql).unwrap_err();
+            assert_eq!(error.to_string(), *message, "{}", sql);
+            assert_eq!(changed, catalog, "{}", sql);
+        }
+    }
+
+    #[test]
+    fn test_check_constraint_names() {
+        let mut catalog = Catalog::new();
+        apply(
+            &mut catalog,
+            "CREATE TABLE t (
+                a INT,
+                b INT,
+                CHECK (a > b),
+                CHECK (a > 0 AND t.a < 10),
+                CHECK (b > 0),
+                CHECK (a <> b)
+            );
+            ALTER TABLE t ADD CHECK (a + b > 0)",
+        )
+        .unwrap();
+        // Like PostgreSQL, a check is only named after its column if it
+        // refers to exactly one.
+        assert_eq!(
+            constraint_names(table(&catalog, "t")),
+            vec!["t_check", "t_a_check", "t_b_check", "t_check1", "t_check2"]
+        );
+    }
+
+    #[test]
+    fn test_wildcard_columns() {
+        let mut catalog = Catalog::new();
+        apply(
+            &mut catalog,
+            "CREATE TABLE t (a INT, b INT);
+            CREATE TABLE u (c INT, d INT);
+            CREATE TABLE w (e INT);
+            CREATE VIEW v AS SELECT * FROM t;
+            CREATE VIEW x AS SELECT s.*, 1 FROM u AS s JOIN w ON true;
+            CREATE VIEW y AS WITH w AS (SELECT 1 AS e) SELECT * FROM w;
+            ALTER TABLE t ADD COLUMN f INT;
+            ALTER TABLE t DROP COLUMN f;
+            ALTER TABLE w DROP COLUMN e",
+        )
+        .unwrap();
+
+        // The columns that a `*` expanded to are used by the view.
+        for (sql, message) in &[
+            (
+                "ALTER TABLE t DROP COLUMN a",
+                "column a of relation t is used by v",
+            ),
+            (
+                "ALTER TABLE t ALTER COLUMN b TYPE text",
+                "column b of relation t is used by v",
+            ),
+            (
+                "ALTER TABLE u DROP COLUMN d",
+                "column d of relation u is used by x",
+            ),
+        ] {
+            let mut changed = catalog.clone();
+            let error = apply(&mut changed, sql).unwrap_err();
+            assert_eq!(error.to_string(), *message, "{}", sql);
+            assert_eq!(changed, catalog, "{}", sql);
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let cases = &[
+            ("CREATE TABLE a (y INT)", "table a already exists"),
+            ("CREATE VIEW A AS SELECT 1", "view a already exists"),
+            (
+                "CREATE TABLE c (x INT, X INT)",
+                "column X of relation c specified more than once",
+            ),
+            (
+                "CREATE TABLE c (x INT, UNIQUE (y))",
+                "column y of relation c does not exist",
+            ),
+            (
+                "CREATE TABLE c (x INT CHECK (y > 0))",
+                "column y of relation c does not exist",
+            ),
+            (
+                "CREATE TABLE c (x INT REFERENCES d (x))",
+                "table d does not exist",
+            ),
+            (
+                "CREATE TABLE c (x INT REFERENCES a (y))",
+                "column y of relation a does not exist",
+            ),
+            (
+                "CREATE TABLE c (x INT REFERENCES v (x))",
+                "v is not a table",
+            ),
+            (
+                "CREATE TABLE c (x INT, CONSTRAINT k UNIQUE (x), CONSTRAINT k CHECK (x > 0))",
+                "constraint k for relation c already exists",
+            ),
+            (
+                "CREATE VIEW w AS SELECT * FROM d",
+                "relation d does not exist",
+            ),
+            (
+                "CREATE VIEW w AS SELECT y FROM a",
+                "invalid query for w: column y does not exist at Line: 1, Column: 25",
+            ),
+            (
+                "CREATE SINK l FROM i INTO 'file:///sink'",
+                "relation i does not exist",
+            ),
+            ("CREATE INDEX i ON a (x)", "index i already exists"),
+            (
+                "CREATE INDEX j ON a (y)",
+                "column y of relation a does not exist",
+            ),
+            ("ALTER TABLE v ADD UNIQUE (x)", "v is not a table"),
+            (
+                "ALTER TABLE a ADD UNIQUE (y)",
+                "column y of relation a does not exist",
+            ),
+            (
+                "ALTER TABLE a ADD CONSTRAINT a_x_key UNIQUE (x)",
+                "constraint a_x_key for relation a already exists",
+            ),
+            (
+                "CREATE TABLE c (x INT PRIMARY KEY, y INT, PRIMARY KEY (y))",
+                "multiple primary keys for table c are not allowed",
+            ),
+            (
+                "ALTER TABLE u ADD PRIMARY KEY (y)",
+                "multiple primary keys for table u are not allowed",
+            ),
+            (
+                "ALTER TABLE u ADD COLUMN z INT PRIMARY KEY",
+                "multiple primary keys for table u are not allowed",
+            ),
+            ("DROP TABLE c", "table c does not exist"),
+            ("DROP VIEW a", "a is not a view"),
+            ("DROP TABLE a", "cannot drop a because b depends on it"),
+            ("DROP VIEW v, v", "cannot drop v because k depends on it"),
+            ("DROP TABLE b, a", "cannot drop a because v depends on it"),
+        ];
+        let mut catalog = Catalog::new();
+        apply(
+            &mut catalog,
+            "CREATE TABLE a (x INT UNIQUE);
+            CREATE TABLE b (x INT REFERENCES a (x));
+            CREATE VIEW v AS SELECT x FROM a;
+            CREATE INDEX i ON a (x);
+            CREATE SINK k FROM v INTO 'file:///sink';
+            CREATE TABLE u (x INT PRIMARY KEY, y INT)",
+        )
+        .unwrap();
+        for (sql, message) in cases {
+            let mut changed = catalog.clone();
+            let error = apply(&mut changed, sql).unwrap_err();
+            assert_eq!(error.to_string(), *message, "{}", sql);
+            assert_eq!(changed, catalog, "{}", sql);
+        }
+
+        let drop_constraint = Statement::AlterTable {
+            name: name("a"),
+            operation: AlterTableOperation::DropConstraint {
+                name: Ident::new("k"),
+            },
+        };
+        let error = catalog.clone().apply(&drop_constraint).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "constraint k of relation a does not exist"
+        );
+    }
+}
@@ -0,0 +1,625 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generating the statements that migrate one schema to another
+//!
+//! [`diff`] applies two sets of DDL statements to [`Catalog`]s, and
+//! [`diff_catalogs`] compares the objects of the same name of the two
+//! catalogs:
+//!
+//! - tables are altered in place, adding, dropping and altering their
+//!   columns, and dropping and adding their constraints;
+//! - other objects that differ, or whose type differs, are dropped and
+//!   created again, along with the views, sinks and indexes that depend on
+//!   them, as are the views over a table whose columns are dropped or change
+//!   type.
+//!
+//! Definitions are compared with unquoted identifiers folded to lower case,
+//! so `A` and `a` are the same. Constraints are matched by name, which
+//! [`Catalog`] gives them as PostgreSQL does when they are not named, and the
+//! statements always name the constraints they create, so that both
+//! databases end up with the same names. Renames cannot be told from a drop
+//! and a create, and changes of the collation of a column are not detected.
+//!
+//! The statements are ordered so that each one applies to the catalog that
+//! the previous ones produce:
+//!
+//! 1. `ALTER TABLE .. DROP CONSTRAINT`, foreign keys first;
+//! 2. `DROP` of the objects to drop or create again, in the reverse order of
+//!    their creation;
+//! 3. `ALTER TABLE` to drop, add and alter columns;
+//! 4. `CREATE` of the new objects, in the order of their creation;
+//! 5. `ALTER TABLE .. ADD` of constraints, foreign keys last.
+
+use super::catalog::{Catalog, CatalogError, CatalogObject, ObjectKind, Table};
+use super::{ident_eq, object_name_eq};
+use crate::ast::fold::Fold;
+use crate::ast::{
+    AlterColumnOperation, AlterTableOperation, ColumnDef, ColumnOption, ColumnOptionDef, DataType,
+    Expr, Ident, ObjectName, Query, SqlOption, Statement, TableConstraint,
+};
+
+/// The statements that migrate the schema created by the DDL statements
+/// `from` to the one created by `to`, or the first error that applying them
+/// to a [`Catalog`] returns
+pub fn diff(from: &[Statement], to: &[Statement]) -> Result<Vec<Statement>, CatalogError> {
+    let mut from_catalog = Catalog::new();
+    for statement in from {
+        from_catalog.apply(statement)?;
+    }
+    let mut to_catalog = Catalog::new();
+    for statement in to {
+        to_catalog.apply(statement)?;
+    }
+    Ok(diff_catalogs(&from_catalog, &to_catalog))
+}
+
+/// The statements that migrate the objects of `from` to those of `to`
+pub fn diff_catalogs(from: &Catalog, to: &Catalog) -> Vec<Statement> {
+    let mut diff = Diff {
+        from,
+        to,
+        dropped: vec![],
+        statements: vec![],
+    };
+    diff.find_dropped();
+
+    // The constraints to drop and add, which include the foreign keys that
+    // refer to a column that changes type.
+    let mut dropped_constraints = vec![];
+    let mut added_constraints = vec![];
+    let unchanged = |from: &TableConstraint, to: &TableConstraint| {
+        normalize(from) == normalize(to) && !diff.refers_to_retyped_column(to)
+    };
+    for (name, from_table, to_table) in diff.altered_tables() {
+        for constraint in &from_table.constraints {
+            match to_table.constraint(constraint_name(constraint)) {
+                Some(to_constraint) if unchanged(constraint, to_constraint) => (),
+                _ => dropped_constraints.push((name, constraint)),
+            }
+        }
+        for constraint in &to_table.constraints {
+            match from_table.constraint(constraint_name(constraint)) {
+                Some(from_constraint) if unchanged(from_constraint, constraint) => (),
+                _ => added_constraints.push((name, constraint)),
+            }
+        }
+    }
+
+    dropped_constraints.sort_by_key(|(_, constraint)| !is_foreign_key(constraint));
+    for (name, constraint) in dropped_constraints {
+        diff.alter_table(
+            name,
+            AlterTableOperation::DropConstraint {
+                name: constraint_name(constraint).clone(),
+            },
+        );
+    }
+
+    for object in from.objects().rev() {
+        if diff.is_dropped(&object.name) {
+            diff.statements.push(Statement::Drop {
+                object_type: object.object_type(),
+                if_exists: false,
+                names: vec![object.name.clone()],
+                cascade: false,
+            });
+        }
+    }
+
+    for (name, from_table, to_table) in diff.altered_tables() {
+        diff.alter_columns(name, from_table, to_table);
+    }
+
+    // Foreign keys are added with the table that they belong to if the table
+    // that they refer to exists by then.
+    let mut created: Vec<&ObjectName> = vec![];
+    for object in to.objects() {
+        if from.get(&object.name).is_none() || diff.is_dropped(&object.name) {
+            let deferred = diff.create(object, &created);
+            added_constraints.extend(deferred.into_iter().map(|c| (&object.name, c)));
+            created.push(&object.name);
+        }
+    }
+
+    added_constraints.sort_by_key(|(_, constraint)| is_foreign_key(constraint));
+    for (name, constraint) in added_constraints {
+        diff.alter_table(name, AlterTableOperation::AddConstraint(constraint.clone()));
+    }
+
+    diff.statements
+}
+
+struct Diff<'a> {
+    from: &'a Catalog,
+    to: &'a Catalog,
+    /// The objects of `from` to drop, including those to create again
+    dropped: Vec<&'a ObjectName>,
+    statements: Vec<Statement>,
+}
+
+impl<'a> Diff<'a> {
+    /// Find the objects to drop: those that `to` does not have, or differ
+    /// from the object of the same name of `to` and are not tables, and the
+    /// objects that depend on them, except tables, which only lose their
+    /// foreign keys. The views over a table whose columns are dropped or
+    /// change type are created again too.
+    fn find_dropped(&mut self) {
+        for object in self.from.objects() {
+            let dropped = match self.to.get(&object.name) {
+                Some(to_object) => !same_definition(object, to_object),
+                None => true,
+            };
+            if dropped {
+                self.dropped.push(&object.name);
+            }
+        }
+        for (name, from_table, to_table) in self.altered_tables() {
+            let columns_changed =
+                from_table
+                    .columns
+                    .iter()
+                    .any(|column| match to_table.column(&column.name) {
+                        Some(to_column) => {
+                            normalize(&column.data_type) != normalize(&to_column.data_type)
+                        }
+                        None => true,
+                    });
+            if columns_changed {
+                for dependent in self.from.dependents(name) {
+                    if let ObjectKind::View(_) = dependent.kind {
+                        self.drop_object(&dependent.name);
+                    }
+                }
+            }
+        }
+        let mut i = 0;
+        while i < self.dropped.len() {
+            for dependent in self.from.dependents(self.dropped[i]) {
+                if let ObjectKind::Table(_) = dependent.kind {
+                    continue;
+                }
+                self.drop_object(&dependent.name);
+            }
+            i += 1;
+        }
+    }
+
+    fn drop_object(&mut self, name: &'a ObjectName) {
+        if !self.is_dropped(name) {
+            self.dropped.push(name);
+        }
+    }
+
+    fn is_dropped(&self, name: &ObjectName) -> bool {
+        self.dropped.iter().any(|d| object_name_eq(d, name))
+    }
+
+    /// The tables of `from` that `to` has too, with their name in `to`
+    fn altered_tables(&self) -> Vec<(&'a ObjectName, &'a Table, &'a Table)> {
+        let mut tables = vec![];
+        for object in self.from.objects() {
+            if let ObjectKind::Table(from_table) = &object.kind {
+                if let Some(CatalogObject {
+                    name,
+                    kind: ObjectKind::Table(to_table),
+                    ..
+                }) = self.to.get(&object.name)
+                {
+                    tables.push((name, from_table, to_table));
+                }
+            }
+        }
+        tables
+    }
+
+    /// Whether `constraint` is a foreign key that refers to a column of a
+    /// table that both catalogs have, and that changes type
+    fn refers_to_retyped_column(&self, constraint: &TableConstraint) -> bool {
+        let (foreign_table, referred_columns) = match constraint {
+            TableConstraint::ForeignKey {
+                foreign_table,
+                referred_columns,
+                ..
+            } => (foreign_table, referred_columns),
+            _ => return false,
+        };
+        let (from_table, to_table) =
+            match (self.from.get(foreign_table), self.to.get(foreign_table)) {
+                (
+                    Some(CatalogObject {
+                        kind: ObjectKind::Table(from_table),
+                        ..
+                    }),
+                    Some(CatalogObject {
+                        kind: ObjectKind::Table(to_table),
+                        ..
+                    }),
+                ) => (from_table, to_table),
+                _ => return false,
+            };
+        referred_columns.iter().any(|column| {
+            match (from_table.column(column), to_table.column(column)) {
+                (Some(from_column), Some(to_column)) => {
+                    normalize(&from_column.data_type) != normalize(&to_column.data_type)
+                }
+                _ => false,
+            }
+        })
+    }
+
+    fn alter_table(&mut self, name: &ObjectName, operation: AlterTableOperation) {
+        self.statements.push(Statement::AlterTable {
+            name: name.clone(),
+            operation,
+        });
+    }
+
+    /// Drop the columns of `from_table` that `to_table` does not have, add
+    /// those that it does not have, and alter the type, default and
+    /// nullability of the others
+    fn alter_columns(&mut self, name: &ObjectName, from_table: &Table, to_table: &Table) {
+        for column in &from_table.columns {
+            if to_table.column(&column.name).is_none() {
+                self.alter_table(
+                    name,
+                    AlterTableOperation::DropColumn {
+                        name: column.name.clone(),
+                    },
+                );
+            }
+        }
+        for column in &to_table.columns {
+            if from_table.column(&column.name).is_none() {
+                self.alter_table(name, AlterTableOperation::AddColumn(column.clone()));
+            }
+        }
+        for from_column in &from_table.columns {
+            let to_column = match to_table.column(&from_column.name) {
+                Some(to_column) => to_column,
+                None => continue,
+            };
+            let mut alter_column = |operation| {
+                self.alter_table(
+                    name,
+                    AlterTableOperation::AlterColumn {
+                        name: to_column.name.clone(),
+                        operation,
+                    },
+                )
+            };
+            if normalize(&from_column.data_type) != normalize(&to_column.data_type) {
+                alter_column(AlterColumnOperation::SetDataType(
+                    to_column.data_type.clone(),
+                ));
+            }
+            match (column_default(from_column), column_default(to_column)) {
+                (Some(from_default), Some(to_default))
+                    if normalize(from_default) == normalize(to_default) => {}
+                (None, None) => (),
+                (_, Some(to_default)) => {
+                    alter_column(AlterColumnOperation::SetDefault(to_default.clone()))
+                }
+                (Some(_), None) => alter_column(AlterColumnOperation::DropDefault),
+            }
+            match (is_not_null(from_column), is_not_null(to_column)) {
+                (false, true) => alter_column(AlterColumnOperation::SetNotNull),
+                (true, false) => alter_column(AlterColumnOperation::DropNotNull),
+                _ => (),
+            }
+        }
+    }
+
+    /// Create `object` of `to`, and return the foreign keys to add once the
+    /// objects that `to` creates after it exist
+    fn create(
+        &mut self,
+        object: &'a CatalogObject,
+        created: &[&ObjectName],
+    ) -> Vec<&'a TableConstraint> {
+        let name = object.name.clone();
+        let mut deferred = vec![];
+        let statement = match &object.kind {
+            ObjectKind::Table(table) => {
+                let mut constraints = vec![];
+                for constraint in &table.constraints {
+                    let exists = |foreign_table: &ObjectName| {
+                        object_name_eq(foreign_table, &name)
+                            || created.iter().any(|c| object_name_eq(c, foreign_table))
+                            || (self.from.get(foreign_table).is_some()
+                                && !self.is_dropped(foreign_table))
+                    };
+                    match constraint {
+                        TableConstraint::ForeignKey { foreign_table, .. }
+                            if !exists(foreign_table) =>
+                        {
+                            deferred.push(constraint)
+                        }
+                        _ => constraints.push(constraint.clone()),
+                    }
+                }
+                Statement::CreateTable {
+                    name,
+                    columns: table.columns.clone(),
+                    constraints,
+                    with_options: vec![],
+                    external: false,
+                    file_format: None,
+                    location: None,
+                }
+            }
+            ObjectKind::View(view) => Statement::CreateView {
+                name,
+                columns: view.columns.clone().unwrap_or_default(),
+                query: view.query.clone(),
+                materialized: view.materialized,
+                with_options: vec![],
+            },
+            ObjectKind::Source(source) => Statement::CreateSource {
+                name,
+                url: source.url.clone(),
+                schema: source.schema.clone(),
+                with_options: source.with_options.clone(),
+            },
+            ObjectKind::Sink(sink) => Statement::CreateSink {
+                name,
+                from: sink.from.clone(),
+                url: sink.url.clone(),
+                with_options: sink.with_options.clone(),
+            },
+            ObjectKind::Index(index) => Statement::CreateIndex {
+                name: name.0.last().unwrap().clone(),
+                on_name: index.on_name.clone(),
+                key_parts: index.key_parts.clone(),
+            },
+        };
+        self.statements.push(statement);
+        deferred
+    }
+}
+
+/// Whether `from` and `to`, which have the same name, can be kept as they
+/// are, or altered in place for tables
+fn same_definition(from: &CatalogObject, to: &CatalogObject) -> bool {
+    match (&from.kind, &to.kind) {
+        (ObjectKind::Table(_), ObjectKind::Table(_)) => true,
+        (ObjectKind::View(from), ObjectKind::View(to)) => {
+            normalize(&from.query) == normalize(&to.query)
+                && from.materialized == to.materialized
+                && match (&from.columns, &to.columns) {
+                    (Some(from), Some(to)) => {
+                        from.len() == to.len()
+                            && from.iter().zip(to).all(|(from, to)| ident_eq(from, to))
+                    }
+                    (from, to) => from.is_none() && to.is_none(),
+                }
+        }
+        (ObjectKind::Source(from), ObjectKind::Source(to)) => {
+            from.url == to.url
+                && from.schema == to.schema
+                && normalize(&from.with_options) == normalize(&to.with_options)
+        }
+        (ObjectKind::Sink(from), ObjectKind::Sink(to)) => {
+            object_name_eq(&from.from, &to.from)
+                && from.url == to.url
+                && normalize(&from.with_options) == normalize(&to.with_options)
+        }
+        (ObjectKind::Index(from), ObjectKind::Index(to)) => {
+            object_name_eq(&from.on_name, &to.on_name)
+                && normalize(&from.key_parts) == normalize(&to.key_parts)
+        }
+        _ => false,
+    }
+}
+
+/// The name of a constraint of a [`Catalog`], which always has one
+fn constraint_name(constraint: &TableConstraint) -> &Ident {
+    super::catalog::constraint_name(constraint).unwrap()
+}
+
+fn is_foreign_key(constraint: &TableConstraint) -> bool {
+    matches!(constraint, TableConstraint::ForeignKey { .. })
+}
+
+fn column_default(column: &ColumnDef) -> Option<&Expr> {
+    column
+        .options
+        .iter()
+        .find_map(|option| match &option.option {
+            ColumnOption::Default(expr) => Some(expr),
+            _ => None,
+        })
+}
+
+fn is_not_null(column: &ColumnDef) -> bool {
+    column
+        .options
+        .iter()
+        .any(|ColumnOptionDef { option, .. }| matches!(option, ColumnOption::NotNull))
+}
+
+/// A copy of `node` whose identifiers are in canonical form: unquoted ones
+/// folded to lower case, and quoted ones unquoted, so that equal copies mean
+/// equal definitions
+fn normalize<T: Normalize + Clone>(node: &T) -> T {
+    node.clone().normalize(&mut CanonicalIdents)
+}
+
+struct CanonicalIdents;
+
+impl Fold for CanonicalIdents {
+    fn fold_ident(&mut self, ident: Ident) -> Ident {
+        match ident.quote_style {
+            None => Ident::new(ident.value.to_lowercase()),
+            Some(_) => Ident::new(ident.value),
+        }
+    }
+}
+
+/// The nodes that [`normalize`] applies to
+trait Normalize {
+    fn normalize(self, folder: &mut CanonicalIdents) -> Self;
+}
+
+macro_rules! impl_normalize {
+    ($($ty:ty => $fold:ident,)*) => {
+        $(
+            impl Normalize for $ty {
+                fn normalize(self, folder: &mut CanonicalIdents) -> Self {
+                    folder.$fold(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_normalize!(
+    DataType => fold_type,
+    Expr => fold_expr,
+    Query => fold_query,
+    SqlOption => fold_option,
+    TableConstraint => fold_table_constraint,
+);
+
+impl<T: Normalize> Normalize for Vec<T> {
+    fn normalize(self, folder: &mut CanonicalIdents) -> Self {
+        self.into_iter()
+            .map(|node| node.normalize(folder))
+            .collect()
+    }
+}
+
+impl<T: Normalize> Normalize for Box<T> {
+    fn normalize(self, folder: &mut CanonicalIdents) -> Self {
+        Box::new((*self).normalize(folder))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::GenericDialect;
+    use crate::parser::Parser;
+
+    fn parse(sql: &str) -> Vec<Statement> {
+        Parser::parse_sql(&GenericDialect {}, sql.to_string()).unwrap()
+    }
+
+    /// Check that the statements that migrate `from` to `to` are `expected`,
+    /// and that once they are parsed and applied to `from`, there is nothing
+    /// left to migrate
+    fn check(from: &str, to: &str, expected: &[&str]) {
+        let from = parse(from);
+        let to = parse(to);
+        let statements = diff(&from, &to).unwrap();
+        let sql: Vec<String> = statements.iter().map(|s| s.to_string()).collect();
+        assert_eq!(sql, expected);
+
+        let mut migrated = from;
+        migrated.extend(parse(&sql.join(";\n")));
+        assert_eq!(diff(&migrated, &to), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_diff_tables() {
+        check(
+            "CREATE TABLE a (id INT PRIMARY KEY, x INT UNIQUE, y TEXT DEFAULT 'y', z INT);
+            CREATE TABLE b (id INT, a_x INT REFERENCES a (x), CHECK (id > 0))",
+            "CREATE TABLE A (ID INT PRIMARY KEY, x BIGINT NOT NULL UNIQUE, y TEXT, w INT CHECK (w > 0));
+            CREATE TABLE b (id INT, a_x BIGINT REFERENCES a (x), CHECK (id > 1));
+            CREATE TABLE c (d_id INT);
+            CREATE TABLE d (id INT UNIQUE, c_id INT REFERENCES c (d_id));
+            ALTER TABLE c ADD FOREIGN KEY (d_id) REFERENCES d (id)",
+            &[
+                "ALTER TABLE b DROP CONSTRAINT b_a_x_fkey",
+                "ALTER TABLE b DROP CONSTRAINT b_id_check",
+                "ALTER TABLE A DROP COLUMN z",
+                "ALTER TABLE A ADD COLUMN w int",
+                "ALTER TABLE A ALTER COLUMN x SET DATA TYPE bigint",
+                "ALTER TABLE A ALTER COLUMN x SET NOT NULL",
+                "ALTER TABLE A ALTER COLUMN y DROP DEFAULT",
+                "ALTER TABLE b ALTER COLUMN a_x SET DATA TYPE bigint",
+                "CREATE TABLE c (d_id int)",
+                "CREATE TABLE d (id int, c_id int, CONSTRAINT d_id_key UNIQUE (id), \
+                 CONSTRAINT d_c_id_fkey FOREIGN KEY (c_id) REFERENCES c(d_id))",
+                "ALTER TABLE A ADD CONSTRAINT a_w_check CHECK (w > 0)",
+                "ALTER TABLE b ADD CONSTRAINT b_id_check CHECK (id > 1)",
+                "ALTER TABLE b ADD CONSTRAINT b_a_x_fkey FOREIGN KEY (a_x) REFERENCES a(x)",
+                "ALTER TABLE c ADD CONSTRAINT c_d_id_fkey FOREIGN KEY (d_id) REFERENCES d(id)",
+            ],
+        );
+
+        // Definitions that only differ in the case of unquoted identifiers
+        // are the same.
+        check(
+            "CREATE TABLE a (x INT DEFAULT f(1), CHECK (x > 0));
+            CREATE VIEW v AS SELECT x FROM a",
+            "CREATE TABLE A (X INT DEFAULT F(1) CHECK (X > 0));
+            CREATE VIEW V AS SELECT X FROM A",
+            &[],
+        );
+    }
+
+    #[test]
+    fn test_diff_objects() {
+        check(
+            "CREATE TABLE t (x INT, y INT);
+            CREATE VIEW v AS SELECT x FROM t;
+            CREATE VIEW w AS SELECT x FROM v;
+            CREATE INDEX i ON t (x);
+            CREATE SINK s FROM w INTO 'file:///s';
+            CREATE SOURCE src FROM 'kafka://a' USING SCHEMA 'x';
+            CREATE VIEW old AS SELECT 1",
+            "CREATE TABLE t (x INT);
+            CREATE VIEW v AS SELECT x FROM t;
+            CREATE VIEW w AS SELECT x + 1 AS x FROM v;
+            CREATE INDEX i ON t (x);
+            CREATE SINK s FROM w INTO 'file:///s';
+            CREATE SOURCE src FROM 'kafka://b' USING SCHEMA 'x'",
+            &[
+                "DROP VIEW old",
+                "DROP SOURCE src",
+                "DROP SINK s",
+                "DROP VIEW w",
+                "DROP VIEW v",
+                "ALTER TABLE t DROP COLUMN y",
+                "CREATE VIEW v (x) AS SELECT x FROM t",
+                "CREATE VIEW w (x) AS SELECT x + 1 AS x FROM v",
+                "CREATE SINK s FROM w INTO 'file:///s'",
+                "CREATE SOURCE src FROM 'kafka://b' USING SCHEMA 'x'",
+            ],
+        );
+
+        // Objects whose type changes are created again, after the objects
+        // that depend on them are dropped.
+        check(
+            "CREATE TABLE t (x INT);
+            CREATE VIEW a AS SELECT x FROM t;
+            CREATE INDEX i ON t (x)",
+            "CREATE TABLE a (x INT);
+            CREATE INDEX i ON a (x)",
+            &[
+                "DROP INDEX i",
+                "DROP VIEW a",
+                "DROP TABLE t",
+                "CREATE TABLE a (x int)",
+                "CREATE INDEX i ON a (x)",
+            ],
+        );
+    }
+
+    #[test]
+    fn test_diff_errors() {
+        let error = diff(&parse("CREATE VIEW v AS SELECT 1"), &parse("DROP VIEW v"));
+        assert_eq!(error.unwrap_err().to_string(), "view v does not exist");
+    }
+}
@@ -0,0 +1,256 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Fingerprinting statements that only differ in their literals
+//!
+//! [`normalize`] rewrites a statement with a `VisitMut`or so that statements
+//! that only differ in the values of their literals, in the case of their
+//! unquoted identifiers, or in their whitespace and comments, become equal:
+//!
+//! - each literal of an expression becomes a positional parameter `$n`,
+//!   numbered in the order of the literals, after the parameters that the
+//!   statement already has. A literal with a sign, such as `-1`, is one
+//!   literal, and so is the list of an `IN` whose items are all literals, so
+//!   that `x IN (1, 2)` and `x IN (3)` are the same;
+//! - unquoted identifiers are folded to lower case.
+//!
+//! Literals that are not expressions, such as the values of `WITH` options
+//! and of `SET`, are kept. The AST has no whitespace or comments, so the
+//! `Display` of a normalized statement is its canonical text.
+//!
+//! [`fingerprint`] hashes the normalized statement through its `Hash`
+//! implementation, which ignores the source locations of identifiers, with
+//! FNV-1a rather than `DefaultHasher`, so that the hash does not depend on a
+//! random seed or on the platform. The encoding of derived `Hash`
+//! implementations may change with the version of Rust or of this crate, and
+//! extension nodes hash their type, so the hash should only be compared with
+//! hashes computed by the same build, not stored.
+
+use std::hash::{Hash, Hasher};
+
+use crate::ast::visit::{self, Visit};
+use crate::ast::visit_mut::{self, VisitMut};
+use crate::ast::{Expr, Ident, Statement, UnaryOperator};
+
+/// A normalized statement and its hash, as computed by [`fingerprint`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fingerprint {
+    /// The statement, as [`normalize`] rewrites it
+    pub statement: Statement,
+    /// The hash of `statement`, which is the same for the statements that
+    /// have the same normalized form
+    pub hash: u64,
+}
+
+/// Normalize `statement` and hash it
+pub fn fingerprint(statement: &Statement) -> Fingerprint {
+    let mut statement = statement.clone();
+    normalize(&mut statement);
+    let mut hasher = StableHasher::new();
+    statement.hash(&mut hasher);
+    Fingerprint {
+        statement,
+        hash: hasher.finish(),
+    }
+}
+
+/// Replace the literals of `statement` with parameters, and fold its unquoted
+/// identifiers to lower case
+pub fn normalize(statement: &mut Statement) {
+    let mut parameters = MaxParameter(0);
+    parameters.visit_statement(statement);
+    Normalizer {
+        next_parameter: parameters.0 + 1,
+    }
+    .visit_statement(statement);
+}
+
+/// Finds the highest parameter number of a statement
+struct MaxParameter(usize);
+
+impl<'ast> Visit<'ast> for MaxParameter {
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        if let Expr::Parameter(n) = expr {
+            self.0 = self.0.max(*n);
+        }
+        visit::visit_expr(self, expr);
+    }
+}
+
+struct Normalizer {
+    next_parameter: usize,
+}
+
+impl Normalizer {
+    fn parameter(&mut self) -> Expr {
+        let parameter = Expr::Parameter(self.next_parameter);
+        self.next_parameter += 1;
+        parameter
+    }
+}
+
+impl<'ast> VisitMut<'ast> for Normalizer {
+    fn visit_expr(&mut self, expr: &'ast mut Expr) {
+        match expr {
+            Expr::InList { expr, list, .. } if !list.is_empty() && list.iter().all(is_literal) => {
+                self.visit_expr(expr);
+                *list = vec![self.parameter()];
+            }
+            _ if is_literal(expr) => *expr = self.parameter(),
+            _ => visit_mut::visit_expr(self, expr),
+        }
+    }
+
+    fn visit_ident(&mut self, ident: &'ast mut Ident) {
+        if ident.quote_style.is_none() {
+            ident.value = ident.value.to_lowercase();
+        }
+    }
+}
+
+fn is_literal(expr: &Expr) -> bool {
+    match expr {
+        Expr::Value(_) => true,
+        Expr::UnaryOp {
+            op: UnaryOperator::Plus,
+            expr,
+        }
+        | Expr::UnaryOp {
+            op: UnaryOperator::Minus,
+            expr,
+        } => is_literal(expr),
+        _ => false,
+    }
+}
+
+/// A 64-bit FNV-1a hasher, which hashes integers in little-endian order
+/// whatever their size, unlike `DefaultHasher`, which is randomly seeded
+struct StableHasher(u64);
+
+impl StableHasher {
+    fn new() -> Self {
+        StableHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::GenericDialect;
+    use crate::parser::Parser;
+
+    fn fingerprint_sql(sql: &str) -> Fingerprint {
+        let statements = Parser::parse_sql(&GenericDialect {}, sql.to_string()).unwrap();
+        fingerprint(&statements[0])
+    }
+
+    #[test]
+    fn test_normalize() {
+        let cases = &[
+            (
+                "SELECT * FROM T WHERE A = 1 AND b IN (1, 2, 3) LIMIT 10",
+                "SELECT * FROM t WHERE a = $1 AND b IN ($2) LIMIT $3",
+            ),
+            (
+                "SELECT -1, +2.5, - -3, NULL, TRUE, 'x', X'ab', N'y', ARRAY[1, 2]",
+                "SELECT $1, $2, $3, $4, $5, $6, $7, $8, $9",
+            ),
+            (
+                "SELECT DATE '2020-01-01', TIMESTAMP '2020-01-01 00:00:00', INTERVAL '1' DAY",
+                "SELECT $1, $2, $3",
+            ),
+            (
+                "SELECT x FROM t WHERE 1 IN (2, y, -3) AND z NOT IN ('a')",
+                "SELECT x FROM t WHERE $1 IN ($2, y, $3) AND z NOT IN ($4)",
+            ),
+            (
+                "SELECT $2 + 1, \"Quoted\".X FROM t WHERE x = $1",
+                "SELECT $2 + $3, \"Quoted\".x FROM t WHERE x = $1",
+            ),
+            (
+                "INSERT INTO t (A) VALUES (1, CAST('2' AS INT))",
+                "INSERT INTO t (a) VALUES ($1, CAST($2 AS int))",
+            ),
+            (
+                "UPDATE t SET a = a + 1 WHERE b = 'x'",
+                "UPDATE t SET a = a + $1 WHERE b = $2",
+            ),
+            (
+                "CREATE VIEW v WITH (a = 1) AS SELECT 1",
+                "CREATE VIEW v WITH (a = 1) AS SELECT $1",
+            ),
+        ];
+        for (sql, expected) in cases {
+            assert_eq!(fingerprint_sql(sql).statement.to_string(), *expected);
+        }
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        let hash = |sql| fingerprint_sql(sql).hash;
+
+        let a = hash("SELECT a, count(*) FROM t WHERE b IN (1, 2) AND c = 'x' GROUP BY a");
+        assert_eq!(
+            a,
+            hash(
+                "select A, COUNT(*)
+                 from T -- the table
+                 where B in (3) and C = 'y'
+                 group by A"
+            )
+        );
+        assert_ne!(
+            a,
+            hash("SELECT a, count(*) FROM t WHERE b IN (1, 2) GROUP BY a")
+        );
+        assert_ne!(
+            a,
+            hash("SELECT \"A\", count(*) FROM t WHERE b IN (1) AND c = 'x' GROUP BY a")
+        );
+
+        // The hash does not depend on the source locations of identifiers.
+        assert_eq!(hash("SELECT a FROM t"), hash("SELECT\n  a\nFROM\n  t"));
+    }
+}
@@ -0,0 +1,46 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Analyses of parsed SQL statements
+
+pub mod catalog;
+mod diff;
+mod fingerprint;
+mod references;
+mod resolve;
+
+pub use self::diff::{diff, diff_catalogs};
+pub use self::fingerprint::{fingerprint, normalize, Fingerprint};
+pub use self::references::{references, ColumnRef, References};
+pub use self::resolve::{
+    resolve, Binding, Relation, Resolution, ResolveError, ResolveErrorKind, TableCatalog,
+};
+
+use crate::ast::{Ident, ObjectName};
+
+/// Whether two identifiers name the same object: unquoted identifiers are
+/// case-insensitive, as they are folded to lower case, while quoted ones are
+/// compared exactly.
+pub(crate) fn ident_eq(a: &Ident, b: &Ident) -> bool {
+    match (a.quote_style, b.quote_style) {
+        (None, None) => a.value.eq_ignore_ascii_case(&b.value),
+        (None, Some(_)) => a.value.to_lowercase() == b.value,
+        (Some(_), None) => a.value == b.value.to_lowercase(),
+        (Some(_), Some(_)) => a.value == b.value,
+    }
+}
+
+/// Whether two names name the same object, comparing their identifiers with
+/// [`ident_eq`]
+pub(crate) fn object_name_eq(a: &ObjectName, b: &ObjectName) -> bool {
+    a.0.len() == b.0.len() && a.0.iter().zip(&b.0).all(|(a, b)| ident_eq(a, b))
+}
@@ -0,0 +1,680 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Finding the tables and columns that a statement refers to
+//!
+//! [`references`] walks a statement with a `Visit`or, keeping track of the
+//! CTEs and the relations of the `FROM` clauses in scope, so that the names
+//! of CTEs are not reported as tables, and columns are reported along with
+//! the table they belong to when it can be told from the statement alone:
+//!
+//! - a column qualified with the name or alias of a relation belongs to the
+//!   innermost relation of that name;
+//! - an unqualified column belongs to the only relation of the innermost
+//!   `FROM` clause, if there is one, or to the only relation whose columns
+//!   are not listed by a CTE or an alias such as `AS t (a, b)`;
+//! - a column that `USING` lists belongs to both sides of the join, each of
+//!   which is looked up like the `FROM` clause of an unqualified column.
+//!
+//! Columns of CTEs and derived tables are not reported, as the columns they
+//! are computed from are reported where the CTE or subquery refers to them,
+//! nor are references to the aliases of the `SELECT` list in `ORDER BY`.
+
+use std::slice;
+
+use super::{ident_eq, object_name_eq};
+use crate::ast::visit::{self, Visit};
+use crate::ast::{
+    Assignment, Expr, Ident, JoinConstraint, JoinOperator, ObjectName, Query, Select, SelectItem,
+    SetExpr, SqlOption, Statement, TableAlias, TableFactor, TableWithJoins,
+};
+
+/// The tables and columns that a statement refers to, as found by
+/// [`references`]
+///
+/// Each list is in the order in which its items first appear in the
+/// statement, and only has the first of the names that refer to the same
+/// table or column, such as `a` and `A`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct References {
+    /// The tables and views that the statement reads from, not including
+    /// CTEs
+    pub read_tables: Vec<ObjectName>,
+    /// The tables that the statement writes to: the target of `INSERT`,
+    /// `UPDATE`, `DELETE` and `COPY`, and the view or sink created by
+    /// `CREATE VIEW ... AS` and `CREATE SINK`
+    pub written_tables: Vec<ObjectName>,
+    /// The columns of tables that the statement refers to
+    pub columns: Vec<ColumnRef>,
+}
+
+/// A reference to a column
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ColumnRef {
+    /// The table of the column, as it is named in the statement, or `None`
+    /// if it cannot be told without knowing the columns of each table,
+    /// e.g. for an unqualified column of a join
+    pub table: Option<ObjectName>,
+    pub column: Ident,
+}
+
+/// Find the tables that `statement` reads and writes, and the columns it
+/// refers to
+pub fn references(statement: &Statement) -> References {
+    let mut collector = Collector::default();
+    collector.visit_statement(statement);
+    collector.references
+}
+
+#[derive(Default)]
+struct Collector<'ast> {
+    references: References,
+    /// The scopes of the queries and `SELECT`s being visited, innermost last
+    scopes: Vec<Scope<'ast>>,
+}
+
+/// The names defined by a query or a `SELECT`
+#[derive(Default)]
+struct Scope<'ast> {
+    /// The CTEs of a query that are visible so far
+    ctes: Vec<&'ast TableAlias>,
+    /// The relations of the `FROM` clause of a `SELECT`
+    relations: Vec<Relation<'ast>>,
+    /// The aliases of the `SELECT` list, while visiting `ORDER BY`
+    aliases: Vec<&'ast Ident>,
+}
+
+struct Relation<'ast> {
+    /// The name that qualifies the columns of the relation: its alias, or
+    /// the name of the table; empty for a derived table without an alias
+    name: &'ast [Ident],
+    /// The table, if the relation is a table whose columns are not renamed
+    table: Option<&'ast ObjectName>,
+    /// The names of the columns, if the relation is a CTE or has an alias
+    /// that lists them
+    columns: Option<&'ast [Ident]>,
+}
+
+/// What a column refers to
+enum Resolution<'ast> {
+    /// A column of a table
+    Table(&'ast ObjectName),
+    /// A column of a CTE, a derived table or a renamed table, or an alias
+    /// of the `SELECT` list
+    Derived,
+    /// A column of a table that cannot be told
+    Unknown,
+}
+
+impl<'ast> Collector<'ast> {
+    fn add_read_table(&mut self, name: &ObjectName) {
+        add_table(&mut self.references.read_tables, name)
+    }
+
+    fn add_written_table(&mut self, name: &ObjectName) {
+        add_table(&mut self.references.written_tables, name)
+    }
+
+    fn add_column(&mut self, table: Option<&ObjectName>, column: &Ident) {
+        let seen = self.references.columns.iter().any(|c| {
+            ident_eq(&c.column, column)
+                && match (&c.table, table) {
+                    (Some(a), Some(b)) => object_name_eq(a, b),
+                    (a, b) => a.is_none() && b.is_none(),
+                }
+        });
+        if !seen {
+            self.references.columns.push(ColumnRef {
+                table: table.cloned(),
+                column: column.clone(),
+            });
+        }
+    }
+
+    /// The CTE that `name` refers to, if any
+    fn cte(&self, name: &ObjectName) -> Option<&'ast TableAlias> {
+        match &name.0[..] {
+            [name] => self
+                .scopes
+                .iter()
+                .rev()
+                .flat_map(|scope| scope.ctes.iter().rev())
+                .find(|cte| ident_eq(&cte.name, name))
+                .copied(),
+            _ => None,
+        }
+    }
+
+    /// A scope with the relations of the `FROM` clause of `select`
+    fn select_scope(&self, select: &'ast Select) -> Scope<'ast> {
+        let mut scope = Scope::default();
+        for table_with_joins in &select.from {
+            self.add_relations(table_with_joins, &mut scope.relations);
+        }
+        scope
+    }
+
+    /// A scope with the table that `UPDATE`, `DELETE` or `CREATE INDEX` refer
+    /// to
+    fn table_scope(table: &'ast ObjectName) -> Scope<'ast> {
+        Scope {
+            relations: vec![Relation {
+                name: &table.0,
+                table: Some(table),
+                columns: None,
+            }],
+            ..Scope::default()
+        }
+    }
+
+    fn add_relations(
+        &self,
+        table_with_joins: &'ast TableWithJoins,
+        relations: &mut Vec<Relation<'ast>>,
+    ) {
+        self.add_relation(&table_with_joins.relation, relations);
+        for join in &table_with_joins.joins {
+            self.add_relation(&join.relation, relations);
+        }
+    }
+
+    fn add_relation(&self, table_factor: &'ast TableFactor, relations: &mut Vec<Relation<'ast>>) {
+        match table_factor {
+            TableFactor::Table { name, alias, .. } => {
+                let cte = self.cte(name);
+                let columns = alias
+                    .iter()
+                    .chain(cte)
+                    .map(|alias| &alias.columns[..])
+                    .find(|columns| !columns.is_empty());
+                relations.push(Relation {
+                    name: match alias {
+                        Some(alias) => slice::from_ref(&alias.name),
+                        None => &name.0,
+                    },
+                    table: if cte.is_none() && columns.is_none() {
+                        Some(name)
+                    } else {
+                        None
+                    },
+                    columns,
+                })
+            }
+            TableFactor::Derived { alias, .. } => relations.push(Relation {
+                name: match alias {
+                    Some(alias) => slice::from_ref(&alias.name),
+                    None => &[],
+                },
+                table: None,
+                columns: alias
+                    .as_ref()
+                    .map(|alias| &alias.columns[..])
+                    .filter(|columns| !columns.is_empty()),
+            }),
+            TableFactor::NestedJoin(table_with_joins) => {
+                self.add_relations(table_with_joins, relations)
+            }
+        }
+    }
+
+    fn resolve(&self, qualifier: &[Ident], column: &Ident) -> Resolution<'ast> {
+        if qualifier.is_empty() {
+            self.resolve_unqualified(column)
+        } else {
+            self.resolve_qualified(qualifier)
+        }
+    }
+
+    fn resolve_qualified(&self, qualifier: &[Ident]) -> Resolution<'ast> {
+        let relation = self
+            .scopes
+            .iter()
+            .rev()
+            .flat_map(|scope| scope.relations.iter())
+            .find(|relation| {
+                relation.name.len() >= qualifier.len()
+                    && relation.name[relation.name.len() - qualifier.len()..]
+                        .iter()
+                        .zip(qualifier)
+                        .all(|(a, b)| ident_eq(a, b))
+            });
+        match relation {
+            Some(Relation {
+                table: Some(table), ..
+            }) => Resolution::Table(table),
+            Some(_) => Resolution::Derived,
+            None => Resolution::Unknown,
+        }
+    }
+
+    fn resolve_unqualified(&self, column: &Ident) -> Resolution<'ast> {
+        let scope = self
+            .scopes
+            .iter()
+            .rev()
+            .find(|scope| !scope.relations.is_empty() || !scope.aliases.is_empty());
+        let scope = match scope {
+            Some(scope) => scope,
+            None => return Resolution::Unknown,
+        };
+        if scope.aliases.iter().any(|alias| ident_eq(alias, column)) {
+            return Resolution::Derived;
+        }
+        resolve_in(&scope.relations, column)
+    }
+
+    fn add_resolved_column(&mut self, resolution: Resolution, column: &Ident) {
+        match resolution {
+            Resolution::Table(table) => self.add_column(Some(table), column),
+            Resolution::Derived => (),
+            Resolution::Unknown => self.add_column(None, column),
+        }
+    }
+
+    /// Visit `exprs` in `scope`
+    fn visit_exprs_in(&mut self, scope: Scope<'ast>, exprs: impl Iterator<Item = &'ast Expr>) {
+        self.scopes.push(scope);
+        for expr in exprs {
+            self.visit_expr(expr);
+        }
+        self.scopes.pop();
+    }
+}
+
+/// What the unqualified `column` refers to among `relations`
+fn resolve_in<'ast>(relations: &[Relation<'ast>], column: &Ident) -> Resolution<'ast> {
+    let has_column = |relation: &Relation| match relation.columns {
+        Some(columns) => columns.iter().any(|c| ident_eq(c, column)),
+        None => false,
+    };
+    if relations.iter().any(has_column) {
+        return Resolution::Derived;
+    }
+    let mut candidates = relations
+        .iter()
+        .filter(|relation| relation.columns.is_none());
+    match (candidates.next(), candidates.next()) {
+        (
+            Some(Relation {
+                table: Some(table), ..
+            }),
+            None,
+        ) => Resolution::Table(table),
+        (Some(_), None) => Resolution::Derived,
+        _ => Resolution::Unknown,
+    }
+}
+
+fn add_table(tables: &mut Vec<ObjectName>, name: &ObjectName) {
+    if !tables.iter().any(|table| object_name_eq(table, name)) {
+        tables.push(name.clone());
+    }
+}
+
+impl<'ast> Visit<'ast> for Collector<'ast> {
+    fn visit_query(&mut self, query: &'ast Query) {
+        self.scopes.push(Scope::default());
+        for cte in &query.ctes {
+            self.visit_cte(cte);
+            self.scopes.last_mut().unwrap().ctes.push(&cte.alias);
+        }
+        self.visit_set_expr(&query.body);
+        // `ORDER BY` refers to the columns of the `FROM` clause or the
+        // aliases of a `SELECT`, or else to the columns of the result.
+        let scope = match &query.body {
+            SetExpr::Select(select) => Scope {
+                aliases: select
+                    .projection
+                    .iter()
+                    .filter_map(|item| match item {
+                        SelectItem::ExprWithAlias { alias, .. } => Some(alias),
+                        _ => None,
+                    })
+                    .collect(),
+                ..self.select_scope(select)
+            },
+            _ => Scope {
+                relations: vec![Relation {
+                    name: &[],
+                    table: None,
+                    columns: None,
+                }],
+                ..Scope::default()
+            },
+        };
+        self.scopes.push(scope);
+        for order_by in &query.order_by {
+            self.visit_order_by(order_by);
+        }
+        self.scopes.pop();
+        if let Some(limit) = &query.limit {
+            self.visit_limit(limit);
+        }
+        if let Some(offset) = &query.offset {
+            self.visit_offset(offset);
+        }
+        if let Some(fetch) = &query.fetch {
+            self.visit_fetch(fetch);
+        }
+        self.scopes.pop();
+    }
+
+    fn visit_select(&mut self, select: &'ast Select) {
+        let scope = self.select_scope(select);
+        self.scopes.push(scope);
+        visit::visit_select(self, select);
+        self.scopes.pop();
+    }
+
+    fn visit_table_table_factor(
+        &mut self,
+        name: &'ast ObjectName,
+        alias: Option<&'ast TableAlias>,
+        args: &'ast [Expr],
+        with_hints: &'ast [Expr],
+    ) {
+        if self.cte(name).is_none() {
+            self.add_read_table(name);
+        }
+        visit::visit_table_table_factor(self, name, alias, args, with_hints)
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        let column = match expr {
+            Expr::Identifier(ident) => Some((ident, &[][..])),
+            Expr::CompoundIdentifier(idents) => idents.split_last(),
+            _ => None,
+        };
+        if let Some((column, qualifier)) = column {
+            let resolution = self.resolve(qualifier, column);
+            self.add_resolved_column(resolution, column);
+        }
+        visit::visit_expr(self, expr)
+    }
+
+    fn visit_table_with_joins(&mut self, table_with_joins: &'ast TableWithJoins) {
+        self.visit_table_factor(&table_with_joins.relation);
+        let mut left = vec![];
+        self.add_relation(&table_with_joins.relation, &mut left);
+        for join in &table_with_joins.joins {
+            self.visit_join(join);
+            let mut right = vec![];
+            self.add_relation(&join.relation, &mut right);
+            // The columns that `USING` lists are columns of both sides.
+            if let JoinOperator::Inner(JoinConstraint::Using(columns))
+            | JoinOperator::LeftOuter(JoinConstraint::Using(columns))
+            | JoinOperator::RightOuter(JoinConstraint::Using(columns))
+            | JoinOperator::FullOuter(JoinConstraint::Using(columns)) = &join.join_operator
+            {
+                for column in columns {
+                    self.add_resolved_column(resolve_in(&left, column), column);
+                    self.add_resolved_column(resolve_in(&right, column), column);
+                }
+            }
+            left.extend(right);
+        }
+    }
+
+    fn visit_insert(
+        &mut self,
+        table_name: &'ast ObjectName,
+        columns: &'ast [Ident],
+        source: &'ast Query,
+    ) {
+        self.add_written_table(table_name);
+        for column in columns {
+            self.add_column(Some(table_name), column);
+        }
+        self.visit_query(source)
+    }
+
+    fn visit_copy(
+        &mut self,
+        table_name: &'ast ObjectName,
+        columns: &'ast [Ident],
+        _values: &'ast [Option<String>],
+    ) {
+        self.add_written_table(table_name);
+        for column in columns {
+            self.add_column(Some(table_name), column);
+        }
+    }
+
+    fn visit_update(
+        &mut self,
+        table_name: &'ast ObjectName,
+        assignments: &'ast [Assignment],
+        selection: Option<&'ast Expr>,
+    ) {
+        self.add_written_table(table_name);
+        for assignment in assignments {
+            self.add_column(Some(table_name), &assignment.id);
+        }
+        let exprs = assignments.iter().map(|assignment| &assignment.value);
+        self.visit_exprs_in(Self::table_scope(table_name), exprs.chain(selection))
+    }
+
+    fn visit_delete(&mut self, table_name: &'ast ObjectName, selection: Option<&'ast Expr>) {
+        self.add_written_table(table_name);
+        self.visit_exprs_in(Self::table_scope(table_name), selection.into_iter())
+    }
+
+    fn visit_create_view(
+        &mut self,
+        name: &'ast ObjectName,
+        _columns: &'ast [Ident],
+        query: &'ast Query,
+        _materialized: bool,
+        _with_options: &'ast [SqlOption],
+    ) {
+        self.add_written_table(name);
+        self.visit_query(query)
+    }
+
+    fn visit_create_sink(
+        &mut self,
+        name: &'ast ObjectName,
+        from: &'ast ObjectName,
+        _url: &'ast String,
+        _with_options: &'ast Vec<SqlOption>,
+    ) {
+        self.add_written_table(name);
+        self.add_read_table(from);
+    }
+
+    fn visit_create_index(
+        &mut self,
+        _name: &'ast Ident,
+        on_name: &'ast ObjectName,
+        key_parts: &'ast Vec<Expr>,
+    ) {
+        self.add_read_table(on_name);
+        self.visit_exprs_in(Self::table_scope(on_name), key_parts.iter())
+    }
+
+    fn visit_peek(&mut self, name: &'ast ObjectName, _immediate: bool) {
+        self.add_read_table(name);
+    }
+
+    fn visit_tail(&mut self, name: &'ast ObjectName) {
+        self.add_read_table(name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::GenericDialect;
+    use crate::parser::Parser;
+
+    fn parse(sql: &str) -> References {
+        let statements = Parser::parse_sql(&GenericDialect {}, sql.to_string()).unwrap();
+        assert_eq!(statements.len(), 1, "{}", sql);
+        references(&statements[0])
+    }
+
+    fn names(names: &[ObjectName]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    /// The columns that `sql` refers to, as `table.column`, or `?.column`
+    /// when the table is not known
+    fn columns(sql: &str) -> Vec<String> {
+        parse(sql)
+            .columns
+            .iter()
+            .map(|column| match &column.table {
+                Some(table) => format!("{}.{}", table, column.column),
+                None => format!("?.{}", column.column),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_read_tables() {
+        let cases: &[(&str, &[&str])] = &[
+            ("SELECT * FROM a, b JOIN c ON b.x = c.x", &["a", "b", "c"]),
+            (
+                "SELECT * FROM s.a AS x JOIN (b CROSS JOIN a) ON true",
+                &["s.a", "b", "a"],
+            ),
+            (
+                "SELECT * FROM a WHERE x IN (SELECT x FROM b) AND EXISTS (SELECT * FROM c)",
+                &["a", "b", "c"],
+            ),
+            (
+                "SELECT * FROM (SELECT * FROM a) AS d, LATERAL (SELECT 1) AS e",
+                &["a"],
+            ),
+            // CTEs are visible after their definition, and shadow tables
+            // whose name is not qualified.
+            (
+                "WITH c1 AS (SELECT * FROM c2), c2 AS (SELECT * FROM c1) \
+                 SELECT * FROM c1, c2, s.c1",
+                &["c2", "s.c1"],
+            ),
+            (
+                "SELECT * FROM (WITH c AS (SELECT 1) SELECT * FROM c) AS d, c",
+                &["c"],
+            ),
+            ("SELECT * FROM A, \"A\", a", &["A", "\"A\""]),
+            ("PEEK v", &["v"]),
+            ("TAIL v", &["v"]),
+            ("EXPLAIN PLAN FOR SELECT * FROM a", &["a"]),
+        ];
+        for (sql, tables) in cases {
+            let references = parse(sql);
+            assert_eq!(names(&references.read_tables), *tables, "{}", sql);
+            assert!(references.written_tables.is_empty(), "{}", sql);
+        }
+    }
+
+    #[test]
+    fn test_written_tables() {
+        let cases: &[(&str, &[&str], &[&str])] = &[
+            ("INSERT INTO a SELECT * FROM b", &["b"], &["a"]),
+            ("INSERT INTO a (x) VALUES (1)", &[], &["a"]),
+            ("UPDATE a SET x = (SELECT y FROM b)", &["b"], &["a"]),
+            ("DELETE FROM a WHERE x IN (SELECT x FROM b)", &["b"], &["a"]),
+            ("COPY a (x, y) FROM STDIN;", &[], &["a"]),
+            (
+                "CREATE VIEW v AS SELECT * FROM a JOIN b ON a.x = b.x",
+                &["a", "b"],
+                &["v"],
+            ),
+            ("CREATE SINK s FROM v INTO 'file:///sink'", &["v"], &["s"]),
+        ];
+        for (sql, read, written) in cases {
+            let references = parse(sql);
+            assert_eq!(names(&references.read_tables), *read, "{}", sql);
+            assert_eq!(names(&references.written_tables), *written, "{}", sql);
+        }
+    }
+
+    #[test]
+    fn test_columns() {
+        let cases: &[(&str, &[&str])] = &[
+            (
+                "SELECT x, a.y FROM a WHERE z = 1 GROUP BY w",
+                &["a.x", "a.y", "a.z", "a.w"],
+            ),
+            (
+                "SELECT x, t.y, s.a.z FROM s.a AS t",
+                &["s.a.x", "s.a.y", "?.z"],
+            ),
+            ("SELECT a.x, s.a.y FROM s.a", &["s.a.x", "s.a.y"]),
+            ("SELECT X, \"X\", x FROM a", &["a.X", "a.\"X\""]),
+            // Unqualified columns of joins could belong to any table.
+            (
+                "SELECT x, a.y FROM a JOIN b ON a.z = b.z",
+                &["?.x", "a.y", "a.z", "b.z"],
+            ),
+            // Columns of CTEs and derived tables are reported inside them.
+            ("WITH c AS (SELECT x FROM a) SELECT x, c.x FROM c", &["a.x"]),
+            ("SELECT d.y, y FROM (SELECT x AS y FROM a) AS d", &["a.x"]),
+            // Unless the columns are listed, they could belong to other tables.
+            ("SELECT x, y FROM (SELECT 1, 2) AS d (x), b", &["b.y"]),
+            (
+                "WITH c (x) AS (SELECT 1) SELECT x, y FROM c JOIN b USING (x)",
+                &["b.y", "b.x"],
+            ),
+            // `USING` refers to the columns of both sides of a join.
+            ("SELECT 1 FROM a JOIN b USING (k)", &["a.k", "b.k"]),
+            (
+                "SELECT k FROM a JOIN b USING (k) LEFT JOIN (SELECT 1 AS k) AS d USING (k)",
+                &["?.k", "a.k", "b.k"],
+            ),
+            (
+                "SELECT 1 FROM a JOIN (b JOIN c USING (j)) USING (k)",
+                &["b.j", "c.j", "a.k", "?.k"],
+            ),
+            ("SELECT x, t.y FROM a AS t (x, y)", &[]),
+            // Subqueries can refer to the columns of the outer query.
+            (
+                "SELECT x FROM a WHERE EXISTS (SELECT 1 FROM b WHERE b.y = a.y AND z)",
+                &["a.x", "b.y", "a.y", "b.z"],
+            ),
+            ("SELECT (SELECT x) FROM a", &["a.x"]),
+            ("SELECT * FROM a, LATERAL (SELECT a.x) AS d", &["a.x"]),
+            // `ORDER BY` can refer to aliases and the columns of the `FROM`
+            // clause, but not to those of a union.
+            ("SELECT x AS y FROM a ORDER BY y, z", &["a.x", "a.z"]),
+            (
+                "SELECT x FROM a UNION SELECT y FROM b ORDER BY x",
+                &["a.x", "b.y"],
+            ),
+            (
+                "SELECT x FROM a ORDER BY (SELECT y FROM b)",
+                &["a.x", "b.y"],
+            ),
+            (
+                "SELECT COUNT(*) OVER (PARTITION BY x ORDER BY y) FROM a",
+                &["a.x", "a.y"],
+            ),
+            ("SELECT unknown.x, y", &["?.x", "?.y"]),
+            (
+                "INSERT INTO a (x, y) SELECT z FROM b",
+                &["a.x", "a.y", "b.z"],
+            ),
+            ("UPDATE a SET x = y WHERE a.z = 1", &["a.x", "a.y", "a.z"]),
+            (
+                "DELETE FROM s.a WHERE x = (SELECT MAX(x) FROM b)",
+                &["s.a.x", "b.x"],
+            ),
+            ("COPY a (x) FROM STDIN;", &["a.x"]),
+            ("CREATE INDEX i ON a (x, y + 1)", &["a.x", "a.y"]),
+        ];
+        for (sql, expected) in cases {
+            assert_eq!(columns(sql), *expected, "{}", sql);
+        }
+    }
+}
@@ -0,0 +1,906 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Binding the columns of a query to the relations they come from
+//!
+//! [`resolve`] builds the scopes of a query as PostgreSQL does:
+//!
+//! - each query defines its CTEs, which are visible to the CTEs after them,
+//!   to its body and to its subqueries;
+//! - each `SELECT` defines the relations of its `FROM` clause, named by their
+//!   alias or the name of their table, whose columns may be renamed by the
+//!   alias, e.g. `AS t (a, b)`. The columns of a CTE or a derived table are
+//!   those of the result of its query;
+//! - a subquery in `FROM` sees the relations before it only if it is
+//!   `LATERAL`, and the condition of a join sees the relations before it;
+//! - the columns that `USING` or `NATURAL` joins merge are referred to once,
+//!   as columns of the leftmost relation that has them;
+//! - an unqualified name in `ORDER BY` refers to a column of the result
+//!   before the columns of the `FROM` clause, and one in `GROUP BY` after
+//!   them;
+//! - a name that is not found in a scope is looked up in the enclosing ones.
+//!
+//! The columns of tables are provided by a [`TableCatalog`]. Without one, or
+//! for the tables it does not know, a column is assumed to belong to the
+//! only relation of the innermost scope whose columns are not all known.
+
+use std::error::Error;
+use std::fmt;
+use std::mem;
+use std::ops::Range;
+use std::slice;
+
+use super::ident_eq;
+use crate::ast::visit::{self, Visit};
+use crate::ast::{
+    Cte, Expr, Ident, JoinConstraint, JoinOperator, ObjectName, Query, Select, SelectItem, SetExpr,
+    SetOperator, Spanned, TableFactor, TableWithJoins, Values,
+};
+
+/// Provides the columns of the tables and views that queries refer to
+pub trait TableCatalog {
+    /// The columns of the table or view `name`, in order, or `None` if the
+    /// table is not known
+    fn table_columns(&self, name: &ObjectName) -> Option<Vec<Ident>>;
+}
+
+/// Bind the column references of `query` to the relations they come from,
+/// looking up the columns of tables in `catalog`
+pub fn resolve<'ast>(query: &'ast Query, catalog: Option<&dyn TableCatalog>) -> Resolution<'ast> {
+    let mut resolver = Resolver {
+        catalog,
+        scopes: vec![],
+        output: Columns::default(),
+        select_scope: None,
+        resolution: Resolution::default(),
+    };
+    resolver.visit_query(query);
+    if resolver.output.complete {
+        resolver.resolution.columns = Some(resolver.output.names);
+    }
+    resolver.resolution
+}
+
+/// The result of [`resolve`]
+///
+/// Each `Expr::Identifier` and `Expr::CompoundIdentifier` of the query is
+/// in exactly one of the lists, in the order in which they appear.
+#[derive(Debug, Clone, Default)]
+pub struct Resolution<'ast> {
+    /// The column references and the relations they come from
+    pub bindings: Vec<Binding<'ast>>,
+    /// The column references that may belong to several relations whose
+    /// columns are not all known
+    pub unresolved: Vec<&'ast Expr>,
+    /// The column references that are ambiguous or refer to nothing
+    pub errors: Vec<ResolveError<'ast>>,
+    /// The names of the columns of the result of the query, if they are all
+    /// known
+    pub columns: Option<Vec<Ident>>,
+}
+
+/// A column reference bound to a relation
+#[derive(Debug, Clone, Copy)]
+pub struct Binding<'ast> {
+    /// The identifier or compound identifier that refers to the column
+    pub expr: &'ast Expr,
+    pub relation: Relation<'ast>,
+}
+
+/// A relation that columns can come from
+#[derive(Debug, Clone, Copy)]
+pub enum Relation<'ast> {
+    /// A table or view, as found in a `FROM` clause
+    Table(&'ast TableFactor),
+    /// A CTE, and the reference to it in a `FROM` clause
+    Cte {
+        cte: &'ast Cte,
+        table_factor: &'ast TableFactor,
+    },
+    /// A subquery in a `FROM` clause
+    Derived(&'ast TableFactor),
+    /// The result of a query, to which its `ORDER BY` refers
+    Output(&'ast Query),
+    /// The result of a `SELECT`, to which its `GROUP BY` refers
+    SelectOutput(&'ast Select),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResolveErrorKind {
+    /// No relation in scope has the column
+    UnknownColumn,
+    /// No relation in scope has the name that qualifies the column
+    UnknownTable,
+    /// Several relations of the same scope have the column
+    AmbiguousColumn,
+    /// Several relations of the same scope have the name that qualifies the
+    /// column
+    AmbiguousTable,
+}
+
+/// A column reference that [`resolve`] could not bind
+#[derive(Debug, Clone, Copy)]
+pub struct ResolveError<'ast> {
+    pub kind: ResolveErrorKind,
+    /// The identifier or compound identifier that refers to the column
+    pub expr: &'ast Expr,
+}
+
+impl<'ast> fmt::Display for ResolveError<'ast> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let idents = match self.expr {
+            Expr::Identifier(ident) => slice::from_ref(ident),
+            Expr::CompoundIdentifier(idents) => &idents[..],
+            _ => &[],
+        };
+        let qualifier = ObjectName(idents[..idents.len().saturating_sub(1)].to_vec());
+        match self.kind {
+            ResolveErrorKind::UnknownColumn => write!(f, "column {} does not exist", self.expr)?,
+            ResolveErrorKind::UnknownTable => {
+                write!(f, "missing FROM-clause entry for table {}", qualifier)?
+            }
+            ResolveErrorKind::AmbiguousColumn => {
+                write!(f, "column reference {} is ambiguous", self.expr)?
+            }
+            ResolveErrorKind::AmbiguousTable => {
+                write!(f, "table reference {} is ambiguous", qualifier)?
+            }
+        }
+        let span = self.expr.span();
+        if !span.is_empty() {
+            write!(f, " at {}", span.start)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'ast> Error for ResolveError<'ast> {}
+
+/// The names of the columns of a relation
+#[derive(Debug, Clone, Default)]
+struct Columns {
+    names: Vec<Ident>,
+    /// Whether these are all the columns of the relation
+    complete: bool,
+}
+
+impl Columns {
+    fn contains(&self, column: &Ident) -> bool {
+        self.names.iter().any(|name| ident_eq(name, column))
+    }
+
+    /// The columns as renamed by the columns of an alias
+    fn renamed(mut self, aliases: &[Ident]) -> Columns {
+        for (i, alias) in aliases.iter().enumerate() {
+            match self.names.get_mut(i) {
+                Some(name) => *name = alias.clone(),
+                None => self.names.push(alias.clone()),
+            }
+        }
+        self
+    }
+}
+
+/// The names defined by a query or a `SELECT`
+#[derive(Default)]
+struct Scope<'ast> {
+    /// The CTEs of a query that are visible so far, and their columns
+    ctes: Vec<(&'ast Cte, Columns)>,
+    /// The relations of the `FROM` clause of a `SELECT` that are visible so
+    /// far
+    relations: Vec<RelationScope<'ast>>,
+    /// The columns of `relations`, by index, that `USING` and `NATURAL` joins
+    /// merged into the columns of relations to their left
+    merged: Vec<(usize, Ident)>,
+    /// Whether the columns merged by a `NATURAL` join are not known
+    unknown_merged: bool,
+}
+
+impl<'ast> Scope<'ast> {
+    /// Whether a `USING` or `NATURAL` join merged `column` of the `i`th
+    /// relation into that of a relation to its left
+    fn is_merged(&self, i: usize, column: &Ident) -> bool {
+        self.merged
+            .iter()
+            .any(|(j, c)| *j == i && ident_eq(c, column))
+    }
+}
+
+struct RelationScope<'ast> {
+    relation: Relation<'ast>,
+    /// The name that qualifies the columns of the relation: its alias, or
+    /// the name of its table; empty for a derived table without an alias
+    name: &'ast [Ident],
+    columns: Columns,
+}
+
+/// What a column reference refers to
+enum Lookup<'ast> {
+    Found(Relation<'ast>),
+    Unresolved,
+    Error(ResolveErrorKind),
+}
+
+struct Resolver<'a, 'ast> {
+    catalog: Option<&'a dyn TableCatalog>,
+    /// The scopes of the queries and `SELECT`s being visited, innermost last
+    scopes: Vec<Scope<'ast>>,
+    /// The columns of the result of the last query, `SELECT` or `VALUES`
+    /// visited
+    output: Columns,
+    /// The scope of the last `SELECT` visited, to which the `ORDER BY` of a
+    /// query also refers
+    select_scope: Option<Scope<'ast>>,
+    resolution: Resolution<'ast>,
+}
+
+impl<'a, 'ast> Resolver<'a, 'ast> {
+    fn scope(&mut self) -> &mut Scope<'ast> {
+        self.scopes
+            .last_mut()
+            .expect("visiting outside of any scope")
+    }
+
+    /// The CTE that `name` refers to, if any, and its columns
+    fn cte(&self, name: &ObjectName) -> Option<(&'ast Cte, Columns)> {
+        let name = match &name.0[..] {
+            [name] => name,
+            _ => return None,
+        };
+        self.scopes
+            .iter()
+            .rev()
+            .flat_map(|scope| scope.ctes.iter().rev())
+            .find(|(cte, _)| ident_eq(&cte.alias.name, name))
+            .map(|(cte, columns)| (*cte, columns.clone()))
+    }
+
+    fn table_columns(&self, name: &ObjectName) -> Columns {
+        match self.catalog.and_then(|catalog| catalog.table_columns(name)) {
+            Some(names) => Columns {
+                names,
+                complete: true,
+            },
+            None => Columns::default(),
+        }
+    }
+
+    fn add_relation(&mut self, relation: Relation<'ast>, name: &'ast [Ident], columns: Columns) {
+        self.scope().relations.push(RelationScope {
+            relation,
+            name,
+            columns,
+        })
+    }
+
+    /// Merge the columns of the right side of a join that `USING` lists, or
+    /// for a `NATURAL` join, that its left side also has. The relations of
+    /// the left side are those from `left` to `right`, and those of the right
+    /// side are the ones after them.
+    fn merge(&mut self, left: usize, right: usize, using: Option<&'ast [Ident]>) {
+        let scope = self.scope();
+        let columns: Vec<Ident> = match using {
+            Some(using) => using.to_vec(),
+            None if scope.relations[left..].iter().any(|r| !r.columns.complete) => {
+                scope.unknown_merged = true;
+                return;
+            }
+            None => {
+                let visible_in = |range: Range<usize>, column: &Ident| {
+                    range.into_iter().any(|i| {
+                        scope.relations[i].columns.contains(column) && !scope.is_merged(i, column)
+                    })
+                };
+                (right..scope.relations.len())
+                    .flat_map(|i| &scope.relations[i].columns.names)
+                    .filter(|column| visible_in(left..right, column))
+                    .filter(|column| visible_in(right..scope.relations.len(), column))
+                    .cloned()
+                    .collect()
+            }
+        };
+        for i in right..scope.relations.len() {
+            for column in &columns {
+                scope.merged.push((i, column.clone()));
+            }
+        }
+    }
+
+    fn bind(&mut self, expr: &'ast Expr, lookup: Lookup<'ast>) {
+        match lookup {
+            Lookup::Found(relation) => self.resolution.bindings.push(Binding { expr, relation }),
+            Lookup::Unresolved => self.resolution.unresolved.push(expr),
+            Lookup::Error(kind) => self.resolution.errors.push(ResolveError { kind, expr }),
+        }
+    }
+
+    fn lookup_qualified(&self, qualifier: &[Ident], column: &Ident) -> Lookup<'ast> {
+        for scope in self.scopes.iter().rev() {
+            let mut relations = scope.relations.iter().filter(|relation| {
+                relation.name.len() >= qualifier.len()
+                    && relation.name[relation.name.len() - qualifier.len()..]
+                        .iter()
+                        .zip(qualifier)
+                        .all(|(a, b)| ident_eq(a, b))
+            });
+            match (relations.next(), relations.next()) {
+                (None, _) => continue,
+                (Some(relation), None) => {
+                    return if relation.columns.complete && !relation.columns.contains(column) {
+                        Lookup::Error(ResolveErrorKind::UnknownColumn)
+                    } else {
+                        Lookup::Found(relation.relation)
+                    };
+                }
+                (Some(_), Some(_)) => return Lookup::Error(ResolveErrorKind::AmbiguousTable),
+            }
+        }
+        Lookup::Error(ResolveErrorKind::UnknownTable)
+    }
+
+    fn lookup_unqualified(&self, column: &Ident) -> Lookup<'ast> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| lookup_in_scope(scope, column))
+            .unwrap_or(Lookup::Error(ResolveErrorKind::UnknownColumn))
+    }
+
+    /// The columns of the result of `select`, whose scope is the innermost
+    fn select_output(&self, select: &'ast Select) -> Columns {
+        let scope = self.scopes.last().expect("visiting outside of any scope");
+        let mut output = Columns {
+            names: vec![],
+            complete: true,
+        };
+        for item in &select.projection {
+            let relations: Vec<usize> = match item {
+                SelectItem::UnnamedExpr(expr) => {
+                    output.names.push(output_name(expr));
+                    continue;
+                }
+                SelectItem::ExprWithAlias { alias, .. } => {
+                    output.names.push(alias.clone());
+                    continue;
+                }
+                SelectItem::Wildcard => (0..scope.relations.len()).collect(),
+                SelectItem::QualifiedWildcard(name) => {
+                    let relation = scope.relations.iter().position(|relation| {
+                        relation.name.len() == name.0.len()
+                            && relation
+                                .name
+                                .iter()
+                                .zip(&name.0)
+                                .all(|(a, b)| ident_eq(a, b))
+                    });
+                    match relation {
+                        Some(i) => vec![i],
+                        None => {
+                            output.complete = false;
+                            continue;
+                        }
+                    }
+                }
+            };
+            for i in relations {
+                let columns = &scope.relations[i].columns;
+                output.complete &= columns.complete;
+                output.names.extend(
+                    columns
+                        .names
+                        .iter()
+                        .filter(|column| !scope.is_merged(i, column))
+                        .cloned(),
+                );
+            }
+        }
+        output
+    }
+}
+
+/// Look up an unqualified `column` in the relations of `scope`, or return
+/// `None` if none of them may have it
+fn lookup_in_scope<'ast>(scope: &Scope<'ast>, column: &Ident) -> Option<Lookup<'ast>> {
+    // The relations that have the column, and those that may have it
+    let mut found = vec![];
+    let mut unknown = vec![];
+    for (i, relation) in scope.relations.iter().enumerate() {
+        if scope.is_merged(i, column) {
+            continue;
+        }
+        if relation.columns.contains(column) {
+            found.push(relation.relation);
+        } else if !relation.columns.complete {
+            unknown.push(relation.relation);
+        }
+    }
+    match (&found[..], &unknown[..]) {
+        ([], []) => None,
+        ([relation], []) | ([], [relation]) => Some(Lookup::Found(*relation)),
+        ([_, _, ..], _) if !scope.unknown_merged => {
+            Some(Lookup::Error(ResolveErrorKind::AmbiguousColumn))
+        }
+        _ => Some(Lookup::Unresolved),
+    }
+}
+
+/// The name of the column of the result of a query that `expr` computes
+fn output_name(expr: &Expr) -> Ident {
+    match expr {
+        Expr::Identifier(ident) => ident.clone(),
+        Expr::CompoundIdentifier(idents) => idents.last().unwrap().clone(),
+        Expr::Function(function) => function.name.0.last().unwrap().clone(),
+        Expr::Cast { expr, .. } | Expr::Nested(expr) => output_name(expr),
+        Expr::Case { .. } => Ident::new("case"),
+        _ => Ident::with_quote('"', "?column?"),
+    }
+}
+
+impl<'a, 'ast> Visit<'ast> for Resolver<'a, 'ast> {
+    fn visit_query(&mut self, query: &'ast Query) {
+        self.scopes.push(Scope::default());
+        for cte in &query.ctes {
+            self.visit_query(&cte.query);
+            let columns = mem::take(&mut self.output).renamed(&cte.alias.columns);
+            self.scope().ctes.push((cte, columns));
+        }
+        self.visit_set_expr(&query.body);
+        let output = mem::take(&mut self.output);
+        // `ORDER BY` also sees the `FROM` clause of a `SELECT`.
+        let scope = match &query.body {
+            SetExpr::Select(_) => self.select_scope.take().unwrap_or_default(),
+            _ => Scope::default(),
+        };
+        self.scopes.push(scope);
+        for order_by in &query.order_by {
+            match &order_by.expr {
+                Expr::Identifier(ident) if output.contains(ident) => {
+                    let count = output.names.iter().filter(|c| ident_eq(c, ident)).count();
+                    let lookup = if count == 1 {
+                        Lookup::Found(Relation::Output(query))
+                    } else {
+                        Lookup::Error(ResolveErrorKind::AmbiguousColumn)
+                    };
+                    self.bind(&order_by.expr, lookup)
+                }
+                _ => self.visit_order_by(order_by),
+            }
+        }
+        self.scopes.pop();
+        if let Some(limit) = &query.limit {
+            self.visit_limit(limit);
+        }
+        if let Some(offset) = &query.offset {
+            self.visit_offset(offset);
+        }
+        if let Some(fetch) = &query.fetch {
+            self.visit_fetch(fetch);
+        }
+        self.scopes.pop();
+        self.output = output;
+    }
+
+    fn visit_select(&mut self, select: &'ast Select) {
+        self.scopes.push(Scope::default());
+        for table_with_joins in &select.from {
+            self.visit_table_with_joins(table_with_joins);
+        }
+        for select_item in &select.projection {
+            self.visit_select_item(select_item);
+        }
+        if let Some(selection) = &select.selection {
+            self.visit_where(selection);
+        }
+        let output = self.select_output(select);
+        for expr in &select.group_by {
+            match expr {
+                // An unqualified name that no relation of the `FROM` clause
+                // has refers to a column of the result.
+                Expr::Identifier(ident)
+                    if output.contains(ident) && lookup_in_scope(self.scope(), ident).is_none() =>
+                {
+                    let count = output.names.iter().filter(|c| ident_eq(c, ident)).count();
+                    let lookup = if count == 1 {
+                        Lookup::Found(Relation::SelectOutput(select))
+                    } else {
+                        Lookup::Error(ResolveErrorKind::AmbiguousColumn)
+                    };
+                    self.bind(expr, lookup)
+                }
+                _ => self.visit_expr(expr),
+            }
+        }
+        if let Some(having) = &select.having {
+            self.visit_having(having);
+        }
+        self.output = output;
+        self.select_scope = self.scopes.pop();
+    }
+
+    fn visit_table_with_joins(&mut self, table_with_joins: &'ast TableWithJoins) {
+        let left = self.scope().relations.len();
+        self.visit_table_factor(&table_with_joins.relation);
+        for join in &table_with_joins.joins {
+            let right = self.scope().relations.len();
+            self.visit_table_factor(&join.relation);
+            let constraint = match &join.join_operator {
+                JoinOperator::Inner(constraint)
+                | JoinOperator::LeftOuter(constraint)
+                | JoinOperator::RightOuter(constraint)
+                | JoinOperator::FullOuter(constraint) => constraint,
+                JoinOperator::CrossJoin | JoinOperator::CrossApply | JoinOperator::OuterApply => {
+                    continue
+                }
+            };
+            match constraint {
+                JoinConstraint::On(expr) => self.visit_expr(expr),
+                JoinConstraint::Using(columns) => self.merge(left, right, Some(columns)),
+                JoinConstraint::Natural => self.merge(left, right, None),
+            }
+        }
+    }
+
+    fn visit_table_factor(&mut self, table_factor: &'ast TableFactor) {
+        match table_factor {
+            TableFactor::Table {
+                name,
+                alias,
+                args,
+                with_hints,
+            } => {
+                visit::visit_table_table_factor(self, name, alias.as_ref(), args, with_hints);
+                let (relation, columns) = match self.cte(name) {
+                    Some((cte, columns)) => (Relation::Cte { cte, table_factor }, columns),
+                    None => (Relation::Table(table_factor), self.table_columns(name)),
+                };
+                match alias {
+                    Some(alias) => self.add_relation(
+                        relation,
+                        slice::from_ref(&alias.name),
+                        columns.renamed(&alias.columns),
+                    ),
+                    None => self.add_relation(relation, &name.0, columns),
+                }
+            }
+            TableFactor::Derived {
+                lateral,
+                subquery,
+                alias,
+            } => {
+                // Only a `LATERAL` subquery sees the relations before it.
+                if *lateral {
+                    self.visit_query(subquery);
+                } else {
+                    let scope = mem::take(self.scope());
+                    self.visit_query(subquery);
+                    *self.scope() = scope;
+                }
+                let columns = mem::take(&mut self.output);
+                match alias {
+                    Some(alias) => self.add_relation(
+                        Relation::Derived(table_factor),
+                        slice::from_ref(&alias.name),
+                        columns.renamed(&alias.columns),
+                    ),
+                    None => self.add_relation(Relation::Derived(table_factor), &[], columns),
+                }
+            }
+            TableFactor::NestedJoin(table_with_joins) => {
+                self.visit_table_with_joins(table_with_joins)
+            }
+        }
+    }
+
+    fn visit_set_operation(
+        &mut self,
+        left: &'ast SetExpr,
+        op: &'ast SetOperator,
+        right: &'ast SetExpr,
+        _all: bool,
+    ) {
+        // The columns of the result are named after those of the left side.
+        self.visit_set_expr(left);
+        let output = mem::take(&mut self.output);
+        self.visit_set_operator(op);
+        self.visit_set_expr(right);
+        self.output = output;
+    }
+
+    fn visit_values(&mut self, values: &'ast Values) {
+        visit::visit_values(self, values);
+        let width = values.0.first().map_or(0, |row| row.len());
+        self.output = Columns {
+            names: (1..=width)
+                .map(|i| Ident::new(format!("column{}", i)))
+                .collect(),
+            complete: true,
+        };
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        match expr {
+            Expr::Identifier(ident) => {
+                let lookup = self.lookup_unqualified(ident);
+                self.bind(expr, lookup)
+            }
+            Expr::CompoundIdentifier(idents) => match idents.split_last() {
+                Some((column, qualifier)) if !qualifier.is_empty() => {
+                    let lookup = self.lookup_qualified(qualifier, column);
+                    self.bind(expr, lookup)
+                }
+                Some((column, _)) => {
+                    let lookup = self.lookup_unqualified(column);
+                    self.bind(expr, lookup)
+                }
+                None => (),
+            },
+            _ => visit::visit_expr(self, expr),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::Statement;
+    use crate::dialect::GenericDialect;
+    use crate::parser::Parser;
+
+    /// A catalog of tables and their columns
+    struct Tables(&'static [(&'static str, &'static [&'static str])]);
+
+    impl TableCatalog for Tables {
+        fn table_columns(&self, name: &ObjectName) -> Option<Vec<Ident>> {
+            let name = name.to_string();
+            self.0
+                .iter()
+                .find(|(table, _)| *table == name)
+                .map(|(_, columns)| columns.iter().map(|&column| Ident::new(column)).collect())
+        }
+    }
+
+    const TABLES: Tables = Tables(&[("a", &["x", "y"]), ("b", &["x", "z"]), ("s.c", &["w"])]);
+
+    fn parse(sql: &str) -> Box<Query> {
+        match Parser::parse_sql(&GenericDialect {}, sql.to_string())
+            .unwrap()
+            .pop()
+            .unwrap()
+        {
+            Statement::Query(query) => query,
+            statement => panic!("not a query: {}", statement),
+        }
+    }
+
+    /// Describe the column references of `query` in the order in which they
+    /// appear, as `column -> relation`, `column -> ?` if it is unresolved, or
+    /// `column: error kind`
+    fn describe(query: &Query, catalog: Option<&dyn TableCatalog>) -> Vec<String> {
+        let resolution = resolve(query, catalog);
+        let mut references = vec![];
+        for binding in &resolution.bindings {
+            let relation = match binding.relation {
+                Relation::Table(TableFactor::Table { name, alias, .. }) => match alias {
+                    Some(alias) => format!("{} AS {}", name, alias.name),
+                    None => name.to_string(),
+                },
+                Relation::Cte { cte, table_factor } => match table_factor {
+                    TableFactor::Table {
+                        alias: Some(alias), ..
+                    } => format!("WITH {} AS {}", cte.alias.name, alias.name),
+                    _ => format!("WITH {}", cte.alias.name),
+                },
+                Relation::Derived(TableFactor::Derived { alias, .. }) => match alias {
+                    Some(alias) => format!("derived {}", alias.name),
+                    None => "derived".to_string(),
+                },
+                Relation::Output(_) => "output".to_string(),
+                Relation::SelectOutput(_) => "select output".to_string(),
+                relation => panic!("unexpected relation: {:?}", relation),
+            };
+            references.push((binding.expr, format!("{} -> {}", binding.expr, relation)));
+        }
+        for expr in &resolution.unresolved {
+            references.push((expr, format!("{} -> ?", expr)));
+        }
+        for error in &resolution.errors {
+            references.push((error.expr, format!("{}: {:?}", error.expr, error.kind)));
+        }
+        references.sort_by_key(|(expr, _)| expr.span().start.offset);
+        references
+            .into_iter()
+            .map(|(_, description)| description)
+            .collect()
+    }
+
+    #[test]
+    fn test_resolve_with_catalog() {
+        let cases: &[(&str, &[&str])] = &[
+            ("SELECT x, a.y FROM a", &["x -> a", "a.y -> a"]),
+            (
+                "SELECT t.x, y FROM a AS t",
+                &["t.x -> a AS t", "y -> a AS t"],
+            ),
+            (
+                "SELECT s.c.w, c.w, w FROM s.c",
+                &["s.c.w -> s.c", "c.w -> s.c", "w -> s.c"],
+            ),
+            (
+                "SELECT q, a.q FROM a",
+                &["q: UnknownColumn", "a.q: UnknownColumn"],
+            ),
+            ("SELECT a.x FROM a AS t", &["a.x: UnknownTable"]),
+            (
+                "SELECT x, y, z FROM a, b",
+                &["x: AmbiguousColumn", "y -> a", "z -> b"],
+            ),
+            ("SELECT c.w FROM s.c, t.c", &["c.w: AmbiguousTable"]),
+            // Aliases rename the first columns.
+            (
+                "SELECT u, y, x FROM a AS t (u)",
+                &["u -> a AS t", "y -> a AS t", "x: UnknownColumn"],
+            ),
+            // `USING` and `NATURAL` merge columns.
+            (
+                "SELECT x, a.x, b.x FROM a JOIN b USING (x)",
+                &["x -> a", "a.x -> a", "b.x -> b"],
+            ),
+            ("SELECT x, z FROM a NATURAL JOIN b", &["x -> a", "z -> b"]),
+            (
+                "SELECT x FROM a JOIN b ON a.x = b.x",
+                &["x: AmbiguousColumn", "a.x -> a", "b.x -> b"],
+            ),
+            // CTEs and derived tables have the columns of their query.
+            (
+                "WITH c AS (SELECT x, y AS v FROM a) SELECT x, v, d.v FROM c AS d",
+                &[
+                    "x -> a",
+                    "y -> a",
+                    "x -> WITH c AS d",
+                    "v -> WITH c AS d",
+                    "d.v -> WITH c AS d",
+                ],
+            ),
+            (
+                "WITH c (p) AS (SELECT x FROM a) SELECT p, x FROM c",
+                &["x -> a", "p -> WITH c", "x: UnknownColumn"],
+            ),
+            (
+                "WITH c AS (SELECT 1 AS x) SELECT x FROM (SELECT x FROM c) AS c",
+                &["x -> derived c", "x -> WITH c"],
+            ),
+            (
+                "SELECT d.x, z FROM (SELECT x, 1 AS z FROM a) AS d",
+                &["d.x -> derived d", "z -> derived d", "x -> a"],
+            ),
+            (
+                "SELECT x FROM (SELECT * FROM a JOIN b USING (x)) AS d",
+                &["x -> derived d"],
+            ),
+            (
+                "SELECT y FROM (SELECT b.* FROM a, b) AS d",
+                &["y: UnknownColumn"],
+            ),
+            (
+                "SELECT column2 FROM (VALUES (1, 2)) AS v",
+                &["column2 -> derived v"],
+            ),
+            (
+                "SELECT x FROM (SELECT x FROM a UNION SELECT z FROM b) AS d",
+                &["x -> derived d", "x -> a", "z -> b"],
+            ),
+            // Only `LATERAL` subqueries see the relations before them.
+            ("SELECT 1 FROM a, (SELECT a.x) AS d", &["a.x: UnknownTable"]),
+            ("SELECT 1 FROM a, LATERAL (SELECT a.x) AS d", &["a.x -> a"]),
+            // Subqueries see the relations of the enclosing queries.
+            (
+                "SELECT x FROM a WHERE EXISTS (SELECT 1 FROM b WHERE b.z = a.y AND z = y)",
+                &["x -> a", "b.z -> b", "a.y -> a", "z -> b", "y -> a"],
+            ),
+            (
+                "SELECT x FROM a WHERE y IN (SELECT x FROM b)",
+                &["x -> a", "y -> a", "x -> b"],
+            ),
+            // `ORDER BY` sees the columns of the result first.
+            (
+                "SELECT x AS v FROM a ORDER BY v, y, v + 1",
+                &["x -> a", "v -> output", "y -> a", "v: UnknownColumn"],
+            ),
+            (
+                "SELECT x FROM a UNION SELECT z FROM b ORDER BY x",
+                &["x -> a", "z -> b", "x -> output"],
+            ),
+            (
+                "SELECT x, y AS x FROM a ORDER BY x",
+                &["x -> a", "y -> a", "x: AmbiguousColumn"],
+            ),
+            // `GROUP BY` sees the columns of the result last.
+            (
+                "SELECT x AS v, count(*) FROM a GROUP BY v",
+                &["x -> a", "v -> select output"],
+            ),
+            (
+                "SELECT x AS y FROM a GROUP BY y, v + 1",
+                &["x -> a", "y -> a", "v: UnknownColumn"],
+            ),
+            (
+                "SELECT x AS v, y AS v FROM a GROUP BY v",
+                &["x -> a", "y -> a", "v: AmbiguousColumn"],
+            ),
+        ];
+        for (sql, expected) in cases {
+            assert_eq!(describe(&parse(sql), Some(&TABLES)), *expected, "{}", sql);
+        }
+    }
+
+    #[test]
+    fn test_resolve_without_catalog() {
+        let cases: &[(&str, &[&str])] = &[
+            ("SELECT x, a.q FROM a", &["x -> a", "a.q -> a"]),
+            ("SELECT x, a.x FROM a, b", &["x -> ?", "a.x -> a"]),
+            (
+                "SELECT d.v, v, x FROM (SELECT 1 AS v) AS d, a",
+                &["d.v -> derived d", "v -> ?", "x -> a"],
+            ),
+            ("SELECT x FROM (SELECT * FROM a) AS d", &["x -> derived d"]),
+            ("SELECT x FROM a NATURAL JOIN b", &["x -> ?"]),
+            ("SELECT b.x FROM a", &["b.x: UnknownTable"]),
+            ("SELECT x", &["x: UnknownColumn"]),
+        ];
+        for (sql, expected) in cases {
+            assert_eq!(describe(&parse(sql), None), *expected, "{}", sql);
+        }
+    }
+
+    #[test]
+    fn test_resolve_columns() {
+        let cases: &[(&str, Option<&[&str]>)] = &[
+            (
+                "SELECT x, a.y AS v, 1, COUNT(*), CAST(z AS INT) FROM a, b",
+                Some(&["x", "v", "\"?column?\"", "COUNT", "z"]),
+            ),
+            ("SELECT *, t.* FROM a", None),
+            ("SELECT * FROM a JOIN b USING (x)", Some(&["x", "y", "z"])),
+            ("SELECT * FROM a AS t (u), s.c", Some(&["u", "y", "w"])),
+            ("SELECT * FROM a, d", None),
+            (
+                "VALUES (1, 2) UNION SELECT * FROM a",
+                Some(&["column1", "column2"]),
+            ),
+        ];
+        for (sql, expected) in cases {
+            let columns = resolve(&parse(sql), Some(&TABLES))
+                .columns
+                .map(|columns| columns.iter().map(|c| c.to_string()).collect::<Vec<_>>());
+            let expected = expected.map(|e| e.iter().map(|c| c.to_string()).collect());
+            assert_eq!(columns, expected, "{}", sql);
+        }
+    }
+
+    #[test]
+    fn test_resolve_error_display() {
+        let query = parse("SELECT x, q,\n  a.x, t.x FROM a, b");
+        let errors: Vec<String> = resolve(&query, Some(&TABLES))
+            .errors
+            .iter()
+            .map(|error| error.to_string())
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                "column reference x is ambiguous at Line: 1, Column: 8",
+                "column q does not exist at Line: 1, Column: 11",
+                "missing FROM-clause entry for table t at Line: 2, Column: 8",
+            ]
+        );
+    }
+}
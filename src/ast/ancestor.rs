@@ -0,0 +1,53 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{
+    Cte, Expr, Function, Join, Query, Select, SelectItem, SetExpr, Statement, TableFactor,
+};
+
+/// A node that contains the node being visited, as passed to the `enter` and
+/// `leave` functions of `Visit` and `TryVisit`.
+///
+/// The ancestors that have been entered but not left yet are the path from
+/// the root of the AST to the node being visited, e.g. an `Expr` inside the
+/// `WHERE` clause of a `Select` inside a `Cte`:
+///
+/// ```text
+/// [Statement, Query, Cte, Query, SetExpr, Select, Clause(Where), Expr]
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub enum Ancestor<'ast> {
+    Statement(&'ast Statement),
+    Query(&'ast Query),
+    Cte(&'ast Cte),
+    SetExpr(&'ast SetExpr),
+    Select(&'ast Select),
+    SelectItem(&'ast SelectItem),
+    TableFactor(&'ast TableFactor),
+    Join(&'ast Join),
+    Expr(&'ast Expr),
+    Function(&'ast Function),
+    /// A clause of the enclosing `Select` or `Query`
+    Clause(Clause),
+}
+
+/// A clause of a `Select` or `Query` whose expressions are being visited
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Clause {
+    Where,
+    GroupBy,
+    Having,
+    OrderBy,
+    Limit,
+    Offset,
+    Fetch,
+}
@@ -13,15 +13,27 @@
 //! AST types specific to CREATE/ALTER variants of [Statement]
 //! (commonly referred to as Data Definition Language, or DDL)
 use super::{display_comma_separated, DataType, Expr, Ident, ObjectName};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// An `ALTER TABLE` (`Statement::AlterTable`) operation
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum AlterTableOperation {
     /// `ADD <table_constraint>`
     AddConstraint(TableConstraint),
-    /// TODO: implement `DROP CONSTRAINT <name>`
+    /// `DROP CONSTRAINT <name>`
     DropConstraint { name: Ident },
+    /// `ADD [ COLUMN ] <column_def>`
+    AddColumn(ColumnDef),
+    /// `DROP [ COLUMN ] <name>`
+    DropColumn { name: Ident },
+    /// `ALTER [ COLUMN ] <name> <operation>`
+    AlterColumn {
+        name: Ident,
+        operation: AlterColumnOperation,
+    },
 }
 
 impl fmt::Display for AlterTableOperation {
@@ -29,6 +41,41 @@ impl fmt::Display for AlterTableOperation {
         match self {
             AlterTableOperation::AddConstraint(c) => write!(f, "ADD {}", c),
             AlterTableOperation::DropConstraint { name } => write!(f, "DROP CONSTRAINT {}", name),
+            AlterTableOperation::AddColumn(column) => write!(f, "ADD COLUMN {}", column),
+            AlterTableOperation::DropColumn { name } => write!(f, "DROP COLUMN {}", name),
+            AlterTableOperation::AlterColumn { name, operation } => {
+                write!(f, "ALTER COLUMN {} {}", name, operation)
+            }
+        }
+    }
+}
+
+/// An `ALTER TABLE .. ALTER COLUMN` operation
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AlterColumnOperation {
+    /// `SET DATA TYPE <data_type>`, or just `TYPE <data_type>`
+    SetDataType(DataType),
+    /// `SET DEFAULT <expr>`
+    SetDefault(Expr),
+    /// `DROP DEFAULT`
+    DropDefault,
+    /// `SET NOT NULL`
+    SetNotNull,
+    /// `DROP NOT NULL`
+    DropNotNull,
+}
+
+impl fmt::Display for AlterColumnOperation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AlterColumnOperation::SetDataType(data_type) => {
+                write!(f, "SET DATA TYPE {}", data_type)
+            }
+            AlterColumnOperation::SetDefault(expr) => write!(f, "SET DEFAULT {}", expr),
+            AlterColumnOperation::DropDefault => write!(f, "DROP DEFAULT"),
+            AlterColumnOperation::SetNotNull => write!(f, "SET NOT NULL"),
+            AlterColumnOperation::DropNotNull => write!(f, "DROP NOT NULL"),
         }
     }
 }
@@ -36,6 +83,7 @@ impl fmt::Display for AlterTableOperation {
 /// A table-level constraint, specified in a `CREATE TABLE` or an
 /// `ALTER TABLE ADD <constraint>` statement.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TableConstraint {
     /// `[ CONSTRAINT <name> ] { PRIMARY KEY | UNIQUE } (<columns>)`
     Unique {
@@ -95,6 +143,7 @@ impl fmt::Display for TableConstraint {
 
 /// SQL column definition
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ColumnDef {
     pub name: Ident,
     pub data_type: DataType,
@@ -129,6 +178,7 @@ impl fmt::Display for ColumnDef {
 /// non-constraint options, lumping them all together under the umbrella of
 /// "column options," and we allow any column option to be named.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ColumnOptionDef {
     pub name: Option<Ident>,
     pub option: ColumnOption,
@@ -143,6 +193,7 @@ impl fmt::Display for ColumnOptionDef {
 /// `ColumnOption`s are modifiers that follow a column definition in a `CREATE
 /// TABLE` statement.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ColumnOption {
     /// `NULL`
     Null,
@@ -0,0 +1,105 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::{Any, TypeId};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use super::{visit, visit_mut, Span};
+
+/// A node of the AST defined outside of this crate, to represent syntax that
+/// is specific to a dialect. Extensions are stored as `Statement::Extension`
+/// or `Expr::Extension`, and are typically produced by the `Dialect` parsing
+/// hooks.
+///
+/// The `Display` implementation of an extension must produce valid SQL, like
+/// that of the other AST nodes. Any type that also implements `Debug`,
+/// `Clone`, `Eq` and `Hash` gets the rest of the required methods from
+/// `ExtensionBase`; only the child nodes need to be provided, if any, so
+/// that visitors can traverse them.
+pub trait Extension: ExtensionBase {
+    /// The standard AST nodes contained in this extension, to be visited by
+    /// `Visit`
+    fn children(&self) -> Vec<visit::Node<'_>> {
+        vec![]
+    }
+
+    /// The standard AST nodes contained in this extension, to be visited by
+    /// `VisitMut`. Must return the same nodes as `children`.
+    fn children_mut(&mut self) -> Vec<visit_mut::Node<'_>> {
+        vec![]
+    }
+
+    /// The location of this extension in the source text, for `Spanned`
+    fn span(&self) -> Span {
+        Span::default()
+    }
+}
+
+/// The object-safe equivalents of the traits that AST nodes implement,
+/// implemented for every `Extension` that implements the original traits
+pub trait ExtensionBase: Any + fmt::Debug + fmt::Display + Send + Sync {
+    /// Return `self`, so that the extension can be downcast to its type
+    fn as_any(&self) -> &dyn Any;
+    /// Return `self`, so that the extension can be downcast to its type
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    /// Equivalent to `Clone::clone`
+    fn clone_extension(&self) -> Box<dyn Extension>;
+    /// Equivalent to `PartialEq::eq`. Extensions of different types are never
+    /// equal.
+    fn eq_extension(&self, other: &dyn Extension) -> bool;
+    /// Equivalent to `Hash::hash`
+    fn hash_extension(&self, state: &mut dyn Hasher);
+}
+
+impl<T: Extension + Clone + Eq + Hash> ExtensionBase for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_extension(&self) -> Box<dyn Extension> {
+        Box::new(self.clone())
+    }
+
+    fn eq_extension(&self, other: &dyn Extension) -> bool {
+        other.as_any().downcast_ref::<T>() == Some(self)
+    }
+
+    fn hash_extension(&self, mut state: &mut dyn Hasher) {
+        TypeId::of::<T>().hash(&mut state);
+        self.hash(&mut state);
+    }
+}
+
+impl Clone for Box<dyn Extension> {
+    fn clone(&self) -> Self {
+        self.clone_extension()
+    }
+}
+
+impl PartialEq for Box<dyn Extension> {
+    fn eq(&self, other: &Self) -> bool {
+        self.eq_extension(&**other)
+    }
+}
+
+impl Eq for Box<dyn Extension> {}
+
+impl Hash for Box<dyn Extension> {
+    fn hash<H: Hasher>(&self, mut state: &mut H) {
+        self.hash_extension(&mut state)
+    }
+}
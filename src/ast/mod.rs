@@ -12,10 +12,13 @@
 
 //! SQL Abstract Syntax Tree (AST) types
 
+mod ancestor;
 mod data_type;
 mod ddl;
+mod extension;
 mod operator;
 mod query;
+mod spans;
 mod value;
 #[macro_use]
 mod visit_macro;
@@ -28,30 +31,80 @@ pub mod visit {
     // Disable lints that want us to rewrite `&Ident` as `&str`, as `&str` is not as
     // self-documenting as &Ident.
     #![allow(clippy::ptr_arg)]
-    make_visitor!(Visit: &);
+    pub use super::ancestor::{Ancestor, Clause};
+    make_visitor!(@visit Visit, children: &);
 }
 
 pub mod visit_mut {
     // See justification for these attributes in the `visit` module.
     #![allow(clippy::too_many_arguments)]
     #![allow(clippy::ptr_arg)]
-    make_visitor!(VisitMut: &mut);
+    make_visitor!(@visit VisitMut, children_mut: &mut);
 }
 
+pub mod fold {
+    //! Rewriting the SQL AST by consuming it and building a new one.
+    make_fold!(Fold);
+}
+
+pub mod try_visit {
+    //! A variant of `Visit` that can stop walking through the AST early, e.g.
+    //! to search for the first node of some kind or to stop at the first
+    //! validation error.
+
+    // See justification for these attributes in the `visit` module.
+    #![allow(clippy::too_many_arguments)]
+    #![allow(clippy::ptr_arg)]
+    pub use super::ancestor::{Ancestor, Clause};
+    pub use super::visit::Node;
+    pub use std::ops::ControlFlow;
+    make_visitor!(
+        @try
+        /// Each function returns `ControlFlow<Self::Break>`, and the traversal
+        /// stops as soon as a function returns `ControlFlow::Break`, which is
+        /// returned from the function that started it. Use `Result<T, E>` as
+        /// the `Break` type for a fallible visitor. When the traversal stops,
+        /// `leave` is not called for the ancestors of the node that stopped it.
+        TryVisit, children: &
+    );
+}
+
+pub mod try_visit_mut {
+    //! A variant of `VisitMut` that can stop walking through the AST early.
+
+    // See justification for these attributes in the `visit` module.
+    #![allow(clippy::too_many_arguments)]
+    #![allow(clippy::ptr_arg)]
+    pub use super::visit_mut::Node;
+    pub use std::ops::ControlFlow;
+    make_visitor!(
+        @try
+        /// See `TryVisit`.
+        TryVisitMut, children_mut: &mut
+    );
+}
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::hash::{Hash, Hasher};
 
 pub use self::data_type::DataType;
 pub use self::ddl::{
-    AlterTableOperation, ColumnDef, ColumnOption, ColumnOptionDef, TableConstraint,
+    AlterColumnOperation, AlterTableOperation, ColumnDef, ColumnOption, ColumnOptionDef,
+    TableConstraint,
 };
+pub use self::extension::{Extension, ExtensionBase};
 pub use self::operator::{BinaryOperator, UnaryOperator};
 pub use self::query::{
     Cte, Fetch, Join, JoinConstraint, JoinOperator, OrderByExpr, Query, Select, SelectItem,
     SetExpr, SetOperator, TableAlias, TableFactor, TableWithJoins, Values,
 };
+pub use self::spans::Spanned;
 pub use self::value::{
     DateTimeField, Interval, IntervalValue, ParsedDate, ParsedDateTime, ParsedTimestamp, Value,
 };
+pub use crate::tokenizer::{Location, Span};
 
 struct DisplaySeparated<'a, T>
 where
@@ -91,13 +144,36 @@ where
 }
 
 /// An identifier, decomposed into its value or character data and the quote style.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Ident {
     /// The value of the identifier without quotes.
     pub value: String,
     /// The starting quote if any. Valid quote characters are the single quote,
     /// double quote, backtick, and opening square bracket.
     pub quote_style: Option<char>,
+    /// The location of the identifier in the source text, including the
+    /// quotes, if any. Empty for identifiers not produced by the parser.
+    ///
+    /// The span is not taken into account when comparing or hashing
+    /// identifiers, so that ASTs parsed from differently formatted SQL compare
+    /// equal.
+    pub span: Span,
+}
+
+impl PartialEq for Ident {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value && self.quote_style == other.quote_style
+    }
+}
+
+impl Eq for Ident {}
+
+impl Hash for Ident {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+        self.quote_style.hash(state);
+    }
 }
 
 impl Ident {
@@ -109,6 +185,7 @@ impl Ident {
         Ident {
             value: value.into(),
             quote_style: None,
+            span: Span::default(),
         }
     }
 
@@ -122,8 +199,15 @@ impl Ident {
         Ident {
             value: value.into(),
             quote_style: Some(quote),
+            span: Span::default(),
         }
     }
+
+    /// Return this identifier with its location set to `span`.
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = span;
+        self
+    }
 }
 
 impl From<&str> for Ident {
@@ -131,6 +215,7 @@ impl From<&str> for Ident {
         Ident {
             value: value.to_string(),
             quote_style: None,
+            span: Span::default(),
         }
     }
 }
@@ -139,7 +224,7 @@ impl fmt::Display for Ident {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self.quote_style {
             Some(q) if q == '"' || q == '\'' || q == '`' => write!(f, "{}{}{}", q, self.value, q),
-            Some(q) if q == '[' => write!(f, "[{}]", self.value),
+            Some('[') => write!(f, "[{}]", self.value),
             None => f.write_str(&self.value),
             _ => panic!("unexpected quote style"),
         }
@@ -148,6 +233,7 @@ impl fmt::Display for Ident {
 
 /// A name of a table, view, custom type, etc., possibly multi-part, i.e. db.schema.obj
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ObjectName(pub Vec<Ident>);
 
 impl fmt::Display for ObjectName {
@@ -162,6 +248,7 @@ impl fmt::Display for ObjectName {
 /// (e.g. boolean vs string), so the caller must handle expressions of
 /// inappropriate type, like `WHERE 1` or `SELECT 1=1`, as necessary.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Expr {
     /// Identifier e.g. table name or column name
     Identifier(Ident),
@@ -260,6 +347,10 @@ pub enum Expr {
         op: BinaryOperator,
         right: Box<Query>,
     },
+    /// An expression defined outside of this crate. Extensions cannot be
+    /// serialized.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Extension(Box<dyn Extension>),
 }
 
 impl fmt::Display for Expr {
@@ -350,12 +441,14 @@ impl fmt::Display for Expr {
                 right
             ),
             Expr::All { left, op, right } => write!(f, "{} {} ALL ({})", left, op, right),
+            Expr::Extension(extension) => write!(f, "{}", extension),
         }
     }
 }
 
 /// A window specification (i.e. `OVER (PARTITION BY .. ORDER BY .. etc.)`)
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct WindowSpec {
     pub partition_by: Vec<Expr>,
     pub order_by: Vec<OrderByExpr>,
@@ -401,6 +494,7 @@ impl fmt::Display for WindowSpec {
 /// Note: The parser does not validate the specified bounds; the caller should
 /// reject invalid bounds like `ROWS UNBOUNDED FOLLOWING` before execution.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct WindowFrame {
     pub units: WindowFrameUnits,
     pub start_bound: WindowFrameBound,
@@ -412,6 +506,7 @@ pub struct WindowFrame {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum WindowFrameUnits {
     Rows,
     Range,
@@ -436,7 +531,7 @@ impl FromStr for WindowFrameUnits {
             "ROWS" => Ok(WindowFrameUnits::Rows),
             "RANGE" => Ok(WindowFrameUnits::Range),
             "GROUPS" => Ok(WindowFrameUnits::Groups),
-            _ => Err(ParserError::ParserError(format!(
+            _ => Err(ParserError::new(format!(
                 "Expected ROWS, RANGE, or GROUPS, found: {}",
                 s
             ))),
@@ -446,6 +541,7 @@ impl FromStr for WindowFrameUnits {
 
 /// Specifies [WindowFrame]'s `start_bound` and `end_bound`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum WindowFrameBound {
     /// `CURRENT ROW`
     CurrentRow,
@@ -469,6 +565,7 @@ impl fmt::Display for WindowFrameBound {
 
 /// Specifies what [Statement::Explain] is actually explaining
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Stage {
     /// The dataflow graph after translation from SQL.
     Dataflow,
@@ -489,6 +586,7 @@ impl fmt::Display for Stage {
 /// A top-level statement (SELECT, INSERT, CREATE, etc.)
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Statement {
     /// `SELECT`
     Query(Box<Query>),
@@ -658,6 +756,10 @@ pub enum Statement {
     Tail { name: ObjectName },
     /// `EXPLAIN [ DATAFLOW | PLAN ] FOR`
     Explain { stage: Stage, query: Box<Query> },
+    /// A statement defined outside of this crate. Extensions cannot be
+    /// serialized.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Extension(Box<dyn Extension>),
 }
 
 impl fmt::Display for Statement {
@@ -737,22 +839,19 @@ impl fmt::Display for Statement {
                 write!(
                     f,
                     "CREATE SOURCE {} FROM {}",
-                    name.to_string(),
-                    Value::SingleQuotedString(url.clone()).to_string()
+                    name,
+                    Value::SingleQuotedString(url.clone())
                 )?;
-                match schema {
-                    Some(schema) => {
-                        write!(f, " USING SCHEMA ")?;
-                        match schema {
-                            SourceSchema::Raw(schema) => {
-                                write!(f, "{}", Value::SingleQuotedString(schema.clone()))?;
-                            }
-                            SourceSchema::Registry(url) => {
-                                write!(f, "REGISTRY {}", Value::SingleQuotedString(url.clone()))?;
-                            }
+                if let Some(schema) = schema {
+                    write!(f, " USING SCHEMA ")?;
+                    match schema {
+                        SourceSchema::Raw(schema) => {
+                            write!(f, "{}", Value::SingleQuotedString(schema.clone()))?;
+                        }
+                        SourceSchema::Registry(url) => {
+                            write!(f, "REGISTRY {}", Value::SingleQuotedString(url.clone()))?;
                         }
                     }
-                    None => {}
                 }
                 if !with_options.is_empty() {
                     write!(f, " WITH ({})", display_comma_separated(with_options))?;
@@ -772,8 +871,8 @@ impl fmt::Display for Statement {
                 write!(
                     f,
                     "FROM {} USING SCHEMA REGISTRY {}",
-                    Value::SingleQuotedString(url.clone()).to_string(),
-                    Value::SingleQuotedString(schema_registry.clone()).to_string(),
+                    Value::SingleQuotedString(url.clone()),
+                    Value::SingleQuotedString(schema_registry.clone()),
                 )?;
                 if !with_options.is_empty() {
                     write!(f, " WITH ({})", display_comma_separated(with_options))?;
@@ -984,12 +1083,14 @@ impl fmt::Display for Statement {
             Statement::Explain { stage, query } => write!(f, "EXPLAIN {} FOR {}", stage, query),
             Statement::FlushSource { name } => write!(f, "FLUSH SOURCE {}", name),
             Statement::FlushAllSources => write!(f, "FLUSH ALL SOURCES"),
+            Statement::Extension(extension) => write!(f, "{}", extension),
         }
     }
 }
 
 /// SQL assignment `foo = expr` as used in SQLUpdate
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Assignment {
     pub id: Ident,
     pub value: Expr,
@@ -1003,6 +1104,7 @@ impl fmt::Display for Assignment {
 
 /// A function call
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Function {
     pub name: ObjectName,
     pub args: Vec<Expr>,
@@ -1029,6 +1131,7 @@ impl fmt::Display for Function {
 
 /// Specifies the schema associated with a given Kafka topic.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SourceSchema {
     /// The schema is specified directly in the contained string.
     Raw(String),
@@ -1039,6 +1142,7 @@ pub enum SourceSchema {
 
 /// External table's available file format
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum FileFormat {
     TEXTFILE,
     SEQUENCEFILE,
@@ -1079,15 +1183,13 @@ impl FromStr for FileFormat {
             "AVRO" => Ok(AVRO),
             "RCFILE" => Ok(RCFILE),
             "JSONFILE" => Ok(JSONFILE),
-            _ => Err(ParserError::ParserError(format!(
-                "Unexpected file format: {}",
-                s
-            ))),
+            _ => Err(ParserError::new(format!("Unexpected file format: {}", s))),
         }
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ObjectType {
     Table,
     View,
@@ -1109,6 +1211,7 @@ impl fmt::Display for ObjectType {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SqlOption {
     pub name: Ident,
     pub value: Value,
@@ -1121,6 +1224,7 @@ impl fmt::Display for SqlOption {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TransactionMode {
     AccessMode(TransactionAccessMode),
     IsolationLevel(TransactionIsolationLevel),
@@ -1137,6 +1241,7 @@ impl fmt::Display for TransactionMode {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TransactionAccessMode {
     ReadOnly,
     ReadWrite,
@@ -1153,6 +1258,7 @@ impl fmt::Display for TransactionAccessMode {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TransactionIsolationLevel {
     ReadUncommitted,
     ReadCommitted,
@@ -1173,6 +1279,7 @@ impl fmt::Display for TransactionIsolationLevel {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ShowStatementFilter {
     Like(String),
     Where(Expr),
@@ -1189,6 +1296,7 @@ impl fmt::Display for ShowStatementFilter {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SetVariableValue {
     Ident(Ident),
     Literal(Value),
@@ -0,0 +1,569 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Source locations of AST nodes
+
+use super::*;
+
+/// An AST node whose location in the source text can be determined.
+///
+/// The parser records the location of every identifier it produces (see
+/// [`Ident::span`]). The span of any other node is the smallest span covering
+/// the identifiers it contains, so the keywords, operators and literals at the
+/// edges of a node are not included: the span of `a + 1` is the span of `a`.
+/// Nodes that contain no identifiers at all, like `SELECT 1`, have an empty
+/// span.
+pub trait Spanned {
+    /// Return the location of this node, or an empty span if it is unknown
+    fn span(&self) -> Span;
+}
+
+fn union_spans<'a, T: Spanned + 'a>(nodes: impl IntoIterator<Item = &'a T>) -> Span {
+    Span::union_iter(nodes.into_iter().map(Spanned::span))
+}
+
+impl<T: Spanned> Spanned for Box<T> {
+    fn span(&self) -> Span {
+        (**self).span()
+    }
+}
+
+impl<T: Spanned> Spanned for Option<T> {
+    fn span(&self) -> Span {
+        union_spans(self)
+    }
+}
+
+impl<T: Spanned> Spanned for Vec<T> {
+    fn span(&self) -> Span {
+        union_spans(self)
+    }
+}
+
+impl Spanned for Ident {
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl Spanned for ObjectName {
+    fn span(&self) -> Span {
+        self.0.span()
+    }
+}
+
+impl Spanned for Expr {
+    fn span(&self) -> Span {
+        match self {
+            Expr::Identifier(ident) => ident.span(),
+            Expr::Wildcard => Span::default(),
+            Expr::QualifiedWildcard(idents) => idents.span(),
+            Expr::CompoundIdentifier(idents) => idents.span(),
+            Expr::Parameter(_) => Span::default(),
+            Expr::IsNull(expr) => expr.span(),
+            Expr::IsNotNull(expr) => expr.span(),
+            Expr::InList { expr, list, .. } => expr.span().union(&list.span()),
+            Expr::InSubquery { expr, subquery, .. } => expr.span().union(&subquery.span()),
+            Expr::Between {
+                expr, low, high, ..
+            } => Span::union_iter(vec![expr.span(), low.span(), high.span()]),
+            Expr::BinaryOp { left, right, .. } => left.span().union(&right.span()),
+            Expr::UnaryOp { expr, .. } => expr.span(),
+            Expr::Cast { expr, data_type } => expr.span().union(&data_type.span()),
+            Expr::Extract { expr, .. } => expr.span(),
+            Expr::Collate { expr, collation } => expr.span().union(&collation.span()),
+            Expr::Nested(expr) => expr.span(),
+            Expr::Value(_) => Span::default(),
+            Expr::Function(func) => func.span(),
+            Expr::Case {
+                operand,
+                conditions,
+                results,
+                else_result,
+            } => Span::union_iter(vec![
+                operand.span(),
+                conditions.span(),
+                results.span(),
+                else_result.span(),
+            ]),
+            Expr::Exists(query) => query.span(),
+            Expr::Subquery(query) => query.span(),
+            Expr::Any { left, right, .. } => left.span().union(&right.span()),
+            Expr::All { left, right, .. } => left.span().union(&right.span()),
+            Expr::Extension(extension) => extension.span(),
+        }
+    }
+}
+
+impl Spanned for DataType {
+    fn span(&self) -> Span {
+        match self {
+            DataType::Custom(name) => name.span(),
+            DataType::Array(data_type) => data_type.span(),
+            _ => Span::default(),
+        }
+    }
+}
+
+impl Spanned for Function {
+    fn span(&self) -> Span {
+        let Function {
+            name, args, over, ..
+        } = self;
+        Span::union_iter(vec![name.span(), args.span(), over.span()])
+    }
+}
+
+impl Spanned for WindowSpec {
+    fn span(&self) -> Span {
+        self.partition_by.span().union(&self.order_by.span())
+    }
+}
+
+impl Spanned for OrderByExpr {
+    fn span(&self) -> Span {
+        self.expr.span()
+    }
+}
+
+impl Spanned for Query {
+    fn span(&self) -> Span {
+        let Query {
+            ctes,
+            body,
+            order_by,
+            limit,
+            offset,
+            fetch,
+        } = self;
+        Span::union_iter(vec![
+            ctes.span(),
+            body.span(),
+            order_by.span(),
+            limit.span(),
+            offset.span(),
+            fetch.span(),
+        ])
+    }
+}
+
+impl Spanned for Cte {
+    fn span(&self) -> Span {
+        self.alias.span().union(&self.query.span())
+    }
+}
+
+impl Spanned for SetExpr {
+    fn span(&self) -> Span {
+        match self {
+            SetExpr::Select(select) => select.span(),
+            SetExpr::Query(query) => query.span(),
+            SetExpr::SetOperation { left, right, .. } => left.span().union(&right.span()),
+            SetExpr::Values(values) => values.span(),
+        }
+    }
+}
+
+impl Spanned for Values {
+    fn span(&self) -> Span {
+        Span::union_iter(self.0.iter().map(Spanned::span))
+    }
+}
+
+impl Spanned for Select {
+    fn span(&self) -> Span {
+        let Select {
+            projection,
+            from,
+            selection,
+            group_by,
+            having,
+            ..
+        } = self;
+        Span::union_iter(vec![
+            projection.span(),
+            from.span(),
+            selection.span(),
+            group_by.span(),
+            having.span(),
+        ])
+    }
+}
+
+impl Spanned for SelectItem {
+    fn span(&self) -> Span {
+        match self {
+            SelectItem::UnnamedExpr(expr) => expr.span(),
+            SelectItem::ExprWithAlias { expr, alias } => expr.span().union(&alias.span()),
+            SelectItem::QualifiedWildcard(name) => name.span(),
+            SelectItem::Wildcard => Span::default(),
+        }
+    }
+}
+
+impl Spanned for TableWithJoins {
+    fn span(&self) -> Span {
+        self.relation.span().union(&self.joins.span())
+    }
+}
+
+impl Spanned for TableFactor {
+    fn span(&self) -> Span {
+        match self {
+            TableFactor::Table {
+                name,
+                alias,
+                args,
+                with_hints,
+            } => Span::union_iter(vec![
+                name.span(),
+                alias.span(),
+                args.span(),
+                with_hints.span(),
+            ]),
+            TableFactor::Derived {
+                subquery, alias, ..
+            } => subquery.span().union(&alias.span()),
+            TableFactor::NestedJoin(table) => table.span(),
+        }
+    }
+}
+
+impl Spanned for TableAlias {
+    fn span(&self) -> Span {
+        self.name.span().union(&self.columns.span())
+    }
+}
+
+impl Spanned for Join {
+    fn span(&self) -> Span {
+        let constraint = match &self.join_operator {
+            JoinOperator::Inner(constraint)
+            | JoinOperator::LeftOuter(constraint)
+            | JoinOperator::RightOuter(constraint)
+            | JoinOperator::FullOuter(constraint) => constraint.span(),
+            JoinOperator::CrossJoin | JoinOperator::CrossApply | JoinOperator::OuterApply => {
+                Span::default()
+            }
+        };
+        self.relation.span().union(&constraint)
+    }
+}
+
+impl Spanned for JoinConstraint {
+    fn span(&self) -> Span {
+        match self {
+            JoinConstraint::On(expr) => expr.span(),
+            JoinConstraint::Using(idents) => idents.span(),
+            JoinConstraint::Natural => Span::default(),
+        }
+    }
+}
+
+impl Spanned for Fetch {
+    fn span(&self) -> Span {
+        self.quantity.span()
+    }
+}
+
+impl Spanned for Statement {
+    fn span(&self) -> Span {
+        match self {
+            Statement::Query(query) => query.span(),
+            Statement::Insert {
+                table_name,
+                columns,
+                source,
+            } => Span::union_iter(vec![table_name.span(), columns.span(), source.span()]),
+            Statement::Copy {
+                table_name,
+                columns,
+                ..
+            } => table_name.span().union(&columns.span()),
+            Statement::Update {
+                table_name,
+                assignments,
+                selection,
+            } => Span::union_iter(vec![
+                table_name.span(),
+                assignments.span(),
+                selection.span(),
+            ]),
+            Statement::Delete {
+                table_name,
+                selection,
+            } => table_name.span().union(&selection.span()),
+            Statement::CreateSource {
+                name, with_options, ..
+            } => name.span().union(&with_options.span()),
+            Statement::CreateSources { with_options, .. } => with_options.span(),
+            Statement::CreateSink {
+                name,
+                from,
+                with_options,
+                ..
+            } => Span::union_iter(vec![name.span(), from.span(), with_options.span()]),
+            Statement::FlushSource { name } => name.span(),
+            Statement::FlushAllSources => Span::default(),
+            Statement::Extension(extension) => extension.span(),
+            Statement::CreateView {
+                name,
+                columns,
+                query,
+                with_options,
+                ..
+            } => Span::union_iter(vec![
+                name.span(),
+                columns.span(),
+                query.span(),
+                with_options.span(),
+            ]),
+            Statement::CreateTable {
+                name,
+                columns,
+                constraints,
+                with_options,
+                ..
+            } => Span::union_iter(vec![
+                name.span(),
+                columns.span(),
+                constraints.span(),
+                with_options.span(),
+            ]),
+            Statement::CreateIndex {
+                name,
+                on_name,
+                key_parts,
+            } => Span::union_iter(vec![name.span(), on_name.span(), key_parts.span()]),
+            Statement::AlterTable { name, operation } => name.span().union(&operation.span()),
+            Statement::Drop { names, .. } => names.span(),
+            Statement::SetVariable {
+                variable, value, ..
+            } => variable.span().union(&value.span()),
+            Statement::ShowVariable { variable } => variable.span(),
+            Statement::ShowObjects { filter, .. } => filter.span(),
+            Statement::ShowIndexes { table_name, filter } => {
+                table_name.span().union(&filter.span())
+            }
+            Statement::ShowColumns {
+                table_name, filter, ..
+            } => table_name.span().union(&filter.span()),
+            Statement::ShowCreateView { view_name } => view_name.span(),
+            Statement::ShowCreateSource { source_name } => source_name.span(),
+            Statement::StartTransaction { .. }
+            | Statement::SetTransaction { .. }
+            | Statement::Commit { .. }
+            | Statement::Rollback { .. } => Span::default(),
+            Statement::Peek { name, .. } => name.span(),
+            Statement::Tail { name } => name.span(),
+            Statement::Explain { query, .. } => query.span(),
+        }
+    }
+}
+
+impl Spanned for Assignment {
+    fn span(&self) -> Span {
+        self.id.span().union(&self.value.span())
+    }
+}
+
+impl Spanned for SqlOption {
+    fn span(&self) -> Span {
+        self.name.span()
+    }
+}
+
+impl Spanned for ShowStatementFilter {
+    fn span(&self) -> Span {
+        match self {
+            ShowStatementFilter::Like(_) => Span::default(),
+            ShowStatementFilter::Where(expr) => expr.span(),
+        }
+    }
+}
+
+impl Spanned for SetVariableValue {
+    fn span(&self) -> Span {
+        match self {
+            SetVariableValue::Ident(ident) => ident.span(),
+            SetVariableValue::Literal(_) => Span::default(),
+        }
+    }
+}
+
+impl Spanned for AlterTableOperation {
+    fn span(&self) -> Span {
+        match self {
+            AlterTableOperation::AddConstraint(constraint) => constraint.span(),
+            AlterTableOperation::DropConstraint { name } => name.span(),
+            AlterTableOperation::AddColumn(column) => column.span(),
+            AlterTableOperation::DropColumn { name } => name.span(),
+            AlterTableOperation::AlterColumn { name, operation } => {
+                name.span().union(&operation.span())
+            }
+        }
+    }
+}
+
+impl Spanned for AlterColumnOperation {
+    fn span(&self) -> Span {
+        match self {
+            AlterColumnOperation::SetDataType(data_type) => data_type.span(),
+            AlterColumnOperation::SetDefault(expr) => expr.span(),
+            AlterColumnOperation::DropDefault
+            | AlterColumnOperation::SetNotNull
+            | AlterColumnOperation::DropNotNull => Span::default(),
+        }
+    }
+}
+
+impl Spanned for TableConstraint {
+    fn span(&self) -> Span {
+        match self {
+            TableConstraint::Unique { name, columns, .. } => name.span().union(&columns.span()),
+            TableConstraint::ForeignKey {
+                name,
+                columns,
+                foreign_table,
+                referred_columns,
+            } => Span::union_iter(vec![
+                name.span(),
+                columns.span(),
+                foreign_table.span(),
+                referred_columns.span(),
+            ]),
+            TableConstraint::Check { name, expr } => name.span().union(&expr.span()),
+        }
+    }
+}
+
+impl Spanned for ColumnDef {
+    fn span(&self) -> Span {
+        let ColumnDef {
+            name,
+            data_type,
+            collation,
+            options,
+        } = self;
+        Span::union_iter(vec![
+            name.span(),
+            data_type.span(),
+            collation.span(),
+            options.span(),
+        ])
+    }
+}
+
+impl Spanned for ColumnOptionDef {
+    fn span(&self) -> Span {
+        self.name.span().union(&self.option.span())
+    }
+}
+
+impl Spanned for ColumnOption {
+    fn span(&self) -> Span {
+        match self {
+            ColumnOption::Null | ColumnOption::NotNull | ColumnOption::Unique { .. } => {
+                Span::default()
+            }
+            ColumnOption::Default(expr) => expr.span(),
+            ColumnOption::ForeignKey {
+                foreign_table,
+                referred_columns,
+            } => foreign_table.span().union(&referred_columns.span()),
+            ColumnOption::Check(expr) => expr.span(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::GenericDialect;
+    use crate::parser::Parser;
+
+    fn parse(sql: &str) -> Statement {
+        let mut statements = Parser::parse_sql(&GenericDialect {}, sql.to_string()).unwrap();
+        assert_eq!(statements.len(), 1);
+        statements.pop().unwrap()
+    }
+
+    #[test]
+    fn test_ident_span() {
+        let sql = "SELECT a,\n  \"b c\" FROM t";
+        let select = match parse(sql) {
+            Statement::Query(query) => match query.body {
+                SetExpr::Select(select) => select,
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+        let ident = match &select.projection[1] {
+            SelectItem::UnnamedExpr(Expr::Identifier(ident)) => ident,
+            _ => unreachable!(),
+        };
+        assert_eq!("\"b c\"", ident.span.source(sql));
+        assert_eq!(
+            Location {
+                offset: 12,
+                line: 2,
+                column: 3
+            },
+            ident.span.start
+        );
+        assert_eq!(
+            Location {
+                offset: 17,
+                line: 2,
+                column: 8
+            },
+            ident.span.end
+        );
+    }
+
+    #[test]
+    fn test_statement_span() {
+        let sql = "SELECT a + b FROM db.t1 JOIN t2 ON t1.x = t2.y WHERE c";
+        assert_eq!(
+            "a + b FROM db.t1 JOIN t2 ON t1.x = t2.y WHERE c",
+            parse(sql).span().source(sql)
+        );
+
+        let sql = "INSERT INTO t (a, b) SELECT x FROM u";
+        assert_eq!("t (a, b) SELECT x FROM u", parse(sql).span().source(sql));
+
+        let sql = "COMMIT";
+        assert!(parse(sql).span().is_empty());
+    }
+
+    #[test]
+    fn test_table_factor_span() {
+        let sql = "SELECT * FROM (SELECT x FROM t) AS d, LATERAL (SELECT y FROM u) AS e (z)";
+        let from = match parse(sql) {
+            Statement::Query(query) => match query.body {
+                SetExpr::Select(select) => select.from,
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+        assert_eq!("x FROM t) AS d", from[0].relation.span().source(sql));
+        assert_eq!("y FROM u) AS e (z", from[1].relation.span().source(sql));
+    }
+
+    #[test]
+    fn test_span_ignored_by_eq() {
+        let a = parse("SELECT a FROM t");
+        let b = parse("SELECT\n  a\nFROM\n  t");
+        assert_ne!(a.span(), b.span());
+        assert_eq!(a, b);
+    }
+}
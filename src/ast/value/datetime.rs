@@ -1,9 +1,12 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::time::Duration;
 
 use super::ValueError;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct IntervalValue {
     /// The raw `[value]` that was present in `INTERVAL '[value]'`
     pub value: String,
@@ -90,8 +93,8 @@ impl IntervalValue {
             },
             durationlike_field => {
                 let mut seconds = 0u64;
-                match self.units_of(&durationlike_field) {
-                    Some(time) => seconds += time * seconds_multiplier(&durationlike_field),
+                match self.units_of(durationlike_field) {
+                    Some(time) => seconds += time * seconds_multiplier(durationlike_field),
                     None => {
                         return Err(ValueError(format!(
                             "No {} provided in value string for {}",
@@ -152,14 +155,11 @@ impl IntervalValue {
     /// ```
     pub fn fields_match_precision(&self) -> Result<(), ValueError> {
         let mut errors = vec![];
-        let last_field = self
-            .last_field
-            .as_ref()
-            .unwrap_or_else(|| &self.leading_field);
+        let last_field = self.last_field.as_ref().unwrap_or(&self.leading_field);
         let mut extra_leading_fields = vec![];
         let mut extra_trailing_fields = vec![];
         // check for more data in the input string than was requested in <FIELD> TO <FIELD>
-        for field in std::iter::once(DateTimeField::Year).chain(DateTimeField::Year.into_iter()) {
+        for field in std::iter::once(DateTimeField::Year).chain(DateTimeField::Year) {
             if self.units_of(&field).is_none() {
                 continue;
             }
@@ -190,7 +190,7 @@ impl IntervalValue {
         // check for data requested by the <FIELD> TO <FIELD> that does not exist in the data
         let missing_fields = match (
             self.units_of(&self.leading_field),
-            self.units_of(&last_field),
+            self.units_of(last_field),
         ) {
             (Some(_), Some(_)) => vec![],
             (None, Some(_)) => vec![&self.leading_field],
@@ -214,8 +214,8 @@ impl IntervalValue {
     fn present_fields(&self) -> String {
         fields_msg(
             std::iter::once(DateTimeField::Year)
-                .chain(DateTimeField::Year.into_iter())
-                .filter(|field| self.units_of(&field).is_some()),
+                .chain(DateTimeField::Year)
+                .filter(|field| self.units_of(field).is_some()),
         )
     }
 }
@@ -260,6 +260,7 @@ pub enum Interval {
 ///
 /// This is not guaranteed to be a valid date
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ParsedDate {
     pub year: i64,
     pub month: u8,
@@ -272,6 +273,7 @@ pub struct ParsedDate {
 ///
 /// This is not guaranteed to be a valid date
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ParsedTimestamp {
     pub year: i64,
     pub month: u8,
@@ -289,6 +291,7 @@ pub struct ParsedTimestamp {
 /// fields set, otherwise you are probably looking for [`ParsedDate`] or
 /// [`ParsedTimestamp`].
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ParsedDateTime {
     pub is_positive: bool,
     pub year: Option<u64>,
@@ -328,6 +331,7 @@ impl Default for ParsedDateTime {
 }
 
 #[derive(Debug, Clone, PartialOrd, Ord, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum DateTimeField {
     Year,
     Month,
@@ -40,8 +40,38 @@ macro_rules! make_option_ext {
     }
 }
 
+macro_rules! make_node {
+    ($($mut:tt)*) => {
+        /// A reference to a node of the SQL AST contained in an `Extension`,
+        /// as returned by `Extension::children` or `Extension::children_mut`
+        #[derive(Debug)]
+        pub enum Node<'ast> {
+            Statement(&'ast $($mut)* Statement),
+            Query(&'ast $($mut)* Query),
+            Expr(&'ast $($mut)* Expr),
+            DataType(&'ast $($mut)* DataType),
+            ObjectName(&'ast $($mut)* ObjectName),
+            Ident(&'ast $($mut)* Ident),
+        }
+    }
+}
+
 macro_rules! make_visitor {
-    ($name:ident: &$($mut:tt)*) => {
+    // The visitor, whose functions return `$return` from the trait and
+    // `$fn_return` from the free functions. `$continue` is the value that
+    // continues the traversal, and `$try` is applied to each call that visits
+    // a child node to stop the traversal early. `$enter` and `$leave`, if
+    // present, are the hooks called around the child nodes of each `Ancestor`.
+    (
+        @impl $(#[$attr:meta])* $name:ident, $children:ident,
+        mut: [$($mut:tt)*],
+        return: [$($return:tt)*],
+        fn_return: [$($fn_return:tt)*],
+        continue: [$($continue:tt)*],
+        try: [$($try:tt)*],
+        items: [$($items:tt)*],
+        hooks: [$($enter:ident, $leave:ident)?]
+    ) => {
         use crate::ast::*;
 
         make_option_ext!($($mut)*);
@@ -52,32 +82,44 @@ macro_rules! make_visitor {
         /// implementation that visits all of its child nodes. Implementors of this
         /// trait can override functions as desired to hook into AST traversal without
         /// writing code to traverse the entire AST.
+        $(#[$attr])*
         pub trait $name<'ast> {
-            fn visit_statement(&mut self, statement: &'ast $($mut)* Statement) {
+            $($items)*
+
+            $(
+                /// Called before the child nodes of `node` are visited, by the
+                /// default implementations of the functions that visit them
+                fn $enter(&mut self, _node: Ancestor<'ast>) {}
+
+                /// Called after the child nodes of `node` have been visited
+                fn $leave(&mut self, _node: Ancestor<'ast>) {}
+            )?
+
+            fn visit_statement(&mut self, statement: &'ast $($mut)* Statement) $($return)* {
                 visit_statement(self, statement)
             }
 
-            fn visit_query(&mut self, query: &'ast $($mut)* Query) {
+            fn visit_query(&mut self, query: &'ast $($mut)* Query) $($return)* {
                 visit_query(self, query)
             }
 
-            fn visit_cte(&mut self, cte: &'ast $($mut)* Cte) {
+            fn visit_cte(&mut self, cte: &'ast $($mut)* Cte) $($return)* {
                 visit_cte(self, cte)
             }
 
-            fn visit_select(&mut self, select: &'ast $($mut)* Select) {
+            fn visit_select(&mut self, select: &'ast $($mut)* Select) $($return)* {
                 visit_select(self, select)
             }
 
-            fn visit_select_item(&mut self, select_item: &'ast $($mut)* SelectItem) {
+            fn visit_select_item(&mut self, select_item: &'ast $($mut)* SelectItem) $($return)* {
                 visit_select_item(self, select_item)
             }
 
-            fn visit_table_with_joins(&mut self, table_with_joins: &'ast $($mut)* TableWithJoins) {
+            fn visit_table_with_joins(&mut self, table_with_joins: &'ast $($mut)* TableWithJoins) $($return)* {
                 visit_table_with_joins(self, table_with_joins)
             }
 
-            fn visit_table_factor(&mut self, table_factor: &'ast $($mut)* TableFactor) {
+            fn visit_table_factor(&mut self, table_factor: &'ast $($mut)* TableFactor) $($return)* {
                 visit_table_factor(self, table_factor)
             }
 
@@ -87,7 +129,7 @@ macro_rules! make_visitor {
                 alias: Option<&'ast $($mut)* TableAlias>,
                 args: &'ast $($mut)* [Expr],
                 with_hints: &'ast $($mut)* [Expr],
-            ) {
+            ) $($return)* {
                 visit_table_table_factor(self, name, alias, args, with_hints)
             }
 
@@ -96,43 +138,43 @@ macro_rules! make_visitor {
                 lateral: bool,
                 subquery: &'ast $($mut)* Query,
                 alias: Option<&'ast $($mut)* TableAlias>,
-            ) {
+            ) $($return)* {
                 visit_derived_table_factor(self, lateral, subquery, alias)
             }
 
-            fn visit_nested_join_table_factor(&mut self, table_with_joins: &'ast $($mut)* TableWithJoins) {
+            fn visit_nested_join_table_factor(&mut self, table_with_joins: &'ast $($mut)* TableWithJoins) $($return)* {
                 visit_nested_join_table_factor(self, table_with_joins)
             }
 
-            fn visit_table_alias(&mut self, alias: &'ast $($mut)* TableAlias) {
+            fn visit_table_alias(&mut self, alias: &'ast $($mut)* TableAlias) $($return)* {
                 visit_table_alias(self, alias)
             }
 
-            fn visit_join(&mut self, join: &'ast $($mut)* Join) {
+            fn visit_join(&mut self, join: &'ast $($mut)* Join) $($return)* {
                 visit_join(self, join)
             }
 
-            fn visit_join_operator(&mut self, op: &'ast $($mut)* JoinOperator) {
+            fn visit_join_operator(&mut self, op: &'ast $($mut)* JoinOperator) $($return)* {
                 visit_join_operator(self, op)
             }
 
-            fn visit_join_constraint(&mut self, constraint: &'ast $($mut)* JoinConstraint) {
+            fn visit_join_constraint(&mut self, constraint: &'ast $($mut)* JoinConstraint) $($return)* {
                 visit_join_constraint(self, constraint)
             }
 
-            fn visit_where(&mut self, expr: &'ast $($mut)* Expr) {
+            fn visit_where(&mut self, expr: &'ast $($mut)* Expr) $($return)* {
                 visit_where(self, expr)
             }
 
-            fn visit_group_by(&mut self, exprs: &'ast $($mut)* [Expr]) {
+            fn visit_group_by(&mut self, exprs: &'ast $($mut)* [Expr]) $($return)* {
                 visit_group_by(self, exprs)
             }
 
-            fn visit_having(&mut self, expr: &'ast $($mut)* Expr) {
+            fn visit_having(&mut self, expr: &'ast $($mut)* Expr) $($return)* {
                 visit_having(self, expr)
             }
 
-            fn visit_set_expr(&mut self, set_expr: &'ast $($mut)* SetExpr) {
+            fn visit_set_expr(&mut self, set_expr: &'ast $($mut)* SetExpr) $($return)* {
                 visit_set_expr(self, set_expr)
             }
 
@@ -142,65 +184,75 @@ macro_rules! make_visitor {
                 op: &'ast $($mut)* SetOperator,
                 right: &'ast $($mut)* SetExpr,
                 all: bool,
-            ) {
+            ) $($return)* {
                 visit_set_operation(self, left, op, right, all)
             }
 
-            fn visit_set_operator(&mut self, _operator: &'ast $($mut)* SetOperator) {}
+            fn visit_set_operator(&mut self, _operator: &'ast $($mut)* SetOperator) $($return)* { $($continue)* }
 
-            fn visit_order_by(&mut self, order_by: &'ast $($mut)* OrderByExpr) {
+            fn visit_order_by(&mut self, order_by: &'ast $($mut)* OrderByExpr) $($return)* {
                 visit_order_by(self, order_by)
             }
 
-            fn visit_limit(&mut self, expr: &'ast $($mut)* Expr) {
+            fn visit_limit(&mut self, expr: &'ast $($mut)* Expr) $($return)* {
                 visit_limit(self, expr)
             }
 
-            fn visit_type(&mut self, _data_type: &'ast $($mut)* DataType) {}
+            fn visit_offset(&mut self, expr: &'ast $($mut)* Expr) $($return)* {
+                visit_offset(self, expr)
+            }
+
+            fn visit_fetch(&mut self, fetch: &'ast $($mut)* Fetch) $($return)* {
+                visit_fetch(self, fetch)
+            }
+
+            fn visit_type(&mut self, data_type: &'ast $($mut)* DataType) $($return)* {
+                visit_type(self, data_type)
+            }
 
-            fn visit_expr(&mut self, expr: &'ast $($mut)* Expr) {
+            fn visit_expr(&mut self, expr: &'ast $($mut)* Expr) $($return)* {
                 visit_expr(self, expr)
             }
 
-            fn visit_unnamed_expr(&mut self, expr: &'ast $($mut)* Expr) {
+            fn visit_unnamed_expr(&mut self, expr: &'ast $($mut)* Expr) $($return)* {
                 visit_unnamed_expr(self, expr)
             }
 
-            fn visit_expr_with_alias(&mut self, expr: &'ast $($mut)* Expr, alias: &'ast $($mut)* Ident) {
+            fn visit_expr_with_alias(&mut self, expr: &'ast $($mut)* Expr, alias: &'ast $($mut)* Ident) $($return)* {
                 visit_expr_with_alias(self, expr, alias)
             }
 
-            fn visit_object_name(&mut self, object_name: &'ast $($mut)* ObjectName) {
+            fn visit_object_name(&mut self, object_name: &'ast $($mut)* ObjectName) $($return)* {
                 visit_object_name(self, object_name)
             }
 
-            fn visit_ident(&mut self, _ident: &'ast $($mut)* Ident) {}
+            fn visit_ident(&mut self, _ident: &'ast $($mut)* Ident) $($return)* { $($continue)* }
 
-            fn visit_compound_identifier(&mut self, idents: &'ast $($mut)* [Ident]) {
+            fn visit_compound_identifier(&mut self, idents: &'ast $($mut)* [Ident]) $($return)* {
                 visit_compound_identifier(self, idents)
             }
 
-            fn visit_wildcard(&mut self) {}
+            fn visit_wildcard(&mut self) $($return)* { $($continue)* }
 
-            fn visit_qualified_wildcard(&mut self, idents: &'ast $($mut)* [Ident]) {
+            fn visit_qualified_wildcard(&mut self, idents: &'ast $($mut)* [Ident]) $($return)* {
                 visit_qualified_wildcard(self, idents)
             }
 
-            fn visit_parameter(&mut self, _n: usize) {}
+            fn visit_parameter(&mut self, _n: usize) $($return)* { $($continue)* }
 
-            fn visit_is_null(&mut self, expr: &'ast $($mut)* Expr) {
+            fn visit_is_null(&mut self, expr: &'ast $($mut)* Expr) $($return)* {
                 visit_is_null(self, expr)
             }
 
-            fn visit_is_not_null(&mut self, expr: &'ast $($mut)* Expr) {
+            fn visit_is_not_null(&mut self, expr: &'ast $($mut)* Expr) $($return)* {
                 visit_is_not_null(self, expr)
             }
 
-            fn visit_in_list(&mut self, expr: &'ast $($mut)* Expr, list: &'ast $($mut)* [Expr], negated: bool) {
+            fn visit_in_list(&mut self, expr: &'ast $($mut)* Expr, list: &'ast $($mut)* [Expr], negated: bool) $($return)* {
                 visit_in_list(self, expr, list, negated)
             }
 
-            fn visit_in_subquery(&mut self, expr: &'ast $($mut)* Expr, subquery: &'ast $($mut)* Query, negated: bool) {
+            fn visit_in_subquery(&mut self, expr: &'ast $($mut)* Expr, subquery: &'ast $($mut)* Query, negated: bool) $($return)* {
                 visit_in_subquery(self, expr, subquery, negated)
             }
 
@@ -210,57 +262,63 @@ macro_rules! make_visitor {
                 low: &'ast $($mut)* Expr,
                 high: &'ast $($mut)* Expr,
                 negated: bool,
-            ) {
+            ) $($return)* {
                 visit_between(self, expr, low, high, negated)
             }
 
-            fn visit_binary_op(&mut self, left: &'ast $($mut)* Expr, op: &'ast $($mut)* BinaryOperator, right: &'ast $($mut)* Expr) {
+            fn visit_binary_op(&mut self, left: &'ast $($mut)* Expr, op: &'ast $($mut)* BinaryOperator, right: &'ast $($mut)* Expr) $($return)* {
                 visit_binary_op(self, left, op, right)
             }
 
-            fn visit_binary_operator(&mut self, _op: &'ast $($mut)* BinaryOperator) {}
+            fn visit_binary_operator(&mut self, _op: &'ast $($mut)* BinaryOperator) $($return)* { $($continue)* }
 
-            fn visit_unary_op(&mut self, expr: &'ast $($mut)* Expr, op: &'ast $($mut)* UnaryOperator) {
+            fn visit_unary_op(&mut self, expr: &'ast $($mut)* Expr, op: &'ast $($mut)* UnaryOperator) $($return)* {
                 visit_unary_op(self, expr, op)
             }
 
-            fn visit_unary_operator(&mut self, _op: &'ast $($mut)* UnaryOperator) {}
+            fn visit_unary_operator(&mut self, _op: &'ast $($mut)* UnaryOperator) $($return)* { $($continue)* }
 
-            fn visit_cast(&mut self, expr: &'ast $($mut)* Expr, data_type: &'ast $($mut)* DataType) {
+            fn visit_cast(&mut self, expr: &'ast $($mut)* Expr, data_type: &'ast $($mut)* DataType) $($return)* {
                 visit_cast(self, expr, data_type)
             }
 
-            fn visit_collate(&mut self, expr: &'ast $($mut)* Expr, collation: &'ast $($mut)* ObjectName) {
+            fn visit_collate(&mut self, expr: &'ast $($mut)* Expr, collation: &'ast $($mut)* ObjectName) $($return)* {
                 visit_collate(self, expr, collation)
             }
 
-            fn visit_extract(&mut self, field: &'ast $($mut)* DateTimeField, expr: &'ast $($mut)* Expr) {
+            fn visit_extract(&mut self, field: &'ast $($mut)* DateTimeField, expr: &'ast $($mut)* Expr) $($return)* {
                 visit_extract(self, field, expr)
             }
 
-            fn visit_date_time_field(&mut self, _field: &'ast $($mut)* DateTimeField) {}
+            fn visit_date_time_field(&mut self, _field: &'ast $($mut)* DateTimeField) $($return)* { $($continue)* }
 
-            fn visit_nested(&mut self, expr: &'ast $($mut)* Expr) {
+            fn visit_nested(&mut self, expr: &'ast $($mut)* Expr) $($return)* {
                 visit_nested(self, expr)
             }
 
-            fn visit_value(&mut self, _val: &'ast $($mut)* Value) {}
+            fn visit_value(&mut self, val: &'ast $($mut)* Value) $($return)* {
+                visit_value(self, val)
+            }
+
+            fn visit_interval(&mut self, interval: &'ast $($mut)* IntervalValue) $($return)* {
+                visit_interval(self, interval)
+            }
 
-            fn visit_function(&mut self, func: &'ast $($mut)* Function) {
+            fn visit_function(&mut self, func: &'ast $($mut)* Function) $($return)* {
                 visit_function(self, func)
             }
 
-            fn visit_window_spec(&mut self, window_spec: &'ast $($mut)* WindowSpec) {
+            fn visit_window_spec(&mut self, window_spec: &'ast $($mut)* WindowSpec) $($return)* {
                 visit_window_spec(self, window_spec)
             }
 
-            fn visit_window_frame(&mut self, window_frame: &'ast $($mut)* WindowFrame) {
+            fn visit_window_frame(&mut self, window_frame: &'ast $($mut)* WindowFrame) $($return)* {
                 visit_window_frame(self, window_frame)
             }
 
-            fn visit_window_frame_units(&mut self, _window_frame_units: &'ast $($mut)* WindowFrameUnits) {}
+            fn visit_window_frame_units(&mut self, _window_frame_units: &'ast $($mut)* WindowFrameUnits) $($return)* { $($continue)* }
 
-            fn visit_window_frame_bound(&mut self, _window_frame_bound: &'ast $($mut)* WindowFrameBound) {}
+            fn visit_window_frame_bound(&mut self, _window_frame_bound: &'ast $($mut)* WindowFrameBound) $($return)* { $($continue)* }
 
             fn visit_case(
                 &mut self,
@@ -268,23 +326,23 @@ macro_rules! make_visitor {
                 conditions: &'ast $($mut)* [Expr],
                 results: &'ast $($mut)* [Expr],
                 else_result: Option<&'ast $($mut)* Expr>,
-            ) {
+            ) $($return)* {
                 visit_case(self, operand, conditions, results, else_result)
             }
 
-            fn visit_exists(&mut self, subquery: &'ast $($mut)* Query) {
+            fn visit_exists(&mut self, subquery: &'ast $($mut)* Query) $($return)* {
                 visit_exists(self, subquery)
             }
 
-            fn visit_subquery(&mut self, subquery: &'ast $($mut)* Query) {
+            fn visit_subquery(&mut self, subquery: &'ast $($mut)* Query) $($return)* {
                 visit_subquery(self, subquery)
             }
 
-            fn visit_any(&mut self, left: &'ast $($mut)* Expr, op: &'ast $($mut)* BinaryOperator, right: &'ast $($mut)* Query) {
+            fn visit_any(&mut self, left: &'ast $($mut)* Expr, op: &'ast $($mut)* BinaryOperator, right: &'ast $($mut)* Query) $($return)* {
                 visit_any(self, left, op, right)
             }
 
-             fn visit_all(&mut self, left: &'ast $($mut)* Expr, op: &'ast $($mut)* BinaryOperator, right: &'ast $($mut)* Query) {
+             fn visit_all(&mut self, left: &'ast $($mut)* Expr, op: &'ast $($mut)* BinaryOperator, right: &'ast $($mut)* Query) $($return)* {
                 visit_all(self, left, op, right)
             }
 
@@ -293,15 +351,15 @@ macro_rules! make_visitor {
                 table_name: &'ast $($mut)* ObjectName,
                 columns: &'ast $($mut)* [Ident],
                 source: &'ast $($mut)* Query,
-            ) {
+            ) $($return)* {
                 visit_insert(self, table_name, columns, source)
             }
 
-            fn visit_values(&mut self, values: &'ast $($mut)* Values) {
+            fn visit_values(&mut self, values: &'ast $($mut)* Values) $($return)* {
                 visit_values(self, values)
             }
 
-            fn visit_values_row(&mut self, row: &'ast $($mut)* [Expr]) {
+            fn visit_values_row(&mut self, row: &'ast $($mut)* [Expr]) $($return)* {
                 visit_values_row(self, row)
             }
 
@@ -310,34 +368,34 @@ macro_rules! make_visitor {
                 table_name: &'ast $($mut)* ObjectName,
                 columns: &'ast $($mut)* [Ident],
                 values: &'ast $($mut)* [Option<String>],
-            ) {
+            ) $($return)* {
                 visit_copy(self, table_name, columns, values)
             }
 
-            fn visit_copy_values(&mut self, values: &'ast $($mut)* [Option<String>]) {
+            fn visit_copy_values(&mut self, values: &'ast $($mut)* [Option<String>]) $($return)* {
                 visit_copy_values(self, values)
             }
 
-            fn visit_copy_values_row(&mut self, _row: Option<&$($mut)* String>) {}
+            fn visit_copy_values_row(&mut self, _row: Option<&'ast $($mut)* String>) $($return)* { $($continue)* }
 
             fn visit_update(
                 &mut self,
                 table_name: &'ast $($mut)* ObjectName,
                 assignments: &'ast $($mut)* [Assignment],
                 selection: Option<&'ast $($mut)* Expr>,
-            ) {
+            ) $($return)* {
                 visit_update(self, table_name, assignments, selection)
             }
 
-            fn visit_assignment(&mut self, assignment: &'ast $($mut)* Assignment) {
+            fn visit_assignment(&mut self, assignment: &'ast $($mut)* Assignment) $($return)* {
                 visit_assignment(self, assignment)
             }
 
-            fn visit_delete(&mut self, table_name: &'ast $($mut)* ObjectName, selection: Option<&'ast $($mut)* Expr>) {
+            fn visit_delete(&mut self, table_name: &'ast $($mut)* ObjectName, selection: Option<&'ast $($mut)* Expr>) $($return)* {
                 visit_delete(self, table_name, selection)
             }
 
-            fn visit_literal_string(&mut self, _string: &'ast $($mut)* String) {}
+            fn visit_literal_string(&mut self, _string: &'ast $($mut)* String) $($return)* { $($continue)* }
 
             fn visit_create_source(
                 &mut self,
@@ -345,7 +403,7 @@ macro_rules! make_visitor {
                 url: &'ast $($mut)* String,
                 schema: Option<&'ast $($mut)* SourceSchema>,
                 with_options: &'ast $($mut)* Vec<SqlOption>,
-            ) {
+            ) $($return)* {
                 visit_create_source(self, name, url, schema, with_options)
             }
 
@@ -355,11 +413,11 @@ macro_rules! make_visitor {
                 url: &'ast $($mut)* String,
                 schema_registry: &'ast $($mut)* String,
                 with_options: &'ast $($mut)* Vec<SqlOption>,
-            ) {
+            ) $($return)* {
                 visit_create_sources(self, like, url, schema_registry, with_options)
             }
 
-            fn visit_source_schema(&mut self, source_schema: &'ast $($mut)* SourceSchema) {
+            fn visit_source_schema(&mut self, source_schema: &'ast $($mut)* SourceSchema) $($return)* {
                 visit_source_schema(self, source_schema)
             }
 
@@ -369,7 +427,7 @@ macro_rules! make_visitor {
                 from: &'ast $($mut)* ObjectName,
                 url: &'ast $($mut)* String,
                 with_options: &'ast $($mut)* Vec<SqlOption>,
-            ) {
+            ) $($return)* {
                 visit_create_sink(self, name, from, url, with_options)
             }
 
@@ -380,7 +438,7 @@ macro_rules! make_visitor {
                 query: &'ast $($mut)* Query,
                 materialized: bool,
                 with_options: &'ast $($mut)* [SqlOption],
-            ) {
+            ) $($return)* {
                 visit_create_view(self, name, columns, query, materialized, with_options)
             }
 
@@ -389,7 +447,7 @@ macro_rules! make_visitor {
                 name: &'ast $($mut)* Ident,
                 on_name: &'ast $($mut)* ObjectName,
                 key_parts: &'ast $($mut)* Vec<Expr>
-            ){
+            ) $($return)* {
                 visit_create_index(self, name, on_name, key_parts)
             }
 
@@ -402,7 +460,7 @@ macro_rules! make_visitor {
                 external: bool,
                 file_format: &'ast $($mut)* Option<FileFormat>,
                 location: &'ast $($mut)* Option<String>,
-            ) {
+            ) $($return)* {
                 visit_create_table(
                     self,
                     name,
@@ -415,21 +473,21 @@ macro_rules! make_visitor {
                 )
             }
 
-            fn visit_column_def(&mut self, column_def: &'ast $($mut)* ColumnDef) {
+            fn visit_column_def(&mut self, column_def: &'ast $($mut)* ColumnDef) $($return)* {
                 visit_column_def(self, column_def)
             }
 
-            fn visit_column_option_def(&mut self, column_option_def: &'ast $($mut)* ColumnOptionDef) {
+            fn visit_column_option_def(&mut self, column_option_def: &'ast $($mut)* ColumnOptionDef) $($return)* {
                 visit_column_option_def(self, column_option_def)
             }
 
-            fn visit_column_option(&mut self, column_option: &'ast $($mut)* ColumnOption) {
+            fn visit_column_option(&mut self, column_option: &'ast $($mut)* ColumnOption) $($return)* {
                 visit_column_option(self, column_option)
             }
 
-            fn visit_file_format(&mut self, _file_format: &'ast $($mut)* FileFormat) {}
+            fn visit_file_format(&mut self, _file_format: &'ast $($mut)* FileFormat) $($return)* { $($continue)* }
 
-            fn visit_option(&mut self, option: &'ast $($mut)* SqlOption) {
+            fn visit_option(&mut self, option: &'ast $($mut)* SqlOption) $($return)* {
                 visit_option(self, option)
             }
 
@@ -439,25 +497,25 @@ macro_rules! make_visitor {
                 if_exists: bool,
                 names: &'ast $($mut)* [ObjectName],
                 cascade: bool,
-            ) {
+            ) $($return)* {
                 visit_drop(self, object_type, if_exists, names, cascade)
             }
 
-            fn visit_object_type(&mut self, _object_type: ObjectType) {}
+            fn visit_object_type(&mut self, _object_type: ObjectType) $($return)* { $($continue)* }
 
-            fn visit_alter_table(&mut self, name: &'ast $($mut)* ObjectName, operation: &'ast $($mut)* AlterTableOperation) {
+            fn visit_alter_table(&mut self, name: &'ast $($mut)* ObjectName, operation: &'ast $($mut)* AlterTableOperation) $($return)* {
                 visit_alter_table(self, name, operation)
             }
 
-            fn visit_alter_table_operation(&mut self, operation: &'ast $($mut)* AlterTableOperation) {
+            fn visit_alter_table_operation(&mut self, operation: &'ast $($mut)* AlterTableOperation) $($return)* {
                 visit_alter_table_operation(self, operation)
             }
 
-            fn visit_alter_add_constraint(&mut self, table_constraint: &'ast $($mut)* TableConstraint) {
+            fn visit_alter_add_constraint(&mut self, table_constraint: &'ast $($mut)* TableConstraint) $($return)* {
                 visit_alter_add_constraint(self, table_constraint)
             }
 
-            fn visit_table_constraint(&mut self, table_constraint: &'ast $($mut)* TableConstraint) {
+            fn visit_table_constraint(&mut self, table_constraint: &'ast $($mut)* TableConstraint) $($return)* {
                 visit_table_constraint(self, table_constraint)
             }
 
@@ -466,7 +524,7 @@ macro_rules! make_visitor {
                 name: Option<&'ast $($mut)* Ident>,
                 columns: &'ast $($mut)* [Ident],
                 is_primary: bool,
-            ) {
+            ) $($return)* {
                 visit_table_constraint_unique(self, name, columns, is_primary)
             }
 
@@ -476,40 +534,60 @@ macro_rules! make_visitor {
                 columns: &'ast $($mut)* [Ident],
                 foreign_table: &'ast $($mut)* ObjectName,
                 referred_columns: &'ast $($mut)* [Ident],
-            ) {
+            ) $($return)* {
                 visit_table_constraint_foreign_key(self, name, columns, foreign_table, referred_columns)
             }
 
-            fn visit_table_constraint_check(&mut self, name: Option<&'ast $($mut)* Ident>, expr: &'ast $($mut)* Expr) {
+            fn visit_table_constraint_check(&mut self, name: Option<&'ast $($mut)* Ident>, expr: &'ast $($mut)* Expr) $($return)* {
                 visit_table_constraint_check(self, name, expr)
             }
 
-            fn visit_alter_drop_constraint(&mut self, name: &'ast $($mut)* Ident) {
+            fn visit_alter_drop_constraint(&mut self, name: &'ast $($mut)* Ident) $($return)* {
                 visit_alter_drop_constraint(self, name)
             }
 
+            fn visit_alter_add_column(&mut self, column_def: &'ast $($mut)* ColumnDef) $($return)* {
+                visit_alter_add_column(self, column_def)
+            }
+
+            fn visit_alter_drop_column(&mut self, name: &'ast $($mut)* Ident) $($return)* {
+                visit_alter_drop_column(self, name)
+            }
+
+            fn visit_alter_column(
+                &mut self,
+                name: &'ast $($mut)* Ident,
+                operation: &'ast $($mut)* AlterColumnOperation,
+            ) $($return)* {
+                visit_alter_column(self, name, operation)
+            }
+
+            fn visit_alter_column_operation(&mut self, operation: &'ast $($mut)* AlterColumnOperation) $($return)* {
+                visit_alter_column_operation(self, operation)
+            }
+
             fn visit_set_variable(
                 &mut self,
                 local: bool,
                 variable: &'ast $($mut)* Ident,
                 value: &'ast $($mut)* SetVariableValue,
-            ) {
+            ) $($return)* {
                 visit_set_variable(self, local, variable, value)
             }
 
-            fn visit_set_variable_value(&mut self, value: &'ast $($mut)* SetVariableValue) {
+            fn visit_set_variable_value(&mut self, value: &'ast $($mut)* SetVariableValue) $($return)* {
                 visit_set_variable_value(self, value)
             }
 
-            fn visit_show_variable(&mut self, variable: &'ast $($mut)* Ident) {
+            fn visit_show_variable(&mut self, variable: &'ast $($mut)* Ident) $($return)* {
                 visit_show_variable(self, variable)
             }
 
-            fn visit_show_objects(&mut self, object_type: ObjectType, filter: Option<&'ast $($mut)* ShowStatementFilter>) {
+            fn visit_show_objects(&mut self, object_type: ObjectType, filter: Option<&'ast $($mut)* ShowStatementFilter>) $($return)* {
                 visit_show_objects(self, object_type, filter)
             }
 
-            fn visit_show_indexes(&mut self, table_name: &'ast $($mut)* ObjectName, filter: Option<&'ast $($mut)* ShowStatementFilter>) {
+            fn visit_show_indexes(&mut self, table_name: &'ast $($mut)* ObjectName, filter: Option<&'ast $($mut)* ShowStatementFilter>) $($return)* {
                 visit_show_indexes(self, table_name, filter)
             }
 
@@ -519,73 +597,76 @@ macro_rules! make_visitor {
                 full: bool,
                 table_name: &'ast $($mut)* ObjectName,
                 filter: Option<&'ast $($mut)* ShowStatementFilter>,
-            ) {
+            ) $($return)* {
                 visit_show_columns(self, extended, full, table_name, filter)
             }
 
             fn visit_show_create_view(
                 &mut self,
                 view_name: &'ast $($mut)* ObjectName,
-            ) {
+            ) $($return)* {
                 visit_show_create_view(self, view_name)
             }
 
             fn visit_show_create_source(
                 &mut self,
                 source_name: &'ast $($mut)* ObjectName,
-            ) {
+            ) $($return)* {
                 visit_show_create_source(self, source_name)
             }
 
-            fn visit_show_statement_filter(&mut self, filter: &'ast $($mut)* ShowStatementFilter) {
+            fn visit_show_statement_filter(&mut self, filter: &'ast $($mut)* ShowStatementFilter) $($return)* {
                 visit_show_statement_filter(self, filter)
             }
 
-            fn visit_start_transaction(&mut self, modes: &'ast $($mut)* [TransactionMode]) {
+            fn visit_start_transaction(&mut self, modes: &'ast $($mut)* [TransactionMode]) $($return)* {
                 visit_start_transaction(self, modes)
             }
 
-            fn visit_set_transaction(&mut self, modes: &'ast $($mut)* [TransactionMode]) {
+            fn visit_set_transaction(&mut self, modes: &'ast $($mut)* [TransactionMode]) $($return)* {
                 visit_set_transaction(self, modes)
             }
 
-            fn visit_transaction_mode(&mut self, mode: &'ast $($mut)* TransactionMode) {
+            fn visit_transaction_mode(&mut self, mode: &'ast $($mut)* TransactionMode) $($return)* {
                 visit_transaction_mode(self, mode)
             }
 
-            fn visit_transaction_access_mode(&mut self, _access_mode: &'ast $($mut)* TransactionAccessMode) {}
+            fn visit_transaction_access_mode(&mut self, _access_mode: &'ast $($mut)* TransactionAccessMode) $($return)* { $($continue)* }
 
             fn visit_transaction_isolation_level(
                 &mut self,
                 _isolation_level: &'ast $($mut)* TransactionIsolationLevel,
-            ) {
-
-            }
+            ) $($return)* { $($continue)* }
 
-            fn visit_commit(&mut self, _chain: bool) {}
+            fn visit_commit(&mut self, _chain: bool) $($return)* { $($continue)* }
 
-            fn visit_rollback(&mut self, _chain: bool) {}
+            fn visit_rollback(&mut self, _chain: bool) $($return)* { $($continue)* }
 
-            fn visit_peek(&mut self, name: &'ast $($mut)* ObjectName, immediate: bool) {
+            fn visit_peek(&mut self, name: &'ast $($mut)* ObjectName, immediate: bool) $($return)* {
                 visit_peek(self, name, immediate)
             }
 
-            fn visit_tail(&mut self, name: &'ast $($mut)* ObjectName) {
+            fn visit_tail(&mut self, name: &'ast $($mut)* ObjectName) $($return)* {
                 visit_tail(self, name)
             }
 
-            fn visit_explain(&mut self, stage: &'ast $($mut)* Stage, query: &'ast $($mut)* Query) {
+            fn visit_explain(&mut self, stage: &'ast $($mut)* Stage, query: &'ast $($mut)* Query) $($return)* {
                 visit_explain(self, stage, query)
             }
-            fn visit_flush(&mut self, name: &'ast $($mut)* ObjectName) {
+            fn visit_flush(&mut self, name: &'ast $($mut)* ObjectName) $($return)* {
                 visit_flush(self, name)
             }
-            fn visit_flush_all(&mut self) {
+            fn visit_flush_all(&mut self) $($return)* {
                 visit_flush_all(self)
             }
+
+            fn visit_extension(&mut self, extension: &'ast $($mut)* dyn Extension) $($return)* {
+                visit_extension(self, extension)
+            }
         }
 
-        pub fn visit_statement<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, statement: &'ast $($mut)* Statement) {
+        pub fn visit_statement<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, statement: &'ast $($mut)* Statement) $($fn_return)* {
+            $(visitor.$enter(Ancestor::Statement(statement));)?
             match statement {
                 Statement::Query(query) => visitor.visit_query(query),
                 Statement::Insert {
@@ -686,57 +767,76 @@ macro_rules! make_visitor {
                 Statement::Commit { chain } => visitor.visit_commit(*chain),
                 Statement::Rollback { chain } => visitor.visit_rollback(*chain),
                 Statement::Peek { name, immediate } => {
-                    visitor.visit_peek(name, *immediate);
+                    visitor.visit_peek(name, *immediate)
                 }
                 Statement::Tail { name } => {
-                    visitor.visit_tail(name);
+                    visitor.visit_tail(name)
                 }
                 Statement::Explain { stage, query } => visitor.visit_explain(stage, query),
                 Statement::FlushSource { name } => visitor.visit_flush(name),
                 Statement::FlushAllSources => visitor.visit_flush_all(),
-            }
+                Statement::Extension(extension) => visitor.visit_extension(&$($mut)* **extension),
+            }$($try)*;
+            $(visitor.$leave(Ancestor::Statement(statement));)?
+            $($continue)*
         }
 
-        pub fn visit_query<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, query: &'ast $($mut)* Query) {
+        pub fn visit_query<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, query: &'ast $($mut)* Query) $($fn_return)* {
+            $(visitor.$enter(Ancestor::Query(query));)?
             for cte in &$($mut)* query.ctes {
-                visitor.visit_cte(cte);
+                visitor.visit_cte(cte)$($try)*;
             }
-            visitor.visit_set_expr(&$($mut)* query.body);
+            visitor.visit_set_expr(&$($mut)* query.body)$($try)*;
             for order_by in &$($mut)* query.order_by {
-                visitor.visit_order_by(order_by);
+                visitor.visit_order_by(order_by)$($try)*;
             }
             if let Some(limit) = &$($mut)* query.limit {
-                visitor.visit_limit(limit);
+                visitor.visit_limit(limit)$($try)*;
+            }
+            if let Some(offset) = &$($mut)* query.offset {
+                visitor.visit_offset(offset)$($try)*;
             }
+            if let Some(fetch) = &$($mut)* query.fetch {
+                visitor.visit_fetch(fetch)$($try)*;
+            }
+            $(visitor.$leave(Ancestor::Query(query));)?
+            $($continue)*
         }
 
-        pub fn visit_cte<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, cte: &'ast $($mut)* Cte) {
-            visitor.visit_table_alias(&$($mut)* cte.alias);
-            visitor.visit_query(&$($mut)* cte.query);
+        pub fn visit_cte<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, cte: &'ast $($mut)* Cte) $($fn_return)* {
+            $(visitor.$enter(Ancestor::Cte(cte));)?
+            visitor.visit_table_alias(&$($mut)* cte.alias)$($try)*;
+            visitor.visit_query(&$($mut)* cte.query)$($try)*;
+            $(visitor.$leave(Ancestor::Cte(cte));)?
+            $($continue)*
         }
 
-        pub fn visit_select<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, select: &'ast $($mut)* Select) {
+        pub fn visit_select<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, select: &'ast $($mut)* Select) $($fn_return)* {
+            $(visitor.$enter(Ancestor::Select(select));)?
             for select_item in &$($mut)* select.projection {
-                visitor.visit_select_item(select_item)
+                visitor.visit_select_item(select_item)$($try)*;
             }
             for table_with_joins in &$($mut)* select.from {
-                visitor.visit_table_with_joins(table_with_joins)
+                visitor.visit_table_with_joins(table_with_joins)$($try)*;
             }
             if let Some(selection) = &$($mut)* select.selection {
-                visitor.visit_where(selection);
+                visitor.visit_where(selection)$($try)*;
             }
             if !select.group_by.is_empty() {
-                visitor.visit_group_by(&$($mut)* select.group_by);
+                visitor.visit_group_by(&$($mut)* select.group_by)$($try)*;
             }
             if let Some(having) = &$($mut)* select.having {
-                visitor.visit_having(having);
+                visitor.visit_having(having)$($try)*;
             }
+            $(visitor.$leave(Ancestor::Select(select));)?
+            $($continue)*
         }
 
         pub fn visit_select_item<'ast, V: $name<'ast> + ?Sized>(
             visitor: &mut V,
             select_item: &'ast $($mut)* SelectItem,
-        ) {
+        ) $($fn_return)* {
+            $(visitor.$enter(Ancestor::SelectItem(select_item));)?
             match select_item {
                 SelectItem::UnnamedExpr(expr) => visitor.visit_unnamed_expr(expr),
                 SelectItem::ExprWithAlias { expr, alias } => visitor.visit_expr_with_alias(expr, alias),
@@ -744,23 +844,27 @@ macro_rules! make_visitor {
                     visitor.visit_qualified_wildcard(&$($mut)* object_name.0)
                 }
                 SelectItem::Wildcard => visitor.visit_wildcard(),
-            }
+            }$($try)*;
+            $(visitor.$leave(Ancestor::SelectItem(select_item));)?
+            $($continue)*
         }
 
         pub fn visit_table_with_joins<'ast, V: $name<'ast> + ?Sized>(
             visitor: &mut V,
             table_with_joins: &'ast $($mut)* TableWithJoins,
-        ) {
-            visitor.visit_table_factor(&$($mut)* table_with_joins.relation);
+        ) $($fn_return)* {
+            visitor.visit_table_factor(&$($mut)* table_with_joins.relation)$($try)*;
             for join in &$($mut)* table_with_joins.joins {
-                visitor.visit_join(join);
+                visitor.visit_join(join)$($try)*;
             }
+            $($continue)*
         }
 
         pub fn visit_table_factor<'ast, V: $name<'ast> + ?Sized>(
             visitor: &mut V,
             table_factor: &'ast $($mut)* TableFactor,
-        ) {
+        ) $($fn_return)* {
+            $(visitor.$enter(Ancestor::TableFactor(table_factor));)?
             match table_factor {
                 TableFactor::Table {
                     name,
@@ -776,7 +880,9 @@ macro_rules! make_visitor {
                 TableFactor::NestedJoin(table_with_joins) => {
                     visitor.visit_nested_join_table_factor(table_with_joins)
                 }
-            }
+            }$($try)*;
+            $(visitor.$leave(Ancestor::TableFactor(table_factor));)?
+            $($continue)*
         }
 
         pub fn visit_table_table_factor<'ast, V: $name<'ast> + ?Sized>(
@@ -785,17 +891,18 @@ macro_rules! make_visitor {
             alias: Option<&'ast $($mut)* TableAlias>,
             args: &'ast $($mut)* [Expr],
             with_hints: &'ast $($mut)* [Expr],
-        ) {
-            visitor.visit_object_name(name);
+        ) $($fn_return)* {
+            visitor.visit_object_name(name)$($try)*;
             for expr in args {
-                visitor.visit_expr(expr);
+                visitor.visit_expr(expr)$($try)*;
             }
             if let Some(alias) = alias {
-                visitor.visit_table_alias(alias);
+                visitor.visit_table_alias(alias)$($try)*;
             }
             for expr in with_hints {
-                visitor.visit_expr(expr);
+                visitor.visit_expr(expr)$($try)*;
             }
+            $($continue)*
         }
 
         pub fn visit_derived_table_factor<'ast, V: $name<'ast> + ?Sized>(
@@ -803,72 +910,89 @@ macro_rules! make_visitor {
             _lateral: bool,
             subquery: &'ast $($mut)* Query,
             alias: Option<&'ast $($mut)* TableAlias>,
-        ) {
-            visitor.visit_subquery(subquery);
+        ) $($fn_return)* {
+            visitor.visit_subquery(subquery)$($try)*;
             if let Some(alias) = alias {
-                visitor.visit_table_alias(alias);
+                visitor.visit_table_alias(alias)$($try)*;
             }
+            $($continue)*
         }
 
         pub fn visit_nested_join_table_factor<'ast, V: $name<'ast> + ?Sized>(
             visitor: &mut V,
             table_with_joins: &'ast $($mut)* TableWithJoins,
-        ) {
-            visitor.visit_table_with_joins(table_with_joins);
+        ) $($fn_return)* {
+            visitor.visit_table_with_joins(table_with_joins)$($try)*;
+            $($continue)*
         }
 
-        pub fn visit_table_alias<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, alias: &'ast $($mut)* TableAlias) {
-            visitor.visit_ident(&$($mut)* alias.name);
+        pub fn visit_table_alias<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, alias: &'ast $($mut)* TableAlias) $($fn_return)* {
+            visitor.visit_ident(&$($mut)* alias.name)$($try)*;
             for column in &$($mut)* alias.columns {
-                visitor.visit_ident(column);
+                visitor.visit_ident(column)$($try)*;
             }
+            $($continue)*
         }
 
-        pub fn visit_join<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, join: &'ast $($mut)* Join) {
-            visitor.visit_table_factor(&$($mut)* join.relation);
-            visitor.visit_join_operator(&$($mut)* join.join_operator);
+        pub fn visit_join<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, join: &'ast $($mut)* Join) $($fn_return)* {
+            $(visitor.$enter(Ancestor::Join(join));)?
+            visitor.visit_table_factor(&$($mut)* join.relation)$($try)*;
+            visitor.visit_join_operator(&$($mut)* join.join_operator)$($try)*;
+            $(visitor.$leave(Ancestor::Join(join));)?
+            $($continue)*
         }
 
-        pub fn visit_join_operator<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, op: &'ast $($mut)* JoinOperator) {
+        pub fn visit_join_operator<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, op: &'ast $($mut)* JoinOperator) $($fn_return)* {
             match op {
                 JoinOperator::Inner(constraint) => visitor.visit_join_constraint(constraint),
                 JoinOperator::LeftOuter(constraint) => visitor.visit_join_constraint(constraint),
                 JoinOperator::RightOuter(constraint) => visitor.visit_join_constraint(constraint),
                 JoinOperator::FullOuter(constraint) => visitor.visit_join_constraint(constraint),
-                JoinOperator::CrossJoin | JoinOperator::CrossApply | JoinOperator::OuterApply => (),
+                JoinOperator::CrossJoin | JoinOperator::CrossApply | JoinOperator::OuterApply => { $($continue)* }
             }
         }
 
         pub fn visit_join_constraint<'ast, V: $name<'ast> + ?Sized>(
             visitor: &mut V,
             constraint: &'ast $($mut)* JoinConstraint,
-        ) {
+        ) $($fn_return)* {
             match constraint {
                 JoinConstraint::On(expr) => visitor.visit_expr(expr),
                 JoinConstraint::Using(idents) => {
                     for ident in idents {
-                        visitor.visit_ident(ident);
+                        visitor.visit_ident(ident)$($try)*;
                     }
+                    $($continue)*
                 }
-                JoinConstraint::Natural => (),
+                JoinConstraint::Natural => { $($continue)* }
             }
         }
 
-        pub fn visit_where<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, expr: &'ast $($mut)* Expr) {
-            visitor.visit_expr(expr);
+        pub fn visit_where<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, expr: &'ast $($mut)* Expr) $($fn_return)* {
+            $(visitor.$enter(Ancestor::Clause(Clause::Where));)?
+            visitor.visit_expr(expr)$($try)*;
+            $(visitor.$leave(Ancestor::Clause(Clause::Where));)?
+            $($continue)*
         }
 
-        pub fn visit_group_by<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, exprs: &'ast $($mut)* [Expr]) {
+        pub fn visit_group_by<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, exprs: &'ast $($mut)* [Expr]) $($fn_return)* {
+            $(visitor.$enter(Ancestor::Clause(Clause::GroupBy));)?
             for expr in exprs {
-                visitor.visit_expr(expr);
+                visitor.visit_expr(expr)$($try)*;
             }
+            $(visitor.$leave(Ancestor::Clause(Clause::GroupBy));)?
+            $($continue)*
         }
 
-        pub fn visit_having<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, expr: &'ast $($mut)* Expr) {
-            visitor.visit_expr(expr);
+        pub fn visit_having<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, expr: &'ast $($mut)* Expr) $($fn_return)* {
+            $(visitor.$enter(Ancestor::Clause(Clause::Having));)?
+            visitor.visit_expr(expr)$($try)*;
+            $(visitor.$leave(Ancestor::Clause(Clause::Having));)?
+            $($continue)*
         }
 
-        pub fn visit_set_expr<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, set_expr: &'ast $($mut)* SetExpr) {
+        pub fn visit_set_expr<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, set_expr: &'ast $($mut)* SetExpr) $($fn_return)* {
+            $(visitor.$enter(Ancestor::SetExpr(set_expr));)?
             match set_expr {
                 SetExpr::Select(select) => visitor.visit_select(select),
                 SetExpr::Query(query) => visitor.visit_query(query),
@@ -879,7 +1003,9 @@ macro_rules! make_visitor {
                     right,
                     all,
                 } => visitor.visit_set_operation(left, op, right, *all),
-            }
+            }$($try)*;
+            $(visitor.$leave(Ancestor::SetExpr(set_expr));)?
+            $($continue)*
         }
 
         pub fn visit_set_operation<'ast, V: $name<'ast> + ?Sized>(
@@ -888,21 +1014,53 @@ macro_rules! make_visitor {
             op: &'ast $($mut)* SetOperator,
             right: &'ast $($mut)* SetExpr,
             _all: bool,
-        ) {
-            visitor.visit_set_expr(left);
-            visitor.visit_set_operator(op);
-            visitor.visit_set_expr(right);
+        ) $($fn_return)* {
+            visitor.visit_set_expr(left)$($try)*;
+            visitor.visit_set_operator(op)$($try)*;
+            visitor.visit_set_expr(right)$($try)*;
+            $($continue)*
+        }
+
+        pub fn visit_order_by<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, order_by: &'ast $($mut)* OrderByExpr) $($fn_return)* {
+            $(visitor.$enter(Ancestor::Clause(Clause::OrderBy));)?
+            visitor.visit_expr(&$($mut)* order_by.expr)$($try)*;
+            $(visitor.$leave(Ancestor::Clause(Clause::OrderBy));)?
+            $($continue)*
+        }
+
+        pub fn visit_limit<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, expr: &'ast $($mut)* Expr) $($fn_return)* {
+            $(visitor.$enter(Ancestor::Clause(Clause::Limit));)?
+            visitor.visit_expr(expr)$($try)*;
+            $(visitor.$leave(Ancestor::Clause(Clause::Limit));)?
+            $($continue)*
         }
 
-        pub fn visit_order_by<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, order_by: &'ast $($mut)* OrderByExpr) {
-            visitor.visit_expr(&$($mut)* order_by.expr);
+        pub fn visit_offset<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, expr: &'ast $($mut)* Expr) $($fn_return)* {
+            $(visitor.$enter(Ancestor::Clause(Clause::Offset));)?
+            visitor.visit_expr(expr)$($try)*;
+            $(visitor.$leave(Ancestor::Clause(Clause::Offset));)?
+            $($continue)*
         }
 
-        pub fn visit_limit<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, expr: &'ast $($mut)* Expr) {
-            visitor.visit_expr(expr)
+        pub fn visit_fetch<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, fetch: &'ast $($mut)* Fetch) $($fn_return)* {
+            $(visitor.$enter(Ancestor::Clause(Clause::Fetch));)?
+            if let Some(quantity) = &$($mut)* fetch.quantity {
+                visitor.visit_expr(quantity)$($try)*;
+            }
+            $(visitor.$leave(Ancestor::Clause(Clause::Fetch));)?
+            $($continue)*
+        }
+
+        pub fn visit_type<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, data_type: &'ast $($mut)* DataType) $($fn_return)* {
+            match data_type {
+                DataType::Custom(name) => visitor.visit_object_name(name),
+                DataType::Array(data_type) => visitor.visit_type(data_type),
+                _ => { $($continue)* }
+            }
         }
 
-        pub fn visit_expr<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, expr: &'ast $($mut)* Expr) {
+        pub fn visit_expr<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, expr: &'ast $($mut)* Expr) $($fn_return)* {
+            $(visitor.$enter(Ancestor::Expr(expr));)?
             match expr {
                 Expr::Identifier(ident) => visitor.visit_ident(ident),
                 Expr::Wildcard => visitor.visit_wildcard(),
@@ -950,62 +1108,72 @@ macro_rules! make_visitor {
                 Expr::Subquery(query) => visitor.visit_subquery(query),
                 Expr::Any{left, op, right, some: _} => visitor.visit_any(left, op, right),
                 Expr::All{left, op, right} => visitor.visit_all(left, op, right),
-            }
+                Expr::Extension(extension) => visitor.visit_extension(&$($mut)* **extension),
+            }$($try)*;
+            $(visitor.$leave(Ancestor::Expr(expr));)?
+            $($continue)*
         }
 
-        pub fn visit_unnamed_expr<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, expr: &'ast $($mut)* Expr) {
-            visitor.visit_expr(expr);
+        pub fn visit_unnamed_expr<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, expr: &'ast $($mut)* Expr) $($fn_return)* {
+            visitor.visit_expr(expr)$($try)*;
+            $($continue)*
         }
 
         pub fn visit_expr_with_alias<'ast, V: $name<'ast> + ?Sized>(
             visitor: &mut V,
             expr: &'ast $($mut)* Expr,
             alias: &'ast $($mut)* Ident,
-        ) {
-            visitor.visit_expr(expr);
-            visitor.visit_ident(alias);
+        ) $($fn_return)* {
+            visitor.visit_expr(expr)$($try)*;
+            visitor.visit_ident(alias)$($try)*;
+            $($continue)*
         }
 
         pub fn visit_object_name<'ast, V: $name<'ast> + ?Sized>(
             visitor: &mut V,
             object_name: &'ast $($mut)* ObjectName,
-        ) {
+        ) $($fn_return)* {
             for ident in &$($mut)* object_name.0 {
-                visitor.visit_ident(ident)
+                visitor.visit_ident(ident)$($try)*;
             }
+            $($continue)*
         }
 
         pub fn visit_compound_identifier<'ast, V: $name<'ast> + ?Sized>(
             visitor: &mut V,
             idents: &'ast $($mut)* [Ident],
-        ) {
+        ) $($fn_return)* {
             for ident in idents {
-                visitor.visit_ident(ident);
+                visitor.visit_ident(ident)$($try)*;
             }
+            $($continue)*
         }
 
         pub fn visit_qualified_wildcard<'ast, V: $name<'ast> + ?Sized>(
             visitor: &mut V,
             idents: &'ast $($mut)* [Ident],
-        ) {
+        ) $($fn_return)* {
             for ident in idents {
-                visitor.visit_ident(ident);
+                visitor.visit_ident(ident)$($try)*;
             }
+            $($continue)*
         }
 
         pub fn visit_parameter<'ast, V: $name<'ast> + ?Sized>(
-            visitor: &mut V,
-            n: usize,
-        ) {
-            visitor.visit_parameter(n)
+            _visitor: &mut V,
+            _n: usize,
+        ) $($fn_return)* {
+            $($continue)*
         }
 
-        pub fn visit_is_null<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, expr: &'ast $($mut)* Expr) {
-            visitor.visit_expr(expr);
+        pub fn visit_is_null<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, expr: &'ast $($mut)* Expr) $($fn_return)* {
+            visitor.visit_expr(expr)$($try)*;
+            $($continue)*
         }
 
-        pub fn visit_is_not_null<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, expr: &'ast $($mut)* Expr) {
-            visitor.visit_expr(expr);
+        pub fn visit_is_not_null<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, expr: &'ast $($mut)* Expr) $($fn_return)* {
+            visitor.visit_expr(expr)$($try)*;
+            $($continue)*
         }
 
         pub fn visit_in_list<'ast, V: $name<'ast> + ?Sized>(
@@ -1013,11 +1181,12 @@ macro_rules! make_visitor {
             expr: &'ast $($mut)* Expr,
             list: &'ast $($mut)* [Expr],
             _negated: bool,
-        ) {
-            visitor.visit_expr(expr);
+        ) $($fn_return)* {
+            visitor.visit_expr(expr)$($try)*;
             for e in list {
-                visitor.visit_expr(e);
+                visitor.visit_expr(e)$($try)*;
             }
+            $($continue)*
         }
 
         pub fn visit_in_subquery<'ast, V: $name<'ast> + ?Sized>(
@@ -1025,9 +1194,10 @@ macro_rules! make_visitor {
             expr: &'ast $($mut)* Expr,
             subquery: &'ast $($mut)* Query,
             _negated: bool,
-        ) {
-            visitor.visit_expr(expr);
-            visitor.visit_query(subquery);
+        ) $($fn_return)* {
+            visitor.visit_expr(expr)$($try)*;
+            visitor.visit_query(subquery)$($try)*;
+            $($continue)*
         }
 
         pub fn visit_between<'ast, V: $name<'ast> + ?Sized>(
@@ -1036,10 +1206,11 @@ macro_rules! make_visitor {
             low: &'ast $($mut)* Expr,
             high: &'ast $($mut)* Expr,
             _negated: bool,
-        ) {
-            visitor.visit_expr(expr);
-            visitor.visit_expr(low);
-            visitor.visit_expr(high);
+        ) $($fn_return)* {
+            visitor.visit_expr(expr)$($try)*;
+            visitor.visit_expr(low)$($try)*;
+            visitor.visit_expr(high)$($try)*;
+            $($continue)*
         }
 
         pub fn visit_binary_op<'ast, V: $name<'ast> + ?Sized>(
@@ -1047,86 +1218,121 @@ macro_rules! make_visitor {
             left: &'ast $($mut)* Expr,
             op: &'ast $($mut)* BinaryOperator,
             right: &'ast $($mut)* Expr,
-        ) {
-            visitor.visit_expr(left);
-            visitor.visit_binary_operator(op);
-            visitor.visit_expr(right);
+        ) $($fn_return)* {
+            visitor.visit_expr(left)$($try)*;
+            visitor.visit_binary_operator(op)$($try)*;
+            visitor.visit_expr(right)$($try)*;
+            $($continue)*
         }
 
         pub fn visit_unary_op<'ast, V: $name<'ast> + ?Sized>(
             visitor: &mut V,
             expr: &'ast $($mut)* Expr,
             op: &'ast $($mut)* UnaryOperator,
-        ) {
-            visitor.visit_expr(expr);
-            visitor.visit_unary_operator(op);
+        ) $($fn_return)* {
+            visitor.visit_expr(expr)$($try)*;
+            visitor.visit_unary_operator(op)$($try)*;
+            $($continue)*
         }
 
         pub fn visit_cast<'ast, V: $name<'ast> + ?Sized>(
             visitor: &mut V,
             expr: &'ast $($mut)* Expr,
             data_type: &'ast $($mut)* DataType,
-        ) {
-            visitor.visit_expr(expr);
-            visitor.visit_type(data_type);
+        ) $($fn_return)* {
+            visitor.visit_expr(expr)$($try)*;
+            visitor.visit_type(data_type)$($try)*;
+            $($continue)*
         }
 
         pub fn visit_collate<'ast, V: $name<'ast> + ?Sized>(
             visitor: &mut V,
             expr: &'ast $($mut)* Expr,
             collation: &'ast $($mut)* ObjectName,
-        ) {
-            visitor.visit_expr(expr);
-            visitor.visit_object_name(collation);
+        ) $($fn_return)* {
+            visitor.visit_expr(expr)$($try)*;
+            visitor.visit_object_name(collation)$($try)*;
+            $($continue)*
         }
 
         pub fn visit_extract<'ast, V: $name<'ast> + ?Sized>(
             visitor: &mut V,
             field: &'ast $($mut)* DateTimeField,
             expr: &'ast $($mut)* Expr,
-        ) {
-            visitor.visit_date_time_field(field);
-            visitor.visit_expr(expr);
+        ) $($fn_return)* {
+            visitor.visit_date_time_field(field)$($try)*;
+            visitor.visit_expr(expr)$($try)*;
+            $($continue)*
+        }
+
+        pub fn visit_nested<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, expr: &'ast $($mut)* Expr) $($fn_return)* {
+            visitor.visit_expr(expr)$($try)*;
+            $($continue)*
         }
 
-        pub fn visit_nested<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, expr: &'ast $($mut)* Expr) {
-            visitor.visit_expr(expr);
+        pub fn visit_value<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, val: &'ast $($mut)* Value) $($fn_return)* {
+            match val {
+                Value::Interval(interval) => visitor.visit_interval(interval),
+                Value::Array(values) => {
+                    for val in values {
+                        visitor.visit_value(val)$($try)*;
+                    }
+                    $($continue)*
+                }
+                _ => { $($continue)* }
+            }
+        }
+
+        pub fn visit_interval<'ast, V: $name<'ast> + ?Sized>(
+            visitor: &mut V,
+            interval: &'ast $($mut)* IntervalValue,
+        ) $($fn_return)* {
+            visitor.visit_date_time_field(&$($mut)* interval.leading_field)$($try)*;
+            if let Some(last_field) = &$($mut)* interval.last_field {
+                visitor.visit_date_time_field(last_field)$($try)*;
+            }
+            $($continue)*
         }
 
-        pub fn visit_function<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, func: &'ast $($mut)* Function) {
-            visitor.visit_object_name(&$($mut)* func.name);
+        pub fn visit_function<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, func: &'ast $($mut)* Function) $($fn_return)* {
+            $(visitor.$enter(Ancestor::Function(func));)?
+            visitor.visit_object_name(&$($mut)* func.name)$($try)*;
             for arg in &$($mut)* func.args {
-                visitor.visit_expr(arg);
+                visitor.visit_expr(arg)$($try)*;
             }
             if let Some(over) = &$($mut)* func.over {
-                visitor.visit_window_spec(over);
+                visitor.visit_window_spec(over)$($try)*;
             }
+            $(visitor.$leave(Ancestor::Function(func));)?
+            $($continue)*
         }
 
         pub fn visit_window_spec<'ast, V: $name<'ast> + ?Sized>(
             visitor: &mut V,
             window_spec: &'ast $($mut)* WindowSpec,
-        ) {
+        ) $($fn_return)* {
             for expr in &$($mut)* window_spec.partition_by {
-                visitor.visit_expr(expr);
+                visitor.visit_expr(expr)$($try)*;
             }
             for order_by in &$($mut)* window_spec.order_by {
-                visitor.visit_order_by(order_by);
+                visitor.visit_order_by(order_by)$($try)*;
             }
             if let Some(window_frame) = &$($mut)* window_spec.window_frame {
-                visitor.visit_window_frame(window_frame);
+                visitor.visit_window_frame(window_frame)$($try)*;
             }
+            $($continue)*
         }
 
         pub fn visit_window_frame<'ast, V: $name<'ast> + ?Sized>(
             visitor: &mut V,
             window_frame: &'ast $($mut)* WindowFrame,
-        ) {
-            visitor.visit_window_frame_units(&$($mut)* window_frame.units);
-            visitor.visit_window_frame_bound(&$($mut)* window_frame.start_bound);
+        ) $($fn_return)* {
+            visitor.visit_window_frame_units(&$($mut)* window_frame.units)$($try)*;
+            visitor.visit_window_frame_bound(&$($mut)* window_frame.start_bound)$($try)*;
             if let Some(end_bound) = &$($mut)* window_frame.end_bound {
-                visitor.visit_window_frame_bound(end_bound);
+                visitor.visit_window_frame_bound(end_bound)$($try)*;
             }
+            $($continue)*
         }
 
         pub fn visit_case<'ast, V: $name<'ast> + ?Sized>(
@@ -1135,39 +1341,42 @@ macro_rules! make_visitor {
             conditions: &'ast $($mut)* [Expr],
             results: &'ast $($mut)* [Expr],
             else_result: Option<&'ast $($mut)* Expr>,
-        ) {
+        ) $($fn_return)* {
             if let Some(operand) = operand {
-                visitor.visit_expr(operand);
+                visitor.visit_expr(operand)$($try)*;
             }
             for cond in conditions {
-                visitor.visit_expr(cond);
+                visitor.visit_expr(cond)$($try)*;
             }
             for res in results {
-                visitor.visit_expr(res);
+                visitor.visit_expr(res)$($try)*;
             }
             if let Some(else_result) = else_result {
-                visitor.visit_expr(else_result);
+                visitor.visit_expr(else_result)$($try)*;
             }
+            $($continue)*
         }
 
-        pub fn visit_exists<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, subquery: &'ast $($mut)* Query) {
+        pub fn visit_exists<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, subquery: &'ast $($mut)* Query) $($fn_return)* {
             visitor.visit_query(subquery)
         }
 
-        pub fn visit_subquery<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, subquery: &'ast $($mut)* Query) {
+        pub fn visit_subquery<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, subquery: &'ast $($mut)* Query) $($fn_return)* {
             visitor.visit_query(subquery)
         }
 
-        pub fn visit_any<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, left: &'ast $($mut)* Expr, op: &'ast $($mut)* BinaryOperator, right: &'ast $($mut)* Query) {
-            visitor.visit_expr(left);
-            visitor.visit_binary_operator(op);
-            visitor.visit_query(right);
+        pub fn visit_any<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, left: &'ast $($mut)* Expr, op: &'ast $($mut)* BinaryOperator, right: &'ast $($mut)* Query) $($fn_return)* {
+            visitor.visit_expr(left)$($try)*;
+            visitor.visit_binary_operator(op)$($try)*;
+            visitor.visit_query(right)$($try)*;
+            $($continue)*
         }
 
-        pub fn visit_all<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, left: &'ast $($mut)* Expr, op: &'ast $($mut)* BinaryOperator, right: &'ast $($mut)* Query) {
-            visitor.visit_expr(left);
-            visitor.visit_binary_operator(op);
-            visitor.visit_query(right);
+        pub fn visit_all<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, left: &'ast $($mut)* Expr, op: &'ast $($mut)* BinaryOperator, right: &'ast $($mut)* Query) $($fn_return)* {
+            visitor.visit_expr(left)$($try)*;
+            visitor.visit_binary_operator(op)$($try)*;
+            visitor.visit_query(right)$($try)*;
+            $($continue)*
         }
 
         pub fn visit_insert<'ast, V: $name<'ast> + ?Sized>(
@@ -1175,24 +1384,27 @@ macro_rules! make_visitor {
             table_name: &'ast $($mut)* ObjectName,
             columns: &'ast $($mut)* [Ident],
             source: &'ast $($mut)* Query,
-        ) {
-            visitor.visit_object_name(table_name);
+        ) $($fn_return)* {
+            visitor.visit_object_name(table_name)$($try)*;
             for column in columns {
-                visitor.visit_ident(column);
+                visitor.visit_ident(column)$($try)*;
             }
-            visitor.visit_query(source);
+            visitor.visit_query(source)$($try)*;
+            $($continue)*
         }
 
-        pub fn visit_values<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, values: &'ast $($mut)* Values) {
+        pub fn visit_values<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, values: &'ast $($mut)* Values) $($fn_return)* {
             for row in &$($mut)* values.0 {
-                visitor.visit_values_row(row)
+                visitor.visit_values_row(row)$($try)*;
             }
+            $($continue)*
         }
 
-        pub fn visit_values_row<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, row: &'ast $($mut)* [Expr]) {
+        pub fn visit_values_row<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, row: &'ast $($mut)* [Expr]) $($fn_return)* {
             for expr in row {
-                visitor.visit_expr(expr)
+                visitor.visit_expr(expr)$($try)*;
             }
+            $($continue)*
         }
 
         pub fn visit_copy<'ast, V: $name<'ast> + ?Sized>(
@@ -1200,21 +1412,23 @@ macro_rules! make_visitor {
             table_name: &'ast $($mut)* ObjectName,
             columns: &'ast $($mut)* [Ident],
             values: &'ast $($mut)* [Option<String>],
-        ) {
-            visitor.visit_object_name(table_name);
+        ) $($fn_return)* {
+            visitor.visit_object_name(table_name)$($try)*;
             for column in columns {
-                visitor.visit_ident(column);
+                visitor.visit_ident(column)$($try)*;
             }
-            visitor.visit_copy_values(values);
+            visitor.visit_copy_values(values)$($try)*;
+            $($continue)*
         }
 
         pub fn visit_copy_values<'ast, V: $name<'ast> + ?Sized>(
             visitor: &mut V,
             values: &'ast $($mut)* [Option<String>],
-        ) {
+        ) $($fn_return)* {
             for value in values {
-                visitor.visit_copy_values_row(value.as_auto_ref());
+                visitor.visit_copy_values_row(value.as_auto_ref())$($try)*;
             }
+            $($continue)*
         }
 
         pub fn visit_update<'ast, V: $name<'ast> + ?Sized>(
@@ -1222,33 +1436,36 @@ macro_rules! make_visitor {
             table_name: &'ast $($mut)* ObjectName,
             assignments: &'ast $($mut)* [Assignment],
             selection: Option<&'ast $($mut)* Expr>,
-        ) {
-            visitor.visit_object_name(table_name);
+        ) $($fn_return)* {
+            visitor.visit_object_name(table_name)$($try)*;
             for assignment in assignments {
-                visitor.visit_assignment(assignment);
+                visitor.visit_assignment(assignment)$($try)*;
             }
             if let Some(selection) = selection {
-                visitor.visit_where(selection);
+                visitor.visit_where(selection)$($try)*;
             }
+            $($continue)*
         }
 
         pub fn visit_assignment<'ast, V: $name<'ast> + ?Sized>(
             visitor: &mut V,
             assignment: &'ast $($mut)* Assignment,
-        ) {
-            visitor.visit_ident(&$($mut)* assignment.id);
-            visitor.visit_expr(&$($mut)* assignment.value);
+        ) $($fn_return)* {
+            visitor.visit_ident(&$($mut)* assignment.id)$($try)*;
+            visitor.visit_expr(&$($mut)* assignment.value)$($try)*;
+            $($continue)*
         }
 
         pub fn visit_delete<'ast, V: $name<'ast> + ?Sized>(
             visitor: &mut V,
             table_name: &'ast $($mut)* ObjectName,
             selection: Option<&'ast $($mut)* Expr>,
-        ) {
-            visitor.visit_object_name(table_name);
+        ) $($fn_return)* {
+            visitor.visit_object_name(table_name)$($try)*;
             if let Some(selection) = selection {
-                visitor.visit_where(selection);
+                visitor.visit_where(selection)$($try)*;
             }
+            $($continue)*
         }
 
         pub fn visit_create_source<'ast, V: $name<'ast> + ?Sized>(
@@ -1257,15 +1474,16 @@ macro_rules! make_visitor {
             url: &'ast $($mut)* String,
             schema: Option<&'ast $($mut)* SourceSchema>,
             with_options: &'ast $($mut)* Vec<SqlOption>,
-        ) {
-            visitor.visit_object_name(name);
-            visitor.visit_literal_string(url);
+        ) $($fn_return)* {
+            visitor.visit_object_name(name)$($try)*;
+            visitor.visit_literal_string(url)$($try)*;
             if let Some(schema) = schema {
-                visitor.visit_source_schema(schema);
+                visitor.visit_source_schema(schema)$($try)*;
             }
             for option in with_options {
-                visitor.visit_option(option);
+                visitor.visit_option(option)$($try)*;
             }
+            $($continue)*
         }
 
         pub fn visit_create_sources<'ast, V: $name<'ast> + ?Sized>(
@@ -1274,21 +1492,22 @@ macro_rules! make_visitor {
             url: &'ast $($mut)* String,
             schema_registry: &'ast $($mut)* String,
             with_options: &'ast $($mut)* Vec<SqlOption>,
-        ) {
+        ) $($fn_return)* {
             if let Some(like) = like {
-                visitor.visit_literal_string(like);
+                visitor.visit_literal_string(like)$($try)*;
             }
-            visitor.visit_literal_string(url);
-            visitor.visit_literal_string(schema_registry);
+            visitor.visit_literal_string(url)$($try)*;
+            visitor.visit_literal_string(schema_registry)$($try)*;
             for option in with_options {
-                visitor.visit_option(option);
+                visitor.visit_option(option)$($try)*;
             }
+            $($continue)*
         }
 
         fn visit_source_schema<'ast, V: $name<'ast> + ?Sized>(
             visitor: &mut V,
             source_schema: &'ast $($mut)* SourceSchema,
-        ) {
+        ) $($fn_return)* {
             match source_schema {
                 SourceSchema::Raw(schema) => visitor.visit_literal_string(schema),
                 SourceSchema::Registry(url) => visitor.visit_literal_string(url),
@@ -1301,13 +1520,14 @@ macro_rules! make_visitor {
             from: &'ast $($mut)* ObjectName,
             url: &'ast $($mut)* String,
             with_options: &'ast $($mut)* Vec<SqlOption>,
-        ) {
-            visitor.visit_object_name(name);
-            visitor.visit_object_name(from);
-            visitor.visit_literal_string(url);
+        ) $($fn_return)* {
+            visitor.visit_object_name(name)$($try)*;
+            visitor.visit_object_name(from)$($try)*;
+            visitor.visit_literal_string(url)$($try)*;
             for option in with_options {
-                visitor.visit_option(option);
+                visitor.visit_option(option)$($try)*;
             }
+            $($continue)*
         }
 
         pub fn visit_drop<'ast, V: $name<'ast> + ?Sized>(
@@ -1316,11 +1536,12 @@ macro_rules! make_visitor {
             _if_exists: bool,
             names: &'ast $($mut)* [ObjectName],
             _cascade: bool,
-        ) {
-            visitor.visit_object_type(object_type);
+        ) $($fn_return)* {
+            visitor.visit_object_type(object_type)$($try)*;
             for name in names {
-                visitor.visit_object_name(name);
+                visitor.visit_object_name(name)$($try)*;
             }
+            $($continue)*
         }
 
         pub fn visit_create_view<'ast, V: $name<'ast> + ?Sized>(
@@ -1330,15 +1551,16 @@ macro_rules! make_visitor {
             query: &'ast $($mut)* Query,
             _materialized: bool,
             with_options: &'ast $($mut)* [SqlOption],
-        ) {
-            visitor.visit_object_name(name);
+        ) $($fn_return)* {
+            visitor.visit_object_name(name)$($try)*;
             for column in columns {
-                visitor.visit_ident(column);
+                visitor.visit_ident(column)$($try)*;
             }
             for option in with_options {
-                visitor.visit_option(option);
+                visitor.visit_option(option)$($try)*;
             }
-            visitor.visit_query(query);
+            visitor.visit_query(query)$($try)*;
+            $($continue)*
         }
 
         pub fn visit_create_index<'ast, V: $name<'ast> + ?Sized>(
@@ -1346,12 +1568,13 @@ macro_rules! make_visitor {
             name: &'ast $($mut)* Ident,
             on_name: &'ast $($mut)* ObjectName,
             key_parts: &'ast $($mut)* Vec<Expr>,
-        ) {
-            visitor.visit_ident(name);
-            visitor.visit_object_name(on_name);
+        ) $($fn_return)* {
+            visitor.visit_ident(name)$($try)*;
+            visitor.visit_object_name(on_name)$($try)*;
             for key_part in key_parts {
-                visitor.visit_expr(key_part);
+                visitor.visit_expr(key_part)$($try)*;
             }
+            $($continue)*
         }
 
         pub fn visit_create_table<'ast, V: $name<'ast> + ?Sized>(
@@ -1363,42 +1586,47 @@ macro_rules! make_visitor {
             _external: bool,
             file_format: &'ast $($mut)* Option<FileFormat>,
             location: &'ast $($mut)* Option<String>,
-        ) {
-            visitor.visit_object_name(name);
+        ) $($fn_return)* {
+            visitor.visit_object_name(name)$($try)*;
             for column in columns {
-                visitor.visit_column_def(column);
+                visitor.visit_column_def(column)$($try)*;
             }
             for constraint in constraints {
-                visitor.visit_table_constraint(constraint);
+                visitor.visit_table_constraint(constraint)$($try)*;
             }
             for option in with_options {
-                visitor.visit_option(option);
+                visitor.visit_option(option)$($try)*;
             }
             if let Some(file_format) = file_format {
-                visitor.visit_file_format(file_format);
+                visitor.visit_file_format(file_format)$($try)*;
             }
             if let Some(location) = location {
-                visitor.visit_literal_string(location);
+                visitor.visit_literal_string(location)$($try)*;
             }
+            $($continue)*
         }
 
         pub fn visit_column_def<'ast, V: $name<'ast> + ?Sized>(
             visitor: &mut V,
             column_def: &'ast $($mut)* ColumnDef,
-        ) {
-            visitor.visit_ident(&$($mut)* column_def.name);
-            visitor.visit_type(&$($mut)* column_def.data_type);
+        ) $($fn_return)* {
+            visitor.visit_ident(&$($mut)* column_def.name)$($try)*;
+            visitor.visit_type(&$($mut)* column_def.data_type)$($try)*;
+            if let Some(collation) = &$($mut)* column_def.collation {
+                visitor.visit_object_name(collation)$($try)*;
+            }
             for option in &$($mut)* column_def.options {
-                visitor.visit_column_option_def(option);
+                visitor.visit_column_option_def(option)$($try)*;
             }
+            $($continue)*
         }
 
         pub fn visit_column_option_def<'ast, V: $name<'ast> + ?Sized>(
             visitor: &mut V,
             column_option_def: &'ast $($mut)* ColumnOptionDef,
-        ) {
+        ) $($fn_return)* {
             if let Some(name) = &$($mut)* column_option_def.name {
-                visitor.visit_ident(name);
+                visitor.visit_ident(name)$($try)*;
             }
             visitor.visit_column_option(&$($mut)* column_option_def.option)
         }
@@ -1406,59 +1634,68 @@ macro_rules! make_visitor {
         pub fn visit_column_option<'ast, V: $name<'ast> + ?Sized>(
             visitor: &mut V,
             column_option: &'ast $($mut)* ColumnOption,
-        ) {
+        ) $($fn_return)* {
             match column_option {
-                ColumnOption::Null | ColumnOption::NotNull | ColumnOption::Unique { .. } => (),
+                ColumnOption::Null | ColumnOption::NotNull | ColumnOption::Unique { .. } => { $($continue)* }
                 ColumnOption::Default(expr) | ColumnOption::Check(expr) => visitor.visit_expr(expr),
                 ColumnOption::ForeignKey {
                     foreign_table,
                     referred_columns,
                 } => {
-                    visitor.visit_object_name(foreign_table);
+                    visitor.visit_object_name(foreign_table)$($try)*;
                     for column in referred_columns {
-                        visitor.visit_ident(column);
+                        visitor.visit_ident(column)$($try)*;
                     }
+                    $($continue)*
                 }
             }
         }
 
-        pub fn visit_option<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, option: &'ast $($mut)* SqlOption) {
-            visitor.visit_ident(&$($mut)* option.name);
-            visitor.visit_value(&$($mut)* option.value);
+        pub fn visit_option<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, option: &'ast $($mut)* SqlOption) $($fn_return)* {
+            visitor.visit_ident(&$($mut)* option.name)$($try)*;
+            visitor.visit_value(&$($mut)* option.value)$($try)*;
+            $($continue)*
         }
 
         pub fn visit_alter_table<'ast, V: $name<'ast> + ?Sized>(
             visitor: &mut V,
             name: &'ast $($mut)* ObjectName,
             operation: &'ast $($mut)* AlterTableOperation,
-        ) {
-            visitor.visit_object_name(name);
-            visitor.visit_alter_table_operation(operation);
+        ) $($fn_return)* {
+            visitor.visit_object_name(name)$($try)*;
+            visitor.visit_alter_table_operation(operation)$($try)*;
+            $($continue)*
         }
 
         pub fn visit_alter_table_operation<'ast, V: $name<'ast> + ?Sized>(
             visitor: &mut V,
             operation: &'ast $($mut)* AlterTableOperation,
-        ) {
+        ) $($fn_return)* {
             match operation {
                 AlterTableOperation::AddConstraint(table_constraint) => {
                     visitor.visit_alter_add_constraint(table_constraint)
                 }
                 AlterTableOperation::DropConstraint { name } => visitor.visit_alter_drop_constraint(name),
+                AlterTableOperation::AddColumn(column_def) => visitor.visit_alter_add_column(column_def),
+                AlterTableOperation::DropColumn { name } => visitor.visit_alter_drop_column(name),
+                AlterTableOperation::AlterColumn { name, operation } => {
+                    visitor.visit_alter_column(name, operation)
+                }
             }
         }
 
         pub fn visit_alter_add_constraint<'ast, V: $name<'ast> + ?Sized>(
             visitor: &mut V,
             table_constraint: &'ast $($mut)* TableConstraint,
-        ) {
-            visitor.visit_table_constraint(table_constraint);
+        ) $($fn_return)* {
+            visitor.visit_table_constraint(table_constraint)$($try)*;
+            $($continue)*
         }
 
         pub fn visit_table_constraint<'ast, V: $name<'ast> + ?Sized>(
             visitor: &mut V,
             table_constraint: &'ast $($mut)* TableConstraint,
-        ) {
+        ) $($fn_return)* {
             match table_constraint {
                 TableConstraint::Unique {
                     name,
@@ -1487,13 +1724,14 @@ macro_rules! make_visitor {
             name: Option<&'ast $($mut)* Ident>,
             columns: &'ast $($mut)* [Ident],
             _is_primary: bool,
-        ) {
+        ) $($fn_return)* {
             if let Some(name) = name {
-                visitor.visit_ident(name);
+                visitor.visit_ident(name)$($try)*;
             }
             for column in columns {
-                visitor.visit_ident(column);
+                visitor.visit_ident(column)$($try)*;
             }
+            $($continue)*
         }
 
         pub fn visit_table_constraint_foreign_key<'ast, V: $name<'ast> + ?Sized>(
@@ -1502,35 +1740,77 @@ macro_rules! make_visitor {
             columns: &'ast $($mut)* [Ident],
             foreign_table: &'ast $($mut)* ObjectName,
             referred_columns: &'ast $($mut)* [Ident],
-        ) {
+        ) $($fn_return)* {
             if let Some(name) = name {
-                visitor.visit_ident(name);
+                visitor.visit_ident(name)$($try)*;
             }
             for column in columns {
-                visitor.visit_ident(column);
+                visitor.visit_ident(column)$($try)*;
             }
-            visitor.visit_object_name(foreign_table);
+            visitor.visit_object_name(foreign_table)$($try)*;
             for column in referred_columns {
-                visitor.visit_ident(column);
+                visitor.visit_ident(column)$($try)*;
             }
+            $($continue)*
         }
 
         pub fn visit_table_constraint_check<'ast, V: $name<'ast> + ?Sized>(
             visitor: &mut V,
             name: Option<&'ast $($mut)* Ident>,
             expr: &'ast $($mut)* Expr,
-        ) {
+        ) $($fn_return)* {
             if let Some(name) = name {
-                visitor.visit_ident(name);
+                visitor.visit_ident(name)$($try)*;
             }
-            visitor.visit_expr(expr);
+            visitor.visit_expr(expr)$($try)*;
+            $($continue)*
         }
 
         pub fn visit_alter_drop_constraint<'ast, V: $name<'ast> + ?Sized>(
             visitor: &mut V,
             name: &'ast $($mut)* Ident,
-        ) {
-            visitor.visit_ident(name);
+        ) $($fn_return)* {
+            visitor.visit_ident(name)$($try)*;
+            $($continue)*
+        }
+
+        pub fn visit_alter_add_column<'ast, V: $name<'ast> + ?Sized>(
+            visitor: &mut V,
+            column_def: &'ast $($mut)* ColumnDef,
+        ) $($fn_return)* {
+            visitor.visit_column_def(column_def)$($try)*;
+            $($continue)*
+        }
+
+        pub fn visit_alter_drop_column<'ast, V: $name<'ast> + ?Sized>(
+            visitor: &mut V,
+            name: &'ast $($mut)* Ident,
+        ) $($fn_return)* {
+            visitor.visit_ident(name)$($try)*;
+            $($continue)*
+        }
+
+        pub fn visit_alter_column<'ast, V: $name<'ast> + ?Sized>(
+            visitor: &mut V,
+            name: &'ast $($mut)* Ident,
+            operation: &'ast $($mut)* AlterColumnOperation,
+        ) $($fn_return)* {
+            visitor.visit_ident(name)$($try)*;
+            visitor.visit_alter_column_operation(operation)$($try)*;
+            $($continue)*
+        }
+
+        pub fn visit_alter_column_operation<'ast, V: $name<'ast> + ?Sized>(
+            visitor: &mut V,
+            operation: &'ast $($mut)* AlterColumnOperation,
+        ) $($fn_return)* {
+            match operation {
+                AlterColumnOperation::SetDataType(data_type) => visitor.visit_type(data_type),
+                AlterColumnOperation::SetDefault(expr) => visitor.visit_expr(expr),
+                AlterColumnOperation::DropDefault
+                | AlterColumnOperation::SetNotNull
+                | AlterColumnOperation::DropNotNull => { $($continue)* }
+            }
         }
 
         pub fn visit_set_variable<'ast, V: $name<'ast> + ?Sized>(
@@ -1538,45 +1818,49 @@ macro_rules! make_visitor {
             _local: bool,
             variable: &'ast $($mut)* Ident,
             value: &'ast $($mut)* SetVariableValue,
-        ) {
-            visitor.visit_ident(variable);
-            visitor.visit_set_variable_value(value);
+        ) $($fn_return)* {
+            visitor.visit_ident(variable)$($try)*;
+            visitor.visit_set_variable_value(value)$($try)*;
+            $($continue)*
         }
 
         pub fn visit_set_variable_value<'ast, V: $name<'ast> + ?Sized>(
             visitor: &mut V,
             value: &'ast $($mut)* SetVariableValue,
-        ) {
+        ) $($fn_return)* {
             match value {
                 SetVariableValue::Ident(ident) => visitor.visit_ident(ident),
                 SetVariableValue::Literal(value) => visitor.visit_value(value),
             }
         }
 
-        pub fn visit_show_variable<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, variable: &'ast $($mut)* Ident) {
-            visitor.visit_ident(variable);
+        pub fn visit_show_variable<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, variable: &'ast $($mut)* Ident) $($fn_return)* {
+            visitor.visit_ident(variable)$($try)*;
+            $($continue)*
         }
 
         pub fn visit_show_objects<'ast, V: $name<'ast> + ?Sized>(
             visitor: &mut V,
             object_type: ObjectType,
             filter: Option<&'ast $($mut)* ShowStatementFilter>
-        ) {
-            visitor.visit_object_type(object_type);
+        ) $($fn_return)* {
+            visitor.visit_object_type(object_type)$($try)*;
             if let Some(filter) = filter {
-                visitor.visit_show_statement_filter(filter);
+                visitor.visit_show_statement_filter(filter)$($try)*;
             }
+            $($continue)*
         }
 
         pub fn visit_show_indexes<'ast, V: $name<'ast> + ?Sized>(
             visitor: &mut V,
             table_name: &'ast $($mut)* ObjectName,
             filter: Option<&'ast $($mut)* ShowStatementFilter>
-        ) {
-            visitor.visit_object_name(table_name);
+        ) $($fn_return)* {
+            visitor.visit_object_name(table_name)$($try)*;
             if let Some(filter) = filter {
-                visitor.visit_show_statement_filter(filter);
+                visitor.visit_show_statement_filter(filter)$($try)*;
             }
+            $($continue)*
         }
 
         pub fn visit_show_columns<'ast, V: $name<'ast> + ?Sized>(
@@ -1585,31 +1869,34 @@ macro_rules! make_visitor {
             _full: bool,
             table_name: &'ast $($mut)* ObjectName,
             filter: Option<&'ast $($mut)* ShowStatementFilter>,
-        ) {
-            visitor.visit_object_name(table_name);
+        ) $($fn_return)* {
+            visitor.visit_object_name(table_name)$($try)*;
             if let Some(filter) = filter {
-                visitor.visit_show_statement_filter(filter);
+                visitor.visit_show_statement_filter(filter)$($try)*;
             }
+            $($continue)*
         }
 
         pub fn visit_show_create_view<'ast, V: $name<'ast> + ?Sized>(
             visitor: &mut V,
             view_name: &'ast $($mut)* ObjectName,
-        ) {
-            visitor.visit_object_name(view_name);
+        ) $($fn_return)* {
+            visitor.visit_object_name(view_name)$($try)*;
+            $($continue)*
         }
 
         pub fn visit_show_create_source<'ast, V: $name<'ast> + ?Sized>(
             visitor: &mut V,
             source_name: &'ast $($mut)* ObjectName
-        ) {
-            visitor.visit_object_name(source_name);
+        ) $($fn_return)* {
+            visitor.visit_object_name(source_name)$($try)*;
+            $($continue)*
         }
 
         pub fn visit_show_statement_filter<'ast, V: $name<'ast> + ?Sized>(
             visitor: &mut V,
             filter: &'ast $($mut)* ShowStatementFilter,
-        ) {
+        ) $($fn_return)* {
             match filter {
                 ShowStatementFilter::Like(pattern) => visitor.visit_literal_string(pattern),
                 ShowStatementFilter::Where(expr) => visitor.visit_expr(expr),
@@ -1619,25 +1906,27 @@ macro_rules! make_visitor {
         pub fn visit_start_transaction<'ast, V: $name<'ast> + ?Sized>(
             visitor: &mut V,
             modes: &'ast $($mut)* [TransactionMode],
-        ) {
+        ) $($fn_return)* {
             for mode in modes {
-                visitor.visit_transaction_mode(mode)
+                visitor.visit_transaction_mode(mode)$($try)*;
             }
+            $($continue)*
         }
 
         pub fn visit_set_transaction<'ast, V: $name<'ast> + ?Sized>(
             visitor: &mut V,
             modes: &'ast $($mut)* [TransactionMode],
-        ) {
+        ) $($fn_return)* {
             for mode in modes {
-                visitor.visit_transaction_mode(mode)
+                visitor.visit_transaction_mode(mode)$($try)*;
             }
+            $($continue)*
         }
 
         pub fn visit_transaction_mode<'ast, V: $name<'ast> + ?Sized>(
             visitor: &mut V,
             mode: &'ast $($mut)* TransactionMode,
-        ) {
+        ) $($fn_return)* {
             match mode {
                 TransactionMode::AccessMode(access_mode) => {
                     visitor.visit_transaction_access_mode(access_mode)
@@ -1652,33 +1941,1040 @@ macro_rules! make_visitor {
             visitor: &mut V,
             name: &'ast $($mut)* ObjectName,
             _immediate: bool,
-        ) {
-            visitor.visit_object_name(name);
+        ) $($fn_return)* {
+            visitor.visit_object_name(name)$($try)*;
+            $($continue)*
         }
 
-        pub fn visit_tail<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, name: &'ast $($mut)* ObjectName) {
-            visitor.visit_object_name(name);
+        pub fn visit_tail<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, name: &'ast $($mut)* ObjectName) $($fn_return)* {
+            visitor.visit_object_name(name)$($try)*;
+            $($continue)*
         }
 
-        pub fn visit_explain<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, _stage: &'ast $($mut)* Stage, query: &'ast $($mut)* Query) {
-            visitor.visit_query(query);
+        pub fn visit_explain<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, _stage: &'ast $($mut)* Stage, query: &'ast $($mut)* Query) $($fn_return)* {
+            visitor.visit_query(query)$($try)*;
+            $($continue)*
         }
 
-        pub fn visit_flush<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, name: &'ast $($mut)* ObjectName) {
-            visitor.visit_object_name(name);
+        pub fn visit_flush<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, name: &'ast $($mut)* ObjectName) $($fn_return)* {
+            visitor.visit_object_name(name)$($try)*;
+            $($continue)*
         }
 
-        pub fn visit_flush_all<'ast, V: $name<'ast> + ?Sized>(_visitor: &mut V) {}
-    }
+        pub fn visit_flush_all<'ast, V: $name<'ast> + ?Sized>(_visitor: &mut V) $($fn_return)* { $($continue)* }
+
+        pub fn visit_extension<'ast, V: $name<'ast> + ?Sized>(
+            visitor: &mut V,
+            extension: &'ast $($mut)* dyn Extension,
+        ) $($fn_return)* {
+            for node in extension.$children() {
+                visit_node(visitor, node)$($try)*;
+            }
+            $($continue)*
+        }
+
+        pub fn visit_node<'ast, V: $name<'ast> + ?Sized>(visitor: &mut V, node: Node<'ast>) $($fn_return)* {
+            match node {
+                Node::Statement(statement) => visitor.visit_statement(statement),
+                Node::Query(query) => visitor.visit_query(query),
+                Node::Expr(expr) => visitor.visit_expr(expr),
+                Node::DataType(data_type) => visitor.visit_type(data_type),
+                Node::ObjectName(object_name) => visitor.visit_object_name(object_name),
+                Node::Ident(ident) => visitor.visit_ident(ident),
+            }
+        }
+    };
+    // A visitor that always walks through the whole AST.
+    (
+        @visit $(#[$attr:meta])* $name:ident, $children:ident,
+        mut: [$($mut:tt)*],
+        hooks: [$($hooks:tt)*]
+    ) => {
+        make_node!($($mut)*);
+
+        make_visitor!(
+            @impl $(#[$attr])* $name, $children,
+            mut: [$($mut)*],
+            return: [],
+            fn_return: [],
+            continue: [],
+            try: [],
+            items: [],
+            hooks: [$($hooks)*]
+        );
+    };
+
+    // A visitor whose functions return `ControlFlow<Self::Break>`, and stop
+    // walking through the AST as soon as a child node returns
+    // `ControlFlow::Break`. The enclosing module must import `ControlFlow` and
+    // a `Node` type.
+    (
+        @try $(#[$attr:meta])* $name:ident, $children:ident,
+        mut: [$($mut:tt)*],
+        hooks: [$($hooks:tt)*]
+    ) => {
+        make_visitor!(
+            @impl $(#[$attr])* $name, $children,
+            mut: [$($mut)*],
+            return: [-> ControlFlow<Self::Break>],
+            fn_return: [-> ControlFlow<V::Break>],
+            continue: [ControlFlow::Continue(())],
+            try: [?],
+            items: [
+                /// The value with which the traversal stops early
+                type Break;
+            ],
+            hooks: [$($hooks)*]
+        );
+    };
+
+    // Only the visitors of shared references have the `enter` and `leave`
+    // hooks, as the ancestors of a node cannot be borrowed while the node is
+    // borrowed mutably. Their enclosing module must import `Ancestor` and
+    // `Clause`.
+    (@$kind:ident $(#[$attr:meta])* $name:ident, $children:ident: &) => {
+        make_visitor!(
+            @$kind $(#[$attr])* $name, $children,
+            mut: [],
+            hooks: [enter, leave]
+        );
+    };
+
+    (@$kind:ident $(#[$attr:meta])* $name:ident, $children:ident: &mut) => {
+        make_visitor!(
+            @$kind $(#[$attr])* $name, $children,
+            mut: [mut],
+            hooks: []
+        );
+    };
+}
+
+macro_rules! make_fold {
+    ($name:ident) => {
+        use crate::ast::*;
+
+        /// A trait that represents a folder, which rewrites a SQL AST by
+        /// consuming it and building a new one.
+        ///
+        /// Each function takes ownership of a node of the SQL AST and returns
+        /// the node that replaces it, which may be a different variant of the
+        /// same type. The default implementations fold all of the child nodes
+        /// and rebuild the node from them, so implementors only need to
+        /// override the functions for the nodes that they rewrite.
+        pub trait $name {
+            fn fold_statement(&mut self, statement: Statement) -> Statement {
+                fold_statement(self, statement)
+            }
+
+            fn fold_query(&mut self, query: Query) -> Query {
+                fold_query(self, query)
+            }
+
+            fn fold_cte(&mut self, cte: Cte) -> Cte {
+                fold_cte(self, cte)
+            }
+
+            fn fold_set_expr(&mut self, set_expr: SetExpr) -> SetExpr {
+                fold_set_expr(self, set_expr)
+            }
+
+            fn fold_select(&mut self, select: Select) -> Select {
+                fold_select(self, select)
+            }
+
+            fn fold_select_item(&mut self, select_item: SelectItem) -> SelectItem {
+                fold_select_item(self, select_item)
+            }
+
+            fn fold_table_with_joins(
+                &mut self,
+                table_with_joins: TableWithJoins,
+            ) -> TableWithJoins {
+                fold_table_with_joins(self, table_with_joins)
+            }
+
+            fn fold_table_factor(&mut self, table_factor: TableFactor) -> TableFactor {
+                fold_table_factor(self, table_factor)
+            }
+
+            fn fold_table_alias(&mut self, alias: TableAlias) -> TableAlias {
+                fold_table_alias(self, alias)
+            }
+
+            fn fold_join(&mut self, join: Join) -> Join {
+                fold_join(self, join)
+            }
+
+            fn fold_join_operator(&mut self, op: JoinOperator) -> JoinOperator {
+                fold_join_operator(self, op)
+            }
+
+            fn fold_join_constraint(&mut self, constraint: JoinConstraint) -> JoinConstraint {
+                fold_join_constraint(self, constraint)
+            }
+
+            fn fold_order_by(&mut self, order_by: OrderByExpr) -> OrderByExpr {
+                fold_order_by(self, order_by)
+            }
+
+            fn fold_fetch(&mut self, fetch: Fetch) -> Fetch {
+                fold_fetch(self, fetch)
+            }
+
+            fn fold_values(&mut self, values: Values) -> Values {
+                fold_values(self, values)
+            }
+
+            fn fold_expr(&mut self, expr: Expr) -> Expr {
+                fold_expr(self, expr)
+            }
+
+            fn fold_function(&mut self, func: Function) -> Function {
+                fold_function(self, func)
+            }
+
+            fn fold_window_spec(&mut self, window_spec: WindowSpec) -> WindowSpec {
+                fold_window_spec(self, window_spec)
+            }
+
+            fn fold_type(&mut self, data_type: DataType) -> DataType {
+                fold_type(self, data_type)
+            }
+
+            fn fold_value(&mut self, val: Value) -> Value {
+                fold_value(self, val)
+            }
+
+            fn fold_object_name(&mut self, object_name: ObjectName) -> ObjectName {
+                fold_object_name(self, object_name)
+            }
+
+            fn fold_ident(&mut self, ident: Ident) -> Ident {
+                ident
+            }
+
+            fn fold_assignment(&mut self, assignment: Assignment) -> Assignment {
+                fold_assignment(self, assignment)
+            }
+
+            fn fold_column_def(&mut self, column_def: ColumnDef) -> ColumnDef {
+                fold_column_def(self, column_def)
+            }
+
+            fn fold_column_option(&mut self, column_option: ColumnOption) -> ColumnOption {
+                fold_column_option(self, column_option)
+            }
+
+            fn fold_table_constraint(
+                &mut self,
+                table_constraint: TableConstraint,
+            ) -> TableConstraint {
+                fold_table_constraint(self, table_constraint)
+            }
+
+            fn fold_alter_table_operation(
+                &mut self,
+                op: AlterTableOperation,
+            ) -> AlterTableOperation {
+                fold_alter_table_operation(self, op)
+            }
+
+            fn fold_alter_column_operation(
+                &mut self,
+                op: AlterColumnOperation,
+            ) -> AlterColumnOperation {
+                fold_alter_column_operation(self, op)
+            }
+
+            fn fold_option(&mut self, option: SqlOption) -> SqlOption {
+                fold_option(self, option)
+            }
+
+            fn fold_set_variable_value(&mut self, value: SetVariableValue) -> SetVariableValue {
+                fold_set_variable_value(self, value)
+            }
+
+            fn fold_show_statement_filter(
+                &mut self,
+                filter: ShowStatementFilter,
+            ) -> ShowStatementFilter {
+                fold_show_statement_filter(self, filter)
+            }
+
+            fn fold_extension(&mut self, extension: Box<dyn Extension>) -> Box<dyn Extension> {
+                fold_extension(self, extension)
+            }
+        }
+
+        fn fold_vec<T, V: ?Sized>(
+            folder: &mut V,
+            nodes: Vec<T>,
+            fold: fn(&mut V, T) -> T,
+        ) -> Vec<T> {
+            nodes.into_iter().map(|node| fold(folder, node)).collect()
+        }
+
+        fn fold_box<T, V: ?Sized>(
+            folder: &mut V,
+            mut node: Box<T>,
+            fold: fn(&mut V, T) -> T,
+        ) -> Box<T> {
+            // Reuse the allocation
+            *node = fold(folder, *node);
+            node
+        }
+
+        pub fn fold_statement<V: $name + ?Sized>(
+            folder: &mut V,
+            statement: Statement,
+        ) -> Statement {
+            match statement {
+                Statement::Query(query) => Statement::Query(fold_box(folder, query, V::fold_query)),
+                Statement::Insert {
+                    table_name,
+                    columns,
+                    source,
+                } => Statement::Insert {
+                    table_name: folder.fold_object_name(table_name),
+                    columns: fold_vec(folder, columns, V::fold_ident),
+                    source: fold_box(folder, source, V::fold_query),
+                },
+                Statement::Copy {
+                    table_name,
+                    columns,
+                    values,
+                } => Statement::Copy {
+                    table_name: folder.fold_object_name(table_name),
+                    columns: fold_vec(folder, columns, V::fold_ident),
+                    values,
+                },
+                Statement::Update {
+                    table_name,
+                    assignments,
+                    selection,
+                } => Statement::Update {
+                    table_name: folder.fold_object_name(table_name),
+                    assignments: fold_vec(folder, assignments, V::fold_assignment),
+                    selection: selection.map(|expr| folder.fold_expr(expr)),
+                },
+                Statement::Delete {
+                    table_name,
+                    selection,
+                } => Statement::Delete {
+                    table_name: folder.fold_object_name(table_name),
+                    selection: selection.map(|expr| folder.fold_expr(expr)),
+                },
+                Statement::CreateSource {
+                    name,
+                    url,
+                    schema,
+                    with_options,
+                } => Statement::CreateSource {
+                    name: folder.fold_object_name(name),
+                    url,
+                    schema,
+                    with_options: fold_vec(folder, with_options, V::fold_option),
+                },
+                Statement::CreateSources {
+                    like,
+                    url,
+                    schema_registry,
+                    with_options,
+                } => Statement::CreateSources {
+                    like,
+                    url,
+                    schema_registry,
+                    with_options: fold_vec(folder, with_options, V::fold_option),
+                },
+                Statement::CreateSink {
+                    name,
+                    from,
+                    url,
+                    with_options,
+                } => Statement::CreateSink {
+                    name: folder.fold_object_name(name),
+                    from: folder.fold_object_name(from),
+                    url,
+                    with_options: fold_vec(folder, with_options, V::fold_option),
+                },
+                Statement::FlushSource { name } => Statement::FlushSource {
+                    name: folder.fold_object_name(name),
+                },
+                Statement::FlushAllSources => Statement::FlushAllSources,
+                Statement::CreateView {
+                    name,
+                    columns,
+                    query,
+                    materialized,
+                    with_options,
+                } => Statement::CreateView {
+                    name: folder.fold_object_name(name),
+                    columns: fold_vec(folder, columns, V::fold_ident),
+                    query: fold_box(folder, query, V::fold_query),
+                    materialized,
+                    with_options: fold_vec(folder, with_options, V::fold_option),
+                },
+                Statement::CreateTable {
+                    name,
+                    columns,
+                    constraints,
+                    with_options,
+                    external,
+                    file_format,
+                    location,
+                } => Statement::CreateTable {
+                    name: folder.fold_object_name(name),
+                    columns: fold_vec(folder, columns, V::fold_column_def),
+                    constraints: fold_vec(folder, constraints, V::fold_table_constraint),
+                    with_options: fold_vec(folder, with_options, V::fold_option),
+                    external,
+                    file_format,
+                    location,
+                },
+                Statement::CreateIndex {
+                    name,
+                    on_name,
+                    key_parts,
+                } => Statement::CreateIndex {
+                    name: folder.fold_ident(name),
+                    on_name: folder.fold_object_name(on_name),
+                    key_parts: fold_vec(folder, key_parts, V::fold_expr),
+                },
+                Statement::AlterTable { name, operation } => Statement::AlterTable {
+                    name: folder.fold_object_name(name),
+                    operation: folder.fold_alter_table_operation(operation),
+                },
+                Statement::Drop {
+                    object_type,
+                    if_exists,
+                    names,
+                    cascade,
+                } => Statement::Drop {
+                    object_type,
+                    if_exists,
+                    names: fold_vec(folder, names, V::fold_object_name),
+                    cascade,
+                },
+                Statement::SetVariable {
+                    local,
+                    variable,
+                    value,
+                } => Statement::SetVariable {
+                    local,
+                    variable: folder.fold_ident(variable),
+                    value: folder.fold_set_variable_value(value),
+                },
+                Statement::ShowVariable { variable } => Statement::ShowVariable {
+                    variable: folder.fold_ident(variable),
+                },
+                Statement::ShowObjects {
+                    object_type,
+                    filter,
+                } => Statement::ShowObjects {
+                    object_type,
+                    filter: filter.map(|filter| folder.fold_show_statement_filter(filter)),
+                },
+                Statement::ShowIndexes { table_name, filter } => Statement::ShowIndexes {
+                    table_name: folder.fold_object_name(table_name),
+                    filter: filter.map(|filter| folder.fold_show_statement_filter(filter)),
+                },
+                Statement::ShowColumns {
+                    extended,
+                    full,
+                    table_name,
+                    filter,
+                } => Statement::ShowColumns {
+                    extended,
+                    full,
+                    table_name: folder.fold_object_name(table_name),
+                    filter: filter.map(|filter| folder.fold_show_statement_filter(filter)),
+                },
+                Statement::ShowCreateView { view_name } => Statement::ShowCreateView {
+                    view_name: folder.fold_object_name(view_name),
+                },
+                Statement::ShowCreateSource { source_name } => Statement::ShowCreateSource {
+                    source_name: folder.fold_object_name(source_name),
+                },
+                Statement::StartTransaction { modes } => Statement::StartTransaction { modes },
+                Statement::SetTransaction { modes } => Statement::SetTransaction { modes },
+                Statement::Commit { chain } => Statement::Commit { chain },
+                Statement::Rollback { chain } => Statement::Rollback { chain },
+                Statement::Peek { name, immediate } => Statement::Peek {
+                    name: folder.fold_object_name(name),
+                    immediate,
+                },
+                Statement::Tail { name } => Statement::Tail {
+                    name: folder.fold_object_name(name),
+                },
+                Statement::Explain { stage, query } => Statement::Explain {
+                    stage,
+                    query: fold_box(folder, query, V::fold_query),
+                },
+                Statement::Extension(extension) => {
+                    Statement::Extension(folder.fold_extension(extension))
+                }
+            }
+        }
+
+        pub fn fold_query<V: $name + ?Sized>(folder: &mut V, query: Query) -> Query {
+            Query {
+                ctes: fold_vec(folder, query.ctes, V::fold_cte),
+                body: folder.fold_set_expr(query.body),
+                order_by: fold_vec(folder, query.order_by, V::fold_order_by),
+                limit: query.limit.map(|expr| folder.fold_expr(expr)),
+                offset: query.offset.map(|expr| folder.fold_expr(expr)),
+                fetch: query.fetch.map(|fetch| folder.fold_fetch(fetch)),
+            }
+        }
+
+        pub fn fold_cte<V: $name + ?Sized>(folder: &mut V, cte: Cte) -> Cte {
+            Cte {
+                alias: folder.fold_table_alias(cte.alias),
+                query: folder.fold_query(cte.query),
+            }
+        }
+
+        pub fn fold_set_expr<V: $name + ?Sized>(folder: &mut V, set_expr: SetExpr) -> SetExpr {
+            match set_expr {
+                SetExpr::Select(select) => {
+                    SetExpr::Select(fold_box(folder, select, V::fold_select))
+                }
+                SetExpr::Query(query) => SetExpr::Query(fold_box(folder, query, V::fold_query)),
+                SetExpr::SetOperation {
+                    op,
+                    all,
+                    left,
+                    right,
+                } => SetExpr::SetOperation {
+                    op,
+                    all,
+                    left: fold_box(folder, left, V::fold_set_expr),
+                    right: fold_box(folder, right, V::fold_set_expr),
+                },
+                SetExpr::Values(values) => SetExpr::Values(folder.fold_values(values)),
+            }
+        }
+
+        pub fn fold_select<V: $name + ?Sized>(folder: &mut V, select: Select) -> Select {
+            Select {
+                distinct: select.distinct,
+                projection: fold_vec(folder, select.projection, V::fold_select_item),
+                from: fold_vec(folder, select.from, V::fold_table_with_joins),
+                selection: select.selection.map(|expr| folder.fold_expr(expr)),
+                group_by: fold_vec(folder, select.group_by, V::fold_expr),
+                having: select.having.map(|expr| folder.fold_expr(expr)),
+            }
+        }
+
+        pub fn fold_select_item<V: $name + ?Sized>(
+            folder: &mut V,
+            select_item: SelectItem,
+        ) -> SelectItem {
+            match select_item {
+                SelectItem::UnnamedExpr(expr) => SelectItem::UnnamedExpr(folder.fold_expr(expr)),
+                SelectItem::ExprWithAlias { expr, alias } => SelectItem::ExprWithAlias {
+                    expr: folder.fold_expr(expr),
+                    alias: folder.fold_ident(alias),
+                },
+                SelectItem::QualifiedWildcard(object_name) => {
+                    SelectItem::QualifiedWildcard(folder.fold_object_name(object_name))
+                }
+                SelectItem::Wildcard => SelectItem::Wildcard,
+            }
+        }
+
+        pub fn fold_table_with_joins<V: $name + ?Sized>(
+            folder: &mut V,
+            table_with_joins: TableWithJoins,
+        ) -> TableWithJoins {
+            TableWithJoins {
+                relation: folder.fold_table_factor(table_with_joins.relation),
+                joins: fold_vec(folder, table_with_joins.joins, V::fold_join),
+            }
+        }
+
+        pub fn fold_table_factor<V: $name + ?Sized>(
+            folder: &mut V,
+            table_factor: TableFactor,
+        ) -> TableFactor {
+            match table_factor {
+                TableFactor::Table {
+                    name,
+                    alias,
+                    args,
+                    with_hints,
+                } => TableFactor::Table {
+                    name: folder.fold_object_name(name),
+                    alias: alias.map(|alias| folder.fold_table_alias(alias)),
+                    args: fold_vec(folder, args, V::fold_expr),
+                    with_hints: fold_vec(folder, with_hints, V::fold_expr),
+                },
+                TableFactor::Derived {
+                    lateral,
+                    subquery,
+                    alias,
+                } => TableFactor::Derived {
+                    lateral,
+                    subquery: fold_box(folder, subquery, V::fold_query),
+                    alias: alias.map(|alias| folder.fold_table_alias(alias)),
+                },
+                TableFactor::NestedJoin(table_with_joins) => TableFactor::NestedJoin(fold_box(
+                    folder,
+                    table_with_joins,
+                    V::fold_table_with_joins,
+                )),
+            }
+        }
+
+        pub fn fold_table_alias<V: $name + ?Sized>(
+            folder: &mut V,
+            alias: TableAlias,
+        ) -> TableAlias {
+            TableAlias {
+                name: folder.fold_ident(alias.name),
+                columns: fold_vec(folder, alias.columns, V::fold_ident),
+            }
+        }
+
+        pub fn fold_join<V: $name + ?Sized>(folder: &mut V, join: Join) -> Join {
+            Join {
+                relation: folder.fold_table_factor(join.relation),
+                join_operator: folder.fold_join_operator(join.join_operator),
+            }
+        }
+
+        pub fn fold_join_operator<V: $name + ?Sized>(
+            folder: &mut V,
+            op: JoinOperator,
+        ) -> JoinOperator {
+            match op {
+                JoinOperator::Inner(constraint) => {
+                    JoinOperator::Inner(folder.fold_join_constraint(constraint))
+                }
+                JoinOperator::LeftOuter(constraint) => {
+                    JoinOperator::LeftOuter(folder.fold_join_constraint(constraint))
+                }
+                JoinOperator::RightOuter(constraint) => {
+                    JoinOperator::RightOuter(folder.fold_join_constraint(constraint))
+                }
+                JoinOperator::FullOuter(constraint) => {
+                    JoinOperator::FullOuter(folder.fold_join_constraint(constraint))
+                }
+                JoinOperator::CrossJoin => JoinOperator::CrossJoin,
+                JoinOperator::CrossApply => JoinOperator::CrossApply,
+                JoinOperator::OuterApply => JoinOperator::OuterApply,
+            }
+        }
+
+        pub fn fold_join_constraint<V: $name + ?Sized>(
+            folder: &mut V,
+            constraint: JoinConstraint,
+        ) -> JoinConstraint {
+            match constraint {
+                JoinConstraint::On(expr) => JoinConstraint::On(folder.fold_expr(expr)),
+                JoinConstraint::Using(idents) => {
+                    JoinConstraint::Using(fold_vec(folder, idents, V::fold_ident))
+                }
+                JoinConstraint::Natural => JoinConstraint::Natural,
+            }
+        }
+
+        pub fn fold_order_by<V: $name + ?Sized>(
+            folder: &mut V,
+            order_by: OrderByExpr,
+        ) -> OrderByExpr {
+            OrderByExpr {
+                expr: folder.fold_expr(order_by.expr),
+                asc: order_by.asc,
+            }
+        }
+
+        pub fn fold_fetch<V: $name + ?Sized>(folder: &mut V, fetch: Fetch) -> Fetch {
+            Fetch {
+                with_ties: fetch.with_ties,
+                percent: fetch.percent,
+                quantity: fetch.quantity.map(|expr| folder.fold_expr(expr)),
+            }
+        }
+
+        pub fn fold_values<V: $name + ?Sized>(folder: &mut V, values: Values) -> Values {
+            Values(
+                values
+                    .0
+                    .into_iter()
+                    .map(|row| fold_vec(folder, row, V::fold_expr))
+                    .collect(),
+            )
+        }
+
+        pub fn fold_expr<V: $name + ?Sized>(folder: &mut V, expr: Expr) -> Expr {
+            match expr {
+                Expr::Identifier(ident) => Expr::Identifier(folder.fold_ident(ident)),
+                Expr::Wildcard => Expr::Wildcard,
+                Expr::QualifiedWildcard(idents) => {
+                    Expr::QualifiedWildcard(fold_vec(folder, idents, V::fold_ident))
+                }
+                Expr::CompoundIdentifier(idents) => {
+                    Expr::CompoundIdentifier(fold_vec(folder, idents, V::fold_ident))
+                }
+                Expr::Parameter(n) => Expr::Parameter(n),
+                Expr::IsNull(expr) => Expr::IsNull(fold_box(folder, expr, V::fold_expr)),
+                Expr::IsNotNull(expr) => Expr::IsNotNull(fold_box(folder, expr, V::fold_expr)),
+                Expr::InList {
+                    expr,
+                    list,
+                    negated,
+                } => Expr::InList {
+                    expr: fold_box(folder, expr, V::fold_expr),
+                    list: fold_vec(folder, list, V::fold_expr),
+                    negated,
+                },
+                Expr::InSubquery {
+                    expr,
+                    subquery,
+                    negated,
+                } => Expr::InSubquery {
+                    expr: fold_box(folder, expr, V::fold_expr),
+                    subquery: fold_box(folder, subquery, V::fold_query),
+                    negated,
+                },
+                Expr::Between {
+                    expr,
+                    negated,
+                    low,
+                    high,
+                } => Expr::Between {
+                    expr: fold_box(folder, expr, V::fold_expr),
+                    negated,
+                    low: fold_box(folder, low, V::fold_expr),
+                    high: fold_box(folder, high, V::fold_expr),
+                },
+                Expr::BinaryOp { left, op, right } => Expr::BinaryOp {
+                    left: fold_box(folder, left, V::fold_expr),
+                    op,
+                    right: fold_box(folder, right, V::fold_expr),
+                },
+                Expr::UnaryOp { op, expr } => Expr::UnaryOp {
+                    op,
+                    expr: fold_box(folder, expr, V::fold_expr),
+                },
+                Expr::Cast { expr, data_type } => Expr::Cast {
+                    expr: fold_box(folder, expr, V::fold_expr),
+                    data_type: folder.fold_type(data_type),
+                },
+                Expr::Extract { field, expr } => Expr::Extract {
+                    field,
+                    expr: fold_box(folder, expr, V::fold_expr),
+                },
+                Expr::Collate { expr, collation } => Expr::Collate {
+                    expr: fold_box(folder, expr, V::fold_expr),
+                    collation: folder.fold_object_name(collation),
+                },
+                Expr::Nested(expr) => Expr::Nested(fold_box(folder, expr, V::fold_expr)),
+                Expr::Value(val) => Expr::Value(folder.fold_value(val)),
+                Expr::Function(func) => Expr::Function(folder.fold_function(func)),
+                Expr::Case {
+                    operand,
+                    conditions,
+                    results,
+                    else_result,
+                } => Expr::Case {
+                    operand: operand.map(|expr| fold_box(folder, expr, V::fold_expr)),
+                    conditions: fold_vec(folder, conditions, V::fold_expr),
+                    results: fold_vec(folder, results, V::fold_expr),
+                    else_result: else_result.map(|expr| fold_box(folder, expr, V::fold_expr)),
+                },
+                Expr::Exists(subquery) => Expr::Exists(fold_box(folder, subquery, V::fold_query)),
+                Expr::Subquery(subquery) => {
+                    Expr::Subquery(fold_box(folder, subquery, V::fold_query))
+                }
+                Expr::Any {
+                    left,
+                    op,
+                    right,
+                    some,
+                } => Expr::Any {
+                    left: fold_box(folder, left, V::fold_expr),
+                    op,
+                    right: fold_box(folder, right, V::fold_query),
+                    some,
+                },
+                Expr::All { left, op, right } => Expr::All {
+                    left: fold_box(folder, left, V::fold_expr),
+                    op,
+                    right: fold_box(folder, right, V::fold_query),
+                },
+                Expr::Extension(extension) => Expr::Extension(folder.fold_extension(extension)),
+            }
+        }
+
+        pub fn fold_function<V: $name + ?Sized>(folder: &mut V, func: Function) -> Function {
+            Function {
+                name: folder.fold_object_name(func.name),
+                args: fold_vec(folder, func.args, V::fold_expr),
+                over: func.over.map(|over| folder.fold_window_spec(over)),
+                distinct: func.distinct,
+            }
+        }
+
+        pub fn fold_window_spec<V: $name + ?Sized>(
+            folder: &mut V,
+            window_spec: WindowSpec,
+        ) -> WindowSpec {
+            WindowSpec {
+                partition_by: fold_vec(folder, window_spec.partition_by, V::fold_expr),
+                order_by: fold_vec(folder, window_spec.order_by, V::fold_order_by),
+                window_frame: window_spec.window_frame,
+            }
+        }
+
+        pub fn fold_type<V: $name + ?Sized>(folder: &mut V, data_type: DataType) -> DataType {
+            match data_type {
+                DataType::Custom(name) => DataType::Custom(folder.fold_object_name(name)),
+                DataType::Array(data_type) => {
+                    DataType::Array(fold_box(folder, data_type, V::fold_type))
+                }
+                data_type => data_type,
+            }
+        }
+
+        pub fn fold_value<V: $name + ?Sized>(folder: &mut V, val: Value) -> Value {
+            match val {
+                Value::Array(values) => Value::Array(fold_vec(folder, values, V::fold_value)),
+                val => val,
+            }
+        }
+
+        pub fn fold_object_name<V: $name + ?Sized>(
+            folder: &mut V,
+            object_name: ObjectName,
+        ) -> ObjectName {
+            ObjectName(fold_vec(folder, object_name.0, V::fold_ident))
+        }
+
+        pub fn fold_assignment<V: $name + ?Sized>(
+            folder: &mut V,
+            assignment: Assignment,
+        ) -> Assignment {
+            Assignment {
+                id: folder.fold_ident(assignment.id),
+                value: folder.fold_expr(assignment.value),
+            }
+        }
+
+        pub fn fold_column_def<V: $name + ?Sized>(
+            folder: &mut V,
+            column_def: ColumnDef,
+        ) -> ColumnDef {
+            ColumnDef {
+                name: folder.fold_ident(column_def.name),
+                data_type: folder.fold_type(column_def.data_type),
+                collation: column_def
+                    .collation
+                    .map(|name| folder.fold_object_name(name)),
+                options: column_def
+                    .options
+                    .into_iter()
+                    .map(|option| ColumnOptionDef {
+                        name: option.name.map(|name| folder.fold_ident(name)),
+                        option: folder.fold_column_option(option.option),
+                    })
+                    .collect(),
+            }
+        }
+
+        pub fn fold_column_option<V: $name + ?Sized>(
+            folder: &mut V,
+            column_option: ColumnOption,
+        ) -> ColumnOption {
+            match column_option {
+                ColumnOption::Null => ColumnOption::Null,
+                ColumnOption::NotNull => ColumnOption::NotNull,
+                ColumnOption::Default(expr) => ColumnOption::Default(folder.fold_expr(expr)),
+                ColumnOption::Unique { is_primary } => ColumnOption::Unique { is_primary },
+                ColumnOption::ForeignKey {
+                    foreign_table,
+                    referred_columns,
+                } => ColumnOption::ForeignKey {
+                    foreign_table: folder.fold_object_name(foreign_table),
+                    referred_columns: fold_vec(folder, referred_columns, V::fold_ident),
+                },
+                ColumnOption::Check(expr) => ColumnOption::Check(folder.fold_expr(expr)),
+            }
+        }
+
+        pub fn fold_table_constraint<V: $name + ?Sized>(
+            folder: &mut V,
+            table_constraint: TableConstraint,
+        ) -> TableConstraint {
+            match table_constraint {
+                TableConstraint::Unique {
+                    name,
+                    columns,
+                    is_primary,
+                } => TableConstraint::Unique {
+                    name: name.map(|name| folder.fold_ident(name)),
+                    columns: fold_vec(folder, columns, V::fold_ident),
+                    is_primary,
+                },
+                TableConstraint::ForeignKey {
+                    name,
+                    columns,
+                    foreign_table,
+                    referred_columns,
+                } => TableConstraint::ForeignKey {
+                    name: name.map(|name| folder.fold_ident(name)),
+                    columns: fold_vec(folder, columns, V::fold_ident),
+                    foreign_table: folder.fold_object_name(foreign_table),
+                    referred_columns: fold_vec(folder, referred_columns, V::fold_ident),
+                },
+                TableConstraint::Check { name, expr } => TableConstraint::Check {
+                    name: name.map(|name| folder.fold_ident(name)),
+                    expr: fold_box(folder, expr, V::fold_expr),
+                },
+            }
+        }
+
+        pub fn fold_alter_table_operation<V: $name + ?Sized>(
+            folder: &mut V,
+            op: AlterTableOperation,
+        ) -> AlterTableOperation {
+            match op {
+                AlterTableOperation::AddConstraint(table_constraint) => {
+                    AlterTableOperation::AddConstraint(
+                        folder.fold_table_constraint(table_constraint),
+                    )
+                }
+                AlterTableOperation::DropConstraint { name } => {
+                    AlterTableOperation::DropConstraint {
+                        name: folder.fold_ident(name),
+                    }
+                }
+                AlterTableOperation::AddColumn(column_def) => {
+                    AlterTableOperation::AddColumn(folder.fold_column_def(column_def))
+                }
+                AlterTableOperation::DropColumn { name } => AlterTableOperation::DropColumn {
+                    name: folder.fold_ident(name),
+                },
+                AlterTableOperation::AlterColumn { name, operation } => {
+                    AlterTableOperation::AlterColumn {
+                        name: folder.fold_ident(name),
+                        operation: folder.fold_alter_column_operation(operation),
+                    }
+                }
+            }
+        }
+
+        pub fn fold_alter_column_operation<V: $name + ?Sized>(
+            folder: &mut V,
+            op: AlterColumnOperation,
+        ) -> AlterColumnOperation {
+            match op {
+                AlterColumnOperation::SetDataType(data_type) => {
+                    AlterColumnOperation::SetDataType(folder.fold_type(data_type))
+                }
+                AlterColumnOperation::SetDefault(expr) => {
+                    AlterColumnOperation::SetDefault(folder.fold_expr(expr))
+                }
+                AlterColumnOperation::DropDefault
+                | AlterColumnOperation::SetNotNull
+                | AlterColumnOperation::DropNotNull => op,
+            }
+        }
+
+        pub fn fold_option<V: $name + ?Sized>(folder: &mut V, option: SqlOption) -> SqlOption {
+            SqlOption {
+                name: folder.fold_ident(option.name),
+                value: folder.fold_value(option.value),
+            }
+        }
+
+        pub fn fold_set_variable_value<V: $name + ?Sized>(
+            folder: &mut V,
+            value: SetVariableValue,
+        ) -> SetVariableValue {
+            match value {
+                SetVariableValue::Ident(ident) => SetVariableValue::Ident(folder.fold_ident(ident)),
+                SetVariableValue::Literal(value) => {
+                    SetVariableValue::Literal(folder.fold_value(value))
+                }
+            }
+        }
+
+        pub fn fold_show_statement_filter<V: $name + ?Sized>(
+            folder: &mut V,
+            filter: ShowStatementFilter,
+        ) -> ShowStatementFilter {
+            match filter {
+                ShowStatementFilter::Like(pattern) => ShowStatementFilter::Like(pattern),
+                ShowStatementFilter::Where(expr) => {
+                    ShowStatementFilter::Where(folder.fold_expr(expr))
+                }
+            }
+        }
+
+        /// Fold the nodes returned by `Extension::children_mut`, which are
+        /// replaced in place, as an extension cannot be rebuilt from its
+        /// children.
+        pub fn fold_extension<V: $name + ?Sized>(
+            folder: &mut V,
+            mut extension: Box<dyn Extension>,
+        ) -> Box<dyn Extension> {
+            use std::mem;
+
+            for node in extension.children_mut() {
+                match node {
+                    visit_mut::Node::Statement(statement) => {
+                        let taken = mem::replace(statement, Statement::FlushAllSources);
+                        *statement = folder.fold_statement(taken);
+                    }
+                    visit_mut::Node::Query(query) => {
+                        let empty = Query {
+                            ctes: vec![],
+                            body: SetExpr::Values(Values(vec![])),
+                            order_by: vec![],
+                            limit: None,
+                            offset: None,
+                            fetch: None,
+                        };
+                        let taken = mem::replace(query, empty);
+                        *query = folder.fold_query(taken);
+                    }
+                    visit_mut::Node::Expr(expr) => {
+                        let taken = mem::replace(expr, Expr::Wildcard);
+                        *expr = folder.fold_expr(taken);
+                    }
+                    visit_mut::Node::DataType(data_type) => {
+                        let taken = mem::replace(data_type, DataType::Boolean);
+                        *data_type = folder.fold_type(taken);
+                    }
+                    visit_mut::Node::ObjectName(object_name) => {
+                        let taken = mem::replace(object_name, ObjectName(vec![]));
+                        *object_name = folder.fold_object_name(taken);
+                    }
+                    visit_mut::Node::Ident(ident) => {
+                        let taken = mem::replace(ident, Ident::new(""));
+                        *ident = folder.fold_ident(taken);
+                    }
+                }
+            }
+            extension
+        }
+    };
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::ast::visit::Visit;
+    use crate::ast::fold::{fold_expr, Fold};
+    use crate::ast::try_visit::{ControlFlow, TryVisit};
+    use crate::ast::try_visit_mut::TryVisitMut;
+    use crate::ast::visit::{visit_value, Ancestor, Clause, Visit};
     use crate::ast::visit_mut::VisitMut;
-    use crate::ast::Ident;
+    use crate::ast::{BinaryOperator, DateTimeField, Expr, Ident, Query, Statement, Value};
+    use crate::dialect::keywords::Keyword;
     use crate::dialect::GenericDialect;
     use crate::parser::Parser;
+    use crate::tokenizer::{Token, Tokenizer};
     use std::error::Error;
 
     #[test]
@@ -1799,4 +3095,369 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_try_visitor() -> Result<(), Box<dyn Error>> {
+        /// Finds the first subquery, stopping there
+        struct FindSubquery<'a> {
+            seen_idents: Vec<&'a str>,
+        }
+
+        impl<'a> TryVisit<'a> for FindSubquery<'a> {
+            type Break = &'a Query;
+
+            fn visit_ident(&mut self, ident: &'a Ident) -> ControlFlow<&'a Query> {
+                self.seen_idents.push(&ident.value);
+                ControlFlow::Continue(())
+            }
+
+            fn visit_subquery(&mut self, subquery: &'a Query) -> ControlFlow<&'a Query> {
+                ControlFlow::Break(subquery)
+            }
+        }
+
+        /// Uppercases identifiers up to the first invalid one
+        struct Uppercase;
+
+        impl<'a> TryVisitMut<'a> for Uppercase {
+            type Break = Result<(), String>;
+
+            fn visit_ident(&mut self, ident: &'a mut Ident) -> ControlFlow<Self::Break> {
+                if ident.value == "bad" {
+                    return ControlFlow::Break(Err(format!("invalid identifier: {}", ident)));
+                }
+                ident.value.make_ascii_uppercase();
+                ControlFlow::Continue(())
+            }
+        }
+
+        let sql = "SELECT a, b + (SELECT c FROM d) FROM e WHERE f IN (SELECT g)";
+        let stmts = Parser::parse_sql(&GenericDialect {}, sql.into())?;
+        let mut visitor = FindSubquery {
+            seen_idents: Vec::new(),
+        };
+        match visitor.visit_statement(&stmts[0]) {
+            ControlFlow::Break(subquery) => assert_eq!("SELECT c FROM d", subquery.to_string()),
+            ControlFlow::Continue(()) => panic!("no subquery found"),
+        }
+        assert_eq!(visitor.seen_idents, vec!["a", "b"]);
+
+        let sql = "SELECT a FROM bad, c";
+        let mut stmts = Parser::parse_sql(&GenericDialect {}, sql.into())?;
+        let result = Uppercase.visit_statement(&mut stmts[0]);
+        assert_eq!(
+            result,
+            ControlFlow::Break(Err("invalid identifier: bad".to_string()))
+        );
+        assert_eq!("SELECT A FROM bad, c", stmts[0].to_string());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fold() -> Result<(), Box<dyn Error>> {
+        /// Rewrites `x BETWEEN low AND high` as `x >= low AND x <= high`,
+        /// and uppercases identifiers
+        struct ExpandBetween;
+
+        impl Fold for ExpandBetween {
+            fn fold_expr(&mut self, expr: Expr) -> Expr {
+                match fold_expr(self, expr) {
+                    Expr::Between {
+                        expr,
+                        negated: false,
+                        low,
+                        high,
+                    } => Expr::BinaryOp {
+                        left: Box::new(Expr::BinaryOp {
+                            left: expr.clone(),
+                            op: BinaryOperator::GtEq,
+                            right: low,
+                        }),
+                        op: BinaryOperator::And,
+                        right: Box::new(Expr::BinaryOp {
+                            left: expr,
+                            op: BinaryOperator::LtEq,
+                            right: high,
+                        }),
+                    },
+                    expr => expr,
+                }
+            }
+
+            fn fold_ident(&mut self, ident: Ident) -> Ident {
+                Ident::new(ident.value.to_uppercase())
+            }
+        }
+
+        let sql =
+            "SELECT a FROM b WHERE c BETWEEN 1 AND (SELECT d FROM e WHERE f BETWEEN g AND 2) \
+                   AND h NOT BETWEEN 3 AND 4";
+        let stmts = Parser::parse_sql(&GenericDialect {}, sql.into())?;
+        let stmt = ExpandBetween.fold_statement(stmts.into_iter().next().unwrap());
+        assert_eq!(
+            "SELECT A FROM B WHERE C >= 1 AND C <= (SELECT D FROM E WHERE F >= G AND F <= 2) \
+             AND H NOT BETWEEN 3 AND 4",
+            stmt.to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_visitor_hooks() -> Result<(), Box<dyn Error>> {
+        /// Records the identifiers in `WHERE` clauses, but not in subqueries
+        /// in them, along with the name of the innermost CTE they are in
+        struct WhereIdents<'a> {
+            path: Vec<Ancestor<'a>>,
+            seen_idents: Vec<(Option<&'a str>, &'a str)>,
+        }
+
+        impl<'a> Visit<'a> for WhereIdents<'a> {
+            fn enter(&mut self, node: Ancestor<'a>) {
+                self.path.push(node);
+            }
+
+            fn leave(&mut self, _node: Ancestor<'a>) {
+                self.path.pop();
+            }
+
+            fn visit_ident(&mut self, ident: &'a Ident) {
+                // The innermost clause or `Select` is the one directly
+                // containing the identifier
+                let in_where = self.path.iter().rev().find_map(|node| match node {
+                    Ancestor::Clause(clause) => Some(*clause == Clause::Where),
+                    Ancestor::Select(_) => Some(false),
+                    _ => None,
+                });
+                let cte = self.path.iter().rev().find_map(|node| match node {
+                    Ancestor::Cte(cte) => Some(&cte.alias.name.value[..]),
+                    _ => None,
+                });
+                if in_where == Some(true) {
+                    self.seen_idents.push((cte, &ident.value));
+                }
+            }
+        }
+
+        let sql = "WITH x AS (SELECT a FROM b WHERE c = (SELECT d FROM e WHERE f)) \
+                   SELECT g FROM x WHERE h";
+        let stmts = Parser::parse_sql(&GenericDialect {}, sql.into())?;
+        let mut visitor = WhereIdents {
+            path: vec![],
+            seen_idents: vec![],
+        };
+        visitor.visit_statement(&stmts[0]);
+        assert_eq!(
+            visitor.seen_idents,
+            vec![(Some("x"), "c"), (Some("x"), "f"), (None, "h")]
+        );
+        assert!(visitor.path.is_empty());
+
+        Ok(())
+    }
+
+    /// Collects the identifiers visited in a node
+    #[derive(Default)]
+    struct IdentCollector<'a> {
+        seen_idents: Vec<&'a str>,
+    }
+
+    impl<'a> Visit<'a> for IdentCollector<'a> {
+        fn visit_ident(&mut self, ident: &'a Ident) {
+            self.seen_idents.push(&ident.value);
+        }
+    }
+
+    /// Check that `seen_idents` contains every identifier in `sql`
+    fn check_idents(sql: &str, mut seen_idents: Vec<&str>) {
+        let tokens = Tokenizer::new(&GenericDialect {}, sql).tokenize().unwrap();
+        let mut idents: Vec<_> = tokens
+            .iter()
+            .filter_map(|token| match token {
+                Token::Word(w) if w.keyword == Keyword::NoKeyword || w.quote_style.is_some() => {
+                    Some(&w.value[..])
+                }
+                _ => None,
+            })
+            .collect();
+        idents.sort_unstable();
+        seen_idents.sort_unstable();
+        assert_eq!(idents, seen_idents, "identifiers visited in {:?}", sql);
+    }
+
+    /// Generate a test that parses the SQL of each variant of `$ty` (or skips
+    /// it if it is `None`), and checks that `$ty` was parsed and that all of
+    /// its identifiers were visited. The `match` on the variants has no
+    /// catch-all arm, so that adding a variant breaks the test until it is
+    /// added here, along with its visitor support.
+    macro_rules! visit_coverage_test {
+        ($test:ident, $ty:ident, $parse:expr, $visit:ident, { $($variant:ident => $sql:expr,)* }) => {
+            #[test]
+            fn $test() {
+                fn variant(node: &$ty) -> &'static str {
+                    match node {
+                        $($ty::$variant { .. } => stringify!($variant),)*
+                    }
+                }
+
+                $(
+                    let sql: Option<&str> = $sql;
+                    if let Some(sql) = sql {
+                        let node: $ty = $parse(sql);
+                        assert_eq!(stringify!($variant), variant(&node), "{}", sql);
+                        let mut visitor = IdentCollector::default();
+                        visitor.$visit(&node);
+                        check_idents(sql, visitor.seen_idents);
+                    }
+                )*
+            }
+        };
+    }
+
+    visit_coverage_test!(
+        test_visit_statement_coverage,
+        Statement,
+        |sql: &str| Parser::parse_sql(&GenericDialect {}, sql.into()).unwrap().remove(0),
+        visit_statement,
+        {
+            Query => Some(
+                "WITH a1 (a2) AS (SELECT a3) SELECT a4 FROM a5 ORDER BY a6 LIMIT 1"
+            ),
+            Insert => Some("INSERT INTO a1 (a2) VALUES (a3)"),
+            Copy => Some("COPY a1 (a2) FROM stdin;\n1\t2\n"),
+            Update => Some("UPDATE a1 SET a2 = a3 WHERE a4"),
+            Delete => Some("DELETE FROM a1 WHERE a2"),
+            CreateSource => Some("CREATE SOURCE a1 FROM 'x' USING SCHEMA 'y' WITH (a2 = 'z')"),
+            CreateSources => Some(
+                "CREATE SOURCES LIKE 'x' FROM 'y' USING SCHEMA REGISTRY 'z' WITH (a1 = 1)"
+            ),
+            CreateSink => Some("CREATE SINK a1 FROM a2 INTO 'x' WITH (a3 = 'y')"),
+            FlushSource => Some("FLUSH SOURCE a1"),
+            FlushAllSources => Some("FLUSH ALL SOURCES"),
+            CreateView => Some("CREATE MATERIALIZED VIEW a1 (a2) WITH (a3 = 1) AS SELECT a4"),
+            CreateTable => Some(
+                "CREATE TABLE a1 (a2 a3[] COLLATE a4 CONSTRAINT a5 DEFAULT a6 REFERENCES a7 (a8), \
+                 CONSTRAINT a9 CHECK (a10)) WITH (a11 = 1)"
+            ),
+            CreateIndex => Some("CREATE INDEX a1 ON a2 (a3)"),
+            AlterTable => Some("ALTER TABLE a1 ADD CONSTRAINT a2 FOREIGN KEY (a3) REFERENCES a4 (a5)"),
+            Drop => Some("DROP TABLE a1, a2"),
+            SetVariable => Some("SET a1 = a2"),
+            ShowVariable => Some("SHOW a1"),
+            ShowObjects => Some("SHOW TABLES WHERE a1"),
+            ShowIndexes => Some("SHOW INDEXES FROM a1 WHERE a2"),
+            ShowColumns => Some("SHOW COLUMNS FROM a1 WHERE a2"),
+            ShowCreateView => Some("SHOW CREATE VIEW a1"),
+            ShowCreateSource => Some("SHOW CREATE SOURCE a1"),
+            StartTransaction => Some("START TRANSACTION READ ONLY"),
+            SetTransaction => Some("SET TRANSACTION ISOLATION LEVEL SERIALIZABLE"),
+            Commit => Some("COMMIT"),
+            Rollback => Some("ROLLBACK"),
+            Peek => Some("PEEK a1"),
+            Tail => Some("TAIL a1"),
+            Explain => Some("EXPLAIN PLAN FOR SELECT a1"),
+            // Extensions are covered by the tests of `Extension::children`
+            Extension => None,
+        }
+    );
+
+    #[test]
+    fn test_visit_alter_table_operations() {
+        for sql in &[
+            "ALTER TABLE a1 DROP CONSTRAINT a2",
+            "ALTER TABLE a1 ADD COLUMN a2 a3 DEFAULT a4",
+            "ALTER TABLE a1 DROP COLUMN a2",
+            "ALTER TABLE a1 ALTER COLUMN a2 SET DATA TYPE a3",
+            "ALTER TABLE a1 ALTER COLUMN a2 SET DEFAULT a3",
+            "ALTER TABLE a1 ALTER COLUMN a2 DROP NOT NULL",
+        ] {
+            let statement = Parser::parse_sql(&GenericDialect {}, sql.to_string()).unwrap();
+            let mut visitor = IdentCollector::default();
+            visitor.visit_statement(&statement[0]);
+            check_idents(sql, visitor.seen_idents);
+        }
+    }
+
+    visit_coverage_test!(
+        test_visit_expr_coverage,
+        Expr,
+        |sql: &str| {
+            let tokens = Tokenizer::new(&GenericDialect {}, sql).tokenize().unwrap();
+            Parser::new(tokens, &GenericDialect {}).parse_expr().unwrap()
+        },
+        visit_expr,
+        {
+            Identifier => Some("a1"),
+            Wildcard => Some("*"),
+            QualifiedWildcard => Some("a1.a2.*"),
+            CompoundIdentifier => Some("a1.a2"),
+            Parameter => Some("$1"),
+            IsNull => Some("a1 IS NULL"),
+            IsNotNull => Some("a1 IS NOT NULL"),
+            InList => Some("a1 IN (a2, a3)"),
+            InSubquery => Some("a1 IN (SELECT a2)"),
+            Between => Some("a1 BETWEEN a2 AND a3"),
+            BinaryOp => Some("a1 + a2"),
+            UnaryOp => Some("-a1"),
+            Cast => Some("CAST(a1 AS a2)"),
+            Extract => Some("EXTRACT(YEAR FROM a1)"),
+            Collate => Some("a1 COLLATE a2"),
+            Nested => Some("(a1)"),
+            Value => Some("ARRAY[1, 2]"),
+            Function => Some(
+                "a1(a2) OVER (PARTITION BY a3 ORDER BY a4 ROWS BETWEEN 1 PRECEDING AND CURRENT ROW)"
+            ),
+            Case => Some("CASE a1 WHEN a2 THEN a3 ELSE a4 END"),
+            Exists => Some("EXISTS (SELECT a1)"),
+            Subquery => Some("(SELECT a1)"),
+            Any => Some("a1 = ANY (SELECT a2)"),
+            All => Some("a1 = ALL (SELECT a2)"),
+            // Extensions are covered by the tests of `Extension::children`
+            Extension => None,
+        }
+    );
+
+    #[test]
+    fn test_visit_values() -> Result<(), Box<dyn Error>> {
+        /// Records the literals and date/time fields visited
+        #[derive(Default)]
+        struct Visitor {
+            seen: Vec<String>,
+        }
+
+        impl<'a> Visit<'a> for Visitor {
+            fn visit_value(&mut self, val: &'a Value) {
+                self.seen.push(val.to_string());
+                visit_value(self, val);
+            }
+
+            fn visit_date_time_field(&mut self, field: &'a DateTimeField) {
+                self.seen.push(field.to_string());
+            }
+        }
+
+        let sql = "SELECT ARRAY[1, ARRAY[2]], INTERVAL '1:2' HOUR TO MINUTE \
+                   LIMIT 3 OFFSET 4 ROWS FETCH FIRST 5 ROWS ONLY";
+        let stmts = Parser::parse_sql(&GenericDialect {}, sql.into())?;
+        let mut visitor = Visitor::default();
+        visitor.visit_statement(&stmts[0]);
+        assert_eq!(
+            visitor.seen,
+            vec![
+                "ARRAY[1, ARRAY[2]]",
+                "1",
+                "ARRAY[2]",
+                "2",
+                "INTERVAL '1:2' HOUR TO MINUTE",
+                "HOUR",
+                "MINUTE",
+                "3",
+                "4",
+                "5"
+            ]
+        );
+
+        Ok(())
+    }
 }
@@ -0,0 +1,558 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Lossless concrete syntax tree
+//!
+//! The AST leaves out the whitespace and comments of the source text, as well
+//! as the exact spelling of keywords and literals. `Parser::parse_sql_lossless`
+//! additionally returns a [`SourceFile`], whose tree of [`SyntaxNode`]s holds
+//! every token of the source text, with the whitespace and comments around
+//! it (the "trivia") attached to it. This lets tools rewrite part of a file,
+//! say a clause of a statement, and leave the rest of it unchanged.
+//!
+//! Trivia is attached to tokens as follows: the trivia after a token, up to
+//! the end of its line, is the token's trailing trivia; any trivia on the
+//! following lines is the leading trivia of the next token. So a comment on a
+//! line of its own belongs to the token after it, and a comment at the end of
+//! a line to the token before it.
+//!
+//! The [`SyntaxKind`] of a node says which part of the AST it corresponds
+//! to, e.g. the node of kind `SyntaxKind::Where` of a `SELECT` holds the
+//! tokens of its `Select::selection`. [`SourceFile::covering_node`] finds the
+//! node of the span of an AST node, whose trivia can then be looked up.
+
+use std::fmt;
+use std::iter::Peekable;
+
+use crate::ast::Statement;
+use crate::tokenizer::{Span, Token, TokenWithSpan, Whitespace};
+
+/// A run of whitespace or a comment, along with its location
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trivia {
+    pub whitespace: Whitespace<'static>,
+    pub span: Span,
+}
+
+impl Trivia {
+    pub fn is_comment(&self) -> bool {
+        matches!(
+            self.whitespace,
+            Whitespace::SingleLineComment(_) | Whitespace::MultiLineComment(_)
+        )
+    }
+}
+
+/// A token of the source text, along with the trivia attached to it
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntaxToken {
+    pub token: Token<'static>,
+    /// The location of the token itself, excluding its trivia
+    pub span: Span,
+    pub leading_trivia: Vec<Trivia>,
+    pub trailing_trivia: Vec<Trivia>,
+}
+
+/// A child of a `SyntaxNode`
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyntaxElement {
+    Node(SyntaxNode),
+    Token(SyntaxToken),
+}
+
+/// What a `SyntaxNode` represents, and the part of the AST that its tokens
+/// were parsed into. The nodes of clauses start after the keywords that
+/// introduce them, e.g. `WHERE` or `ORDER BY`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SyntaxKind {
+    /// The root of the tree, whose children are a `Statement` node for each
+    /// of `SourceFile::statements` and the semicolons between them
+    SourceFile,
+    /// A `Statement`
+    Statement,
+    /// The CTEs of a query, `Query::ctes`
+    With,
+    /// The `Select::projection` of a `SELECT`
+    Projection,
+    /// The `Select::from` of a `SELECT`
+    From,
+    /// The `Select::selection` of a `SELECT`, or the `selection` of an
+    /// `UPDATE` or a `DELETE`
+    Where,
+    /// The `Select::group_by` of a `SELECT`
+    GroupBy,
+    /// The `Select::having` of a `SELECT`
+    Having,
+    /// The `Query::order_by` of a query
+    OrderBy,
+    /// The `Query::limit` of a query
+    Limit,
+    /// The `Query::offset` of a query
+    Offset,
+    /// The `Query::fetch` of a query
+    Fetch,
+    /// The expression of a `JoinConstraint::On`
+    JoinConstraint,
+    /// The query of an `Expr::Subquery`, inside its parentheses
+    Subquery,
+    /// The `Function::args` of a function call, with their parentheses
+    FunctionArguments,
+    /// The `columns` and `constraints` of a `CREATE TABLE`, with their
+    /// parentheses
+    ColumnDefinitions,
+    /// The `assignments` of an `UPDATE`
+    Assignments,
+    /// The rows of a `Values`
+    Values,
+}
+
+impl SyntaxKind {
+    /// The description of the part of the statement that parser errors use
+    /// as their `context` when it has an error, e.g. `"WHERE clause"`
+    pub fn description(self) -> &'static str {
+        match self {
+            SyntaxKind::SourceFile => "source file",
+            SyntaxKind::Statement => "statement",
+            SyntaxKind::With => "WITH clause",
+            SyntaxKind::Projection => "SELECT projection",
+            SyntaxKind::From => "FROM clause",
+            SyntaxKind::Where => "WHERE clause",
+            SyntaxKind::GroupBy => "GROUP BY clause",
+            SyntaxKind::Having => "HAVING clause",
+            SyntaxKind::OrderBy => "ORDER BY clause",
+            SyntaxKind::Limit => "LIMIT clause",
+            SyntaxKind::Offset => "OFFSET clause",
+            SyntaxKind::Fetch => "FETCH clause",
+            SyntaxKind::JoinConstraint => "JOIN constraint",
+            SyntaxKind::Subquery => "subquery",
+            SyntaxKind::FunctionArguments => "function arguments",
+            SyntaxKind::ColumnDefinitions => "column definitions",
+            SyntaxKind::Assignments => "SET clause",
+            SyntaxKind::Values => "VALUES list",
+        }
+    }
+}
+
+/// A node of the concrete syntax tree: a sequence of tokens that the parser
+/// recognized as a statement, or as a part of a statement such as a clause.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntaxNode {
+    pub kind: SyntaxKind,
+    pub children: Vec<SyntaxElement>,
+}
+
+impl SyntaxNode {
+    /// Return the tokens of this node, in order, including those of its
+    /// descendants
+    pub fn tokens(&self) -> Vec<&SyntaxToken> {
+        let mut tokens = vec![];
+        self.collect_tokens(&mut tokens);
+        tokens
+    }
+
+    fn collect_tokens<'a>(&'a self, tokens: &mut Vec<&'a SyntaxToken>) {
+        for child in &self.children {
+            match child {
+                SyntaxElement::Node(node) => node.collect_tokens(tokens),
+                SyntaxElement::Token(token) => tokens.push(token),
+            }
+        }
+    }
+
+    fn first_token(&self) -> Option<&SyntaxToken> {
+        self.children.first().and_then(|child| match child {
+            SyntaxElement::Node(node) => node.first_token(),
+            SyntaxElement::Token(token) => Some(token),
+        })
+    }
+
+    fn last_token(&self) -> Option<&SyntaxToken> {
+        self.children.last().and_then(|child| match child {
+            SyntaxElement::Node(node) => node.last_token(),
+            SyntaxElement::Token(token) => Some(token),
+        })
+    }
+
+    /// Return the child nodes of this node
+    pub fn child_nodes(&self) -> impl Iterator<Item = &SyntaxNode> {
+        self.children.iter().filter_map(|child| match child {
+            SyntaxElement::Node(node) => Some(node),
+            SyntaxElement::Token(_) => None,
+        })
+    }
+
+    /// Return this node and all of its descendants, in source order (parents
+    /// before their children)
+    pub fn descendants(&self) -> Vec<&SyntaxNode> {
+        let mut nodes = vec![self];
+        for node in self.child_nodes() {
+            nodes.extend(node.descendants());
+        }
+        nodes
+    }
+
+    /// The location of the tokens of this node, excluding the leading trivia
+    /// of its first token and the trailing trivia of its last one
+    pub fn span(&self) -> Span {
+        match (self.first_token(), self.last_token()) {
+            (Some(first), Some(last)) => Span::new(first.span.start, last.span.end),
+            _ => Span::default(),
+        }
+    }
+
+    /// The trivia before the first token of this node
+    pub fn leading_trivia(&self) -> &[Trivia] {
+        self.first_token()
+            .map_or(&[], |token| &token.leading_trivia[..])
+    }
+
+    /// The trivia after the last token of this node
+    pub fn trailing_trivia(&self) -> &[Trivia] {
+        self.last_token()
+            .map_or(&[], |token| &token.trailing_trivia[..])
+    }
+}
+
+/// The result of `Parser::parse_sql_lossless`: the AST of some SQL, along
+/// with its concrete syntax tree
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceFile {
+    /// The SQL that was parsed
+    pub source: String,
+    pub statements: Vec<Statement>,
+    /// The root of the concrete syntax tree, of kind `SyntaxKind::SourceFile`.
+    /// Its children are a `SyntaxKind::Statement` node for each of the
+    /// `statements`, in the same order, and the semicolons between them.
+    pub root: SyntaxNode,
+    /// The trivia that follows the line of the last token, which is not
+    /// attached to any token
+    pub trailing_trivia: Vec<Trivia>,
+}
+
+impl SourceFile {
+    /// Return the `SyntaxKind::Statement` nodes, which correspond one to one to
+    /// `self.statements`
+    pub fn statement_nodes(&self) -> impl Iterator<Item = &SyntaxNode> {
+        self.root.child_nodes()
+    }
+
+    /// Return the smallest node whose span contains `span`, e.g. the span of
+    /// an AST node computed by `ast::Spanned`. Of nested nodes with the same
+    /// span, the innermost one is returned.
+    pub fn covering_node(&self, span: Span) -> Option<&SyntaxNode> {
+        if span.is_empty() {
+            return None;
+        }
+        self.root
+            .descendants()
+            .into_iter()
+            .rev()
+            .filter(|node| {
+                let node_span = node.span();
+                !node_span.is_empty()
+                    && node_span.start.offset <= span.start.offset
+                    && span.end.offset <= node_span.end.offset
+            })
+            .min_by_key(|node| node.span().end.offset - node.span().start.offset)
+    }
+
+    /// Return the text of the source covered by `span`
+    pub fn text(&self, span: Span) -> &str {
+        span.source(&self.source)
+    }
+
+    /// Return the source with the text covered by `span` replaced by `sql`,
+    /// keeping everything else, comments included, unchanged
+    pub fn replace(&self, span: Span, sql: &str) -> String {
+        format!(
+            "{}{}{}",
+            &self.source[..span.start.offset],
+            sql,
+            &self.source[span.end.offset..]
+        )
+    }
+
+    fn write_node(&self, f: &mut fmt::Formatter, node: &SyntaxNode) -> fmt::Result {
+        for child in &node.children {
+            match child {
+                SyntaxElement::Node(node) => self.write_node(f, node)?,
+                SyntaxElement::Token(token) => {
+                    self.write_trivia(f, &token.leading_trivia)?;
+                    f.write_str(self.text(token.span))?;
+                    self.write_trivia(f, &token.trailing_trivia)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn write_trivia(&self, f: &mut fmt::Formatter, trivia: &[Trivia]) -> fmt::Result {
+        for t in trivia {
+            f.write_str(self.text(t.span))?;
+        }
+        Ok(())
+    }
+}
+
+/// Reproduces the source text from the concrete syntax tree
+impl fmt::Display for SourceFile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write_node(f, &self.root)?;
+        self.write_trivia(f, &self.trailing_trivia)
+    }
+}
+
+/// The range of tokens, as indexes into the tokens given to the parser, that
+/// the parser recognized as a node of kind `kind`
+#[derive(Debug)]
+pub(crate) struct NodeRange {
+    pub kind: SyntaxKind,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Build the concrete syntax tree of `tokens`, given the ranges of its nodes
+/// in the order they were completed, i.e. children before their parents
+pub(crate) fn build(
+    source: String,
+    tokens: Vec<TokenWithSpan<'static>>,
+    ranges: Vec<NodeRange>,
+    statements: Vec<Statement>,
+) -> SourceFile {
+    // Map the indexes of the tokens to indexes of the non-whitespace tokens
+    let mut positions = Vec::with_capacity(tokens.len() + 1);
+    let mut syntax_tokens = vec![];
+    let mut trivia = vec![];
+    let mut at_line_start = true;
+    for TokenWithSpan { token, span } in tokens {
+        positions.push(syntax_tokens.len());
+        match token {
+            Token::Whitespace(whitespace) => {
+                let ends_line = matches!(
+                    whitespace,
+                    Whitespace::Newline | Whitespace::SingleLineComment(_)
+                );
+                let t = Trivia { whitespace, span };
+                match syntax_tokens.last_mut() {
+                    Some(SyntaxToken {
+                        trailing_trivia, ..
+                    }) if !at_line_start => trailing_trivia.push(t),
+                    _ => trivia.push(t),
+                }
+                at_line_start |= ends_line;
+            }
+            token => {
+                syntax_tokens.push(SyntaxToken {
+                    token,
+                    span,
+                    leading_trivia: std::mem::take(&mut trivia),
+                    trailing_trivia: vec![],
+                });
+                at_line_start = false;
+            }
+        }
+    }
+    positions.push(syntax_tokens.len());
+
+    // Convert the ranges to ranges of non-whitespace tokens, parents first
+    let mut ranges: Vec<_> = ranges
+        .into_iter()
+        .enumerate()
+        .map(|(order, range)| {
+            let start = positions[range.start.min(positions.len() - 1)];
+            let end = positions[range.end.min(positions.len() - 1)];
+            (start, end, order, range.kind)
+        })
+        .filter(|(start, end, ..)| start < end)
+        .collect();
+    ranges.sort_by(|a, b| (a.0, b.1, b.2).cmp(&(b.0, a.1, a.2)));
+
+    let mut tokens = syntax_tokens.into_iter().enumerate().peekable();
+    let mut ranges = ranges.into_iter().peekable();
+    let root = build_node(SyntaxKind::SourceFile, usize::MAX, &mut tokens, &mut ranges);
+    SourceFile {
+        source,
+        statements,
+        root,
+        trailing_trivia: trivia,
+    }
+}
+
+fn build_node<T, R>(
+    kind: SyntaxKind,
+    end: usize,
+    tokens: &mut Peekable<T>,
+    ranges: &mut Peekable<R>,
+) -> SyntaxNode
+where
+    T: Iterator<Item = (usize, SyntaxToken)>,
+    R: Iterator<Item = (usize, usize, usize, SyntaxKind)>,
+{
+    let mut children = vec![];
+    while let Some(&(position, _)) = tokens.peek() {
+        if position >= end {
+            break;
+        }
+        // Skip the ranges that overlap a previous node without being nested
+        // in it, which can only happen after backtracking
+        while matches!(ranges.peek(), Some(&(start, ..)) if start < position) {
+            ranges.next();
+        }
+        match ranges.peek() {
+            Some(&(start, child_end, _, child_kind)) if start == position => {
+                ranges.next();
+                let node = build_node(child_kind, child_end.min(end), tokens, ranges);
+                children.push(SyntaxElement::Node(node));
+            }
+            _ => children.push(SyntaxElement::Token(tokens.next().unwrap().1)),
+        }
+    }
+    SyntaxNode { kind, children }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::Spanned;
+    use crate::dialect::GenericDialect;
+    use crate::parser::Parser;
+
+    fn parse(sql: &str) -> SourceFile {
+        Parser::parse_sql_lossless(&GenericDialect {}, sql.to_string()).unwrap()
+    }
+
+    fn comments(trivia: &[Trivia]) -> Vec<String> {
+        trivia
+            .iter()
+            .filter(|t| t.is_comment())
+            .map(|t| t.whitespace.to_string())
+            .collect()
+    }
+
+    const SQL: &str = "-- Active users\r\n\
+                       SELECT id, /* the name */ name\n\
+                       FROM users -- all of them\n\
+                       WHERE active = true;\n\
+                       \n\
+                       /* Stale */ DELETE FROM sessions;  -- done\n\
+                       -- end of file\n";
+
+    #[test]
+    fn lossless_round_trip() {
+        let file = parse(SQL);
+        assert_eq!(SQL, file.to_string());
+        assert_eq!(2, file.statements.len());
+        let kinds: Vec<_> = file.root.descendants().iter().map(|n| n.kind).collect();
+        assert_eq!(
+            vec![
+                SyntaxKind::SourceFile,
+                SyntaxKind::Statement,
+                SyntaxKind::Projection,
+                SyntaxKind::From,
+                SyntaxKind::Where,
+                SyntaxKind::Statement
+            ],
+            kinds
+        );
+        assert_eq!(vec!["-- end of file\n"], comments(&file.trailing_trivia));
+
+        for sql in &[
+            "",
+            "  ",
+            "-- nothing\n",
+            "SELECT 1",
+            "SELECT (SELECT 1) -- x",
+        ] {
+            assert_eq!(*sql, parse(sql).to_string());
+        }
+    }
+
+    #[test]
+    fn trivia_is_attached_to_neighboring_nodes() {
+        let file = parse(SQL);
+        let statements: Vec<_> = file.statement_nodes().collect();
+        assert_eq!(
+            vec!["-- Active users\r\n"],
+            comments(statements[0].leading_trivia())
+        );
+        assert_eq!(
+            vec!["/* Stale */"],
+            comments(statements[1].leading_trivia())
+        );
+        // The comment after the semicolon is on the same line
+        let semicolon = file.root.children.last().unwrap();
+        match semicolon {
+            SyntaxElement::Token(token) => {
+                assert_eq!(Token::SemiColon, token.token);
+                assert_eq!(vec!["-- done\n"], comments(&token.trailing_trivia));
+            }
+            _ => unreachable!(),
+        }
+
+        let from = statements[0].child_nodes().nth(1).unwrap();
+        assert_eq!("users", file.text(from.span()));
+        assert_eq!(vec!["-- all of them\n"], comments(from.trailing_trivia()));
+        let projection = statements[0].child_nodes().next().unwrap();
+        assert_eq!("id, /* the name */ name", file.text(projection.span()));
+    }
+
+    #[test]
+    fn rewrite_a_clause() {
+        let file = parse(SQL);
+        let selection = match &file.statements[0] {
+            Statement::Query(query) => match &query.body {
+                crate::ast::SetExpr::Select(select) => select.selection.clone().unwrap(),
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+        let node = file.covering_node(selection.span()).unwrap();
+        assert_eq!(SyntaxKind::Where, node.kind);
+        assert_eq!(
+            SQL.replace("active = true", "NOT deleted"),
+            file.replace(node.span(), "NOT deleted")
+        );
+    }
+
+    #[test]
+    fn node_kinds_describe_error_contexts() {
+        let sql = "SELECT a FROM t WHERE b = (SELECT (";
+        let err = Parser::parse_sql(&GenericDialect {}, sql.to_string()).unwrap_err();
+        let kinds = [
+            SyntaxKind::Where,
+            SyntaxKind::Subquery,
+            SyntaxKind::Projection,
+        ];
+        let descriptions: Vec<_> = kinds.iter().map(|kind| kind.description()).collect();
+        assert_eq!(descriptions, err.context);
+    }
+
+    #[test]
+    fn backtracking_discards_nodes() {
+        let sql = "SELECT * FROM ((SELECT a FROM t) AS x JOIN y ON x.a = y.a)";
+        let file = parse(sql);
+        assert_eq!(sql, file.to_string());
+        let kinds: Vec<_> = file.root.descendants().iter().map(|n| n.kind).collect();
+        assert_eq!(
+            vec![
+                SyntaxKind::SourceFile,
+                SyntaxKind::Statement,
+                SyntaxKind::Projection,
+                SyntaxKind::From,
+                SyntaxKind::Projection,
+                SyntaxKind::From,
+                SyntaxKind::JoinConstraint
+            ],
+            kinds
+        );
+    }
+}
@@ -17,16 +17,28 @@ pub struct GenericDialect {}
 
 impl Dialect for GenericDialect {
     fn is_identifier_start(&self, ch: char) -> bool {
-        (ch >= 'a' && ch <= 'z') || (ch >= 'A' && ch <= 'Z') || ch == '_' || ch == '#' || ch == '@'
+        ch.is_ascii_lowercase() || ch.is_ascii_uppercase() || ch == '_' || ch == '#' || ch == '@'
     }
 
     fn is_identifier_part(&self, ch: char) -> bool {
-        (ch >= 'a' && ch <= 'z')
-            || (ch >= 'A' && ch <= 'Z')
-            || (ch >= '0' && ch <= '9')
+        ch.is_ascii_lowercase()
+            || ch.is_ascii_uppercase()
+            || ch.is_ascii_digit()
             || ch == '@'
             || ch == '$'
             || ch == '#'
             || ch == '_'
     }
+
+    fn supports_delimiter_command(&self) -> bool {
+        true
+    }
+
+    fn supports_go_batch_separator(&self) -> bool {
+        true
+    }
+
+    fn supports_backslash_commands(&self) -> bool {
+        true
+    }
 }
@@ -10,453 +10,538 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-///! This module defines
-/// 1) a list of constants for every keyword that
-/// can appear in [Word::keyword]:
-///    pub const KEYWORD = "KEYWORD"
-/// 2) an `ALL_KEYWORDS` array with every keyword in it
-///     This is not a list of *reserved* keywords: some of these can be
-///     parsed as identifiers if the parser decides so. This means that
-///     new keywords can be added here without affecting the parse result.
-///
-///     As a matter of fact, most of these keywords are not used at all
-///     and could be removed.
-/// 3) a `RESERVED_FOR_TABLE_ALIAS` array with keywords reserved in a
-/// "table alias" context.
+//! This module defines
+//! 1) a `Keyword` enum with a variant for every keyword that
+//!    can appear in [Word::keyword], e.g. `Keyword::Select` for `SELECT`,
+//!    and `Keyword::lookup` to recognize a keyword regardless of its case
+//! 2) an `ALL_KEYWORDS` array with every keyword in it
+//!    This is not a list of *reserved* keywords: some of these can be
+//!    parsed as identifiers if the parser decides so. This means that
+//!    new keywords can be added here without affecting the parse result.
+//!
+//!    As a matter of fact, most of these keywords are not used at all
+//!    and could be removed.
+//! 3) a `RESERVED_FOR_TABLE_ALIAS` array with keywords reserved in a
+//!    "table alias" context.
 
-/// Defines a string constant for a single keyword: `kw_def!(SELECT);`
-/// expands to `pub const SELECT = "SELECT";`
-macro_rules! kw_def {
-    ($ident:ident = $string_keyword:expr) => {
-        pub const $ident: &'static str = $string_keyword;
-    };
-    ($ident:ident) => {
-        kw_def!($ident = stringify!($ident));
-    };
-}
+use std::fmt;
 
-/// Expands to a list of `kw_def!()` invocations for each keyword
-/// and defines an ALL_KEYWORDS array of the defined constants.
+/// Defines the `Keyword` enum, with a variant for each keyword, and the
+/// tables to convert keywords to and from their spelling in SQL.
+/// `define_keywords!(Select = "SELECT")` defines `Keyword::Select`.
 macro_rules! define_keywords {
     ($(
-        $ident:ident $(= $string_keyword:expr)?
+        $ident:ident = $string_keyword:literal
     ),*) => {
-        $(kw_def!($ident $(= $string_keyword)?);)*
+        /// A keyword, as recognized by the tokenizer
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub enum Keyword {
+            /// Not a keyword: an identifier, or a quoted word
+            NoKeyword,
+            $($ident),*
+        }
+
+        impl Keyword {
+            /// The keyword as it is spelled in SQL, in upper case. Empty for
+            /// `Keyword::NoKeyword`.
+            pub fn as_str(self) -> &'static str {
+                match self {
+                    Keyword::NoKeyword => "",
+                    $(Keyword::$ident => $string_keyword),*
+                }
+            }
+        }
 
         pub const ALL_KEYWORDS: &[&str] = &[
-            $($ident),*
+            $($string_keyword),*
         ];
+
+        /// Maps the (upper case) spelling of each keyword to the keyword
+        static KEYWORDS_BY_NAME: phf::Map<&'static str, Keyword> = phf::phf_map! {
+            $($string_keyword => Keyword::$ident),*
+        };
+    }
+}
+
+/// The length of the longest keyword, in bytes
+const MAX_KEYWORD_LEN: usize = {
+    let mut max = 0;
+    let mut i = 0;
+    while i < ALL_KEYWORDS.len() {
+        if ALL_KEYWORDS[i].len() > max {
+            max = ALL_KEYWORDS[i].len();
+        }
+        i += 1;
+    }
+    max
+};
+
+impl Keyword {
+    /// Returns the keyword spelled `word`, ignoring ASCII case, or
+    /// `Keyword::NoKeyword` if it is not a keyword.
+    pub fn lookup(word: &str) -> Keyword {
+        // Upper case the word on the stack, as keywords are short
+        let mut buf = [0; MAX_KEYWORD_LEN];
+        let upper = match buf.get_mut(..word.len()) {
+            Some(upper) => upper,
+            None => return Keyword::NoKeyword,
+        };
+        upper.copy_from_slice(word.as_bytes());
+        upper.make_ascii_uppercase();
+        std::str::from_utf8(upper)
+            .ok()
+            .and_then(|upper| KEYWORDS_BY_NAME.get(upper))
+            .copied()
+            .unwrap_or(Keyword::NoKeyword)
+    }
+}
+
+impl fmt::Display for Keyword {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
     }
 }
 
 define_keywords!(
-    ABS,
-    ADD,
-    ASC,
-    ALL,
-    ALLOCATE,
-    ALTER,
-    AND,
-    ANY,
-    APPLY,
-    ARE,
-    ARRAY,
-    ARRAY_AGG,
-    ARRAY_MAX_CARDINALITY,
-    AS,
-    ASENSITIVE,
-    ASYMMETRIC,
-    AT,
-    ATOMIC,
-    AUTHORIZATION,
-    AVG,
-    BEGIN,
-    BEGIN_FRAME,
-    BEGIN_PARTITION,
-    BETWEEN,
-    BIGINT,
-    BINARY,
-    BLOB,
-    BOOLEAN,
-    BOTH,
-    BY,
-    BYTEA,
-    CALL,
-    CALLED,
-    CARDINALITY,
-    CASCADE,
-    CASCADED,
-    CASE,
-    CAST,
-    CEIL,
-    CEILING,
-    CHAIN,
-    CHAR,
-    CHAR_LENGTH,
-    CHARACTER,
-    CHARACTER_LENGTH,
-    CHECK,
-    CLOB,
-    CLOSE,
-    COALESCE,
-    COLLATE,
-    COLLECT,
-    COLUMN,
-    COLUMNS,
-    COMMIT,
-    COMMITTED,
-    CONDITION,
-    CONNECT,
-    CONSTRAINT,
-    CONTAINS,
-    CONVERT,
-    COPY,
-    CORR,
-    CORRESPONDING,
-    COUNT,
-    COVAR_POP,
-    COVAR_SAMP,
-    CREATE,
-    CROSS,
-    CSV,
-    CUBE,
-    CUME_DIST,
-    CURRENT,
-    CURRENT_CATALOG,
-    CURRENT_DATE,
-    CURRENT_DEFAULT_TRANSFORM_GROUP,
-    CURRENT_PATH,
-    CURRENT_ROLE,
-    CURRENT_ROW,
-    CURRENT_SCHEMA,
-    CURRENT_TIME,
-    CURRENT_TIMESTAMP,
-    CURRENT_TRANSFORM_GROUP_FOR_TYPE,
-    CURRENT_USER,
-    CURSOR,
-    CYCLE,
-    DATAFLOW,
-    DATE,
-    DAY,
-    DEALLOCATE,
-    DEC,
-    DECIMAL,
-    DECLARE,
-    DEFAULT,
-    DELETE,
-    DENSE_RANK,
-    DEREF,
-    DESC,
-    DESCRIBE,
-    DETERMINISTIC,
-    DISCONNECT,
-    DISTINCT,
-    DOUBLE,
-    DROP,
-    DYNAMIC,
-    EACH,
-    ELEMENT,
-    ELSE,
-    END,
-    END_FRAME,
-    END_PARTITION,
-    EQUALS,
-    ESCAPE,
-    EVERY,
-    EXCEPT,
-    EXEC,
-    EXECUTE,
-    EXISTS,
-    EXP,
-    EXPLAIN,
-    EXTENDED,
-    EXTERNAL,
-    EXTRACT,
-    FALSE,
-    FETCH,
-    FIELDS,
-    FIRST,
-    FILTER,
-    FIRST_VALUE,
-    FLOAT,
-    FLOOR,
-    FLUSH,
-    FOLLOWING,
-    FOR,
-    FOREIGN,
-    FRAME_ROW,
-    FREE,
-    FROM,
-    FULL,
-    FUNCTION,
-    FUSION,
-    GET,
-    GLOBAL,
-    GRANT,
-    GROUP,
-    GROUPING,
-    GROUPS,
-    HAVING,
-    HEADER,
-    HOLD,
-    HOUR,
-    IDENTITY,
-    IF,
-    IMMEDIATE,
-    IN,
-    INDEX,
-    INDEXES,
-    INDICATOR,
-    INNER,
-    INOUT,
-    INSENSITIVE,
-    INSERT,
-    INT,
-    INTEGER,
-    INTERSECT,
-    INTERSECTION,
-    INTERVAL,
-    INTO,
-    IS,
-    ISOLATION,
-    JOIN,
-    KEY,
-    KEYS,
-    LAG,
-    LANGUAGE,
-    LARGE,
-    LAST_VALUE,
-    LATERAL,
-    LEAD,
-    LEADING,
-    LEFT,
-    LEVEL,
-    LIKE,
-    LIKE_REGEX,
-    LIMIT,
-    LN,
-    LOCAL,
-    LOCALTIME,
-    LOCALTIMESTAMP,
-    LOCATION,
-    LOWER,
-    MATCH,
-    MATERIALIZED,
-    MAX,
-    MEMBER,
-    MERGE,
-    METHOD,
-    MIN,
-    MINUTE,
-    MOD,
-    MODIFIES,
-    MODULE,
-    MONTH,
-    MULTISET,
-    NATIONAL,
-    NATURAL,
-    NCHAR,
-    NCLOB,
-    NEXT,
-    NEW,
-    NO,
-    NONE,
-    NORMALIZE,
-    NOT,
-    NTH_VALUE,
-    NTILE,
-    NULL,
-    NULLIF,
-    NUMERIC,
-    OBJECT,
-    OCTET_LENGTH,
-    OCCURRENCES_REGEX,
-    OF,
-    OFFSET,
-    OLD,
-    ON,
-    ONLY,
-    OPEN,
-    OR,
-    ORDER,
-    OUT,
-    OUTER,
-    OVER,
-    OVERLAPS,
-    OVERLAY,
-    PARAMETER,
-    PARTITION,
-    PARQUET,
-    PEEK,
-    PERCENT,
-    PERCENT_RANK,
-    PERCENTILE_CONT,
-    PERCENTILE_DISC,
-    PERIOD,
-    PLAN,
-    PORTION,
-    POSITION,
-    POSITION_REGEX,
-    POWER,
-    PRECEDES,
-    PRECEDING,
-    PRECISION,
-    PREPARE,
-    PRIMARY,
-    PROCEDURE,
-    RANGE,
-    RANK,
-    READ,
-    READS,
-    REAL,
-    RECURSIVE,
-    REF,
-    REFERENCES,
-    REFERENCING,
-    REGCLASS,
-    REGISTRY,
-    REGR_AVGX,
-    REGR_AVGY,
-    REGR_COUNT,
-    REGR_INTERCEPT,
-    REGR_R2,
-    REGR_SLOPE,
-    REGR_SXX,
-    REGR_SXY,
-    REGR_SYY,
-    RELEASE,
-    REPEATABLE,
-    RESTRICT,
-    RESULT,
-    RETURN,
-    RETURNS,
-    REVOKE,
-    RIGHT,
-    ROLLBACK,
-    ROLLUP,
-    ROW,
-    ROW_NUMBER,
-    ROWS,
-    SAVEPOINT,
-    SCHEMA,
-    SCOPE,
-    SCROLL,
-    SEARCH,
-    SECOND,
-    SELECT,
-    SENSITIVE,
-    SERIALIZABLE,
-    SESSION,
-    SESSION_USER,
-    SET,
-    SHOW,
-    SIMILAR,
-    SINK,
-    SINKS,
-    SMALLINT,
-    SOME,
-    SOURCE,
-    SOURCES,
-    SPECIFIC,
-    SPECIFICTYPE,
-    SQL,
-    SQLEXCEPTION,
-    SQLSTATE,
-    SQLWARNING,
-    SQRT,
-    START,
-    STATIC,
-    STDDEV_POP,
-    STDDEV_SAMP,
-    STDIN,
-    STORED,
-    SUBMULTISET,
-    SUBSTRING,
-    SUBSTRING_REGEX,
-    SUCCEEDS,
-    SUM,
-    SYMMETRIC,
-    SYSTEM,
-    SYSTEM_TIME,
-    SYSTEM_USER,
-    TABLE,
-    TABLES,
-    TABLESAMPLE,
-    TAIL,
-    TEXT,
-    THEN,
-    TIES,
-    TIME,
-    TIMESTAMP,
-    TIMESTAMPTZ,
-    TIMEZONE_HOUR,
-    TIMEZONE_MINUTE,
-    TO,
-    TRAILING,
-    TRANSACTION,
-    TRANSLATE,
-    TRANSLATE_REGEX,
-    TRANSLATION,
-    TREAT,
-    TRIGGER,
-    TRUNCATE,
-    TRIM,
-    TRIM_ARRAY,
-    TRUE,
-    UESCAPE,
-    UNBOUNDED,
-    UNCOMMITTED,
-    UNION,
-    UNIQUE,
-    UNKNOWN,
-    UNNEST,
-    UPDATE,
-    UPPER,
-    USER,
-    USING,
-    UUID,
-    VALUE,
-    VALUES,
-    VALUE_OF,
-    VAR_POP,
-    VAR_SAMP,
-    VARBINARY,
-    VARCHAR,
-    VARYING,
-    VERSIONING,
-    VIEW,
-    VIEWS,
-    WHEN,
-    WHENEVER,
-    WHERE,
-    WIDTH_BUCKET,
-    WINDOW,
-    WITH,
-    WITHIN,
-    WITHOUT,
-    WRITE,
-    WORK,
-    YEAR,
-    ZONE,
-    END_EXEC = "END-EXEC"
+    Abs = "ABS",
+    Add = "ADD",
+    Asc = "ASC",
+    All = "ALL",
+    Allocate = "ALLOCATE",
+    Alter = "ALTER",
+    And = "AND",
+    Any = "ANY",
+    Apply = "APPLY",
+    Are = "ARE",
+    Array = "ARRAY",
+    ArrayAgg = "ARRAY_AGG",
+    ArrayMaxCardinality = "ARRAY_MAX_CARDINALITY",
+    As = "AS",
+    Asensitive = "ASENSITIVE",
+    Asymmetric = "ASYMMETRIC",
+    At = "AT",
+    Atomic = "ATOMIC",
+    Authorization = "AUTHORIZATION",
+    Avg = "AVG",
+    Begin = "BEGIN",
+    BeginFrame = "BEGIN_FRAME",
+    BeginPartition = "BEGIN_PARTITION",
+    Between = "BETWEEN",
+    Bigint = "BIGINT",
+    Binary = "BINARY",
+    Blob = "BLOB",
+    Boolean = "BOOLEAN",
+    Both = "BOTH",
+    By = "BY",
+    Bytea = "BYTEA",
+    Call = "CALL",
+    Called = "CALLED",
+    Cardinality = "CARDINALITY",
+    Cascade = "CASCADE",
+    Cascaded = "CASCADED",
+    Case = "CASE",
+    Cast = "CAST",
+    Ceil = "CEIL",
+    Ceiling = "CEILING",
+    Chain = "CHAIN",
+    Char = "CHAR",
+    CharLength = "CHAR_LENGTH",
+    Character = "CHARACTER",
+    CharacterLength = "CHARACTER_LENGTH",
+    Check = "CHECK",
+    Clob = "CLOB",
+    Close = "CLOSE",
+    Coalesce = "COALESCE",
+    Collate = "COLLATE",
+    Collect = "COLLECT",
+    Column = "COLUMN",
+    Columns = "COLUMNS",
+    Commit = "COMMIT",
+    Committed = "COMMITTED",
+    Condition = "CONDITION",
+    Connect = "CONNECT",
+    Constraint = "CONSTRAINT",
+    Contains = "CONTAINS",
+    Convert = "CONVERT",
+    Copy = "COPY",
+    Corr = "CORR",
+    Corresponding = "CORRESPONDING",
+    Count = "COUNT",
+    CovarPop = "COVAR_POP",
+    CovarSamp = "COVAR_SAMP",
+    Create = "CREATE",
+    Cross = "CROSS",
+    Csv = "CSV",
+    Cube = "CUBE",
+    CumeDist = "CUME_DIST",
+    Current = "CURRENT",
+    CurrentCatalog = "CURRENT_CATALOG",
+    CurrentDate = "CURRENT_DATE",
+    CurrentDefaultTransformGroup = "CURRENT_DEFAULT_TRANSFORM_GROUP",
+    CurrentPath = "CURRENT_PATH",
+    CurrentRole = "CURRENT_ROLE",
+    CurrentRow = "CURRENT_ROW",
+    CurrentSchema = "CURRENT_SCHEMA",
+    CurrentTime = "CURRENT_TIME",
+    CurrentTimestamp = "CURRENT_TIMESTAMP",
+    CurrentTransformGroupForType = "CURRENT_TRANSFORM_GROUP_FOR_TYPE",
+    CurrentUser = "CURRENT_USER",
+    Cursor = "CURSOR",
+    Cycle = "CYCLE",
+    Data = "DATA",
+    Dataflow = "DATAFLOW",
+    Date = "DATE",
+    Day = "DAY",
+    Deallocate = "DEALLOCATE",
+    Dec = "DEC",
+    Decimal = "DECIMAL",
+    Declare = "DECLARE",
+    Default = "DEFAULT",
+    Delete = "DELETE",
+    DenseRank = "DENSE_RANK",
+    Deref = "DEREF",
+    Desc = "DESC",
+    Describe = "DESCRIBE",
+    Deterministic = "DETERMINISTIC",
+    Disconnect = "DISCONNECT",
+    Distinct = "DISTINCT",
+    Double = "DOUBLE",
+    Drop = "DROP",
+    Dynamic = "DYNAMIC",
+    Each = "EACH",
+    Element = "ELEMENT",
+    Else = "ELSE",
+    End = "END",
+    EndFrame = "END_FRAME",
+    EndPartition = "END_PARTITION",
+    Equals = "EQUALS",
+    Escape = "ESCAPE",
+    Every = "EVERY",
+    Except = "EXCEPT",
+    Exec = "EXEC",
+    Execute = "EXECUTE",
+    Exists = "EXISTS",
+    Exp = "EXP",
+    Explain = "EXPLAIN",
+    Extended = "EXTENDED",
+    External = "EXTERNAL",
+    Extract = "EXTRACT",
+    False = "FALSE",
+    Fetch = "FETCH",
+    Fields = "FIELDS",
+    First = "FIRST",
+    Filter = "FILTER",
+    FirstValue = "FIRST_VALUE",
+    Float = "FLOAT",
+    Floor = "FLOOR",
+    Flush = "FLUSH",
+    Following = "FOLLOWING",
+    For = "FOR",
+    Foreign = "FOREIGN",
+    FrameRow = "FRAME_ROW",
+    Free = "FREE",
+    From = "FROM",
+    Full = "FULL",
+    Function = "FUNCTION",
+    Fusion = "FUSION",
+    Get = "GET",
+    Global = "GLOBAL",
+    Grant = "GRANT",
+    Group = "GROUP",
+    Grouping = "GROUPING",
+    Groups = "GROUPS",
+    Having = "HAVING",
+    Header = "HEADER",
+    Hold = "HOLD",
+    Hour = "HOUR",
+    Identity = "IDENTITY",
+    If = "IF",
+    Immediate = "IMMEDIATE",
+    In = "IN",
+    Index = "INDEX",
+    Indexes = "INDEXES",
+    Indicator = "INDICATOR",
+    Inner = "INNER",
+    Inout = "INOUT",
+    Insensitive = "INSENSITIVE",
+    Insert = "INSERT",
+    Int = "INT",
+    Integer = "INTEGER",
+    Intersect = "INTERSECT",
+    Intersection = "INTERSECTION",
+    Interval = "INTERVAL",
+    Into = "INTO",
+    Is = "IS",
+    Isolation = "ISOLATION",
+    Join = "JOIN",
+    Key = "KEY",
+    Keys = "KEYS",
+    Lag = "LAG",
+    Language = "LANGUAGE",
+    Large = "LARGE",
+    LastValue = "LAST_VALUE",
+    Lateral = "LATERAL",
+    Lead = "LEAD",
+    Leading = "LEADING",
+    Left = "LEFT",
+    Level = "LEVEL",
+    Like = "LIKE",
+    LikeRegex = "LIKE_REGEX",
+    Limit = "LIMIT",
+    Ln = "LN",
+    Local = "LOCAL",
+    Localtime = "LOCALTIME",
+    Localtimestamp = "LOCALTIMESTAMP",
+    Location = "LOCATION",
+    Lower = "LOWER",
+    Match = "MATCH",
+    Materialized = "MATERIALIZED",
+    Max = "MAX",
+    Member = "MEMBER",
+    Merge = "MERGE",
+    Method = "METHOD",
+    Min = "MIN",
+    Minute = "MINUTE",
+    Mod = "MOD",
+    Modifies = "MODIFIES",
+    Module = "MODULE",
+    Month = "MONTH",
+    Multiset = "MULTISET",
+    National = "NATIONAL",
+    Natural = "NATURAL",
+    Nchar = "NCHAR",
+    Nclob = "NCLOB",
+    Next = "NEXT",
+    New = "NEW",
+    No = "NO",
+    None = "NONE",
+    Normalize = "NORMALIZE",
+    Not = "NOT",
+    NthValue = "NTH_VALUE",
+    Ntile = "NTILE",
+    Null = "NULL",
+    Nullif = "NULLIF",
+    Numeric = "NUMERIC",
+    Object = "OBJECT",
+    OctetLength = "OCTET_LENGTH",
+    OccurrencesRegex = "OCCURRENCES_REGEX",
+    Of = "OF",
+    Offset = "OFFSET",
+    Old = "OLD",
+    On = "ON",
+    Only = "ONLY",
+    Open = "OPEN",
+    Or = "OR",
+    Order = "ORDER",
+    Out = "OUT",
+    Outer = "OUTER",
+    Over = "OVER",
+    Overlaps = "OVERLAPS",
+    Overlay = "OVERLAY",
+    Parameter = "PARAMETER",
+    Partition = "PARTITION",
+    Parquet = "PARQUET",
+    Peek = "PEEK",
+    Percent = "PERCENT",
+    PercentRank = "PERCENT_RANK",
+    PercentileCont = "PERCENTILE_CONT",
+    PercentileDisc = "PERCENTILE_DISC",
+    Period = "PERIOD",
+    Plan = "PLAN",
+    Portion = "PORTION",
+    Position = "POSITION",
+    PositionRegex = "POSITION_REGEX",
+    Power = "POWER",
+    Precedes = "PRECEDES",
+    Preceding = "PRECEDING",
+    Precision = "PRECISION",
+    Prepare = "PREPARE",
+    Primary = "PRIMARY",
+    Procedure = "PROCEDURE",
+    Range = "RANGE",
+    Rank = "RANK",
+    Read = "READ",
+    Reads = "READS",
+    Real = "REAL",
+    Recursive = "RECURSIVE",
+    Ref = "REF",
+    References = "REFERENCES",
+    Referencing = "REFERENCING",
+    Regclass = "REGCLASS",
+    Registry = "REGISTRY",
+    RegrAvgx = "REGR_AVGX",
+    RegrAvgy = "REGR_AVGY",
+    RegrCount = "REGR_COUNT",
+    RegrIntercept = "REGR_INTERCEPT",
+    RegrR2 = "REGR_R2",
+    RegrSlope = "REGR_SLOPE",
+    RegrSxx = "REGR_SXX",
+    RegrSxy = "REGR_SXY",
+    RegrSyy = "REGR_SYY",
+    Release = "RELEASE",
+    Repeatable = "REPEATABLE",
+    Restrict = "RESTRICT",
+    Result = "RESULT",
+    Return = "RETURN",
+    Returns = "RETURNS",
+    Revoke = "REVOKE",
+    Right = "RIGHT",
+    Rollback = "ROLLBACK",
+    Rollup = "ROLLUP",
+    Row = "ROW",
+    RowNumber = "ROW_NUMBER",
+    Rows = "ROWS",
+    Savepoint = "SAVEPOINT",
+    Schema = "SCHEMA",
+    Scope = "SCOPE",
+    Scroll = "SCROLL",
+    Search = "SEARCH",
+    Second = "SECOND",
+    Select = "SELECT",
+    Sensitive = "SENSITIVE",
+    Serializable = "SERIALIZABLE",
+    Session = "SESSION",
+    SessionUser = "SESSION_USER",
+    Set = "SET",
+    Show = "SHOW",
+    Similar = "SIMILAR",
+    Sink = "SINK",
+    Sinks = "SINKS",
+    Smallint = "SMALLINT",
+    Some = "SOME",
+    Source = "SOURCE",
+    Sources = "SOURCES",
+    Specific = "SPECIFIC",
+    Specifictype = "SPECIFICTYPE",
+    Sql = "SQL",
+    Sqlexception = "SQLEXCEPTION",
+    Sqlstate = "SQLSTATE",
+    Sqlwarning = "SQLWARNING",
+    Sqrt = "SQRT",
+    Start = "START",
+    Static = "STATIC",
+    StddevPop = "STDDEV_POP",
+    StddevSamp = "STDDEV_SAMP",
+    Stdin = "STDIN",
+    Stored = "STORED",
+    Submultiset = "SUBMULTISET",
+    Substring = "SUBSTRING",
+    SubstringRegex = "SUBSTRING_REGEX",
+    Succeeds = "SUCCEEDS",
+    Sum = "SUM",
+    Symmetric = "SYMMETRIC",
+    System = "SYSTEM",
+    SystemTime = "SYSTEM_TIME",
+    SystemUser = "SYSTEM_USER",
+    Table = "TABLE",
+    Tables = "TABLES",
+    Tablesample = "TABLESAMPLE",
+    Tail = "TAIL",
+    Text = "TEXT",
+    Then = "THEN",
+    Ties = "TIES",
+    Time = "TIME",
+    Timestamp = "TIMESTAMP",
+    Timestamptz = "TIMESTAMPTZ",
+    TimezoneHour = "TIMEZONE_HOUR",
+    TimezoneMinute = "TIMEZONE_MINUTE",
+    To = "TO",
+    Trailing = "TRAILING",
+    Transaction = "TRANSACTION",
+    Translate = "TRANSLATE",
+    TranslateRegex = "TRANSLATE_REGEX",
+    Translation = "TRANSLATION",
+    Treat = "TREAT",
+    Trigger = "TRIGGER",
+    Truncate = "TRUNCATE",
+    Trim = "TRIM",
+    TrimArray = "TRIM_ARRAY",
+    True = "TRUE",
+    Type = "TYPE",
+    Uescape = "UESCAPE",
+    Unbounded = "UNBOUNDED",
+    Uncommitted = "UNCOMMITTED",
+    Union = "UNION",
+    Unique = "UNIQUE",
+    Unknown = "UNKNOWN",
+    Unnest = "UNNEST",
+    Update = "UPDATE",
+    Upper = "UPPER",
+    User = "USER",
+    Using = "USING",
+    Uuid = "UUID",
+    Value = "VALUE",
+    Values = "VALUES",
+    ValueOf = "VALUE_OF",
+    VarPop = "VAR_POP",
+    VarSamp = "VAR_SAMP",
+    Varbinary = "VARBINARY",
+    Varchar = "VARCHAR",
+    Varying = "VARYING",
+    Versioning = "VERSIONING",
+    View = "VIEW",
+    Views = "VIEWS",
+    When = "WHEN",
+    Whenever = "WHENEVER",
+    Where = "WHERE",
+    WidthBucket = "WIDTH_BUCKET",
+    Window = "WINDOW",
+    With = "WITH",
+    Within = "WITHIN",
+    Without = "WITHOUT",
+    Write = "WRITE",
+    Work = "WORK",
+    Year = "YEAR",
+    Zone = "ZONE",
+    EndExec = "END-EXEC"
 );
 
 /// These keywords can't be used as a table alias, so that `FROM table_name alias`
 /// can be parsed unambiguously without looking ahead.
-pub const RESERVED_FOR_TABLE_ALIAS: &[&str] = &[
+pub const RESERVED_FOR_TABLE_ALIAS: &[Keyword] = &[
     // Reserved as both a table and a column alias:
-    WITH, SELECT, WHERE, GROUP, HAVING, ORDER, LIMIT, OFFSET, FETCH, UNION, EXCEPT, INTERSECT,
+    Keyword::With,
+    Keyword::Select,
+    Keyword::Where,
+    Keyword::Group,
+    Keyword::Having,
+    Keyword::Order,
+    Keyword::Limit,
+    Keyword::Offset,
+    Keyword::Fetch,
+    Keyword::Union,
+    Keyword::Except,
+    Keyword::Intersect,
     // Reserved only as a table alias in the `FROM`/`JOIN` clauses:
-    ON, JOIN, INNER, CROSS, FULL, LEFT, RIGHT, NATURAL, USING,
+    Keyword::On,
+    Keyword::Join,
+    Keyword::Inner,
+    Keyword::Cross,
+    Keyword::Full,
+    Keyword::Left,
+    Keyword::Right,
+    Keyword::Natural,
+    Keyword::Using,
     // for MSSQL-specific OUTER APPLY (seems reserved in most dialects)
-    OUTER,
+    Keyword::Outer,
 ];
 
 /// Can't be used as a column alias, so that `SELECT <expr> alias`
 /// can be parsed unambiguously without looking ahead.
-pub const RESERVED_FOR_COLUMN_ALIAS: &[&str] = &[
+pub const RESERVED_FOR_COLUMN_ALIAS: &[Keyword] = &[
     // Reserved as both a table and a column alias:
-    WITH, SELECT, WHERE, GROUP, HAVING, ORDER, LIMIT, OFFSET, FETCH, UNION, EXCEPT, INTERSECT,
+    Keyword::With,
+    Keyword::Select,
+    Keyword::Where,
+    Keyword::Group,
+    Keyword::Having,
+    Keyword::Order,
+    Keyword::Limit,
+    Keyword::Offset,
+    Keyword::Fetch,
+    Keyword::Union,
+    Keyword::Except,
+    Keyword::Intersect,
     // Reserved only as a column alias in the `SELECT` clause:
-    FROM,
+    Keyword::From,
 ];
@@ -19,6 +19,10 @@ mod postgresql;
 
 use std::fmt::Debug;
 
+use self::keywords::Keyword;
+use crate::ast::{Expr, Statement};
+use crate::parser::{Parser, ParserError};
+
 pub use self::ansi::AnsiDialect;
 pub use self::generic::GenericDialect;
 pub use self::mssql::MsSqlDialect;
@@ -38,4 +42,70 @@ pub trait Dialect: Debug {
     fn is_identifier_start(&self, ch: char) -> bool;
     /// Determine if a character is a valid unquoted identifier character
     fn is_identifier_part(&self, ch: char) -> bool;
+    /// Determine if a keyword is reserved, so that it can't be used as an
+    /// identifier (e.g. the name of a table or column) unless it is quoted.
+    /// The default implementation reserves no keywords.
+    fn is_reserved_keyword(&self, _keyword: Keyword) -> bool {
+        false
+    }
+    /// Determine if a keyword can't be used as a column alias without `AS`,
+    /// e.g. `FROM` in `SELECT 1 FROM t`. The default implementation reserves
+    /// the keywords that may follow the projection, and the reserved keywords.
+    fn is_reserved_for_column_alias(&self, keyword: Keyword) -> bool {
+        keywords::RESERVED_FOR_COLUMN_ALIAS.contains(&keyword) || self.is_reserved_keyword(keyword)
+    }
+    /// Determine if a keyword can't be used as a table alias without `AS`,
+    /// e.g. `JOIN` in `FROM t JOIN u`. The default implementation reserves
+    /// the keywords that may follow a table, and the reserved keywords.
+    fn is_reserved_for_table_alias(&self, keyword: Keyword) -> bool {
+        keywords::RESERVED_FOR_TABLE_ALIAS.contains(&keyword) || self.is_reserved_keyword(keyword)
+    }
+    /// Determine if scripts may contain `DELIMITER` commands, which change
+    /// the delimiter that ends statements, as in the MySQL client; see
+    /// `script::split`
+    fn supports_delimiter_command(&self) -> bool {
+        false
+    }
+    /// Determine if `GO` on a line of its own separates batches of statements
+    /// in scripts, as in the MS SQL tools; see `script::split`
+    fn supports_go_batch_separator(&self) -> bool {
+        false
+    }
+    /// Determine if a backslash starts a meta-command that extends to the end
+    /// of the line in scripts, as in psql; see `script::split`
+    fn supports_backslash_commands(&self) -> bool {
+        false
+    }
+    /// Parse a dialect-specific statement. Called before the parser tries
+    /// any of the standard statements; return `None` to fall back to them,
+    /// leaving the parser where it was.
+    fn parse_statement(&self, _parser: &mut Parser) -> Option<Result<Statement, ParserError>> {
+        None
+    }
+    /// Parse a dialect-specific expression prefix, i.e. an expression that is
+    /// not an infix operation. Called before the parser tries any of the
+    /// standard prefixes; return `None` to fall back to them, leaving the
+    /// parser where it was.
+    fn parse_prefix(&self, _parser: &mut Parser) -> Option<Result<Expr, ParserError>> {
+        None
+    }
+    /// Parse a dialect-specific infix operation whose left-hand side is
+    /// `expr`. The operator is the next token, and `precedence` is the one
+    /// returned for it by `get_next_precedence`. Return `None` to fall back to
+    /// the standard operators, leaving the parser where it was.
+    fn parse_infix(
+        &self,
+        _parser: &mut Parser,
+        _expr: &Expr,
+        _precedence: u8,
+    ) -> Option<Result<Expr, ParserError>> {
+        None
+    }
+    /// Determine the precedence of the next token when it is used as an
+    /// infix operator, or zero if it is not one. Dialect-specific operators
+    /// must be given a non-zero precedence here for `parse_infix` to be
+    /// called for them. Return `None` to use the standard precedences.
+    fn get_next_precedence(&self, _parser: &Parser) -> Option<Result<u8, ParserError>> {
+        None
+    }
 }
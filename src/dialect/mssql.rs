@@ -10,6 +10,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::dialect::keywords::Keyword;
 use crate::dialect::Dialect;
 
 #[derive(Debug)]
@@ -23,16 +24,144 @@ impl Dialect for MsSqlDialect {
     fn is_identifier_start(&self, ch: char) -> bool {
         // See https://docs.microsoft.com/en-us/sql/relational-databases/databases/database-identifiers?view=sql-server-2017#rules-for-regular-identifiers
         // We don't support non-latin "letters" currently.
-        (ch >= 'a' && ch <= 'z') || (ch >= 'A' && ch <= 'Z') || ch == '_' || ch == '#' || ch == '@'
+        ch.is_ascii_lowercase() || ch.is_ascii_uppercase() || ch == '_' || ch == '#' || ch == '@'
     }
 
     fn is_identifier_part(&self, ch: char) -> bool {
-        (ch >= 'a' && ch <= 'z')
-            || (ch >= 'A' && ch <= 'Z')
-            || (ch >= '0' && ch <= '9')
+        ch.is_ascii_lowercase()
+            || ch.is_ascii_uppercase()
+            || ch.is_ascii_digit()
             || ch == '@'
             || ch == '$'
             || ch == '#'
             || ch == '_'
     }
+    fn is_reserved_keyword(&self, keyword: Keyword) -> bool {
+        RESERVED_KEYWORDS.contains(&keyword)
+    }
+
+    fn supports_go_batch_separator(&self) -> bool {
+        true
+    }
 }
+
+/// The keywords MS SQL reserves, among the ones the tokenizer recognizes.
+/// See https://docs.microsoft.com/en-us/sql/t-sql/language-elements/reserved-keywords-transact-sql
+const RESERVED_KEYWORDS: &[Keyword] = &[
+    Keyword::Add,
+    Keyword::All,
+    Keyword::Alter,
+    Keyword::And,
+    Keyword::Any,
+    Keyword::As,
+    Keyword::Asc,
+    Keyword::Authorization,
+    Keyword::Begin,
+    Keyword::Between,
+    Keyword::By,
+    Keyword::Cascade,
+    Keyword::Case,
+    Keyword::Check,
+    Keyword::Close,
+    Keyword::Coalesce,
+    Keyword::Collate,
+    Keyword::Column,
+    Keyword::Commit,
+    Keyword::Constraint,
+    Keyword::Contains,
+    Keyword::Convert,
+    Keyword::Create,
+    Keyword::Cross,
+    Keyword::Current,
+    Keyword::CurrentDate,
+    Keyword::CurrentTime,
+    Keyword::CurrentTimestamp,
+    Keyword::CurrentUser,
+    Keyword::Cursor,
+    Keyword::Deallocate,
+    Keyword::Declare,
+    Keyword::Default,
+    Keyword::Delete,
+    Keyword::Desc,
+    Keyword::Distinct,
+    Keyword::Double,
+    Keyword::Drop,
+    Keyword::Else,
+    Keyword::End,
+    Keyword::Escape,
+    Keyword::Except,
+    Keyword::Exec,
+    Keyword::Execute,
+    Keyword::Exists,
+    Keyword::External,
+    Keyword::Fetch,
+    Keyword::For,
+    Keyword::Foreign,
+    Keyword::From,
+    Keyword::Full,
+    Keyword::Function,
+    Keyword::Grant,
+    Keyword::Group,
+    Keyword::Having,
+    Keyword::Identity,
+    Keyword::If,
+    Keyword::In,
+    Keyword::Index,
+    Keyword::Inner,
+    Keyword::Insert,
+    Keyword::Intersect,
+    Keyword::Into,
+    Keyword::Is,
+    Keyword::Join,
+    Keyword::Key,
+    Keyword::Left,
+    Keyword::Like,
+    Keyword::Merge,
+    Keyword::National,
+    Keyword::Not,
+    Keyword::Null,
+    Keyword::Nullif,
+    Keyword::Of,
+    Keyword::On,
+    Keyword::Open,
+    Keyword::Or,
+    Keyword::Order,
+    Keyword::Outer,
+    Keyword::Over,
+    Keyword::Percent,
+    Keyword::Plan,
+    Keyword::Precision,
+    Keyword::Primary,
+    Keyword::Procedure,
+    Keyword::Read,
+    Keyword::References,
+    Keyword::Restrict,
+    Keyword::Return,
+    Keyword::Revoke,
+    Keyword::Right,
+    Keyword::Rollback,
+    Keyword::Schema,
+    Keyword::Select,
+    Keyword::SessionUser,
+    Keyword::Set,
+    Keyword::Some,
+    Keyword::SystemUser,
+    Keyword::Table,
+    Keyword::Tablesample,
+    Keyword::Then,
+    Keyword::To,
+    Keyword::Transaction,
+    Keyword::Trigger,
+    Keyword::Truncate,
+    Keyword::Union,
+    Keyword::Unique,
+    Keyword::Update,
+    Keyword::User,
+    Keyword::Values,
+    Keyword::Varying,
+    Keyword::View,
+    Keyword::When,
+    Keyword::Where,
+    Keyword::With,
+    Keyword::Within,
+];
@@ -10,6 +10,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::dialect::keywords::Keyword;
 use crate::dialect::Dialect;
 
 #[derive(Debug)]
@@ -20,14 +21,184 @@ impl Dialect for MySqlDialect {
         // See https://dev.mysql.com/doc/refman/8.0/en/identifiers.html.
         // We don't yet support identifiers beginning with numbers, as that
         // makes it hard to distinguish numeric literals.
-        (ch >= 'a' && ch <= 'z')
-            || (ch >= 'A' && ch <= 'Z')
+        ch.is_ascii_lowercase()
+            || ch.is_ascii_uppercase()
             || ch == '_'
             || ch == '$'
-            || (ch >= '\u{0080}' && ch <= '\u{ffff}')
+            || ('\u{0080}'..='\u{ffff}').contains(&ch)
     }
 
     fn is_identifier_part(&self, ch: char) -> bool {
-        self.is_identifier_start(ch) || (ch >= '0' && ch <= '9')
+        self.is_identifier_start(ch) || ch.is_ascii_digit()
+    }
+    fn is_reserved_keyword(&self, keyword: Keyword) -> bool {
+        RESERVED_KEYWORDS.contains(&keyword)
+    }
+
+    fn supports_delimiter_command(&self) -> bool {
+        true
     }
 }
+
+/// The keywords MySQL reserves, among the ones the tokenizer recognizes.
+/// See https://dev.mysql.com/doc/refman/8.0/en/keywords.html
+const RESERVED_KEYWORDS: &[Keyword] = &[
+    Keyword::Add,
+    Keyword::All,
+    Keyword::Alter,
+    Keyword::And,
+    Keyword::As,
+    Keyword::Asc,
+    Keyword::Asensitive,
+    Keyword::Between,
+    Keyword::Bigint,
+    Keyword::Binary,
+    Keyword::Blob,
+    Keyword::Both,
+    Keyword::By,
+    Keyword::Call,
+    Keyword::Cascade,
+    Keyword::Case,
+    Keyword::Char,
+    Keyword::Character,
+    Keyword::Check,
+    Keyword::Collate,
+    Keyword::Column,
+    Keyword::Condition,
+    Keyword::Constraint,
+    Keyword::Convert,
+    Keyword::Create,
+    Keyword::Cross,
+    Keyword::Cube,
+    Keyword::CumeDist,
+    Keyword::CurrentDate,
+    Keyword::CurrentTime,
+    Keyword::CurrentTimestamp,
+    Keyword::CurrentUser,
+    Keyword::Cursor,
+    Keyword::Dec,
+    Keyword::Decimal,
+    Keyword::Declare,
+    Keyword::Default,
+    Keyword::Delete,
+    Keyword::DenseRank,
+    Keyword::Desc,
+    Keyword::Describe,
+    Keyword::Deterministic,
+    Keyword::Distinct,
+    Keyword::Double,
+    Keyword::Drop,
+    Keyword::Each,
+    Keyword::Else,
+    Keyword::Except,
+    Keyword::Exists,
+    Keyword::Explain,
+    Keyword::False,
+    Keyword::Fetch,
+    Keyword::FirstValue,
+    Keyword::Float,
+    Keyword::For,
+    Keyword::Foreign,
+    Keyword::From,
+    Keyword::Function,
+    Keyword::Get,
+    Keyword::Grant,
+    Keyword::Group,
+    Keyword::Grouping,
+    Keyword::Groups,
+    Keyword::Having,
+    Keyword::If,
+    Keyword::In,
+    Keyword::Index,
+    Keyword::Inner,
+    Keyword::Inout,
+    Keyword::Insensitive,
+    Keyword::Insert,
+    Keyword::Int,
+    Keyword::Integer,
+    Keyword::Intersect,
+    Keyword::Interval,
+    Keyword::Into,
+    Keyword::Is,
+    Keyword::Join,
+    Keyword::Key,
+    Keyword::Keys,
+    Keyword::Lag,
+    Keyword::LastValue,
+    Keyword::Lateral,
+    Keyword::Lead,
+    Keyword::Leading,
+    Keyword::Left,
+    Keyword::Like,
+    Keyword::Limit,
+    Keyword::Localtime,
+    Keyword::Localtimestamp,
+    Keyword::Match,
+    Keyword::Mod,
+    Keyword::Modifies,
+    Keyword::Natural,
+    Keyword::Not,
+    Keyword::NthValue,
+    Keyword::Ntile,
+    Keyword::Null,
+    Keyword::Numeric,
+    Keyword::Of,
+    Keyword::On,
+    Keyword::Or,
+    Keyword::Order,
+    Keyword::Out,
+    Keyword::Outer,
+    Keyword::Over,
+    Keyword::Partition,
+    Keyword::PercentRank,
+    Keyword::Precision,
+    Keyword::Primary,
+    Keyword::Procedure,
+    Keyword::Range,
+    Keyword::Rank,
+    Keyword::Read,
+    Keyword::Reads,
+    Keyword::Real,
+    Keyword::Recursive,
+    Keyword::References,
+    Keyword::Release,
+    Keyword::Restrict,
+    Keyword::Return,
+    Keyword::Revoke,
+    Keyword::Right,
+    Keyword::Row,
+    Keyword::Rows,
+    Keyword::RowNumber,
+    Keyword::Schema,
+    Keyword::Select,
+    Keyword::Sensitive,
+    Keyword::Set,
+    Keyword::Show,
+    Keyword::Smallint,
+    Keyword::Specific,
+    Keyword::Sql,
+    Keyword::Sqlexception,
+    Keyword::Sqlstate,
+    Keyword::Sqlwarning,
+    Keyword::Stored,
+    Keyword::System,
+    Keyword::Table,
+    Keyword::Then,
+    Keyword::To,
+    Keyword::Trailing,
+    Keyword::Trigger,
+    Keyword::True,
+    Keyword::Union,
+    Keyword::Unique,
+    Keyword::Update,
+    Keyword::Using,
+    Keyword::Values,
+    Keyword::Varbinary,
+    Keyword::Varchar,
+    Keyword::Varying,
+    Keyword::When,
+    Keyword::Where,
+    Keyword::Window,
+    Keyword::With,
+    Keyword::Write,
+];
@@ -10,6 +10,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::dialect::keywords::Keyword;
 use crate::dialect::Dialect;
 
 #[derive(Debug)]
@@ -20,14 +21,95 @@ impl Dialect for PostgreSqlDialect {
         // See https://www.postgresql.org/docs/11/sql-syntax-lexical.html#SQL-SYNTAX-IDENTIFIERS
         // We don't yet support identifiers beginning with "letters with
         // diacritical marks and non-Latin letters"
-        (ch >= 'a' && ch <= 'z') || (ch >= 'A' && ch <= 'Z') || ch == '_'
+        ch.is_ascii_lowercase() || ch.is_ascii_uppercase() || ch == '_'
     }
 
     fn is_identifier_part(&self, ch: char) -> bool {
-        (ch >= 'a' && ch <= 'z')
-            || (ch >= 'A' && ch <= 'Z')
-            || (ch >= '0' && ch <= '9')
+        ch.is_ascii_lowercase()
+            || ch.is_ascii_uppercase()
+            || ch.is_ascii_digit()
             || ch == '$'
             || ch == '_'
     }
+    fn is_reserved_keyword(&self, keyword: Keyword) -> bool {
+        RESERVED_KEYWORDS.contains(&keyword)
+    }
+
+    fn supports_backslash_commands(&self) -> bool {
+        true
+    }
 }
+
+/// The keywords PostgreSQL reserves, among the ones the tokenizer recognizes.
+/// See https://www.postgresql.org/docs/current/sql-keywords-appendix.html
+const RESERVED_KEYWORDS: &[Keyword] = &[
+    Keyword::All,
+    Keyword::And,
+    Keyword::Any,
+    Keyword::Array,
+    Keyword::As,
+    Keyword::Asc,
+    Keyword::Asymmetric,
+    Keyword::Both,
+    Keyword::Case,
+    Keyword::Cast,
+    Keyword::Check,
+    Keyword::Collate,
+    Keyword::Column,
+    Keyword::Constraint,
+    Keyword::Create,
+    Keyword::CurrentCatalog,
+    Keyword::CurrentDate,
+    Keyword::CurrentRole,
+    Keyword::CurrentTime,
+    Keyword::CurrentTimestamp,
+    Keyword::CurrentUser,
+    Keyword::Default,
+    Keyword::Desc,
+    Keyword::Distinct,
+    Keyword::Else,
+    Keyword::End,
+    Keyword::Except,
+    Keyword::False,
+    Keyword::Fetch,
+    Keyword::For,
+    Keyword::Foreign,
+    Keyword::From,
+    Keyword::Grant,
+    Keyword::Group,
+    Keyword::Having,
+    Keyword::In,
+    Keyword::Intersect,
+    Keyword::Into,
+    Keyword::Lateral,
+    Keyword::Leading,
+    Keyword::Limit,
+    Keyword::Localtime,
+    Keyword::Localtimestamp,
+    Keyword::Not,
+    Keyword::Null,
+    Keyword::Offset,
+    Keyword::On,
+    Keyword::Only,
+    Keyword::Or,
+    Keyword::Order,
+    Keyword::Primary,
+    Keyword::References,
+    Keyword::Select,
+    Keyword::SessionUser,
+    Keyword::Some,
+    Keyword::Symmetric,
+    Keyword::Table,
+    Keyword::Then,
+    Keyword::To,
+    Keyword::Trailing,
+    Keyword::True,
+    Keyword::Union,
+    Keyword::Unique,
+    Keyword::User,
+    Keyword::Using,
+    Keyword::When,
+    Keyword::Where,
+    Keyword::Window,
+    Keyword::With,
+];
@@ -0,0 +1,751 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! SQL pretty-printer
+//!
+//! The `Display` implementations of the AST produce a single line of SQL.
+//! `format_statement` lays that SQL out over several lines, according to
+//! `FormatOptions`. Only the whitespace between tokens and the case of
+//! keywords are changed, so the result parses to the same AST.
+
+use std::collections::HashSet;
+
+use crate::ast::visit::Visit;
+use crate::ast::{DataType, Ident, Statement};
+use crate::dialect::keywords::Keyword;
+use crate::dialect::{Dialect, GenericDialect};
+use crate::tokenizer::{Token, Tokenizer};
+
+/// The case in which `format_statement` writes keywords
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeywordCase {
+    Upper,
+    Lower,
+    /// As written by `Display`, i.e. in upper case
+    Preserve,
+}
+
+/// Where `format_statement` puts the commas of a list whose items are on
+/// separate lines
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommaStyle {
+    /// At the end of each item but the last
+    Trailing,
+    /// At the start of each item but the first
+    Leading,
+}
+
+/// Options for `format_statement`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatOptions {
+    /// The number of spaces per level of indentation
+    pub indent: usize,
+    /// The width lines should not exceed. Lists and parenthesized expressions
+    /// that do not fit are split over several lines, but lines can still be
+    /// longer if they have no such places to split.
+    pub max_width: usize,
+    pub keyword_case: KeywordCase,
+    pub comma_style: CommaStyle,
+    /// Whether to start each clause of a query (`FROM`, `WHERE`, etc.) on a
+    /// new line, and each subquery with several clauses on lines of its own.
+    /// Otherwise clauses are only put on new lines when they do not fit.
+    pub clause_per_line: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        FormatOptions {
+            indent: 4,
+            max_width: 80,
+            keyword_case: KeywordCase::Upper,
+            comma_style: CommaStyle::Trailing,
+            clause_per_line: true,
+        }
+    }
+}
+
+/// Format `statement` as SQL laid out according to `options`
+pub fn format_statement(statement: &Statement, options: &FormatOptions) -> String {
+    let sql = statement.to_string();
+    if let Statement::Copy { .. } = statement {
+        // The data of `COPY ... FROM STDIN` must be left as is
+        return sql;
+    }
+    let tokens = match Tokenizer::new(&FormatterDialect {}, &sql).tokenize_with_span() {
+        Ok(tokens) => tokens,
+        // This can only happen for extension nodes, which may display
+        // anything; leave their SQL alone.
+        Err(_) => return sql,
+    };
+
+    let mut idents = IdentCollector::default();
+    idents.visit_statement(statement);
+
+    let mut atoms = vec![];
+    let mut prev_end = 0;
+    for token in tokens {
+        if let Token::Whitespace(_) = token.token {
+            continue;
+        }
+        let (start, end) = (token.span.start.offset, token.span.end.offset);
+        let keyword = match &token.token {
+            Token::Word(w) if w.quote_style.is_none() && w.keyword != Keyword::NoKeyword => {
+                Some(w.keyword)
+            }
+            _ => None,
+        };
+        atoms.push(Atom {
+            ws: &sql[prev_end..start],
+            text: &sql[start..end],
+            // Identifiers are case sensitive, even if they are keywords
+            recase: keyword.is_some() && !idents.values.contains(&sql[start..end]),
+            keyword,
+            token: token.token,
+        });
+        prev_end = end;
+    }
+
+    let mut atoms = atoms.into_iter().peekable();
+    let pieces = parse_pieces(&mut atoms);
+    if atoms.peek().is_some() {
+        // Unbalanced parentheses, which only extension nodes can produce
+        return sql;
+    }
+
+    let mut printer = Printer {
+        out: String::new(),
+        column: 0,
+        options,
+    };
+    printer.print_clauses(&split_clauses(pieces), 0);
+    printer.out
+}
+
+/// The dialect used to tokenize the `Display` output of the AST, which
+/// accepts the identifiers of all the dialects
+#[derive(Debug)]
+struct FormatterDialect {}
+
+impl Dialect for FormatterDialect {
+    fn is_delimited_identifier_start(&self, ch: char) -> bool {
+        ch == '"' || ch == '`'
+    }
+
+    fn is_identifier_start(&self, ch: char) -> bool {
+        GenericDialect {}.is_identifier_start(ch)
+    }
+
+    fn is_identifier_part(&self, ch: char) -> bool {
+        GenericDialect {}.is_identifier_part(ch)
+    }
+}
+
+/// Collects the values of the unquoted identifiers in the AST, which must
+/// not be recased even if they are keywords. `[bracketed]` identifiers are
+/// included, as `FormatterDialect` does not treat brackets as quotes.
+#[derive(Default)]
+struct IdentCollector<'ast> {
+    values: HashSet<&'ast str>,
+}
+
+impl<'ast> Visit<'ast> for IdentCollector<'ast> {
+    fn visit_ident(&mut self, ident: &'ast Ident) {
+        if ident.quote_style.is_none() || ident.quote_style == Some('[') {
+            self.values.insert(&ident.value);
+        }
+    }
+
+    fn visit_type(&mut self, data_type: &'ast DataType) {
+        match data_type {
+            DataType::Custom(name) => self.visit_object_name(name),
+            DataType::Array(data_type) => self.visit_type(data_type),
+            _ => (),
+        }
+    }
+}
+
+/// A token, along with the whitespace that precedes it
+struct Atom<'a> {
+    ws: &'a str,
+    text: &'a str,
+    token: Token<'a>,
+    /// The keyword this token is, if it is an unquoted keyword
+    keyword: Option<Keyword>,
+    /// Whether `FormatOptions::keyword_case` applies to this token
+    recase: bool,
+}
+
+impl<'a> Atom<'a> {
+    fn is_keyword(&self, keyword: Keyword) -> bool {
+        self.keyword == Some(keyword)
+    }
+}
+
+enum Piece<'a> {
+    Atom(Atom<'a>),
+    Group(Group<'a>),
+}
+
+impl<'a> Piece<'a> {
+    fn keyword(&self) -> Option<Keyword> {
+        match self {
+            Piece::Atom(atom) => atom.keyword,
+            Piece::Group(_) => None,
+        }
+    }
+
+    fn ws(&self) -> &'a str {
+        match self {
+            Piece::Atom(atom) => atom.ws,
+            Piece::Group(group) => group.open.ws,
+        }
+    }
+}
+
+/// A parenthesized part of the SQL
+struct Group<'a> {
+    open: Atom<'a>,
+    body: Body<'a>,
+    close: Option<Atom<'a>>,
+}
+
+enum Body<'a> {
+    /// A subquery, laid out like a statement
+    Query(Vec<Clause<'a>>),
+    /// Anything else, such as function arguments or a column list
+    List(Vec<Item<'a>>),
+}
+
+/// A clause of a statement, e.g. `WHERE a = 1`, with the keywords that start
+/// it (which are missing from the first clause of most statements) and the
+/// comma-separated items that follow them
+#[derive(Default)]
+struct Clause<'a> {
+    head: Vec<Atom<'a>>,
+    items: Vec<Item<'a>>,
+}
+
+type Item<'a> = Vec<Piece<'a>>;
+
+/// Group the atoms up to the first unmatched closing parenthesis
+fn parse_pieces<'a, I>(atoms: &mut std::iter::Peekable<I>) -> Vec<Piece<'a>>
+where
+    I: Iterator<Item = Atom<'a>>,
+{
+    let mut pieces = vec![];
+    while let Some(atom) = atoms.peek() {
+        match atom.token {
+            Token::RParen => break,
+            Token::LParen => {
+                let open = atoms.next().unwrap();
+                let inner = parse_pieces(atoms);
+                let close = atoms.next();
+                let is_query = match inner.first() {
+                    Some(Piece::Group(group)) => match group.body {
+                        Body::Query(_) => true,
+                        Body::List(_) => false,
+                    },
+                    Some(piece) => matches!(
+                        piece.keyword(),
+                        Some(Keyword::Select) | Some(Keyword::With) | Some(Keyword::Values)
+                    ),
+                    None => false,
+                };
+                let body = if is_query {
+                    Body::Query(split_clauses(inner))
+                } else {
+                    Body::List(split_items(inner))
+                };
+                pieces.push(Piece::Group(Group { open, body, close }));
+            }
+            _ => pieces.push(Piece::Atom(atoms.next().unwrap())),
+        }
+    }
+    pieces
+}
+
+/// Split `pieces` at the commas
+fn split_items(pieces: Vec<Piece>) -> Vec<Item> {
+    let mut items = vec![];
+    let mut item = vec![];
+    for piece in pieces {
+        match piece {
+            Piece::Atom(Atom {
+                token: Token::Comma,
+                ..
+            }) => items.push(std::mem::take(&mut item)),
+            piece => item.push(piece),
+        }
+    }
+    if !item.is_empty() || !items.is_empty() {
+        items.push(item);
+    }
+    items
+}
+
+const JOIN_MODIFIERS: &[Keyword] = &[
+    Keyword::Natural,
+    Keyword::Inner,
+    Keyword::Left,
+    Keyword::Right,
+    Keyword::Full,
+    Keyword::Cross,
+    Keyword::Outer,
+];
+
+/// Split `pieces`, a statement or subquery, into clauses
+fn split_clauses(pieces: Vec<Piece>) -> Vec<Clause> {
+    // Find where each clause starts, and the number of keywords that start it
+    let mut heads = vec![];
+    let mut i = 0;
+    while i < pieces.len() {
+        match clause_head_len(&pieces, i) {
+            Some(len) => {
+                heads.push((i, len));
+                i += len;
+            }
+            None => i += 1,
+        }
+    }
+
+    let mut clauses = vec![(Clause::default(), vec![])];
+    let mut heads = heads.into_iter().peekable();
+    let mut pieces = pieces.into_iter().enumerate();
+    while let Some((i, piece)) = pieces.next() {
+        match heads.peek() {
+            Some(&(start, len)) if start == i => {
+                heads.next();
+                let head: Vec<Atom> = std::iter::once(piece)
+                    .chain(pieces.by_ref().take(len - 1).map(|(_, piece)| piece))
+                    .map(|piece| match piece {
+                        Piece::Atom(atom) => atom,
+                        Piece::Group(_) => unreachable!(),
+                    })
+                    .collect();
+                let is_set_operation = [Keyword::Union, Keyword::Except, Keyword::Intersect]
+                    .iter()
+                    .any(|&k| head[0].is_keyword(k));
+                clauses.push((
+                    Clause {
+                        head,
+                        items: vec![],
+                    },
+                    vec![],
+                ));
+                if is_set_operation {
+                    // What follows the operator is not part of it
+                    clauses.push((Clause::default(), vec![]));
+                }
+            }
+            _ => clauses.last_mut().unwrap().1.push(piece),
+        }
+    }
+
+    clauses
+        .into_iter()
+        .filter_map(|(mut clause, body)| {
+            let is_list = clause.head.first().is_some_and(|atom| {
+                [
+                    Keyword::Select,
+                    Keyword::From,
+                    Keyword::Group,
+                    Keyword::Order,
+                    Keyword::Values,
+                    Keyword::With,
+                    Keyword::Set,
+                ]
+                .iter()
+                .any(|&k| atom.is_keyword(k))
+            });
+            if is_list {
+                clause.items = split_items(body);
+            } else if !body.is_empty() {
+                clause.items = vec![body];
+            } else if clause.head.is_empty() {
+                return None;
+            }
+            Some(clause)
+        })
+        .collect()
+}
+
+/// The number of keywords that start a clause at `pieces[i]`, if any
+fn clause_head_len(pieces: &[Piece], i: usize) -> Option<usize> {
+    let next_is = |keywords: &[Keyword]| {
+        pieces
+            .get(i + 1)
+            .and_then(Piece::keyword)
+            .is_some_and(|k| keywords.contains(&k))
+    };
+    match pieces[i].keyword()? {
+        Keyword::Select if next_is(&[Keyword::All, Keyword::Distinct]) => Some(2),
+        Keyword::Union | Keyword::Except | Keyword::Intersect if next_is(&[Keyword::All]) => {
+            Some(2)
+        }
+        Keyword::Select
+        | Keyword::From
+        | Keyword::Where
+        | Keyword::Having
+        | Keyword::Limit
+        | Keyword::Offset
+        | Keyword::Fetch
+        | Keyword::Values
+        | Keyword::Union
+        | Keyword::Except
+        | Keyword::Intersect => Some(1),
+        Keyword::Group | Keyword::Order if next_is(&[Keyword::By]) => Some(2),
+        Keyword::With if i == 0 => Some(1),
+        Keyword::Set if pieces[0].keyword() == Some(Keyword::Update) => Some(1),
+        _ => {
+            let modifiers = pieces[i..]
+                .iter()
+                .take_while(|p| p.keyword().is_some_and(|k| JOIN_MODIFIERS.contains(&k)))
+                .count();
+            match pieces.get(i + modifiers).and_then(Piece::keyword) {
+                Some(Keyword::Join) => Some(modifiers + 1),
+                _ => None,
+            }
+        }
+    }
+}
+
+struct Printer<'o> {
+    out: String,
+    /// The number of characters on the last line of `out`
+    column: usize,
+    options: &'o FormatOptions,
+}
+
+impl<'o> Printer<'o> {
+    fn write(&mut self, text: &str) {
+        self.out.push_str(text);
+        self.column = match text.rfind('\n') {
+            Some(i) => text[i + 1..].chars().count(),
+            None => self.column + text.chars().count(),
+        };
+    }
+
+    fn newline(&mut self, level: usize) {
+        let indent = level * self.options.indent;
+        self.out.push('\n');
+        self.out.extend(std::iter::repeat_n(' ', indent));
+        self.column = indent;
+    }
+
+    /// Whether `text` fits on the current line
+    fn fits(&self, text: &str) -> bool {
+        !text.contains('\n') && self.column + text.chars().count() <= self.options.max_width
+    }
+
+    /// Whether a part of the SQL that contains `pieces` can be written on a
+    /// single line, if it fits
+    fn can_inline(&self, pieces: &[Piece]) -> bool {
+        !(self.options.clause_per_line && pieces.iter().any(has_multi_clause_query))
+    }
+
+    fn print_clauses(&mut self, clauses: &[Clause], level: usize) {
+        let mut prev_multi_line = false;
+        for (i, clause) in clauses.iter().enumerate() {
+            if i > 0 {
+                let inline = format!(" {}", self.inline_clause(clause));
+                if self.options.clause_per_line || prev_multi_line || !self.fits(&inline) {
+                    self.newline(level);
+                } else {
+                    self.write(" ");
+                }
+            }
+            let start = self.out.len();
+            self.print_clause(clause, level);
+            prev_multi_line = self.out[start..].contains('\n');
+        }
+    }
+
+    fn print_clause(&mut self, clause: &Clause, level: usize) {
+        let inline = self.inline_clause(clause);
+        if self.fits(&inline) && clause.items.iter().all(|item| self.can_inline(item)) {
+            self.write(&inline);
+            return;
+        }
+
+        let head = self.inline_atoms(&clause.head);
+        self.write(&head);
+        match clause.items.as_slice() {
+            [] => (),
+            [item] => {
+                if !head.is_empty() {
+                    self.write(" ");
+                }
+                self.print_item(item, level);
+            }
+            items => self.print_list(items, level + 1),
+        }
+    }
+
+    /// Print `items` on separate lines
+    fn print_list(&mut self, items: &[Item], level: usize) {
+        for (i, item) in items.iter().enumerate() {
+            self.newline(level);
+            if i > 0 && self.options.comma_style == CommaStyle::Leading {
+                self.write(", ");
+            }
+            self.print_item(item, level);
+            if i < items.len() - 1 && self.options.comma_style == CommaStyle::Trailing {
+                self.write(",");
+            }
+        }
+    }
+
+    fn print_item(&mut self, item: &[Piece], level: usize) {
+        for (i, piece) in item.iter().enumerate() {
+            if i > 0 {
+                self.write(piece.ws());
+            }
+            match piece {
+                Piece::Atom(atom) => {
+                    let text = self.atom_text(atom);
+                    self.write(&text)
+                }
+                Piece::Group(group) => self.print_group(group, level),
+            }
+        }
+    }
+
+    fn print_group(&mut self, group: &Group, level: usize) {
+        let inline = self.inline_group(group);
+        let (can_inline, is_empty) = match &group.body {
+            Body::Query(clauses) => (!self.options.clause_per_line || clauses.len() < 2, false),
+            Body::List(items) => (
+                items.iter().all(|item| self.can_inline(item)),
+                items.is_empty(),
+            ),
+        };
+        if can_inline && (is_empty || self.fits(&inline)) {
+            self.write(&inline);
+            return;
+        }
+
+        self.write(group.open.text);
+        match &group.body {
+            Body::Query(clauses) => {
+                self.newline(level + 1);
+                self.print_clauses(clauses, level + 1);
+            }
+            Body::List(items) => self.print_list(items, level + 1),
+        }
+        if let Some(close) = &group.close {
+            self.newline(level);
+            self.write(close.text);
+        }
+    }
+
+    fn atom_text(&self, atom: &Atom) -> String {
+        match self.options.keyword_case {
+            KeywordCase::Upper if atom.recase => atom.text.to_uppercase(),
+            KeywordCase::Lower if atom.recase => atom.text.to_lowercase(),
+            _ => atom.text.to_string(),
+        }
+    }
+
+    /// The text of `atoms` on a single line, without the leading whitespace
+    fn inline_atoms(&self, atoms: &[Atom]) -> String {
+        let mut out = String::new();
+        for (i, atom) in atoms.iter().enumerate() {
+            if i > 0 {
+                out.push_str(atom.ws);
+            }
+            out.push_str(&self.atom_text(atom));
+        }
+        out
+    }
+
+    fn inline_clause(&self, clause: &Clause) -> String {
+        let mut out = self.inline_atoms(&clause.head);
+        for (i, item) in clause.items.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            match item.first() {
+                Some(piece) if i > 0 || !clause.head.is_empty() => out.push_str(piece.ws()),
+                _ => (),
+            }
+            out.push_str(&self.inline_item(item));
+        }
+        out
+    }
+
+    /// The text of `item` on a single line, without the leading whitespace
+    fn inline_item(&self, item: &[Piece]) -> String {
+        let mut out = String::new();
+        for (i, piece) in item.iter().enumerate() {
+            if i > 0 {
+                out.push_str(piece.ws());
+            }
+            match piece {
+                Piece::Atom(atom) => out.push_str(&self.atom_text(atom)),
+                Piece::Group(group) => out.push_str(&self.inline_group(group)),
+            }
+        }
+        out
+    }
+
+    fn inline_group(&self, group: &Group) -> String {
+        let mut out = group.open.text.to_string();
+        match &group.body {
+            Body::Query(clauses) => {
+                for (i, clause) in clauses.iter().enumerate() {
+                    if i > 0 {
+                        out.push_str(clause_ws(clause));
+                    }
+                    out.push_str(&self.inline_clause(clause));
+                }
+            }
+            Body::List(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                        out.push_str(item.first().map_or("", Piece::ws));
+                    }
+                    out.push_str(&self.inline_item(item));
+                }
+            }
+        }
+        if let Some(close) = &group.close {
+            out.push_str(close.ws);
+            out.push_str(close.text);
+        }
+        out
+    }
+}
+
+/// The whitespace that precedes `clause`
+fn clause_ws<'a>(clause: &Clause<'a>) -> &'a str {
+    match (
+        clause.head.first(),
+        clause.items.first().and_then(|i| i.first()),
+    ) {
+        (Some(atom), _) => atom.ws,
+        (None, Some(piece)) => piece.ws(),
+        (None, None) => "",
+    }
+}
+
+/// Whether `piece` contains a subquery with several clauses
+fn has_multi_clause_query(piece: &Piece) -> bool {
+    match piece {
+        Piece::Atom(_) => false,
+        Piece::Group(group) => match &group.body {
+            Body::Query(clauses) => {
+                clauses.len() > 1
+                    || clauses
+                        .iter()
+                        .flat_map(|c| &c.items)
+                        .flatten()
+                        .any(has_multi_clause_query)
+            }
+            Body::List(items) => items.iter().flatten().any(has_multi_clause_query),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    fn format(sql: &str, options: &FormatOptions) -> String {
+        let statements = Parser::parse_sql(&GenericDialect {}, sql.to_string()).unwrap();
+        format_statement(&statements[0], options)
+    }
+
+    fn compact() -> FormatOptions {
+        FormatOptions {
+            indent: 2,
+            max_width: 40,
+            keyword_case: KeywordCase::Lower,
+            comma_style: CommaStyle::Leading,
+            clause_per_line: false,
+        }
+    }
+
+    const QUERY: &str = "select id, name, email_address, created_at, updated_at \
+                         from users u join orders o on u.id = o.user_id \
+                         where o.total > (select avg(total) from orders) \
+                         order by created_at desc limit 10";
+
+    #[test]
+    fn format_with_default_options() {
+        assert_eq!(
+            "SELECT a, b\nFROM t\nWHERE a = 1",
+            format("select a, b from t where a = 1", &FormatOptions::default())
+        );
+        assert_eq!(
+            "SELECT id, name, email_address, created_at, updated_at\n\
+             FROM users AS u\n\
+             JOIN orders AS o ON u.id = o.user_id\n\
+             WHERE o.total > (\n    \
+                 SELECT avg(total)\n    \
+                 FROM orders\n\
+             )\n\
+             ORDER BY created_at DESC\n\
+             LIMIT 10",
+            format(QUERY, &FormatOptions::default())
+        );
+        assert_eq!(
+            "WITH cte AS (\n    SELECT a\n    FROM t\n)\nSELECT *\nFROM cte",
+            format(
+                "WITH cte AS (SELECT a FROM t) SELECT * FROM cte",
+                &FormatOptions::default()
+            )
+        );
+    }
+
+    #[test]
+    fn format_with_compact_options() {
+        assert_eq!(
+            "select a, b from t where a = 1",
+            format("SELECT a, b FROM t WHERE a = 1", &compact())
+        );
+        assert_eq!(
+            "select\n  \
+               id\n  \
+               , name\n  \
+               , email_address\n  \
+               , created_at\n  \
+               , updated_at\n\
+             from users as u\n\
+             join orders as o on u.id = o.user_id\n\
+             where o.total > (\n  \
+               select avg(total) from orders\n\
+             )\n\
+             order by created_at desc limit 10",
+            format(QUERY, &compact())
+        );
+        assert_eq!(
+            "insert into t (a, b)\nvalues (1, 2), (3, 4)",
+            format("INSERT INTO t (a, b) VALUES (1, 2), (3, 4)", &compact())
+        );
+    }
+
+    #[test]
+    fn format_preserves_identifiers_and_literals() {
+        let options = compact();
+        assert_eq!(
+            "select \"FROM\", 'SELECT' from \"Select\"",
+            format("SELECT \"FROM\", 'SELECT' FROM \"Select\"", &options)
+        );
+        let options = FormatOptions {
+            keyword_case: KeywordCase::Preserve,
+            ..options
+        };
+        assert_eq!("SELECT a FROM t", format("select a from t", &options));
+    }
+}
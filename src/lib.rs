@@ -34,9 +34,13 @@
 //! ```
 #![warn(clippy::all)]
 
+pub mod analysis;
 pub mod ast;
+pub mod cst;
 pub mod dialect;
+pub mod formatter;
 pub mod parser;
+pub mod script;
 pub mod tokenizer;
 
 #[doc(hidden)]
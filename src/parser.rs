@@ -15,30 +15,134 @@
 use log::debug;
 
 use super::ast::*;
-use super::dialect::keywords;
+use super::cst::{self, NodeRange, SourceFile, SyntaxKind};
+use super::dialect::keywords::{self, Keyword};
 use super::dialect::Dialect;
 use super::tokenizer::*;
 use std::error::Error;
 use std::fmt;
+use std::ops::{Deref, DerefMut};
 
 use crate::ast::{ParsedDate, ParsedTimestamp};
 
 // Use `Parser::expected` instead, if possible
 macro_rules! parser_err {
     ($MSG:expr) => {
-        Err(ParserError::ParserError($MSG.to_string()))
+        Err(ParserError::new($MSG.to_string()))
     };
     ($($arg:tt)*) => {
-        Err(ParserError::ParserError(format!($($arg)*)))
+        Err(ParserError::new(format!($($arg)*)))
     };
 }
 
 mod datetime;
+mod stream;
+
+pub use self::stream::StatementStream;
+
+/// Keywords that start a clause of a query, at which the parser can resume
+/// after an error in the preceding clause
+const QUERY_CLAUSE_KEYWORDS: &[Keyword] = &[
+    Keyword::From,
+    Keyword::Where,
+    Keyword::Group,
+    Keyword::Having,
+    Keyword::Order,
+    Keyword::Limit,
+    Keyword::Offset,
+    Keyword::Fetch,
+    Keyword::Union,
+    Keyword::Except,
+    Keyword::Intersect,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParserErrorKind {
+    /// The SQL text could not be split into tokens
+    Tokenizer,
+    /// The tokens do not form a valid statement
+    Syntax,
+    /// Expressions, subqueries or joins are nested more deeply than the
+    /// parser's recursion limit; see `Parser::with_recursion_limit`
+    RecursionLimitExceeded,
+    /// The SQL text could not be read; see `StatementStream`
+    Io,
+}
+
+/// An error encountered while parsing SQL. The details are boxed to keep
+/// `Result<_, ParserError>` small, and are accessible through `Deref`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParserError(Box<ParserErrorDetails>);
 
+/// The details of a `ParserError`
 #[derive(Debug, Clone, PartialEq)]
-pub enum ParserError {
-    TokenizerError(String),
-    ParserError(String),
+pub struct ParserErrorDetails {
+    pub kind: ParserErrorKind,
+    /// A single-line description of the error, e.g. `Expected INTO, found: foo`
+    pub message: String,
+    /// The location of the offending token, if known
+    pub span: Span,
+    /// The alternatives the parser would have accepted instead of `found`
+    /// (empty if the error is not about an unexpected token)
+    pub expected: Vec<String>,
+    /// The unexpected token, or `None` if the parser reached the end of the
+    /// input or the error is not about an unexpected token
+    pub found: Option<Token<'static>>,
+    /// The constructs that were being parsed when the error occurred,
+    /// outermost first, e.g. `["SELECT projection", "function arguments"]`
+    pub context: Vec<&'static str>,
+    /// The line of SQL text containing the start of `span`, as set by
+    /// `with_source`. Used to display the location of the error.
+    pub source_line: Option<String>,
+}
+
+impl ParserError {
+    pub fn new<S: Into<String>>(message: S) -> Self {
+        ParserError::from(ParserErrorDetails {
+            kind: ParserErrorKind::Syntax,
+            message: message.into(),
+            span: Span::default(),
+            expected: vec![],
+            found: None,
+            context: vec![],
+            source_line: None,
+        })
+    }
+
+    /// Return this error with `source_line` extracted from `sql`, the text
+    /// that was being parsed
+    pub fn with_source(mut self, sql: &str) -> Self {
+        let offset = self.span.start.offset;
+        if !self.span.is_empty() && sql.is_char_boundary(offset) {
+            let is_newline = |c: char| c == '\n' || c == '\r';
+            let line_start = sql[..offset].rfind(is_newline).map_or(0, |i| i + 1);
+            let line_end = sql[offset..]
+                .find(is_newline)
+                .map_or(sql.len(), |i| offset + i);
+            self.source_line = Some(sql[line_start..line_end].to_string());
+        }
+        self
+    }
+}
+
+impl From<ParserErrorDetails> for ParserError {
+    fn from(details: ParserErrorDetails) -> Self {
+        ParserError(Box::new(details))
+    }
+}
+
+impl Deref for ParserError {
+    type Target = ParserErrorDetails;
+
+    fn deref(&self) -> &ParserErrorDetails {
+        &self.0
+    }
+}
+
+impl DerefMut for ParserError {
+    fn deref_mut(&mut self) -> &mut ParserErrorDetails {
+        &mut self.0
+    }
 }
 
 #[derive(PartialEq)]
@@ -56,100 +160,336 @@ use IsLateral::*;
 
 impl From<TokenizerError> for ParserError {
     fn from(e: TokenizerError) -> Self {
-        ParserError::TokenizerError(format!("{}", e))
+        let mut err = ParserError::new(e.message);
+        err.kind = ParserErrorKind::Tokenizer;
+        err.span = Span::new(e.location, e.location);
+        err
     }
 }
 
 impl fmt::Display for ParserError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "sql parser error: {}",
-            match self {
-                ParserError::TokenizerError(s) => s,
-                ParserError::ParserError(s) => s,
-            }
-        )
+        write!(f, "sql parser error: {}", self.message)?;
+        if !self.span.is_empty() {
+            write!(f, " at {}", self.span.start)?;
+        }
+        for context in &self.context {
+            write!(f, ", in {}", context)?;
+        }
+        if let Some(line) = &self.source_line {
+            // Underline the span, or the rest of its first line if it spans
+            // several lines
+            let start = self.span.start.column as usize - 1;
+            let end = if self.span.end.line == self.span.start.line {
+                self.span.end.column as usize - 1
+            } else {
+                line.chars().count()
+            };
+            let indent: String = line
+                .chars()
+                .take(start)
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect();
+            let underline = "^".repeat(std::cmp::max(end.saturating_sub(start), 1));
+            write!(f, "\n{}\n{}{}", line, indent, underline)?;
+        }
+        Ok(())
     }
 }
 
 impl Error for ParserError {}
 
+/// The case to which `ParserOptions::identifier_case` folds unquoted
+/// identifiers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdentifierCase {
+    Upper,
+    Lower,
+    /// As written in the SQL
+    Preserve,
+}
+
+/// Options for `Parser::parse_sql_with_options` and `Parser::with_options`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParserOptions {
+    /// The maximum depth to which expressions, subqueries and joins may be
+    /// nested; see `Parser::with_recursion_limit`
+    pub recursion_limit: usize,
+    /// Whether to reject syntax that is not standard SQL, even if the dialect
+    /// accepts it: `::` casts, `LIMIT`, `CROSS APPLY` and `OUTER APPLY`, table
+    /// hints, single-quoted aliases, and the units of an interval inside its
+    /// string (`INTERVAL '1 day'`)
+    pub strict_ansi: bool,
+    /// Whether to check that the string of each interval literal has exactly
+    /// the fields of its qualifier, with `IntervalValue::fields_match_precision`,
+    /// e.g. to reject `INTERVAL '1:2:3' HOUR TO MINUTE`
+    pub validate_intervals: bool,
+    /// Whether to accept a comma after the last item of a list, e.g.
+    /// `SELECT a, b, FROM t`
+    pub trailing_commas: bool,
+    /// The case to fold unquoted identifiers to, e.g. `Lower` to parse
+    /// `SELECT Foo` to the same AST as `SELECT foo`, like PostgreSQL does
+    pub identifier_case: IdentifierCase,
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        ParserOptions {
+            recursion_limit: Parser::DEFAULT_RECURSION_LIMIT,
+            strict_ansi: false,
+            validate_intervals: false,
+            trailing_commas: false,
+            identifier_case: IdentifierCase::Preserve,
+        }
+    }
+}
+
 /// SQL Parser
-pub struct Parser {
-    tokens: Vec<Token>,
+pub struct Parser<'a> {
+    tokens: Vec<TokenWithSpan<'a>>,
     /// The index of the first unprocessed token in `self.tokens`
     index: usize,
+    /// Whether to recover from errors rather than failing on the first one;
+    /// see `parse_statements_with_recovery`
+    recover: bool,
+    /// The errors recovered from so far
+    errors: Vec<ParserError>,
+    /// The contexts (see `in_context`) currently being parsed, outermost first
+    context: Vec<&'static str>,
+    /// The dialect whose extensions to the grammar are to be parsed
+    dialect: &'a dyn Dialect,
+    /// Whether to record the ranges of tokens that make up the nodes of the
+    /// concrete syntax tree; see `parse_sql_lossless`
+    lossless: bool,
+    /// The nodes of the concrete syntax tree recorded so far
+    syntax_nodes: Vec<NodeRange>,
+    /// How deeply nested the construct being parsed is; see `nested`
+    depth: usize,
+    options: ParserOptions,
 }
 
-impl Parser {
-    /// Parse the specified tokens
-    pub fn new(tokens: Vec<Token>) -> Self {
-        Parser { tokens, index: 0 }
+impl<'a> Parser<'a> {
+    /// The default maximum depth of nested expressions, subqueries and joins
+    pub const DEFAULT_RECURSION_LIMIT: usize = 50;
+
+    /// Parse the specified tokens. The resulting AST nodes will not have
+    /// source locations; use `Parser::new_with_spans` to get them.
+    pub fn new(tokens: Vec<Token<'a>>, dialect: &'a dyn Dialect) -> Self {
+        Parser::new_with_spans(
+            tokens
+                .into_iter()
+                .map(|token| TokenWithSpan {
+                    token,
+                    span: Span::default(),
+                })
+                .collect(),
+            dialect,
+        )
+    }
+
+    /// Parse the specified tokens, recording their locations on the AST
+    pub fn new_with_spans(tokens: Vec<TokenWithSpan<'a>>, dialect: &'a dyn Dialect) -> Self {
+        Parser {
+            tokens,
+            index: 0,
+            dialect,
+            recover: false,
+            errors: vec![],
+            context: vec![],
+            lossless: false,
+            syntax_nodes: vec![],
+            depth: 0,
+            options: ParserOptions::default(),
+        }
+    }
+
+    /// Set the options that control how the SQL is parsed
+    pub fn with_options(mut self, options: ParserOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Set the maximum depth to which expressions, subqueries and joins may
+    /// be nested. Deeper nesting fails with a `RecursionLimitExceeded` error,
+    /// rather than overflowing the stack.
+    pub fn with_recursion_limit(mut self, recursion_limit: usize) -> Self {
+        self.options.recursion_limit = recursion_limit;
+        self
     }
 
     /// Parse a SQL statement and produce an Abstract Syntax Tree (AST)
     pub fn parse_sql(dialect: &dyn Dialect, sql: String) -> Result<Vec<Statement>, ParserError> {
+        Parser::parse_sql_with_options(dialect, sql, ParserOptions::default())
+    }
+
+    /// Parse SQL like `parse_sql`, according to `options`
+    pub fn parse_sql_with_options(
+        dialect: &dyn Dialect,
+        sql: String,
+        options: ParserOptions,
+    ) -> Result<Vec<Statement>, ParserError> {
+        let mut tokenizer = Tokenizer::new(dialect, &sql);
+        let tokens = tokenizer
+            .tokenize_with_span()
+            .map_err(|e| ParserError::from(e).with_source(&sql))?;
+        let mut parser = Parser::new_with_spans(tokens, dialect).with_options(options);
+        debug!("Parsing sql '{}'...", sql);
+        parser
+            .parse_statements()
+            .map_err(|e| parser.locate_error(e).with_source(&sql))
+    }
+
+    /// Parse SQL like `parse_sql`, but rather than stopping at the first
+    /// error, recover from it and carry on parsing. Returns the statements
+    /// that could be parsed, some of which may be incomplete, along with all
+    /// the errors encountered; see `parse_statements_with_recovery`.
+    pub fn parse_sql_with_recovery(
+        dialect: &dyn Dialect,
+        sql: String,
+    ) -> (Vec<Statement>, Vec<ParserError>) {
         let mut tokenizer = Tokenizer::new(dialect, &sql);
-        let tokens = tokenizer.tokenize()?;
-        let mut parser = Parser::new(tokens);
+        let tokens = match tokenizer.tokenize_with_span() {
+            Ok(tokens) => tokens,
+            Err(e) => return (vec![], vec![ParserError::from(e).with_source(&sql)]),
+        };
+        let mut parser = Parser::new_with_spans(tokens, dialect);
+        debug!("Parsing sql '{}' with error recovery...", sql);
+        let (stmts, errors) = parser.parse_statements_with_recovery();
+        let errors = errors.into_iter().map(|e| e.with_source(&sql)).collect();
+        (stmts, errors)
+    }
+
+    /// Parse SQL like `parse_sql`, also building its concrete syntax tree,
+    /// which keeps the whitespace and comments that the AST leaves out; see
+    /// the `cst` module.
+    pub fn parse_sql_lossless(
+        dialect: &dyn Dialect,
+        sql: String,
+    ) -> Result<SourceFile, ParserError> {
+        let mut tokenizer = Tokenizer::new(dialect, &sql);
+        let tokens = tokenizer
+            .tokenize_with_span()
+            .map_err(|e| ParserError::from(e).with_source(&sql))?;
+        let mut parser = Parser::new_with_spans(tokens, dialect);
+        parser.lossless = true;
+        debug!("Parsing sql '{}' losslessly...", sql);
+        let statements = parser
+            .parse_statements()
+            .map_err(|e| parser.locate_error(e).with_source(&sql))?;
+        let tokens = std::mem::take(&mut parser.tokens)
+            .into_iter()
+            .map(TokenWithSpan::into_owned)
+            .collect();
+        let ranges = std::mem::take(&mut parser.syntax_nodes);
+        Ok(cst::build(sql, tokens, ranges, statements))
+    }
+
+    /// Parse all the remaining tokens as a list of semicolon-separated
+    /// statements
+    pub fn parse_statements(&mut self) -> Result<Vec<Statement>, ParserError> {
         let mut stmts = Vec::new();
         let mut expecting_statement_delimiter = false;
-        debug!("Parsing sql '{}'...", sql);
         loop {
             // ignore empty statements (between successive statement delimiters)
-            while parser.consume_token(&Token::SemiColon) {
+            while self.consume_token(&Token::SemiColon) {
                 expecting_statement_delimiter = false;
             }
 
-            if parser.peek_token().is_none() {
+            if self.peek_token().is_none() {
                 break;
-            } else if expecting_statement_delimiter {
-                return parser.expected("end of statement", parser.peek_token());
             }
 
-            let statement = parser.parse_statement()?;
-            stmts.push(statement);
-            expecting_statement_delimiter = true;
+            let start = self.index;
+            let result = if expecting_statement_delimiter {
+                self.expected_next("end of statement")
+            } else {
+                self.parse_statement()
+            };
+            if result.is_ok() {
+                self.record_syntax_node(SyntaxKind::Statement, start);
+            }
+            match result {
+                Ok(statement) => {
+                    stmts.push(statement);
+                    expecting_statement_delimiter = true;
+                }
+                Err(e) if self.recover => {
+                    self.record_error(e);
+                    // Resume at the next statement
+                    while !matches!(self.peek_token(), None | Some(Token::SemiColon)) {
+                        self.advance_token();
+                    }
+                    expecting_statement_delimiter = false;
+                }
+                Err(e) => return Err(e),
+            }
         }
         Ok(stmts)
     }
 
+    /// Parse all the remaining tokens like `parse_statements`, recovering from
+    /// errors instead of stopping at the first one.
+    ///
+    /// An error in a statement causes the rest of it to be skipped, up to the
+    /// next semicolon, and the statement to be left out of the result. An
+    /// error in a clause of a SELECT causes the rest of the clause to be
+    /// skipped, up to the keyword that starts the next clause (e.g. `FROM`,
+    /// `WHERE` or `GROUP`), and the clause to be left empty, so that the
+    /// statement is still returned. The projection of a SELECT cannot be
+    /// left empty, so after an error in it, the rest of the SELECT is only
+    /// parsed for its errors, and the SELECT is left out like a statement
+    /// with an error, or like a clause with an error if it is a subquery.
+    /// The errors are returned in the order in which they appear.
+    pub fn parse_statements_with_recovery(&mut self) -> (Vec<Statement>, Vec<ParserError>) {
+        self.recover = true;
+        let stmts = match self.parse_statements() {
+            Ok(stmts) => stmts,
+            Err(_) => unreachable!("errors are recovered from"),
+        };
+        self.recover = false;
+        let mut errors = std::mem::take(&mut self.errors);
+        errors.sort_by_key(|e| e.span.start.offset);
+        (stmts, errors)
+    }
+
     /// Parse a single top-level statement (such as SELECT, INSERT, CREATE, etc.),
     /// stopping before the statement separator, if any.
     pub fn parse_statement(&mut self) -> Result<Statement, ParserError> {
+        if let Some(statement) = self.dialect.parse_statement(self) {
+            return statement;
+        }
         match self.next_token() {
             Some(t) => match t {
-                Token::Word(ref w) if w.keyword != "" => match w.keyword.as_ref() {
-                    "SELECT" | "WITH" | "VALUES" => {
+                Token::Word(ref w) if w.keyword != Keyword::NoKeyword => match w.keyword {
+                    Keyword::Select | Keyword::With | Keyword::Values => {
                         self.prev_token();
                         Ok(Statement::Query(Box::new(self.parse_query()?)))
                     }
-                    "CREATE" => Ok(self.parse_create()?),
-                    "DROP" => Ok(self.parse_drop()?),
-                    "DELETE" => Ok(self.parse_delete()?),
-                    "INSERT" => Ok(self.parse_insert()?),
-                    "UPDATE" => Ok(self.parse_update()?),
-                    "ALTER" => Ok(self.parse_alter()?),
-                    "COPY" => Ok(self.parse_copy()?),
-                    "SET" => Ok(self.parse_set()?),
-                    "SHOW" => Ok(self.parse_show()?),
-                    "START" => Ok(self.parse_start_transaction()?),
+                    Keyword::Create => Ok(self.parse_create()?),
+                    Keyword::Drop => Ok(self.parse_drop()?),
+                    Keyword::Delete => Ok(self.parse_delete()?),
+                    Keyword::Insert => Ok(self.parse_insert()?),
+                    Keyword::Update => Ok(self.parse_update()?),
+                    Keyword::Alter => Ok(self.parse_alter()?),
+                    Keyword::Copy => Ok(self.parse_copy()?),
+                    Keyword::Set => Ok(self.parse_set()?),
+                    Keyword::Show => Ok(self.parse_show()?),
+                    Keyword::Start => Ok(self.parse_start_transaction()?),
                     // `BEGIN` is a nonstandard but common alias for the
                     // standard `START TRANSACTION` statement. It is supported
                     // by at least PostgreSQL and MySQL.
-                    "BEGIN" => Ok(self.parse_begin()?),
-                    "COMMIT" => Ok(self.parse_commit()?),
-                    "ROLLBACK" => Ok(self.parse_rollback()?),
-                    "PEEK" => Ok(Statement::Peek {
-                        immediate: self.parse_keyword("IMMEDIATE"),
+                    Keyword::Begin => Ok(self.parse_begin()?),
+                    Keyword::Commit => Ok(self.parse_commit()?),
+                    Keyword::Rollback => Ok(self.parse_rollback()?),
+                    Keyword::Peek => Ok(Statement::Peek {
+                        immediate: self.parse_keyword(Keyword::Immediate),
                         name: self.parse_object_name()?,
                     }),
-                    "TAIL" => Ok(Statement::Tail {
+                    Keyword::Tail => Ok(Statement::Tail {
                         name: self.parse_object_name()?,
                     }),
-                    "EXPLAIN" => Ok(self.parse_explain()?),
-                    "FLUSH" => Ok(self.parse_flush()?),
+                    Keyword::Explain => Ok(self.parse_explain()?),
+                    Keyword::Flush => Ok(self.parse_flush()?),
                     _ => parser_err!(format!(
                         "Unexpected keyword {:?} at the beginning of a statement",
                         w.to_string()
@@ -161,7 +501,7 @@ impl Parser {
                 }
                 unexpected => self.expected(
                     "a keyword at the beginning of a statement",
-                    Some(unexpected),
+                    Some(&unexpected),
                 ),
             },
             None => self.expected("SQL statement", None),
@@ -175,6 +515,10 @@ impl Parser {
 
     /// Parse tokens until the precedence changes
     pub fn parse_subexpr(&mut self, precedence: u8) -> Result<Expr, ParserError> {
+        self.nested(|parser| parser.parse_subexpr_inner(precedence))
+    }
+
+    fn parse_subexpr_inner(&mut self, precedence: u8) -> Result<Expr, ParserError> {
         debug!("parsing expr");
         let mut expr = self.parse_prefix()?;
         debug!("prefix: {:?}", expr);
@@ -192,48 +536,54 @@ impl Parser {
 
     /// Parse an expression prefix
     pub fn parse_prefix(&mut self) -> Result<Expr, ParserError> {
-        let tok = self
-            .next_token()
-            .ok_or_else(|| ParserError::ParserError("Unexpected EOF".to_string()))?;
+        if let Some(prefix) = self.dialect.parse_prefix(self) {
+            return prefix;
+        }
+        let tok = match self.next_token() {
+            Some(tok) => tok,
+            None => return self.expected("an expression", None),
+        };
         let expr = match tok {
-            Token::Word(w) => match w.keyword.as_ref() {
-                "TRUE" | "FALSE" | "NULL" => {
+            Token::Word(w) => match w.keyword {
+                Keyword::True | Keyword::False | Keyword::Null => {
                     self.prev_token();
                     Ok(Expr::Value(self.parse_value()?))
                 }
-                "ARRAY" => {
+                Keyword::Array => {
                     self.prev_token();
                     Ok(Expr::Value(self.parse_value()?))
                 }
-                "CASE" => self.parse_case_expr(),
-                "CAST" => self.parse_cast_expr(),
-                "DATE" => Ok(Expr::Value(self.parse_date()?)),
-                "EXISTS" => self.parse_exists_expr(),
-                "EXTRACT" => self.parse_extract_expr(),
-                "INTERVAL" => self.parse_literal_interval(),
-                "NOT" => Ok(Expr::UnaryOp {
+                Keyword::Case => self.parse_case_expr(),
+                Keyword::Cast => self.parse_cast_expr(),
+                Keyword::Date => Ok(Expr::Value(self.parse_date()?)),
+                Keyword::Exists => self.parse_exists_expr(),
+                Keyword::Extract => self.parse_extract_expr(),
+                Keyword::Interval => self.parse_literal_interval(),
+                Keyword::Not => Ok(Expr::UnaryOp {
                     op: UnaryOperator::Not,
                     expr: Box::new(self.parse_subexpr(Self::UNARY_NOT_PREC)?),
                 }),
-                "TIME" => Ok(Expr::Value(Value::Time(self.parse_literal_string()?))),
-                "TIMESTAMP" => self.parse_timestamp(),
-                "TIMESTAMPTZ" => self.parse_timestamptz(),
+                Keyword::Time => Ok(Expr::Value(Value::Time(self.parse_literal_string()?))),
+                Keyword::Timestamp => self.parse_timestamp(),
+                Keyword::Timestamptz => self.parse_timestamptz(),
                 // Here `w` is a word, check if it's a part of a multi-part
                 // identifier, a function call, or a simple identifier:
                 _ => match self.peek_token() {
                     Some(Token::LParen) | Some(Token::Period) => {
-                        let mut id_parts: Vec<Ident> = vec![w.to_ident()];
+                        let mut id_parts: Vec<Ident> = vec![self.word_to_ident(&w)];
                         let mut ends_with_wildcard = false;
                         while self.consume_token(&Token::Period) {
                             match self.next_token() {
-                                Some(Token::Word(w)) => id_parts.push(w.to_ident()),
+                                Some(Token::Word(w)) => id_parts.push(self.word_to_ident(&w)),
                                 Some(Token::Mult) => {
                                     ends_with_wildcard = true;
                                     break;
                                 }
                                 unexpected => {
-                                    return self
-                                        .expected("an identifier or a '*' after '.'", unexpected);
+                                    return self.expected(
+                                        "an identifier or a '*' after '.'",
+                                        unexpected.as_ref(),
+                                    );
                                 }
                             }
                         }
@@ -246,7 +596,7 @@ impl Parser {
                             Ok(Expr::CompoundIdentifier(id_parts))
                         }
                     }
-                    _ => Ok(Expr::Identifier(w.to_ident())),
+                    _ => Ok(Expr::Identifier(self.word_to_ident(&w))),
                 },
             }, // End of Token::Word
             Token::Mult => Ok(Expr::Wildcard),
@@ -264,7 +614,8 @@ impl Parser {
             Token::Number(_)
             | Token::SingleQuotedString(_)
             | Token::NationalStringLiteral(_)
-            | Token::HexStringLiteral(_) => {
+            | Token::HexStringLiteral(_)
+            | Token::DollarQuotedString { .. } => {
                 self.prev_token();
                 Ok(Expr::Value(self.parse_value()?))
             }
@@ -273,19 +624,22 @@ impl Parser {
                 Err(err) => return parser_err!("unable to parse parameter: {}", err),
             })),
             Token::LParen => {
-                let expr = if self.parse_keyword("SELECT") || self.parse_keyword("WITH") {
-                    self.prev_token();
-                    Expr::Subquery(Box::new(self.parse_query()?))
-                } else {
-                    Expr::Nested(Box::new(self.parse_expr()?))
-                };
+                let expr =
+                    if self.parse_keyword(Keyword::Select) || self.parse_keyword(Keyword::With) {
+                        self.prev_token();
+                        Expr::Subquery(Box::new(
+                            self.in_node(SyntaxKind::Subquery, Parser::parse_query)?,
+                        ))
+                    } else {
+                        Expr::Nested(Box::new(self.parse_expr()?))
+                    };
                 self.expect_token(&Token::RParen)?;
                 Ok(expr)
             }
-            unexpected => self.expected("an expression", Some(unexpected)),
+            unexpected => self.expected("an expression", Some(&unexpected)),
         }?;
 
-        if self.parse_keyword("COLLATE") {
+        if self.parse_keyword(Keyword::Collate) {
             Ok(Expr::Collate {
                 expr: Box::new(expr),
                 collation: self.parse_object_name()?,
@@ -297,25 +651,25 @@ impl Parser {
 
     pub fn parse_function(&mut self, name: ObjectName) -> Result<Expr, ParserError> {
         self.expect_token(&Token::LParen)?;
-        let all = self.parse_keyword("ALL");
-        let distinct = self.parse_keyword("DISTINCT");
+        let all = self.parse_keyword(Keyword::All);
+        let distinct = self.parse_keyword(Keyword::Distinct);
         if all && distinct {
             return parser_err!(format!(
                 "Cannot specify both ALL and DISTINCT in function: {}",
                 name.to_string(),
             ));
         }
-        let args = self.parse_optional_args()?;
-        let over = if self.parse_keyword("OVER") {
+        let args = self.in_node(SyntaxKind::FunctionArguments, Parser::parse_optional_args)?;
+        let over = if self.parse_keyword(Keyword::Over) {
             // TBD: support window names (`OVER mywin`) in place of inline specification
             self.expect_token(&Token::LParen)?;
-            let partition_by = if self.parse_keywords(vec!["PARTITION", "BY"]) {
+            let partition_by = if self.parse_keywords(&[Keyword::Partition, Keyword::By]) {
                 // a list of possibly-qualified column names
                 self.parse_comma_separated(Parser::parse_expr)?
             } else {
                 vec![]
             };
-            let order_by = if self.parse_keywords(vec!["ORDER", "BY"]) {
+            let order_by = if self.parse_keywords(&[Keyword::Order, Keyword::By]) {
                 self.parse_comma_separated(Parser::parse_order_by_expr)?
             } else {
                 vec![]
@@ -347,12 +701,12 @@ impl Parser {
 
     pub fn parse_window_frame(&mut self) -> Result<WindowFrame, ParserError> {
         let units = match self.next_token() {
-            Some(Token::Word(w)) => w.keyword.parse::<WindowFrameUnits>()?,
-            unexpected => return self.expected("ROWS, RANGE, GROUPS", unexpected),
+            Some(Token::Word(w)) => w.keyword.as_str().parse::<WindowFrameUnits>()?,
+            unexpected => return self.expected("ROWS, RANGE, GROUPS", unexpected.as_ref()),
         };
-        let (start_bound, end_bound) = if self.parse_keyword("BETWEEN") {
+        let (start_bound, end_bound) = if self.parse_keyword(Keyword::Between) {
             let start_bound = self.parse_window_frame_bound()?;
-            self.expect_keyword("AND")?;
+            self.expect_keyword(Keyword::And)?;
             let end_bound = Some(self.parse_window_frame_bound()?);
             (start_bound, end_bound)
         } else {
@@ -367,46 +721,46 @@ impl Parser {
 
     /// Parse `CURRENT ROW` or `{ <positive number> | UNBOUNDED } { PRECEDING | FOLLOWING }`
     pub fn parse_window_frame_bound(&mut self) -> Result<WindowFrameBound, ParserError> {
-        if self.parse_keywords(vec!["CURRENT", "ROW"]) {
+        if self.parse_keywords(&[Keyword::Current, Keyword::Row]) {
             Ok(WindowFrameBound::CurrentRow)
         } else {
-            let rows = if self.parse_keyword("UNBOUNDED") {
+            let rows = if self.parse_keyword(Keyword::Unbounded) {
                 None
             } else {
                 Some(self.parse_literal_uint()?)
             };
-            if self.parse_keyword("PRECEDING") {
+            if self.parse_keyword(Keyword::Preceding) {
                 Ok(WindowFrameBound::Preceding(rows))
-            } else if self.parse_keyword("FOLLOWING") {
+            } else if self.parse_keyword(Keyword::Following) {
                 Ok(WindowFrameBound::Following(rows))
             } else {
-                self.expected("PRECEDING or FOLLOWING", self.peek_token())
+                self.expected_next("PRECEDING or FOLLOWING")
             }
         }
     }
 
     pub fn parse_case_expr(&mut self) -> Result<Expr, ParserError> {
         let mut operand = None;
-        if !self.parse_keyword("WHEN") {
+        if !self.parse_keyword(Keyword::When) {
             operand = Some(Box::new(self.parse_expr()?));
-            self.expect_keyword("WHEN")?;
+            self.expect_keyword(Keyword::When)?;
         }
         let mut conditions = vec![];
         let mut results = vec![];
         loop {
             conditions.push(self.parse_expr()?);
-            self.expect_keyword("THEN")?;
+            self.expect_keyword(Keyword::Then)?;
             results.push(self.parse_expr()?);
-            if !self.parse_keyword("WHEN") {
+            if !self.parse_keyword(Keyword::When) {
                 break;
             }
         }
-        let else_result = if self.parse_keyword("ELSE") {
+        let else_result = if self.parse_keyword(Keyword::Else) {
             Some(Box::new(self.parse_expr()?))
         } else {
             None
         };
-        self.expect_keyword("END")?;
+        self.expect_keyword(Keyword::End)?;
         Ok(Expr::Case {
             operand,
             conditions,
@@ -419,7 +773,7 @@ impl Parser {
     pub fn parse_cast_expr(&mut self) -> Result<Expr, ParserError> {
         self.expect_token(&Token::LParen)?;
         let expr = self.parse_expr()?;
-        self.expect_keyword("AS")?;
+        self.expect_keyword(Keyword::As)?;
         let data_type = self.parse_data_type()?;
         self.expect_token(&Token::RParen)?;
         Ok(Expr::Cast {
@@ -439,7 +793,7 @@ impl Parser {
     pub fn parse_extract_expr(&mut self) -> Result<Expr, ParserError> {
         self.expect_token(&Token::LParen)?;
         let field = self.parse_date_time_field()?;
-        self.expect_keyword("FROM")?;
+        self.expect_keyword(Keyword::From)?;
         let expr = self.parse_expr()?;
         self.expect_token(&Token::RParen)?;
         Ok(Expr::Extract {
@@ -455,17 +809,17 @@ impl Parser {
     pub fn parse_date_time_field(&mut self) -> Result<DateTimeField, ParserError> {
         let tok = self.next_token();
         if let Some(Token::Word(ref k)) = tok {
-            match k.keyword.as_ref() {
-                "YEAR" => Ok(DateTimeField::Year),
-                "MONTH" => Ok(DateTimeField::Month),
-                "DAY" => Ok(DateTimeField::Day),
-                "HOUR" => Ok(DateTimeField::Hour),
-                "MINUTE" => Ok(DateTimeField::Minute),
-                "SECOND" => Ok(DateTimeField::Second),
-                _ => self.expected("date/time field", tok)?,
+            match k.keyword {
+                Keyword::Year => Ok(DateTimeField::Year),
+                Keyword::Month => Ok(DateTimeField::Month),
+                Keyword::Day => Ok(DateTimeField::Day),
+                Keyword::Hour => Ok(DateTimeField::Hour),
+                Keyword::Minute => Ok(DateTimeField::Minute),
+                Keyword::Second => Ok(DateTimeField::Second),
+                _ => self.expected("date/time field", tok.as_ref())?,
             }
         } else {
-            self.expected("date/time field", tok)?
+            self.expected("date/time field", tok.as_ref())?
         }
     }
 
@@ -505,10 +859,7 @@ impl Parser {
         match (pdt.year, pdt.month, pdt.day, pdt.hour) {
             (Some(year), Some(month), Some(day), None) => {
                 let p_err = |e: std::num::TryFromIntError, field: &str| {
-                    ParserError::ParserError(format!(
-                        "{} in date '{}' is invalid: {}",
-                        field, value, e
-                    ))
+                    ParserError::new(format!("{} in date '{}' is invalid: {}", field, value, e))
                 };
 
                 // type inference with try_into() fails so we need to mutate it negative
@@ -533,7 +884,7 @@ impl Parser {
                 hours,
                 value
             ),
-            (_, _, _, _) => Err(ParserError::ParserError(format!(
+            (_, _, _, _) => Err(ParserError::new(format!(
                 "year, day and month are all required, got: '{}'",
                 value
             ))),
@@ -541,11 +892,11 @@ impl Parser {
     }
 
     fn parse_timestamp(&mut self) -> Result<Expr, ParserError> {
-        if self.parse_keyword("WITH") {
-            self.expect_keywords(&["TIME", "ZONE"])?;
+        if self.parse_keyword(Keyword::With) {
+            self.expect_keywords(&[Keyword::Time, Keyword::Zone])?;
             return Ok(Expr::Value(self.parse_timestamp_inner(true)?));
-        } else if self.parse_keyword("WITHOUT") {
-            self.expect_keywords(&["TIME", "ZONE"])?;
+        } else if self.parse_keyword(Keyword::Without) {
+            self.expect_keywords(&[Keyword::Time, Keyword::Zone])?;
         }
         Ok(Expr::Value(self.parse_timestamp_inner(false)?))
     }
@@ -581,10 +932,7 @@ impl Parser {
                 timezone_offset_second,
             ) => {
                 let p_err = |e: std::num::TryFromIntError, field: &str| {
-                    ParserError::ParserError(format!(
-                        "{} in date '{}' is invalid: {}",
-                        field, value, e
-                    ))
+                    ParserError::new(format!("{} in date '{}' is invalid: {}", field, value, e))
                 };
 
                 // type inference with try_into() fails so we need to mutate it negative
@@ -648,7 +996,7 @@ impl Parser {
                     },
                 ))
             }
-            _ => Err(ParserError::ParserError(format!(
+            _ => Err(ParserError::new(format!(
                 "timestamp is missing fields, year through second are all required, got: '{}'",
                 value
             ))),
@@ -675,8 +1023,10 @@ impl Parser {
 
         // The first token in an interval is a string literal which specifies
         // the duration of the interval.
+        let raw_value_span = self.peek_token_span();
         let mut raw_value = self.parse_literal_string()?;
         let leading_field = if self.contains_date_time_str(&raw_value)? {
+            self.reject_if_strict("An interval with its units inside the string")?;
             // Hack to allow INTERVAL types like:
             // INTERVAL '-30 day'
             let (new_raw_value, leading_field) = {
@@ -713,7 +1063,7 @@ impl Parser {
                 (leading_precision, last_field, fsec_precision)
             } else {
                 let leading_precision = self.parse_optional_precision()?;
-                if self.parse_keyword("TO") {
+                if self.parse_keyword(Keyword::To) {
                     let last_field = Some(self.parse_date_time_field()?);
                     let fsec_precision = if last_field == Some(DateTimeField::Second) {
                         self.parse_optional_precision()?
@@ -728,20 +1078,32 @@ impl Parser {
 
         let value = Self::parse_interval_string(&raw_value, &leading_field)?;
 
-        Ok(Expr::Value(Value::Interval(IntervalValue {
+        let interval = IntervalValue {
             value: raw_value,
             parsed: value,
             leading_field,
             leading_precision,
             last_field,
             fractional_seconds_precision: fsec_precision,
-        })))
+        };
+        if self.options.validate_intervals {
+            if let Err(e) = interval.fields_match_precision() {
+                let mut err = ParserError::new(e.to_string());
+                err.span = raw_value_span;
+                return Err(err);
+            }
+        }
+        Ok(Expr::Value(Value::Interval(interval)))
     }
 
     /// Parse an operator following an expression
     pub fn parse_infix(&mut self, expr: Expr, precedence: u8) -> Result<Expr, ParserError> {
+        if let Some(infix) = self.dialect.parse_infix(self, &expr, precedence) {
+            return infix;
+        }
         debug!("parsing infix");
         let tok = self.next_token().unwrap(); // safe as EOF's precedence is the lowest
+        let tok_span = self.prev_token_span();
 
         let regular_binary_operator = match tok {
             Token::Eq => Some(BinaryOperator::Eq),
@@ -755,12 +1117,12 @@ impl Parser {
             Token::Mult => Some(BinaryOperator::Multiply),
             Token::Mod => Some(BinaryOperator::Modulus),
             Token::Div => Some(BinaryOperator::Divide),
-            Token::Word(ref k) => match k.keyword.as_ref() {
-                "AND" => Some(BinaryOperator::And),
-                "OR" => Some(BinaryOperator::Or),
-                "LIKE" => Some(BinaryOperator::Like),
-                "NOT" => {
-                    if self.parse_keyword("LIKE") {
+            Token::Word(ref k) => match k.keyword {
+                Keyword::And => Some(BinaryOperator::And),
+                Keyword::Or => Some(BinaryOperator::Or),
+                Keyword::Like => Some(BinaryOperator::Like),
+                Keyword::Not => {
+                    if self.parse_keyword(Keyword::Like) {
                         Some(BinaryOperator::NotLike)
                     } else {
                         None
@@ -772,14 +1134,14 @@ impl Parser {
         };
 
         if let Some(op) = regular_binary_operator {
-            let any = self.parse_keyword("ANY");
-            let some = !any && self.parse_keyword("SOME");
-            let all = !any && !some && self.parse_keyword("ALL");
+            let any = self.parse_keyword(Keyword::Any);
+            let some = !any && self.parse_keyword(Keyword::Some);
+            let all = !any && !some && self.parse_keyword(Keyword::All);
             if any || some || all {
                 use BinaryOperator::*;
                 match op {
                     Eq | NotEq | Gt | GtEq | Lt | LtEq => (),
-                    _ => self.expected("comparison operator", Some(tok))?,
+                    _ => self.expected_at(tok_span, &["comparison operator"], Some(&tok))?,
                 }
                 self.expect_token(&Token::LParen)?;
                 let query = self.parse_query()?;
@@ -806,31 +1168,32 @@ impl Parser {
                 })
             }
         } else if let Token::Word(ref k) = tok {
-            match k.keyword.as_ref() {
-                "IS" => {
-                    if self.parse_keyword("NULL") {
+            match k.keyword {
+                Keyword::Is => {
+                    if self.parse_keyword(Keyword::Null) {
                         Ok(Expr::IsNull(Box::new(expr)))
-                    } else if self.parse_keywords(vec!["NOT", "NULL"]) {
+                    } else if self.parse_keywords(&[Keyword::Not, Keyword::Null]) {
                         Ok(Expr::IsNotNull(Box::new(expr)))
                     } else {
-                        self.expected("NULL or NOT NULL after IS", self.peek_token())
+                        self.expected_next("NULL or NOT NULL after IS")
                     }
                 }
-                "NOT" | "IN" | "BETWEEN" => {
+                Keyword::Not | Keyword::In | Keyword::Between => {
                     self.prev_token();
-                    let negated = self.parse_keyword("NOT");
-                    if self.parse_keyword("IN") {
+                    let negated = self.parse_keyword(Keyword::Not);
+                    if self.parse_keyword(Keyword::In) {
                         self.parse_in(expr, negated)
-                    } else if self.parse_keyword("BETWEEN") {
+                    } else if self.parse_keyword(Keyword::Between) {
                         self.parse_between(expr, negated)
                     } else {
-                        self.expected("IN or BETWEEN after NOT", self.peek_token())
+                        self.expected_next("IN or BETWEEN after NOT")
                     }
                 }
                 // Can only happen if `get_next_precedence` got out of sync with this function
                 _ => panic!("No infix parser for token {:?}", tok),
             }
         } else if Token::DoubleColon == tok {
+            self.reject_if_strict("A `::` cast")?;
             self.parse_pg_cast(expr)
         } else {
             // Can only happen if `get_next_precedence` got out of sync with this function
@@ -862,7 +1225,7 @@ impl Parser {
         leading_field: &DateTimeField,
     ) -> Result<ParsedDateTime, ParserError> {
         if value.is_empty() {
-            return Err(ParserError::ParserError(
+            return Err(ParserError::new(
                 "Interval date string is empty!".to_string(),
             ));
         }
@@ -875,9 +1238,7 @@ impl Parser {
         parse_timezone: bool,
     ) -> Result<ParsedDateTime, ParserError> {
         if value.is_empty() {
-            return Err(ParserError::ParserError(
-                "Timestamp string is empty!".to_string(),
-            ));
+            return Err(ParserError::new("Timestamp string is empty!".to_string()));
         }
 
         let (ts_string, tz_string) = datetime::split_timestamp_string(value);
@@ -894,7 +1255,7 @@ impl Parser {
     /// Parses the parens following the `[ NOT ] IN` operator
     pub fn parse_in(&mut self, expr: Expr, negated: bool) -> Result<Expr, ParserError> {
         self.expect_token(&Token::LParen)?;
-        let in_op = if self.parse_keyword("SELECT") || self.parse_keyword("WITH") {
+        let in_op = if self.parse_keyword(Keyword::Select) || self.parse_keyword(Keyword::With) {
             self.prev_token();
             Expr::InSubquery {
                 expr: Box::new(expr),
@@ -917,7 +1278,7 @@ impl Parser {
         // Stop parsing subexpressions for <low> and <high> on tokens with
         // precedence lower than that of `BETWEEN`, such as `AND`, `IS`, etc.
         let low = self.parse_subexpr(Self::BETWEEN_PREC)?;
-        self.expect_keyword("AND")?;
+        self.expect_keyword(Keyword::And)?;
         let high = self.parse_subexpr(Self::BETWEEN_PREC)?;
         Ok(Expr::Between {
             expr: Box::new(expr),
@@ -941,27 +1302,30 @@ impl Parser {
 
     /// Get the precedence of the next token
     pub fn get_next_precedence(&self) -> Result<u8, ParserError> {
+        if let Some(precedence) = self.dialect.get_next_precedence(self) {
+            return precedence;
+        }
         if let Some(token) = self.peek_token() {
             debug!("get_next_precedence() {:?}", token);
 
             match &token {
-                Token::Word(k) if k.keyword == "OR" => Ok(5),
-                Token::Word(k) if k.keyword == "AND" => Ok(10),
-                Token::Word(k) if k.keyword == "NOT" => match &self.peek_nth_token(1) {
+                Token::Word(k) if k.keyword == Keyword::Or => Ok(5),
+                Token::Word(k) if k.keyword == Keyword::And => Ok(10),
+                Token::Word(k) if k.keyword == Keyword::Not => match &self.peek_nth_token(1) {
                     // The precedence of NOT varies depending on keyword that
                     // follows it. If it is followed by IN, BETWEEN, or LIKE,
                     // it takes on the precedence of those tokens. Otherwise it
                     // is not an infix operator, and therefore has zero
                     // precedence.
-                    Some(Token::Word(k)) if k.keyword == "IN" => Ok(Self::BETWEEN_PREC),
-                    Some(Token::Word(k)) if k.keyword == "BETWEEN" => Ok(Self::BETWEEN_PREC),
-                    Some(Token::Word(k)) if k.keyword == "LIKE" => Ok(Self::BETWEEN_PREC),
+                    Some(Token::Word(k)) if k.keyword == Keyword::In => Ok(Self::BETWEEN_PREC),
+                    Some(Token::Word(k)) if k.keyword == Keyword::Between => Ok(Self::BETWEEN_PREC),
+                    Some(Token::Word(k)) if k.keyword == Keyword::Like => Ok(Self::BETWEEN_PREC),
                     _ => Ok(0),
                 },
-                Token::Word(k) if k.keyword == "IS" => Ok(17),
-                Token::Word(k) if k.keyword == "IN" => Ok(Self::BETWEEN_PREC),
-                Token::Word(k) if k.keyword == "BETWEEN" => Ok(Self::BETWEEN_PREC),
-                Token::Word(k) if k.keyword == "LIKE" => Ok(Self::BETWEEN_PREC),
+                Token::Word(k) if k.keyword == Keyword::Is => Ok(17),
+                Token::Word(k) if k.keyword == Keyword::In => Ok(Self::BETWEEN_PREC),
+                Token::Word(k) if k.keyword == Keyword::Between => Ok(Self::BETWEEN_PREC),
+                Token::Word(k) if k.keyword == Keyword::Like => Ok(Self::BETWEEN_PREC),
                 Token::Eq | Token::Lt | Token::LtEq | Token::Neq | Token::Gt | Token::GtEq => {
                     Ok(20)
                 }
@@ -977,20 +1341,23 @@ impl Parser {
 
     /// Return the first non-whitespace token that has not yet been processed
     /// (or None if reached end-of-file)
-    pub fn peek_token(&self) -> Option<Token> {
+    pub fn peek_token(&self) -> Option<&Token<'a>> {
         self.peek_nth_token(0)
     }
 
     /// Return nth non-whitespace token that has not yet been processed
-    pub fn peek_nth_token(&self, mut n: usize) -> Option<Token> {
+    pub fn peek_nth_token(&self, mut n: usize) -> Option<&Token<'a>> {
         let mut index = self.index;
         loop {
             index += 1;
             match self.tokens.get(index - 1) {
-                Some(Token::Whitespace(_)) => continue,
+                Some(TokenWithSpan {
+                    token: Token::Whitespace(_),
+                    ..
+                }) => continue,
                 non_whitespace => {
                     if n == 0 {
-                        return non_whitespace.cloned();
+                        return non_whitespace.map(|t| &t.token);
                     }
                     n -= 1;
                 }
@@ -1001,20 +1368,34 @@ impl Parser {
     /// Return the first non-whitespace token that has not yet been processed
     /// (or None if reached end-of-file) and mark it as processed. OK to call
     /// repeatedly after reaching EOF.
-    pub fn next_token(&mut self) -> Option<Token> {
+    ///
+    /// The token is cloned, which is cheap since the text of tokens is
+    /// borrowed from the SQL; use `advance_token` to skip a token that was
+    /// already inspected with `peek_token`.
+    pub fn next_token(&mut self) -> Option<Token<'a>> {
+        self.advance_token();
+        self.tokens.get(self.index - 1).map(|t| t.token.clone())
+    }
+
+    /// Mark the first non-whitespace token that has not yet been processed as
+    /// processed, like `next_token` but without returning it
+    pub fn advance_token(&mut self) {
         loop {
             self.index += 1;
             match self.tokens.get(self.index - 1) {
-                Some(Token::Whitespace(_)) => continue,
-                token => return token.cloned(),
+                Some(TokenWithSpan {
+                    token: Token::Whitespace(_),
+                    ..
+                }) => continue,
+                _ => return,
             }
         }
     }
 
     /// Return the first unprocessed token, possibly whitespace.
-    pub fn next_token_no_skip(&mut self) -> Option<&Token> {
+    pub fn next_token_no_skip(&mut self) -> Option<&Token<'a>> {
         self.index += 1;
-        self.tokens.get(self.index - 1)
+        self.tokens.get(self.index - 1).map(|t| &t.token)
     }
 
     /// Push back the last one non-whitespace token. Must be called after
@@ -1024,33 +1405,279 @@ impl Parser {
         loop {
             assert!(self.index > 0);
             self.index -= 1;
-            if let Some(Token::Whitespace(_)) = self.tokens.get(self.index) {
+            if let Some(TokenWithSpan {
+                token: Token::Whitespace(_),
+                ..
+            }) = self.tokens.get(self.index)
+            {
                 continue;
             }
             return;
         }
     }
 
-    /// Report unexpected token
-    fn expected<T>(&self, expected: &str, found: Option<Token>) -> Result<T, ParserError> {
-        parser_err!(format!(
+    /// Return the location of the first non-whitespace token that has not yet
+    /// been processed (or an empty span if reached end-of-file)
+    pub fn peek_token_span(&self) -> Span {
+        self.tokens[self.index.min(self.tokens.len())..]
+            .iter()
+            .find(|t| !matches!(t.token, Token::Whitespace(_)))
+            .map_or_else(Span::default, |t| t.span)
+    }
+
+    /// Return the location of the last processed non-whitespace token (or an
+    /// empty span if no token has been processed or the last call to
+    /// `next_token()` reached end-of-file)
+    pub fn prev_token_span(&self) -> Span {
+        if self.index > self.tokens.len() {
+            return Span::default();
+        }
+        self.tokens[..self.index]
+            .iter()
+            .rev()
+            .find(|t| !matches!(t.token, Token::Whitespace(_)))
+            .map_or_else(Span::default, |t| t.span)
+    }
+
+    /// Convert a word that was just returned by `next_token()` to an
+    /// identifier located at that token.
+    fn word_to_ident(&self, w: &Word) -> Ident {
+        self.word_to_ident_at(w, self.prev_token_span())
+    }
+
+    /// Convert a word located at `span` to an identifier, folding its case
+    /// according to `ParserOptions::identifier_case` if it is not quoted
+    fn word_to_ident_at(&self, w: &Word, span: Span) -> Ident {
+        let mut ident = w.to_ident().with_span(span);
+        if w.quote_style.is_none() {
+            match self.options.identifier_case {
+                IdentifierCase::Upper => ident.value = ident.value.to_uppercase(),
+                IdentifierCase::Lower => ident.value = ident.value.to_lowercase(),
+                IdentifierCase::Preserve => (),
+            }
+        }
+        ident
+    }
+
+    /// Fail if `ParserOptions::strict_ansi` is set, as `what`, which ends at
+    /// the token that was just consumed, is not standard SQL
+    fn reject_if_strict(&self, what: &str) -> Result<(), ParserError> {
+        if self.options.strict_ansi {
+            let mut err = ParserError::new(format!("{} is not standard SQL", what));
+            err.span = self.prev_token_span();
+            Err(err)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Attribute an error that was reported without a location (e.g. an
+    /// invalid literal) to the last processed token
+    fn locate_error(&self, mut e: ParserError) -> ParserError {
+        if e.span.is_empty() {
+            e.span = self.prev_token_span();
+            if e.span.is_empty() {
+                e.span = self.eof_span();
+            }
+        }
+        e
+    }
+
+    /// Return an empty span located at the end of the input
+    fn eof_span(&self) -> Span {
+        self.tokens
+            .last()
+            .map_or_else(Span::default, |t| Span::new(t.span.end, t.span.end))
+    }
+
+    /// Report unexpected token. `found` must be the token last returned by
+    /// `next_token()`.
+    fn expected<T>(&self, expected: &str, found: Option<&Token>) -> Result<T, ParserError> {
+        let span = match found {
+            Some(_) => self.prev_token_span(),
+            None => self.eof_span(),
+        };
+        self.expected_at(span, &[expected], found)
+    }
+
+    /// Report that the next token, which is left unprocessed, is unexpected
+    pub fn expected_next<T>(&self, expected: &str) -> Result<T, ParserError> {
+        self.expected_one_of_next(&[expected])
+    }
+
+    /// Like `expected_next`, for when any of several alternatives would have
+    /// been accepted
+    fn expected_one_of_next<T>(&self, expected: &[&str]) -> Result<T, ParserError> {
+        let found = self.peek_token();
+        let span = match found {
+            Some(_) => self.peek_token_span(),
+            None => self.eof_span(),
+        };
+        self.expected_at(span, expected, found)
+    }
+
+    fn expected_at<T>(
+        &self,
+        span: Span,
+        expected: &[&str],
+        found: Option<&Token>,
+    ) -> Result<T, ParserError> {
+        let alternatives = match expected {
+            [one] => one.to_string(),
+            _ => format!("one of {}", expected.join(" or ")),
+        };
+        let mut err = ParserError::new(format!(
             "Expected {}, found: {}",
-            expected,
-            found.map_or_else(|| "EOF".to_string(), |t| format!("{}", t))
-        ))
+            alternatives,
+            found
+                .as_ref()
+                .map_or_else(|| "EOF".to_string(), |t| format!("{}", t))
+        ));
+        err.span = span;
+        err.expected = expected.iter().map(|e| e.to_string()).collect();
+        err.found = found.map(|t| t.clone().into_owned());
+        Err(err)
+    }
+
+    /// Run `f`, recording on the error it returns, if any, that it occurred
+    /// while parsing `context`
+    pub fn in_context<T, F>(&mut self, context: &'static str, f: F) -> Result<T, ParserError>
+    where
+        F: FnOnce(&mut Parser<'a>) -> Result<T, ParserError>,
+    {
+        self.context.push(context);
+        let result = f(self);
+        self.context.pop();
+        result.map_err(|mut e| {
+            e.context.insert(0, context);
+            e
+        })
+    }
+
+    /// Run `f` to parse a node of kind `kind` of the concrete syntax tree,
+    /// like `in_context` with the description of `kind`, recording the node
+    /// if `f` succeeds
+    fn in_node<T, F>(&mut self, kind: SyntaxKind, f: F) -> Result<T, ParserError>
+    where
+        F: FnOnce(&mut Parser<'a>) -> Result<T, ParserError>,
+    {
+        let start = self.index;
+        let result = self.in_context(kind.description(), f);
+        if result.is_ok() {
+            self.record_syntax_node(kind, start);
+        }
+        result
+    }
+
+    /// Run `f` to parse a construct nested inside the current one, failing
+    /// instead if that would exceed the recursion limit
+    fn nested<T, F>(&mut self, f: F) -> Result<T, ParserError>
+    where
+        F: FnOnce(&mut Parser<'a>) -> Result<T, ParserError>,
+    {
+        if self.depth >= self.options.recursion_limit {
+            let mut err = ParserError::new(format!(
+                "Exceeded the recursion limit of {}",
+                self.options.recursion_limit
+            ));
+            err.kind = ParserErrorKind::RecursionLimitExceeded;
+            err.span = match self.peek_token() {
+                Some(_) => self.peek_token_span(),
+                None => self.eof_span(),
+            };
+            return Err(err);
+        }
+        self.depth += 1;
+        let result = f(self);
+        self.depth -= 1;
+        result
+    }
+
+    /// Record the tokens from `start` up to the current one as a node of the
+    /// concrete syntax tree, when parsing losslessly
+    fn record_syntax_node(&mut self, kind: SyntaxKind, start: usize) {
+        if self.lossless {
+            self.syntax_nodes.push(NodeRange {
+                kind,
+                start,
+                end: self.index,
+            });
+        }
+    }
+
+    /// Go back to the token at `index`, forgetting the nodes of the concrete
+    /// syntax tree recorded since
+    fn backtrack(&mut self, index: usize) {
+        self.index = index;
+        self.syntax_nodes.retain(|node| node.start < index);
+    }
+
+    /// Parse a clause of a SELECT with `f`, like `in_node`. In recovery
+    /// mode, an error is recorded rather than returned, the rest of the clause
+    /// is skipped, and the clause is treated as empty.
+    fn parse_clause<T, F>(&mut self, kind: SyntaxKind, f: F) -> Result<T, ParserError>
+    where
+        T: Default,
+        F: FnOnce(&mut Parser<'a>) -> Result<T, ParserError>,
+    {
+        let start = self.index;
+        match self.in_node(kind, f) {
+            Err(e) if self.recover => {
+                self.record_error(e);
+                self.skip_to_next_clause(start);
+                Ok(T::default())
+            }
+            result => result,
+        }
+    }
+
+    /// Record an error to be returned by `parse_statements_with_recovery`
+    fn record_error(&mut self, e: ParserError) {
+        let mut e = self.locate_error(e);
+        // The contexts that are still being parsed have not seen the error
+        let mut context = self.context.clone();
+        context.append(&mut e.context);
+        e.context = context;
+        self.errors.push(e);
+    }
+
+    /// Skip tokens up to the start of the next clause of a query, the end of
+    /// the enclosing parenthesized expression, or the end of the statement,
+    /// where the clause started at the token `start`, so that the parentheses
+    /// that were opened since then, e.g. by a subquery that has an error, are
+    /// skipped too
+    fn skip_to_next_clause(&mut self, start: usize) {
+        let mut depth = 0;
+        for token in &self.tokens[start..self.index] {
+            match token.token {
+                Token::LParen => depth += 1,
+                Token::RParen => depth -= 1,
+                _ => (),
+            }
+        }
+        loop {
+            match self.peek_token() {
+                None | Some(Token::SemiColon) => break,
+                Some(Token::RParen) if depth == 0 => break,
+                Some(Token::Word(ref w))
+                    if depth == 0 && QUERY_CLAUSE_KEYWORDS.contains(&w.keyword) =>
+                {
+                    break
+                }
+                Some(Token::LParen) => depth += 1,
+                Some(Token::RParen) => depth -= 1,
+                _ => (),
+            }
+            self.advance_token();
+        }
     }
 
     /// Look for an expected keyword and consume it if it exists
     #[must_use]
-    pub fn parse_keyword(&mut self, expected: &'static str) -> bool {
-        // Ideally, we'd accept a enum variant, not a string, but since
-        // it's not trivial to maintain the enum without duplicating all
-        // the keywords three times, we'll settle for a run-time check that
-        // the string actually represents a known keyword...
-        assert!(keywords::ALL_KEYWORDS.contains(&expected));
+    pub fn parse_keyword(&mut self, expected: Keyword) -> bool {
         match self.peek_token() {
-            Some(Token::Word(ref k)) if expected.eq_ignore_ascii_case(&k.keyword) => {
-                self.next_token();
+            Some(Token::Word(ref k)) if k.keyword == expected => {
+                self.advance_token();
                 true
             }
             _ => false,
@@ -1059,10 +1686,10 @@ impl Parser {
 
     /// Look for an expected sequence of keywords and consume them if they exist
     #[must_use]
-    pub fn parse_keywords(&mut self, keywords: Vec<&'static str>) -> bool {
+    pub fn parse_keywords(&mut self, keywords: &[Keyword]) -> bool {
         let index = self.index;
-        for keyword in keywords {
-            if !self.parse_keyword(&keyword) {
+        for &keyword in keywords {
+            if !self.parse_keyword(keyword) {
                 //println!("parse_keywords aborting .. did not find {}", keyword);
                 // reset index and return immediately
                 self.index = index;
@@ -1074,55 +1701,44 @@ impl Parser {
 
     /// Look for one of the given keywords and return the one that matches.
     #[must_use]
-    pub fn parse_one_of_keywords(&mut self, keywords: &[&'static str]) -> Option<&'static str> {
-        for keyword in keywords {
-            assert!(
-                keywords::ALL_KEYWORDS.contains(keyword),
-                "{} is not contained in keyword list",
-                keyword
-            );
-        }
+    pub fn parse_one_of_keywords(&mut self, keywords: &[Keyword]) -> Option<Keyword> {
         match self.peek_token() {
-            Some(Token::Word(ref k)) => keywords
-                .iter()
-                .find(|keyword| keyword.eq_ignore_ascii_case(&k.keyword))
-                .map(|keyword| {
-                    self.next_token();
-                    *keyword
-                }),
+            Some(Token::Word(ref k)) => {
+                keywords
+                    .iter()
+                    .find(|&&keyword| keyword == k.keyword)
+                    .map(|&keyword| {
+                        self.advance_token();
+                        keyword
+                    })
+            }
             _ => None,
         }
     }
 
     /// Bail out if the current token is not one of the expected keywords, or consume it if it is
-    #[must_use]
-    pub fn expect_one_of_keywords(
-        &mut self,
-        keywords: &[&'static str],
-    ) -> Result<&'static str, ParserError> {
+    pub fn expect_one_of_keywords(&mut self, keywords: &[Keyword]) -> Result<Keyword, ParserError> {
         if let Some(keyword) = self.parse_one_of_keywords(keywords) {
             Ok(keyword)
         } else {
-            self.expected(
-                &format!("one of {}", keywords.join(" or ")),
-                self.peek_token(),
-            )
+            let keywords: Vec<&str> = keywords.iter().map(|k| k.as_str()).collect();
+            self.expected_one_of_next(&keywords)
         }
     }
 
     /// Bail out if the current token is not an expected keyword, or consume it if it is
-    pub fn expect_keyword(&mut self, expected: &'static str) -> Result<(), ParserError> {
+    pub fn expect_keyword(&mut self, expected: Keyword) -> Result<(), ParserError> {
         if self.parse_keyword(expected) {
             Ok(())
         } else {
-            self.expected(expected, self.peek_token())
+            self.expected_next(expected.as_str())
         }
     }
 
     /// Bail out if the following tokens are not the expected sequence of
     /// keywords, or consume them if they are.
-    pub fn expect_keywords(&mut self, expected: &[&'static str]) -> Result<(), ParserError> {
-        for kw in expected {
+    pub fn expect_keywords(&mut self, expected: &[Keyword]) -> Result<(), ParserError> {
+        for &kw in expected {
             self.expect_keyword(kw)?;
         }
         Ok(())
@@ -1131,12 +1747,11 @@ impl Parser {
     /// Consume the next token if it matches the expected token, otherwise return false
     #[must_use]
     pub fn consume_token(&mut self, expected: &Token) -> bool {
-        match &self.peek_token() {
-            Some(t) if *t == *expected => {
-                self.next_token();
-                true
-            }
-            _ => false,
+        if self.peek_token() == Some(expected) {
+            self.advance_token();
+            true
+        } else {
+            false
         }
     }
 
@@ -1145,56 +1760,108 @@ impl Parser {
         if self.consume_token(expected) {
             Ok(())
         } else {
-            self.expected(&expected.to_string(), self.peek_token())
+            self.expected_next(&expected.to_string())
         }
     }
 
     /// Parse a comma-separated list of 1+ items accepted by `F`
     pub fn parse_comma_separated<T, F>(&mut self, mut f: F) -> Result<Vec<T>, ParserError>
     where
-        F: FnMut(&mut Parser) -> Result<T, ParserError>,
+        F: FnMut(&mut Parser<'a>) -> Result<T, ParserError>,
     {
         let mut values = vec![];
         loop {
             values.push(f(self)?);
-            if !self.consume_token(&Token::Comma) {
+            if !self.consume_token(&Token::Comma) || self.at_end_of_list() {
                 break;
             }
         }
         Ok(values)
     }
 
+    /// Whether the comma that was just consumed is a trailing comma that ends
+    /// a list, if `ParserOptions::trailing_commas` allows them
+    fn at_end_of_list(&self) -> bool {
+        if !self.options.trailing_commas {
+            return false;
+        }
+        match self.peek_token() {
+            None | Some(Token::RParen) | Some(Token::RBracket) | Some(Token::SemiColon) => true,
+            // A keyword that the dialect reserves ends the list, unless it
+            // may start an item of the list, e.g. `NULL` or `CASE`.
+            Some(Token::Word(w)) => {
+                self.dialect.is_reserved_for_column_alias(w.keyword)
+                    && (keywords::RESERVED_FOR_COLUMN_ALIAS.contains(&w.keyword)
+                        || !self.reserved_word_starts_item(w.keyword))
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether the next word, which is the reserved keyword `keyword`, may
+    /// start an expression or a table factor: a keyword that `parse_prefix`
+    /// or `parse_table_factor` handles, a function that is called without
+    /// parentheses, or the name of a function that is called
+    fn reserved_word_starts_item(&self, keyword: Keyword) -> bool {
+        match keyword {
+            Keyword::True
+            | Keyword::False
+            | Keyword::Null
+            | Keyword::Array
+            | Keyword::Case
+            | Keyword::Cast
+            | Keyword::Date
+            | Keyword::Exists
+            | Keyword::Extract
+            | Keyword::Interval
+            | Keyword::Not
+            | Keyword::Time
+            | Keyword::Timestamp
+            | Keyword::Timestamptz
+            | Keyword::Lateral => true,
+            Keyword::CurrentCatalog
+            | Keyword::CurrentDate
+            | Keyword::CurrentRole
+            | Keyword::CurrentTime
+            | Keyword::CurrentTimestamp
+            | Keyword::CurrentUser
+            | Keyword::Localtime
+            | Keyword::Localtimestamp
+            | Keyword::SessionUser
+            | Keyword::SystemUser
+            | Keyword::User => true,
+            _ => self.peek_nth_token(1) == Some(&Token::LParen),
+        }
+    }
+
     /// Parse a SQL CREATE statement
     pub fn parse_create(&mut self) -> Result<Statement, ParserError> {
-        if self.parse_keyword("TABLE") {
+        if self.parse_keyword(Keyword::Table) {
             self.parse_create_table()
-        } else if self.parse_keyword("MATERIALIZED") || self.parse_keyword("VIEW") {
+        } else if self.parse_keyword(Keyword::Materialized) || self.parse_keyword(Keyword::View) {
             self.prev_token();
             self.parse_create_view()
-        } else if self.parse_keyword("SOURCE") {
+        } else if self.parse_keyword(Keyword::Source) {
             self.parse_create_source()
-        } else if self.parse_keyword("SOURCES") {
+        } else if self.parse_keyword(Keyword::Sources) {
             self.parse_create_sources()
-        } else if self.parse_keyword("SINK") {
+        } else if self.parse_keyword(Keyword::Sink) {
             self.parse_create_sink()
-        } else if self.parse_keyword("EXTERNAL") {
+        } else if self.parse_keyword(Keyword::External) {
             self.parse_create_external_table()
-        } else if self.parse_keyword("INDEX") {
+        } else if self.parse_keyword(Keyword::Index) {
             self.parse_create_index()
         } else {
-            self.expected(
-                "TABLE, VIEW, SOURCE, SINK, or INDEX after CREATE",
-                self.peek_token(),
-            )
+            self.expected_next("TABLE, VIEW, SOURCE, SINK, or INDEX after CREATE")
         }
     }
 
     pub fn parse_create_source(&mut self) -> Result<Statement, ParserError> {
         let name = self.parse_object_name()?;
-        self.expect_keyword("FROM")?;
+        self.expect_keyword(Keyword::From)?;
         let url = self.parse_literal_string()?;
-        let schema = if self.parse_keywords(vec!["USING", "SCHEMA"]) {
-            let schema = if self.parse_keyword("REGISTRY") {
+        let schema = if self.parse_keywords(&[Keyword::Using, Keyword::Schema]) {
+            let schema = if self.parse_keyword(Keyword::Registry) {
                 SourceSchema::Registry(self.parse_literal_string()?)
             } else {
                 SourceSchema::Raw(self.parse_literal_string()?)
@@ -1215,9 +1882,9 @@ impl Parser {
     pub fn parse_create_sources(&mut self) -> Result<Statement, ParserError> {
         // Need to get the LIKE if it exists, otherwise keep moving.
         let like = self.parse_like_filter()?;
-        self.expect_keyword("FROM")?;
+        self.expect_keyword(Keyword::From)?;
         let url = self.parse_literal_string()?;
-        self.expect_keywords(&["USING", "SCHEMA", "REGISTRY"])?;
+        self.expect_keywords(&[Keyword::Using, Keyword::Schema, Keyword::Registry])?;
         let schema_registry = self.parse_literal_string()?;
         let with_options = self.parse_with_options()?;
         Ok(Statement::CreateSources {
@@ -1229,7 +1896,7 @@ impl Parser {
     }
 
     fn parse_like_filter(&mut self) -> Result<Option<String>, ParserError> {
-        if self.parse_keyword("LIKE") {
+        if self.parse_keyword(Keyword::Like) {
             Ok(Some(self.parse_literal_string()?))
         } else {
             Ok(None)
@@ -1238,9 +1905,9 @@ impl Parser {
 
     pub fn parse_create_sink(&mut self) -> Result<Statement, ParserError> {
         let name = self.parse_object_name()?;
-        self.expect_keyword("FROM")?;
+        self.expect_keyword(Keyword::From)?;
         let from = self.parse_object_name()?;
-        self.expect_keyword("INTO")?;
+        self.expect_keyword(Keyword::Into)?;
         let url = self.parse_literal_string()?;
         let with_options = self.parse_with_options()?;
         Ok(Statement::CreateSink {
@@ -1252,13 +1919,14 @@ impl Parser {
     }
 
     pub fn parse_create_external_table(&mut self) -> Result<Statement, ParserError> {
-        self.expect_keyword("TABLE")?;
+        self.expect_keyword(Keyword::Table)?;
         let table_name = self.parse_object_name()?;
-        let (columns, constraints) = self.parse_columns()?;
-        self.expect_keywords(&["STORED", "AS"])?;
+        let (columns, constraints) =
+            self.in_node(SyntaxKind::ColumnDefinitions, Parser::parse_columns)?;
+        self.expect_keywords(&[Keyword::Stored, Keyword::As])?;
         let file_format = self.parse_identifier()?.value.parse::<FileFormat>()?;
 
-        self.expect_keyword("LOCATION")?;
+        self.expect_keyword(Keyword::Location)?;
         let location = self.parse_literal_string()?;
 
         Ok(Statement::CreateTable {
@@ -1273,14 +1941,14 @@ impl Parser {
     }
 
     pub fn parse_create_view(&mut self) -> Result<Statement, ParserError> {
-        let materialized = self.parse_keyword("MATERIALIZED");
-        self.expect_keyword("VIEW")?;
+        let materialized = self.parse_keyword(Keyword::Materialized);
+        self.expect_keyword(Keyword::View)?;
         // Many dialects support `OR REPLACE` | `OR ALTER` right after `CREATE`, but we don't (yet).
         // ANSI SQL and Postgres support RECURSIVE here, but we don't support it either.
         let name = self.parse_object_name()?;
         let columns = self.parse_parenthesized_column_list(Optional)?;
         let with_options = self.parse_with_options()?;
-        self.expect_keyword("AS")?;
+        self.expect_keyword(Keyword::As)?;
         let query = Box::new(self.parse_query()?);
         // Optional `WITH [ CASCADED | LOCAL ] CHECK OPTION` is widely supported here.
         Ok(Statement::CreateView {
@@ -1294,7 +1962,7 @@ impl Parser {
 
     pub fn parse_create_index(&mut self) -> Result<Statement, ParserError> {
         let name = self.parse_identifier()?;
-        self.expect_keyword("ON")?;
+        self.expect_keyword(Keyword::On)?;
         let on_name = self.parse_object_name()?;
         self.expect_token(&Token::LParen)?;
         let key_parts = self.parse_comma_separated(Parser::parse_expr)?;
@@ -1307,28 +1975,25 @@ impl Parser {
     }
 
     pub fn parse_drop(&mut self) -> Result<Statement, ParserError> {
-        let object_type = if self.parse_keyword("TABLE") {
+        let object_type = if self.parse_keyword(Keyword::Table) {
             ObjectType::Table
-        } else if self.parse_keyword("VIEW") {
+        } else if self.parse_keyword(Keyword::View) {
             ObjectType::View
-        } else if self.parse_keywords(vec!["SOURCE"]) {
+        } else if self.parse_keywords(&[Keyword::Source]) {
             ObjectType::Source
-        } else if self.parse_keywords(vec!["SINK"]) {
+        } else if self.parse_keywords(&[Keyword::Sink]) {
             ObjectType::Sink
-        } else if self.parse_keyword("INDEX") {
+        } else if self.parse_keyword(Keyword::Index) {
             ObjectType::Index
         } else {
-            return self.expected(
-                "TABLE, VIEW, SOURCE, SINK, or INDEX after DROP",
-                self.peek_token(),
-            );
+            return self.expected_next("TABLE, VIEW, SOURCE, SINK, or INDEX after DROP");
         };
         // Many dialects support the non standard `IF EXISTS` clause and allow
         // specifying multiple objects to delete in a single statement
-        let if_exists = self.parse_keywords(vec!["IF", "EXISTS"]);
+        let if_exists = self.parse_keywords(&[Keyword::If, Keyword::Exists]);
         let names = self.parse_comma_separated(Parser::parse_object_name)?;
-        let cascade = self.parse_keyword("CASCADE");
-        let restrict = self.parse_keyword("RESTRICT");
+        let cascade = self.parse_keyword(Keyword::Cascade);
+        let restrict = self.parse_keyword(Keyword::Restrict);
         if cascade && restrict {
             return parser_err!("Cannot specify both CASCADE and RESTRICT in DROP");
         }
@@ -1343,7 +2008,8 @@ impl Parser {
     pub fn parse_create_table(&mut self) -> Result<Statement, ParserError> {
         let table_name = self.parse_object_name()?;
         // parse optional column list (schema)
-        let (columns, constraints) = self.parse_columns()?;
+        let (columns, constraints) =
+            self.in_node(SyntaxKind::ColumnDefinitions, Parser::parse_columns)?;
         let with_options = self.parse_with_options()?;
 
         Ok(Statement::CreateTable {
@@ -1367,74 +2033,79 @@ impl Parser {
         loop {
             if let Some(constraint) = self.parse_optional_table_constraint()? {
                 constraints.push(constraint);
-            } else if let Some(Token::Word(column_name)) = self.peek_token() {
-                self.next_token();
-                let data_type = self.parse_data_type()?;
-                let collation = if self.parse_keyword("COLLATE") {
-                    Some(self.parse_object_name()?)
-                } else {
-                    None
-                };
-                let mut options = vec![];
-                loop {
-                    match self.peek_token() {
-                        None | Some(Token::Comma) | Some(Token::RParen) => break,
-                        _ => options.push(self.parse_column_option_def()?),
-                    }
-                }
-
-                columns.push(ColumnDef {
-                    name: column_name.to_ident(),
-                    data_type,
-                    collation,
-                    options,
-                });
+            } else if let Some(Token::Word(_)) = self.peek_token() {
+                columns.push(self.parse_column_def()?);
             } else {
-                return self.expected("column name or constraint definition", self.peek_token());
+                return self.expected_next("column name or constraint definition");
             }
             let comma = self.consume_token(&Token::Comma);
             if self.consume_token(&Token::RParen) {
                 // allow a trailing comma, even though it's not in standard
                 break;
             } else if !comma {
-                return self.expected("',' or ')' after column definition", self.peek_token());
+                return self.expected_next("',' or ')' after column definition");
             }
         }
 
         Ok((columns, constraints))
     }
 
+    /// Parse a column definition in `CREATE TABLE` or `ALTER TABLE .. ADD
+    /// COLUMN`: `<name> <data_type> [ COLLATE <collation> ] [ <options> ]`
+    pub fn parse_column_def(&mut self) -> Result<ColumnDef, ParserError> {
+        let name = self.parse_identifier()?;
+        let data_type = self.parse_data_type()?;
+        let collation = if self.parse_keyword(Keyword::Collate) {
+            Some(self.parse_object_name()?)
+        } else {
+            None
+        };
+        let mut options = vec![];
+        loop {
+            match self.peek_token() {
+                None | Some(Token::Comma) | Some(Token::RParen) | Some(Token::SemiColon) => break,
+                _ => options.push(self.parse_column_option_def()?),
+            }
+        }
+        Ok(ColumnDef {
+            name,
+            data_type,
+            collation,
+            options,
+        })
+    }
+
     pub fn parse_column_option_def(&mut self) -> Result<ColumnOptionDef, ParserError> {
-        let name = if self.parse_keyword("CONSTRAINT") {
+        let name = if self.parse_keyword(Keyword::Constraint) {
             Some(self.parse_identifier()?)
         } else {
             None
         };
 
-        let option = if self.parse_keywords(vec!["NOT", "NULL"]) {
+        let option = if self.parse_keywords(&[Keyword::Not, Keyword::Null]) {
             ColumnOption::NotNull
-        } else if self.parse_keyword("NULL") {
+        } else if self.parse_keyword(Keyword::Null) {
             ColumnOption::Null
-        } else if self.parse_keyword("DEFAULT") {
+        } else if self.parse_keyword(Keyword::Default) {
             ColumnOption::Default(self.parse_expr()?)
-        } else if self.parse_keywords(vec!["PRIMARY", "KEY"]) {
+        } else if self.parse_keywords(&[Keyword::Primary, Keyword::Key]) {
             ColumnOption::Unique { is_primary: true }
-        } else if self.parse_keyword("UNIQUE") {
+        } else if self.parse_keyword(Keyword::Unique) {
             ColumnOption::Unique { is_primary: false }
-        } else if self.parse_keyword("REFERENCES") {
+        } else if self.parse_keyword(Keyword::References) {
             let foreign_table = self.parse_object_name()?;
             let referred_columns = self.parse_parenthesized_column_list(Mandatory)?;
             ColumnOption::ForeignKey {
                 foreign_table,
                 referred_columns,
             }
-        } else if self.parse_keyword("CHECK") {
+        } else if self.parse_keyword(Keyword::Check) {
             self.expect_token(&Token::LParen)?;
             let expr = self.parse_expr()?;
             self.expect_token(&Token::RParen)?;
             ColumnOption::Check(expr)
         } else {
-            return self.expected("column option", self.peek_token());
+            return self.expected_next("column option");
         };
 
         Ok(ColumnOptionDef { name, option })
@@ -1443,16 +2114,18 @@ impl Parser {
     pub fn parse_optional_table_constraint(
         &mut self,
     ) -> Result<Option<TableConstraint>, ParserError> {
-        let name = if self.parse_keyword("CONSTRAINT") {
+        let name = if self.parse_keyword(Keyword::Constraint) {
             Some(self.parse_identifier()?)
         } else {
             None
         };
         match self.next_token() {
-            Some(Token::Word(ref k)) if k.keyword == "PRIMARY" || k.keyword == "UNIQUE" => {
-                let is_primary = k.keyword == "PRIMARY";
+            Some(Token::Word(ref k))
+                if k.keyword == Keyword::Primary || k.keyword == Keyword::Unique =>
+            {
+                let is_primary = k.keyword == Keyword::Primary;
                 if is_primary {
-                    self.expect_keyword("KEY")?;
+                    self.expect_keyword(Keyword::Key)?;
                 }
                 let columns = self.parse_parenthesized_column_list(Mandatory)?;
                 Ok(Some(TableConstraint::Unique {
@@ -1461,10 +2134,10 @@ impl Parser {
                     is_primary,
                 }))
             }
-            Some(Token::Word(ref k)) if k.keyword == "FOREIGN" => {
-                self.expect_keyword("KEY")?;
+            Some(Token::Word(ref k)) if k.keyword == Keyword::Foreign => {
+                self.expect_keyword(Keyword::Key)?;
                 let columns = self.parse_parenthesized_column_list(Mandatory)?;
-                self.expect_keyword("REFERENCES")?;
+                self.expect_keyword(Keyword::References)?;
                 let foreign_table = self.parse_object_name()?;
                 let referred_columns = self.parse_parenthesized_column_list(Mandatory)?;
                 Ok(Some(TableConstraint::ForeignKey {
@@ -1474,7 +2147,7 @@ impl Parser {
                     referred_columns,
                 }))
             }
-            Some(Token::Word(ref k)) if k.keyword == "CHECK" => {
+            Some(Token::Word(ref k)) if k.keyword == Keyword::Check => {
                 self.expect_token(&Token::LParen)?;
                 let expr = Box::new(self.parse_expr()?);
                 self.expect_token(&Token::RParen)?;
@@ -1482,7 +2155,7 @@ impl Parser {
             }
             unexpected => {
                 if name.is_some() {
-                    self.expected("PRIMARY, UNIQUE, FOREIGN, or CHECK", unexpected)
+                    self.expected("PRIMARY, UNIQUE, FOREIGN, or CHECK", unexpected.as_ref())
                 } else {
                     self.prev_token();
                     Ok(None)
@@ -1492,7 +2165,7 @@ impl Parser {
     }
 
     pub fn parse_with_options(&mut self) -> Result<Vec<SqlOption>, ParserError> {
-        if self.parse_keyword("WITH") {
+        if self.parse_keyword(Keyword::With) {
             self.expect_token(&Token::LParen)?;
             let options = self.parse_comma_separated(Parser::parse_sql_option)?;
             self.expect_token(&Token::RParen)?;
@@ -1510,17 +2183,36 @@ impl Parser {
     }
 
     pub fn parse_alter(&mut self) -> Result<Statement, ParserError> {
-        self.expect_keyword("TABLE")?;
-        let _ = self.parse_keyword("ONLY");
+        self.expect_keyword(Keyword::Table)?;
+        let _ = self.parse_keyword(Keyword::Only);
         let table_name = self.parse_object_name()?;
-        let operation = if self.parse_keyword("ADD") {
+        let operation = if self.parse_keyword(Keyword::Add) {
             if let Some(constraint) = self.parse_optional_table_constraint()? {
                 AlterTableOperation::AddConstraint(constraint)
             } else {
-                return self.expected("a constraint in ALTER TABLE .. ADD", self.peek_token());
+                let _ = self.parse_keyword(Keyword::Column);
+                if let Some(Token::Word(_)) = self.peek_token() {
+                    AlterTableOperation::AddColumn(self.parse_column_def()?)
+                } else {
+                    return self.expected_next("a column or constraint in ALTER TABLE .. ADD");
+                }
+            }
+        } else if self.parse_keyword(Keyword::Drop) {
+            if self.parse_keyword(Keyword::Constraint) {
+                let name = self.parse_identifier()?;
+                AlterTableOperation::DropConstraint { name }
+            } else {
+                let _ = self.parse_keyword(Keyword::Column);
+                let name = self.parse_identifier()?;
+                AlterTableOperation::DropColumn { name }
             }
+        } else if self.parse_keyword(Keyword::Alter) {
+            let _ = self.parse_keyword(Keyword::Column);
+            let name = self.parse_identifier()?;
+            let operation = self.parse_alter_column_operation()?;
+            AlterTableOperation::AlterColumn { name, operation }
         } else {
-            return self.expected("ADD after ALTER TABLE", self.peek_token());
+            return self.expected_next("ADD, DROP or ALTER after ALTER TABLE");
         };
         Ok(Statement::AlterTable {
             name: table_name,
@@ -1528,11 +2220,30 @@ impl Parser {
         })
     }
 
+    /// Parse the operation of an `ALTER TABLE .. ALTER COLUMN` statement
+    fn parse_alter_column_operation(&mut self) -> Result<AlterColumnOperation, ParserError> {
+        if self.parse_keywords(&[Keyword::Set, Keyword::Not, Keyword::Null]) {
+            Ok(AlterColumnOperation::SetNotNull)
+        } else if self.parse_keywords(&[Keyword::Drop, Keyword::Not, Keyword::Null]) {
+            Ok(AlterColumnOperation::DropNotNull)
+        } else if self.parse_keywords(&[Keyword::Set, Keyword::Default]) {
+            Ok(AlterColumnOperation::SetDefault(self.parse_expr()?))
+        } else if self.parse_keywords(&[Keyword::Drop, Keyword::Default]) {
+            Ok(AlterColumnOperation::DropDefault)
+        } else if self.parse_keywords(&[Keyword::Set, Keyword::Data, Keyword::Type])
+            || self.parse_keyword(Keyword::Type)
+        {
+            Ok(AlterColumnOperation::SetDataType(self.parse_data_type()?))
+        } else {
+            self.expected_next("SET, DROP or TYPE after ALTER COLUMN")
+        }
+    }
+
     /// Parse a copy statement
     pub fn parse_copy(&mut self) -> Result<Statement, ParserError> {
         let table_name = self.parse_object_name()?;
         let columns = self.parse_parenthesized_column_list(Optional)?;
-        self.expect_keywords(&["FROM", "STDIN"])?;
+        self.expect_keywords(&[Keyword::From, Keyword::Stdin])?;
         self.expect_token(&Token::SemiColon)?;
         let values = self.parse_tsv()?;
         Ok(Statement::Copy {
@@ -1588,13 +2299,13 @@ impl Parser {
     fn parse_value(&mut self) -> Result<Value, ParserError> {
         match self.next_token() {
             Some(t) => match t {
-                Token::Word(k) => match k.keyword.as_ref() {
-                    "TRUE" => Ok(Value::Boolean(true)),
-                    "FALSE" => Ok(Value::Boolean(false)),
-                    "NULL" => Ok(Value::Null),
-                    "ARRAY" => self.parse_array(),
+                Token::Word(k) => match k.keyword {
+                    Keyword::True => Ok(Value::Boolean(true)),
+                    Keyword::False => Ok(Value::Boolean(false)),
+                    Keyword::Null => Ok(Value::Null),
+                    Keyword::Array => self.parse_array(),
                     _ => {
-                        return parser_err!(format!("No value parser for keyword {}", k.keyword));
+                        parser_err!(format!("No value parser for keyword {}", k.keyword))
                     }
                 },
                 // The call to n.parse() returns a bigdecimal when the
@@ -1609,6 +2320,9 @@ impl Parser {
                     Ok(Value::NationalStringLiteral(s.to_string()))
                 }
                 Token::HexStringLiteral(ref s) => Ok(Value::HexStringLiteral(s.to_string())),
+                Token::DollarQuotedString { ref value, .. } => {
+                    Ok(Value::SingleQuotedString(value.to_string()))
+                }
                 _ => parser_err!(format!("Unsupported value: {:?}", t)),
             },
             None => parser_err!("Expecting a value, but found EOF"),
@@ -1636,7 +2350,7 @@ impl Parser {
             v @ Value::Number(_) => Ok(v),
             _ => {
                 self.prev_token();
-                self.expected("literal number", self.peek_token())
+                self.expected_next("literal number")
             }
         }
     }
@@ -1644,64 +2358,65 @@ impl Parser {
     /// Parse an unsigned literal integer/long
     pub fn parse_literal_uint(&mut self) -> Result<u64, ParserError> {
         match self.next_token() {
-            Some(Token::Number(s)) => s.parse::<u64>().map_err(|e| {
-                ParserError::ParserError(format!("Could not parse '{}' as u64: {}", s, e))
-            }),
-            other => self.expected("literal int", other),
+            Some(Token::Number(s)) => s
+                .parse::<u64>()
+                .map_err(|e| ParserError::new(format!("Could not parse '{}' as u64: {}", s, e))),
+            other => self.expected("literal int", other.as_ref()),
         }
     }
 
     /// Parse a literal string
     pub fn parse_literal_string(&mut self) -> Result<String, ParserError> {
         match self.next_token() {
-            Some(Token::SingleQuotedString(ref s)) => Ok(s.clone()),
-            other => self.expected("literal string", other),
+            Some(Token::SingleQuotedString(s))
+            | Some(Token::DollarQuotedString { value: s, .. }) => Ok(s.into_owned()),
+            other => self.expected("literal string", other.as_ref()),
         }
     }
 
     /// Parse a SQL datatype (in the context of a CREATE TABLE statement for example)
     pub fn parse_data_type(&mut self) -> Result<DataType, ParserError> {
         let mut data_type = match self.next_token() {
-            Some(Token::Word(k)) => match k.keyword.as_ref() {
-                "BOOLEAN" => DataType::Boolean,
-                "FLOAT" => DataType::Float(self.parse_optional_precision()?),
-                "REAL" => DataType::Real,
-                "DOUBLE" => {
-                    let _ = self.parse_keyword("PRECISION");
+            Some(Token::Word(k)) => match k.keyword {
+                Keyword::Boolean => DataType::Boolean,
+                Keyword::Float => DataType::Float(self.parse_optional_precision()?),
+                Keyword::Real => DataType::Real,
+                Keyword::Double => {
+                    let _ = self.parse_keyword(Keyword::Precision);
                     DataType::Double
                 }
-                "SMALLINT" => DataType::SmallInt,
-                "INT" | "INTEGER" => DataType::Int,
-                "BIGINT" => DataType::BigInt,
-                "VARCHAR" => DataType::Varchar(self.parse_optional_precision()?),
-                "CHAR" | "CHARACTER" => {
-                    if self.parse_keyword("VARYING") {
+                Keyword::Smallint => DataType::SmallInt,
+                Keyword::Int | Keyword::Integer => DataType::Int,
+                Keyword::Bigint => DataType::BigInt,
+                Keyword::Varchar => DataType::Varchar(self.parse_optional_precision()?),
+                Keyword::Char | Keyword::Character => {
+                    if self.parse_keyword(Keyword::Varying) {
                         DataType::Varchar(self.parse_optional_precision()?)
                     } else {
                         DataType::Char(self.parse_optional_precision()?)
                     }
                 }
-                "UUID" => DataType::Uuid,
-                "DATE" => DataType::Date,
-                "TIMESTAMP" => {
-                    if self.parse_keyword("WITH") {
-                        self.expect_keywords(&["TIME", "ZONE"])?;
+                Keyword::Uuid => DataType::Uuid,
+                Keyword::Date => DataType::Date,
+                Keyword::Timestamp => {
+                    if self.parse_keyword(Keyword::With) {
+                        self.expect_keywords(&[Keyword::Time, Keyword::Zone])?;
                         DataType::TimestampTz
                     } else {
-                        if self.parse_keyword("WITHOUT") {
-                            self.expect_keywords(&["TIME", "ZONE"])?;
+                        if self.parse_keyword(Keyword::Without) {
+                            self.expect_keywords(&[Keyword::Time, Keyword::Zone])?;
                         }
                         DataType::Timestamp
                     }
                 }
-                "TIMESTAMPTZ" => DataType::TimestampTz,
-                "TIME" => {
-                    if self.parse_keyword("WITH") {
-                        self.expect_keywords(&["TIME", "ZONE"])?;
+                Keyword::Timestamptz => DataType::TimestampTz,
+                Keyword::Time => {
+                    if self.parse_keyword(Keyword::With) {
+                        self.expect_keywords(&[Keyword::Time, Keyword::Zone])?;
                         DataType::TimeTz
                     } else {
-                        if self.parse_keyword("WITHOUT") {
-                            self.expect_keywords(&["TIME", "ZONE"])?;
+                        if self.parse_keyword(Keyword::Without) {
+                            self.expect_keywords(&[Keyword::Time, Keyword::Zone])?;
                         }
                         DataType::Time
                     }
@@ -1709,11 +2424,11 @@ impl Parser {
                 // Interval types can be followed by a complicated interval
                 // qualifier that we don't currently support. See
                 // parse_interval_literal for a taste.
-                "INTERVAL" => DataType::Interval,
-                "REGCLASS" => DataType::Regclass,
-                "TEXT" => DataType::Text,
-                "BYTEA" => DataType::Bytea,
-                "NUMERIC" | "DECIMAL" | "DEC" => {
+                Keyword::Interval => DataType::Interval,
+                Keyword::Regclass => DataType::Regclass,
+                Keyword::Text => DataType::Text,
+                Keyword::Bytea => DataType::Bytea,
+                Keyword::Numeric | Keyword::Decimal | Keyword::Dec => {
                     let (precision, scale) = self.parse_optional_precision_scale()?;
                     DataType::Decimal(precision, scale)
                 }
@@ -1723,7 +2438,7 @@ impl Parser {
                     DataType::Custom(type_name)
                 }
             },
-            other => self.expected("a data type name", other)?,
+            other => self.expected("a data type name", other.as_ref())?,
         };
         match &self.peek_token() {
             Some(Token::LBracket) => {
@@ -1733,8 +2448,8 @@ impl Parser {
                     data_type = DataType::Array(Box::new(data_type));
                 }
             }
-            Some(Token::Word(k)) if &k.keyword == "ARRAY" => {
-                self.next_token();
+            Some(Token::Word(k)) if k.keyword == Keyword::Array => {
+                self.advance_token();
                 data_type = DataType::Array(Box::new(data_type));
             }
             _ => (),
@@ -1742,31 +2457,42 @@ impl Parser {
         Ok(data_type)
     }
 
-    /// Parse `AS identifier` (or simply `identifier` if it's not a reserved keyword)
+    /// Parse `AS identifier` (or simply `identifier` if it's not a keyword the
+    /// dialect reserves for column aliases)
     /// Some examples with aliases: `SELECT 1 foo`, `SELECT COUNT(*) AS cnt`,
     /// `SELECT ... FROM t1 foo, t2 bar`, `SELECT ... FROM (...) AS bar`
-    pub fn parse_optional_alias(
+    pub fn parse_optional_alias(&mut self) -> Result<Option<Ident>, ParserError> {
+        let dialect = self.dialect;
+        self.parse_optional_alias_unless(|k| dialect.is_reserved_for_column_alias(k))
+    }
+
+    /// Parse `AS identifier`, or `identifier` if it's not a keyword for which
+    /// `is_reserved` is true
+    fn parse_optional_alias_unless(
         &mut self,
-        reserved_kwds: &[&str],
+        is_reserved: impl Fn(Keyword) -> bool,
     ) -> Result<Option<Ident>, ParserError> {
-        let after_as = self.parse_keyword("AS");
+        let after_as = self.parse_keyword(Keyword::As);
         match self.next_token() {
             // Accept any identifier after `AS` (though many dialects have restrictions on
             // keywords that may appear here). If there's no `AS`: don't parse keywords,
             // which may start a construct allowed in this position, to be parsed as aliases.
             // (For example, in `FROM t1 JOIN` the `JOIN` will always be parsed as a keyword,
             // not an alias.)
-            Some(Token::Word(ref w))
-                if after_as || !reserved_kwds.contains(&w.keyword.as_str()) =>
-            {
-                Ok(Some(w.to_ident()))
+            Some(Token::Word(ref w)) if after_as || !is_reserved(w.keyword) => {
+                Ok(Some(self.word_to_ident(w)))
             }
             // MSSQL supports single-quoted strings as aliases for columns
             // We accept them as table aliases too, although MSSQL does not.
-            Some(Token::SingleQuotedString(ref s)) => Ok(Some(Ident::with_quote('\'', s.clone()))),
+            Some(Token::SingleQuotedString(ref s)) => {
+                self.reject_if_strict("A single-quoted alias")?;
+                Ok(Some(
+                    Ident::with_quote('\'', s.clone()).with_span(self.prev_token_span()),
+                ))
+            }
             not_an_ident => {
                 if after_as {
-                    return self.expected("an identifier after AS", not_an_ident);
+                    return self.expected("an identifier after AS", not_an_ident.as_ref());
                 }
                 self.prev_token();
                 Ok(None) // no alias found
@@ -1778,11 +2504,9 @@ impl Parser {
     /// like in `... FROM generate_series(1, 10) AS t (col)`. In this case
     /// the alias is allowed to optionally name the columns in the table, in
     /// addition to the table itself.
-    pub fn parse_optional_table_alias(
-        &mut self,
-        reserved_kwds: &[&str],
-    ) -> Result<Option<TableAlias>, ParserError> {
-        match self.parse_optional_alias(reserved_kwds)? {
+    pub fn parse_optional_table_alias(&mut self) -> Result<Option<TableAlias>, ParserError> {
+        let dialect = self.dialect;
+        match self.parse_optional_alias_unless(|k| dialect.is_reserved_for_table_alias(k))? {
             Some(name) => {
                 let columns = self.parse_parenthesized_column_list(Optional)?;
                 Ok(Some(TableAlias { name, columns }))
@@ -1804,11 +2528,23 @@ impl Parser {
         Ok(ObjectName(idents))
     }
 
-    /// Parse a simple one-word identifier (possibly quoted, possibly a keyword)
+    /// Parse a simple one-word identifier (possibly quoted, possibly a keyword
+    /// that the dialect does not reserve)
     pub fn parse_identifier(&mut self) -> Result<Ident, ParserError> {
         match self.next_token() {
-            Some(Token::Word(w)) => Ok(w.to_ident()),
-            unexpected => self.expected("identifier", unexpected),
+            Some(Token::Word(w)) if !self.dialect.is_reserved_keyword(w.keyword) => {
+                Ok(self.word_to_ident(&w))
+            }
+            unexpected => self.expected("identifier", unexpected.as_ref()),
+        }
+    }
+
+    /// Parse the name of a variable in `SET` or `SHOW`, which may be any word,
+    /// even a reserved keyword, e.g. `SHOW ALL`
+    fn parse_variable_name(&mut self) -> Result<Ident, ParserError> {
+        match self.next_token() {
+            Some(Token::Word(w)) => Ok(self.word_to_ident(&w)),
+            unexpected => self.expected("identifier", unexpected.as_ref()),
         }
     }
 
@@ -1824,7 +2560,7 @@ impl Parser {
         } else if optional == Optional {
             Ok(vec![])
         } else {
-            self.expected("a list of columns in parentheses", self.peek_token())
+            self.expected_next("a list of columns in parentheses")
         }
     }
 
@@ -1856,10 +2592,10 @@ impl Parser {
     }
 
     pub fn parse_delete(&mut self) -> Result<Statement, ParserError> {
-        self.expect_keyword("FROM")?;
+        self.expect_keyword(Keyword::From)?;
         let table_name = self.parse_object_name()?;
-        let selection = if self.parse_keyword("WHERE") {
-            Some(self.parse_expr()?)
+        let selection = if self.parse_keyword(Keyword::Where) {
+            Some(self.in_node(SyntaxKind::Where, Parser::parse_expr)?)
         } else {
             None
         };
@@ -1875,35 +2611,44 @@ impl Parser {
     /// by `ORDER BY`. Unlike some other parse_... methods, this one doesn't
     /// expect the initial keyword to be already consumed
     pub fn parse_query(&mut self) -> Result<Query, ParserError> {
-        let ctes = if self.parse_keyword("WITH") {
+        self.nested(Parser::parse_query_inner)
+    }
+
+    fn parse_query_inner(&mut self) -> Result<Query, ParserError> {
+        let ctes = if self.parse_keyword(Keyword::With) {
             // TODO: optional RECURSIVE
-            self.parse_comma_separated(Parser::parse_cte)?
+            self.in_node(SyntaxKind::With, |parser| {
+                parser.parse_comma_separated(Parser::parse_cte)
+            })?
         } else {
             vec![]
         };
 
         let body = self.parse_query_body(0)?;
 
-        let order_by = if self.parse_keywords(vec!["ORDER", "BY"]) {
-            self.parse_comma_separated(Parser::parse_order_by_expr)?
+        let order_by = if self.parse_keywords(&[Keyword::Order, Keyword::By]) {
+            self.in_node(SyntaxKind::OrderBy, |parser| {
+                parser.parse_comma_separated(Parser::parse_order_by_expr)
+            })?
         } else {
             vec![]
         };
 
-        let limit = if self.parse_keyword("LIMIT") {
-            self.parse_limit()?
+        let limit = if self.parse_keyword(Keyword::Limit) {
+            self.reject_if_strict("LIMIT")?;
+            self.in_node(SyntaxKind::Limit, Parser::parse_limit)?
         } else {
             None
         };
 
-        let offset = if self.parse_keyword("OFFSET") {
-            Some(self.parse_offset()?)
+        let offset = if self.parse_keyword(Keyword::Offset) {
+            Some(self.in_node(SyntaxKind::Offset, Parser::parse_offset)?)
         } else {
             None
         };
 
-        let fetch = if self.parse_keyword("FETCH") {
-            Some(self.parse_fetch()?)
+        let fetch = if self.parse_keyword(Keyword::Fetch) {
+            Some(self.in_node(SyntaxKind::Fetch, Parser::parse_fetch)?)
         } else {
             None
         };
@@ -1924,7 +2669,7 @@ impl Parser {
             name: self.parse_identifier()?,
             columns: self.parse_parenthesized_column_list(Optional)?,
         };
-        self.expect_keyword("AS")?;
+        self.expect_keyword(Keyword::As)?;
         self.expect_token(&Token::LParen)?;
         let query = self.parse_query()?;
         self.expect_token(&Token::RParen)?;
@@ -1942,26 +2687,22 @@ impl Parser {
     fn parse_query_body(&mut self, precedence: u8) -> Result<SetExpr, ParserError> {
         // We parse the expression using a Pratt parser, as in `parse_expr()`.
         // Start by parsing a restricted SELECT or a `(subquery)`:
-        let mut expr = if self.parse_keyword("SELECT") {
+        let mut expr = if self.parse_keyword(Keyword::Select) {
             SetExpr::Select(Box::new(self.parse_select()?))
         } else if self.consume_token(&Token::LParen) {
             // CTEs are not allowed here, but the parser currently accepts them
             let subquery = self.parse_query()?;
             self.expect_token(&Token::RParen)?;
             SetExpr::Query(Box::new(subquery))
-        } else if self.parse_keyword("VALUES") {
+        } else if self.parse_keyword(Keyword::Values) {
             SetExpr::Values(self.parse_values()?)
         } else {
-            return self.expected(
-                "SELECT, VALUES, or a subquery in the query body",
-                self.peek_token(),
-            );
+            return self.expected_next("SELECT, VALUES, or a subquery in the query body");
         };
 
         loop {
             // The query can be optionally followed by a set operator:
-            let next_token = self.peek_token();
-            let op = self.parse_set_operator(&next_token);
+            let op = self.parse_set_operator(self.peek_token());
             let next_precedence = match op {
                 // UNION and EXCEPT have the same binding power and evaluate left-to-right
                 Some(SetOperator::Union) | Some(SetOperator::Except) => 10,
@@ -1973,11 +2714,11 @@ impl Parser {
             if precedence >= next_precedence {
                 break;
             }
-            self.next_token(); // skip past the set operator
+            self.advance_token(); // skip past the set operator
             expr = SetExpr::SetOperation {
                 left: Box::new(expr),
                 op: op.unwrap(),
-                all: self.parse_keyword("ALL"),
+                all: self.parse_keyword(Keyword::All),
                 right: Box::new(self.parse_query_body(next_precedence)?),
             };
         }
@@ -1985,11 +2726,11 @@ impl Parser {
         Ok(expr)
     }
 
-    fn parse_set_operator(&mut self, token: &Option<Token>) -> Option<SetOperator> {
+    fn parse_set_operator(&self, token: Option<&Token>) -> Option<SetOperator> {
         match token {
-            Some(Token::Word(w)) if w.keyword == "UNION" => Some(SetOperator::Union),
-            Some(Token::Word(w)) if w.keyword == "EXCEPT" => Some(SetOperator::Except),
-            Some(Token::Word(w)) if w.keyword == "INTERSECT" => Some(SetOperator::Intersect),
+            Some(Token::Word(w)) if w.keyword == Keyword::Union => Some(SetOperator::Union),
+            Some(Token::Word(w)) if w.keyword == Keyword::Except => Some(SetOperator::Except),
+            Some(Token::Word(w)) if w.keyword == Keyword::Intersect => Some(SetOperator::Intersect),
             _ => None,
         }
     }
@@ -1997,45 +2738,62 @@ impl Parser {
     /// Parse a restricted `SELECT` statement (no CTEs / `UNION` / `ORDER BY`),
     /// assuming the initial `SELECT` was already consumed
     pub fn parse_select(&mut self) -> Result<Select, ParserError> {
-        let all = self.parse_keyword("ALL");
-        let distinct = self.parse_keyword("DISTINCT");
+        let all = self.parse_keyword(Keyword::All);
+        let distinct = self.parse_keyword(Keyword::Distinct);
         if all && distinct {
             return parser_err!("Cannot specify both ALL and DISTINCT in SELECT");
         }
-        let projection = self.parse_comma_separated(Parser::parse_select_item)?;
+        // A SELECT cannot be left without its projection, so in recovery mode,
+        // the rest of it is only parsed for its errors, and the error is then
+        // returned to be recovered from by the enclosing clause or statement.
+        let start = self.index;
+        let projection = match self.in_node(SyntaxKind::Projection, |parser| {
+            parser.parse_comma_separated(Parser::parse_select_item)
+        }) {
+            Err(e) if self.recover => {
+                self.skip_to_next_clause(start);
+                Err(e)
+            }
+            Err(e) => return Err(e),
+            Ok(projection) => Ok(projection),
+        };
 
         // Note that for keywords to be properly handled here, they need to be
         // added to `RESERVED_FOR_COLUMN_ALIAS` / `RESERVED_FOR_TABLE_ALIAS`,
         // otherwise they may be parsed as an alias as part of the `projection`
         // or `from`.
 
-        let from = if self.parse_keyword("FROM") {
-            self.parse_comma_separated(Parser::parse_table_and_joins)?
+        let from = if self.parse_keyword(Keyword::From) {
+            self.parse_clause(SyntaxKind::From, |parser| {
+                parser.parse_comma_separated(Parser::parse_table_and_joins)
+            })?
         } else {
             vec![]
         };
 
-        let selection = if self.parse_keyword("WHERE") {
-            Some(self.parse_expr()?)
+        let selection = if self.parse_keyword(Keyword::Where) {
+            self.parse_clause(SyntaxKind::Where, |parser| parser.parse_expr().map(Some))?
         } else {
             None
         };
 
-        let group_by = if self.parse_keywords(vec!["GROUP", "BY"]) {
-            self.parse_comma_separated(Parser::parse_expr)?
+        let group_by = if self.parse_keywords(&[Keyword::Group, Keyword::By]) {
+            self.parse_clause(SyntaxKind::GroupBy, |parser| {
+                parser.parse_comma_separated(Parser::parse_expr)
+            })?
         } else {
             vec![]
         };
 
-        let having = if self.parse_keyword("HAVING") {
-            Some(self.parse_expr()?)
+        let having = if self.parse_keyword(Keyword::Having) {
+            self.parse_clause(SyntaxKind::Having, |parser| parser.parse_expr().map(Some))?
         } else {
             None
         };
 
         Ok(Select {
             distinct,
-            projection,
+            projection: projection?,
             from,
             selection,
             group_by,
@@ -2044,47 +2802,59 @@ impl Parser {
     }
 
     pub fn parse_set(&mut self) -> Result<Statement, ParserError> {
-        let modifier = self.parse_one_of_keywords(&["SESSION", "LOCAL"]);
-        let variable = self.parse_identifier()?;
-        if self.consume_token(&Token::Eq) || self.parse_keyword("TO") {
-            let token = self.peek_token();
+        let modifier = self.parse_one_of_keywords(&[Keyword::Session, Keyword::Local]);
+        if modifier.is_none() && self.parse_keyword(Keyword::Transaction) {
+            return Ok(Statement::SetTransaction {
+                modes: self.parse_transaction_modes()?,
+            });
+        }
+        let variable = self.parse_variable_name()?;
+        if self.consume_token(&Token::Eq) || self.parse_keyword(Keyword::To) {
+            let token = self.peek_token().cloned();
+            let span = self.peek_token_span();
             let value = match (self.parse_value(), token) {
                 (Ok(value), _) => SetVariableValue::Literal(value),
-                (Err(_), Some(Token::Word(ident))) => SetVariableValue::Ident(ident.to_ident()),
-                (Err(_), other) => self.expected("variable value", other)?,
+                (Err(_), Some(Token::Word(ident))) => {
+                    SetVariableValue::Ident(self.word_to_ident_at(&ident, span))
+                }
+                (Err(_), other) => self.expected("variable value", other.as_ref())?,
             };
             Ok(Statement::SetVariable {
-                local: modifier == Some("LOCAL"),
+                local: modifier == Some(Keyword::Local),
                 variable,
                 value,
             })
-        } else if variable.value == "TRANSACTION" && modifier.is_none() {
-            Ok(Statement::SetTransaction {
-                modes: self.parse_transaction_modes()?,
-            })
         } else {
-            self.expected("equals sign or TO", self.peek_token())
+            self.expected_next("equals sign or TO")
         }
     }
 
     pub fn parse_show(&mut self) -> Result<Statement, ParserError> {
         if self
-            .parse_one_of_keywords(&["EXTENDED", "FULL", "COLUMNS", "FIELDS"])
+            .parse_one_of_keywords(&[
+                Keyword::Extended,
+                Keyword::Full,
+                Keyword::Columns,
+                Keyword::Fields,
+            ])
             .is_some()
         {
             self.prev_token();
             self.parse_show_columns()
-        } else if let Some(object_type) =
-            self.parse_one_of_keywords(&["SOURCES", "VIEWS", "SINKS", "TABLES"])
-        {
+        } else if let Some(object_type) = self.parse_one_of_keywords(&[
+            Keyword::Sources,
+            Keyword::Views,
+            Keyword::Sinks,
+            Keyword::Tables,
+        ]) {
             // TODO(benesch): support LIKE/WHERE filters, like we do for SHOW
             // COLUMNS, for parity with MySQL.
             Ok(Statement::ShowObjects {
                 object_type: match object_type {
-                    "SOURCES" => ObjectType::Source,
-                    "VIEWS" => ObjectType::View,
-                    "SINKS" => ObjectType::Sink,
-                    "TABLES" => ObjectType::Table,
+                    Keyword::Sources => ObjectType::Source,
+                    Keyword::Views => ObjectType::View,
+                    Keyword::Sinks => ObjectType::Sink,
+                    Keyword::Tables => ObjectType::Table,
                     val => panic!(
                         "`parse_one_of_keywords` returned an impossible value: {}",
                         val
@@ -2093,41 +2863,41 @@ impl Parser {
                 filter: self.parse_show_statement_filter()?,
             })
         } else if self
-            .parse_one_of_keywords(&["INDEX", "INDEXES", "KEYS"])
+            .parse_one_of_keywords(&[Keyword::Index, Keyword::Indexes, Keyword::Keys])
             .is_some()
         {
-            match self.parse_one_of_keywords(&["FROM", "IN"]) {
+            match self.parse_one_of_keywords(&[Keyword::From, Keyword::In]) {
                 Some(_) => {
                     let table_name = self.parse_object_name()?;
-                    let filter = if self.parse_keyword("WHERE") {
+                    let filter = if self.parse_keyword(Keyword::Where) {
                         Some(ShowStatementFilter::Where(self.parse_expr()?))
                     } else {
                         None
                     };
                     Ok(Statement::ShowIndexes { table_name, filter })
                 }
-                None => self.expected("FROM or IN after SHOW INDEXES", self.peek_token()),
+                None => self.expected_next("FROM or IN after SHOW INDEXES"),
             }
-        } else if self.parse_keywords(vec!["CREATE", "VIEW"]) {
+        } else if self.parse_keywords(&[Keyword::Create, Keyword::View]) {
             Ok(Statement::ShowCreateView {
                 view_name: self.parse_object_name()?,
             })
-        } else if self.parse_keywords(vec!["CREATE", "SOURCE"]) {
+        } else if self.parse_keywords(&[Keyword::Create, Keyword::Source]) {
             Ok(Statement::ShowCreateSource {
                 source_name: self.parse_object_name()?,
             })
         } else {
             Ok(Statement::ShowVariable {
-                variable: self.parse_identifier()?,
+                variable: self.parse_variable_name()?,
             })
         }
     }
 
     fn parse_show_columns(&mut self) -> Result<Statement, ParserError> {
-        let extended = self.parse_keyword("EXTENDED");
-        let full = self.parse_keyword("FULL");
-        self.expect_one_of_keywords(&["COLUMNS", "FIELDS"])?;
-        self.expect_one_of_keywords(&["FROM", "IN"])?;
+        let extended = self.parse_keyword(Keyword::Extended);
+        let full = self.parse_keyword(Keyword::Full);
+        self.expect_one_of_keywords(&[Keyword::Columns, Keyword::Fields])?;
+        self.expect_one_of_keywords(&[Keyword::From, Keyword::In])?;
         let table_name = self.parse_object_name()?;
         // MySQL also supports FROM <database> here. In other words, MySQL
         // allows both FROM <table> FROM <database> and FROM <database>.<table>,
@@ -2142,11 +2912,11 @@ impl Parser {
     }
 
     fn parse_show_statement_filter(&mut self) -> Result<Option<ShowStatementFilter>, ParserError> {
-        if self.parse_keyword("LIKE") {
+        if self.parse_keyword(Keyword::Like) {
             Ok(Some(ShowStatementFilter::Like(
                 self.parse_literal_string()?,
             )))
-        } else if self.parse_keyword("WHERE") {
+        } else if self.parse_keyword(Keyword::Where) {
             Ok(Some(ShowStatementFilter::Where(self.parse_expr()?)))
         } else {
             Ok(None)
@@ -2161,54 +2931,56 @@ impl Parser {
         // a table alias.
         let mut joins = vec![];
         loop {
-            let join = if self.parse_keyword("CROSS") {
-                let join_operator = if self.parse_keyword("JOIN") {
+            let join = if self.parse_keyword(Keyword::Cross) {
+                let join_operator = if self.parse_keyword(Keyword::Join) {
                     JoinOperator::CrossJoin
-                } else if self.parse_keyword("APPLY") {
+                } else if self.parse_keyword(Keyword::Apply) {
                     // MSSQL extension, similar to CROSS JOIN LATERAL
+                    self.reject_if_strict("CROSS APPLY")?;
                     JoinOperator::CrossApply
                 } else {
-                    return self.expected("JOIN or APPLY after CROSS", self.peek_token());
+                    return self.expected_next("JOIN or APPLY after CROSS");
                 };
                 Join {
                     relation: self.parse_table_factor()?,
                     join_operator,
                 }
-            } else if self.parse_keyword("OUTER") {
+            } else if self.parse_keyword(Keyword::Outer) {
                 // MSSQL extension, similar to LEFT JOIN LATERAL .. ON 1=1
-                self.expect_keyword("APPLY")?;
+                self.expect_keyword(Keyword::Apply)?;
+                self.reject_if_strict("OUTER APPLY")?;
                 Join {
                     relation: self.parse_table_factor()?,
                     join_operator: JoinOperator::OuterApply,
                 }
             } else {
-                let natural = self.parse_keyword("NATURAL");
+                let natural = self.parse_keyword(Keyword::Natural);
                 let peek_keyword = if let Some(Token::Word(kw)) = self.peek_token() {
                     kw.keyword
                 } else {
-                    String::default()
+                    Keyword::NoKeyword
                 };
 
-                let join_operator_type = match peek_keyword.as_ref() {
-                    "INNER" | "JOIN" => {
-                        let _ = self.parse_keyword("INNER");
-                        self.expect_keyword("JOIN")?;
+                let join_operator_type = match peek_keyword {
+                    Keyword::Inner | Keyword::Join => {
+                        let _ = self.parse_keyword(Keyword::Inner);
+                        self.expect_keyword(Keyword::Join)?;
                         JoinOperator::Inner
                     }
-                    kw @ "LEFT" | kw @ "RIGHT" | kw @ "FULL" => {
+                    kw @ Keyword::Left | kw @ Keyword::Right | kw @ Keyword::Full => {
                         let _ = self.next_token();
-                        let _ = self.parse_keyword("OUTER");
-                        self.expect_keyword("JOIN")?;
+                        let _ = self.parse_keyword(Keyword::Outer);
+                        self.expect_keyword(Keyword::Join)?;
                         match kw {
-                            "LEFT" => JoinOperator::LeftOuter,
-                            "RIGHT" => JoinOperator::RightOuter,
-                            "FULL" => JoinOperator::FullOuter,
+                            Keyword::Left => JoinOperator::LeftOuter,
+                            Keyword::Right => JoinOperator::RightOuter,
+                            Keyword::Full => JoinOperator::FullOuter,
                             _ => unreachable!(),
                         }
                     }
-                    "OUTER" => return self.expected("LEFT, RIGHT, or FULL", self.peek_token()),
+                    Keyword::Outer => return self.expected_next("LEFT, RIGHT, or FULL"),
                     _ if natural => {
-                        return self.expected("a join type after NATURAL", self.peek_token());
+                        return self.expected_next("a join type after NATURAL");
                     }
                     _ => break,
                 };
@@ -2226,10 +2998,14 @@ impl Parser {
 
     /// A table name or a parenthesized subquery, followed by optional `[AS] alias`
     pub fn parse_table_factor(&mut self) -> Result<TableFactor, ParserError> {
-        if self.parse_keyword("LATERAL") {
+        self.nested(Parser::parse_table_factor_inner)
+    }
+
+    fn parse_table_factor_inner(&mut self) -> Result<TableFactor, ParserError> {
+        if self.parse_keyword(Keyword::Lateral) {
             // LATERAL must always be followed by a subquery.
             if !self.consume_token(&Token::LParen) {
-                self.expected("subquery after LATERAL", self.peek_token())?;
+                self.expected_next("subquery after LATERAL")?;
             }
             return self.parse_derived_table_factor(Lateral);
         }
@@ -2258,6 +3034,8 @@ impl Parser {
                 // alias of the derived table. In the example above this is
                 // case (3), and the next token would be `NATURAL`.
                 Ok(table_factor) => Ok(table_factor),
+                // Nesting the join inside this paren would be just as deep
+                Err(e) if e.kind == ParserErrorKind::RecursionLimitExceeded => Err(e),
                 Err(_) => {
                     // The '(' we've recently consumed does not start a derived
                     // table. For valid input this can happen either when the
@@ -2269,7 +3047,7 @@ impl Parser {
                     // Ignore the error and back up to where we were before.
                     // Either we'll be able to parse a valid nested join, or
                     // we won't, and we'll return that error instead.
-                    self.index = index;
+                    self.backtrack(index);
                     let table_and_joins = self.parse_table_and_joins()?;
                     match table_and_joins.relation {
                         TableFactor::NestedJoin { .. } => (),
@@ -2277,7 +3055,7 @@ impl Parser {
                             if table_and_joins.joins.is_empty() {
                                 // The SQL spec prohibits derived tables and bare
                                 // tables from appearing alone in parentheses.
-                                self.expected("joined table", self.peek_token())?
+                                self.expected_next("joined table")?
                             }
                         }
                     }
@@ -2293,11 +3071,12 @@ impl Parser {
             } else {
                 vec![]
             };
-            let alias = self.parse_optional_table_alias(keywords::RESERVED_FOR_TABLE_ALIAS)?;
+            let alias = self.parse_optional_table_alias()?;
             // MSSQL-specific table hints:
             let mut with_hints = vec![];
-            if self.parse_keyword("WITH") {
+            if self.parse_keyword(Keyword::With) {
                 if self.consume_token(&Token::LParen) {
+                    self.reject_if_strict("A table hint")?;
                     with_hints = self.parse_comma_separated(Parser::parse_expr)?;
                     self.expect_token(&Token::RParen)?;
                 } else {
@@ -2320,7 +3099,7 @@ impl Parser {
     ) -> Result<TableFactor, ParserError> {
         let subquery = Box::new(self.parse_query()?);
         self.expect_token(&Token::RParen)?;
-        let alias = self.parse_optional_table_alias(keywords::RESERVED_FOR_TABLE_ALIAS)?;
+        let alias = self.parse_optional_table_alias()?;
         Ok(TableFactor::Derived {
             lateral: match lateral {
                 Lateral => true,
@@ -2334,20 +3113,20 @@ impl Parser {
     fn parse_join_constraint(&mut self, natural: bool) -> Result<JoinConstraint, ParserError> {
         if natural {
             Ok(JoinConstraint::Natural)
-        } else if self.parse_keyword("ON") {
-            let constraint = self.parse_expr()?;
+        } else if self.parse_keyword(Keyword::On) {
+            let constraint = self.in_node(SyntaxKind::JoinConstraint, Parser::parse_expr)?;
             Ok(JoinConstraint::On(constraint))
-        } else if self.parse_keyword("USING") {
+        } else if self.parse_keyword(Keyword::Using) {
             let columns = self.parse_parenthesized_column_list(Mandatory)?;
             Ok(JoinConstraint::Using(columns))
         } else {
-            self.expected("ON, or USING after JOIN", self.peek_token())
+            self.expected_next("ON, or USING after JOIN")
         }
     }
 
     /// Parse an INSERT statement
     pub fn parse_insert(&mut self) -> Result<Statement, ParserError> {
-        self.expect_keyword("INTO")?;
+        self.expect_keyword(Keyword::Into)?;
         let table_name = self.parse_object_name()?;
         let columns = self.parse_parenthesized_column_list(Optional)?;
         let source = Box::new(self.parse_query()?);
@@ -2360,10 +3139,12 @@ impl Parser {
 
     pub fn parse_update(&mut self) -> Result<Statement, ParserError> {
         let table_name = self.parse_object_name()?;
-        self.expect_keyword("SET")?;
-        let assignments = self.parse_comma_separated(Parser::parse_assignment)?;
-        let selection = if self.parse_keyword("WHERE") {
-            Some(self.parse_expr()?)
+        self.expect_keyword(Keyword::Set)?;
+        let assignments = self.in_node(SyntaxKind::Assignments, |parser| {
+            parser.parse_comma_separated(Parser::parse_assignment)
+        })?;
+        let selection = if self.parse_keyword(Keyword::Where) {
+            Some(self.in_node(SyntaxKind::Where, Parser::parse_expr)?)
         } else {
             None
         };
@@ -2401,7 +3182,7 @@ impl Parser {
             Ok(SelectItem::QualifiedWildcard(ObjectName(prefix)))
         } else {
             // `expr` is a regular SQL expression and can be followed by an alias
-            if let Some(alias) = self.parse_optional_alias(keywords::RESERVED_FOR_COLUMN_ALIAS)? {
+            if let Some(alias) = self.parse_optional_alias()? {
                 Ok(SelectItem::ExprWithAlias { expr, alias })
             } else {
                 Ok(SelectItem::UnnamedExpr(expr))
@@ -2413,9 +3194,9 @@ impl Parser {
     pub fn parse_order_by_expr(&mut self) -> Result<OrderByExpr, ParserError> {
         let expr = self.parse_expr()?;
 
-        let asc = if self.parse_keyword("ASC") {
+        let asc = if self.parse_keyword(Keyword::Asc) {
             Some(true)
-        } else if self.parse_keyword("DESC") {
+        } else if self.parse_keyword(Keyword::Desc) {
             Some(false)
         } else {
             None
@@ -2425,7 +3206,7 @@ impl Parser {
 
     /// Parse a LIMIT clause
     pub fn parse_limit(&mut self) -> Result<Option<Expr>, ParserError> {
-        if self.parse_keyword("ALL") {
+        if self.parse_keyword(Keyword::All) {
             Ok(None)
         } else {
             Ok(Some(Expr::Value(self.parse_number_value()?)))
@@ -2435,27 +3216,30 @@ impl Parser {
     /// Parse an OFFSET clause
     pub fn parse_offset(&mut self) -> Result<Expr, ParserError> {
         let value = Expr::Value(self.parse_number_value()?);
-        self.expect_one_of_keywords(&["ROW", "ROWS"])?;
+        self.expect_one_of_keywords(&[Keyword::Row, Keyword::Rows])?;
         Ok(value)
     }
 
     /// Parse a FETCH clause
     pub fn parse_fetch(&mut self) -> Result<Fetch, ParserError> {
-        self.expect_one_of_keywords(&["FIRST", "NEXT"])?;
-        let (quantity, percent) = if self.parse_one_of_keywords(&["ROW", "ROWS"]).is_some() {
+        self.expect_one_of_keywords(&[Keyword::First, Keyword::Next])?;
+        let (quantity, percent) = if self
+            .parse_one_of_keywords(&[Keyword::Row, Keyword::Rows])
+            .is_some()
+        {
             (None, false)
         } else {
             let quantity = Expr::Value(self.parse_value()?);
-            let percent = self.parse_keyword("PERCENT");
-            self.expect_one_of_keywords(&["ROW", "ROWS"])?;
+            let percent = self.parse_keyword(Keyword::Percent);
+            self.expect_one_of_keywords(&[Keyword::Row, Keyword::Rows])?;
             (Some(quantity), percent)
         };
-        let with_ties = if self.parse_keyword("ONLY") {
+        let with_ties = if self.parse_keyword(Keyword::Only) {
             false
-        } else if self.parse_keywords(vec!["WITH", "TIES"]) {
+        } else if self.parse_keywords(&[Keyword::With, Keyword::Ties]) {
             true
         } else {
-            return self.expected("one of ONLY or WITH TIES", self.peek_token());
+            return self.expected_one_of_next(&["ONLY", "WITH TIES"]);
         };
         Ok(Fetch {
             with_ties,
@@ -2465,24 +3249,26 @@ impl Parser {
     }
 
     pub fn parse_values(&mut self) -> Result<Values, ParserError> {
-        let values = self.parse_comma_separated(|parser| {
-            parser.expect_token(&Token::LParen)?;
-            let exprs = parser.parse_comma_separated(Parser::parse_expr)?;
-            parser.expect_token(&Token::RParen)?;
-            Ok(exprs)
+        let values = self.in_node(SyntaxKind::Values, |parser| {
+            parser.parse_comma_separated(|parser| {
+                parser.expect_token(&Token::LParen)?;
+                let exprs = parser.parse_comma_separated(Parser::parse_expr)?;
+                parser.expect_token(&Token::RParen)?;
+                Ok(exprs)
+            })
         })?;
         Ok(Values(values))
     }
 
     pub fn parse_start_transaction(&mut self) -> Result<Statement, ParserError> {
-        self.expect_keyword("TRANSACTION")?;
+        self.expect_keyword(Keyword::Transaction)?;
         Ok(Statement::StartTransaction {
             modes: self.parse_transaction_modes()?,
         })
     }
 
     pub fn parse_begin(&mut self) -> Result<Statement, ParserError> {
-        let _ = self.parse_one_of_keywords(&["TRANSACTION", "WORK"]);
+        let _ = self.parse_one_of_keywords(&[Keyword::Transaction, Keyword::Work]);
         Ok(Statement::StartTransaction {
             modes: self.parse_transaction_modes()?,
         })
@@ -2492,25 +3278,25 @@ impl Parser {
         let mut modes = vec![];
         let mut required = false;
         loop {
-            let mode = if self.parse_keywords(vec!["ISOLATION", "LEVEL"]) {
-                let iso_level = if self.parse_keywords(vec!["READ", "UNCOMMITTED"]) {
+            let mode = if self.parse_keywords(&[Keyword::Isolation, Keyword::Level]) {
+                let iso_level = if self.parse_keywords(&[Keyword::Read, Keyword::Uncommitted]) {
                     TransactionIsolationLevel::ReadUncommitted
-                } else if self.parse_keywords(vec!["READ", "COMMITTED"]) {
+                } else if self.parse_keywords(&[Keyword::Read, Keyword::Committed]) {
                     TransactionIsolationLevel::ReadCommitted
-                } else if self.parse_keywords(vec!["REPEATABLE", "READ"]) {
+                } else if self.parse_keywords(&[Keyword::Repeatable, Keyword::Read]) {
                     TransactionIsolationLevel::RepeatableRead
-                } else if self.parse_keyword("SERIALIZABLE") {
+                } else if self.parse_keyword(Keyword::Serializable) {
                     TransactionIsolationLevel::Serializable
                 } else {
-                    self.expected("isolation level", self.peek_token())?
+                    self.expected_next("isolation level")?
                 };
                 TransactionMode::IsolationLevel(iso_level)
-            } else if self.parse_keywords(vec!["READ", "ONLY"]) {
+            } else if self.parse_keywords(&[Keyword::Read, Keyword::Only]) {
                 TransactionMode::AccessMode(TransactionAccessMode::ReadOnly)
-            } else if self.parse_keywords(vec!["READ", "WRITE"]) {
+            } else if self.parse_keywords(&[Keyword::Read, Keyword::Write]) {
                 TransactionMode::AccessMode(TransactionAccessMode::ReadWrite)
             } else if required || self.peek_token().is_some() {
-                self.expected("transaction mode", self.peek_token())?
+                self.expected_next("transaction mode")?
             } else {
                 break;
             };
@@ -2537,10 +3323,10 @@ impl Parser {
     }
 
     pub fn parse_commit_rollback_chain(&mut self) -> Result<bool, ParserError> {
-        let _ = self.parse_one_of_keywords(&["TRANSACTION", "WORK"]);
-        if self.parse_keyword("AND") {
-            let chain = !self.parse_keyword("NO");
-            self.expect_keyword("CHAIN")?;
+        let _ = self.parse_one_of_keywords(&[Keyword::Transaction, Keyword::Work]);
+        if self.parse_keyword(Keyword::And) {
+            let chain = !self.parse_keyword(Keyword::No);
+            self.expect_keyword(Keyword::Chain)?;
             Ok(chain)
         } else {
             Ok(false)
@@ -2550,14 +3336,14 @@ impl Parser {
     /// Parse an `EXPLAIN [DATAFLOW | PLAN] FOR` statement, assuming that the `EXPLAIN` token
     /// has already been consumed.
     pub fn parse_explain(&mut self) -> Result<Statement, ParserError> {
-        let stage = if self.parse_keyword("DATAFLOW") {
+        let stage = if self.parse_keyword(Keyword::Dataflow) {
             Stage::Dataflow
-        } else if self.parse_keyword("PLAN") {
+        } else if self.parse_keyword(Keyword::Plan) {
             Stage::Plan
         } else {
-            self.expected("DATAFLOW or PLAN", self.peek_token())?
+            self.expected_next("DATAFLOW or PLAN")?
         };
-        self.expect_keyword("FOR")?;
+        self.expect_keyword(Keyword::For)?;
 
         Ok(Statement::Explain {
             stage,
@@ -2571,23 +3357,24 @@ impl Parser {
     /// This causes the source (or sources) to downgrade their capability(-ies),
     /// promising not to send any new data for the current timestamp
     pub fn parse_flush(&mut self) -> Result<Statement, ParserError> {
-        if self.parse_keywords(vec!["ALL", "SOURCES"]) {
+        if self.parse_keywords(&[Keyword::All, Keyword::Sources]) {
             Ok(Statement::FlushAllSources)
-        } else if self.parse_keyword("SOURCE") {
+        } else if self.parse_keyword(Keyword::Source) {
             Ok(Statement::FlushSource {
                 name: self.parse_object_name()?,
             })
         } else {
-            self.expected("ALL SOURCES or SOURCE", self.peek_token())?
+            self.expected_next("ALL SOURCES or SOURCE")?
         }
     }
 }
 
-impl Word {
+impl Word<'_> {
     pub fn to_ident(&self) -> Ident {
         Ident {
-            value: self.value.clone(),
+            value: self.value.to_string(),
             quote_style: self.quote_style,
+            span: Span::default(),
         }
     }
 }
@@ -2601,13 +3388,16 @@ mod tests {
     fn test_prev_index() {
         let sql = "SELECT version";
         all_dialects().run_parser_method(sql, |parser| {
-            assert_eq!(parser.peek_token(), Some(Token::make_keyword("SELECT")));
+            assert_eq!(parser.peek_token(), Some(&Token::make_keyword("SELECT")));
             assert_eq!(parser.next_token(), Some(Token::make_keyword("SELECT")));
             parser.prev_token();
             assert_eq!(parser.next_token(), Some(Token::make_keyword("SELECT")));
             assert_eq!(parser.next_token(), Some(Token::make_word("version", None)));
             parser.prev_token();
-            assert_eq!(parser.peek_token(), Some(Token::make_word("version", None)));
+            assert_eq!(
+                parser.peek_token(),
+                Some(&Token::make_word("version", None))
+            );
             assert_eq!(parser.next_token(), Some(Token::make_word("version", None)));
             assert_eq!(parser.peek_token(), None);
             parser.prev_token();
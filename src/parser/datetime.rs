@@ -6,12 +6,12 @@ pub(crate) fn tokenize_interval(value: &str) -> Result<Vec<IntervalToken>, Parse
     let mut num_buf = String::with_capacity(4);
     fn parse_num(n: &str, idx: usize) -> Result<IntervalToken, ParserError> {
         Ok(IntervalToken::Num(n.parse().map_err(|e| {
-            ParserError::ParserError(format!(
+            ParserError::new(format!(
                 "Unable to parse value as a number at index {}: {}",
                 idx, e
             ))
         })?))
-    };
+    }
     let mut last_field_is_frac = false;
     for (i, chr) in value.chars().enumerate() {
         match chr {
@@ -39,9 +39,9 @@ pub(crate) fn tokenize_interval(value: &str) -> Result<Vec<IntervalToken>, Parse
                 toks.push(IntervalToken::Dot);
                 last_field_is_frac = true;
             }
-            chr if chr.is_digit(10) => num_buf.push(chr),
+            chr if chr.is_ascii_digit() => num_buf.push(chr),
             chr => {
-                return Err(ParserError::TokenizerError(format!(
+                return Err(ParserError::new(format!(
                     "Invalid character at offset {} in {}: {:?}",
                     i, value, chr
                 )))
@@ -53,7 +53,7 @@ pub(crate) fn tokenize_interval(value: &str) -> Result<Vec<IntervalToken>, Parse
             toks.push(parse_num(&num_buf, 0)?);
         } else {
             let raw: u32 = num_buf.parse().map_err(|e| {
-                ParserError::ParserError(format!(
+                ParserError::new(format!(
                     "couldn't parse fraction of second {}: {}",
                     num_buf, e
                 ))
@@ -87,7 +87,7 @@ fn tokenize_timezone(value: &str) -> Result<Vec<IntervalToken>, ParserError> {
             return Ok(());
         }
 
-        let (first, second) = if n.len() > 2 && split_nums == true {
+        let (first, second) = if n.len() > 2 && split_nums {
             let (first, second) = n.split_at(n.len() - 2);
             (first, Some(second))
         } else {
@@ -95,7 +95,7 @@ fn tokenize_timezone(value: &str) -> Result<Vec<IntervalToken>, ParserError> {
         };
 
         toks.push(IntervalToken::Num(first.parse().map_err(|e| {
-                ParserError::ParserError(format!(
+                ParserError::new(format!(
                     "Error tokenizing timezone string: unable to parse value {} as a number at index {}: {}",
                     first, idx, e
                 ))
@@ -103,7 +103,7 @@ fn tokenize_timezone(value: &str) -> Result<Vec<IntervalToken>, ParserError> {
 
         if let Some(second) = second {
             toks.push(IntervalToken::Num(second.parse().map_err(|e| {
-                ParserError::ParserError(format!(
+                ParserError::new(format!(
                     "Error tokenizing timezone string: unable to parse value {} as a number at index {}: {}",
                     second, idx, e
                 ))
@@ -111,8 +111,8 @@ fn tokenize_timezone(value: &str) -> Result<Vec<IntervalToken>, ParserError> {
         }
 
         Ok(())
-    };
-    for (i, chr) in value.chars().enumerate() {
+    }
+    for (i, chr) in value.char_indices() {
         match chr {
             '-' => {
                 parse_num(&mut toks, &num_buf, split_nums, i)?;
@@ -139,7 +139,7 @@ fn tokenize_timezone(value: &str) -> Result<Vec<IntervalToken>, ParserError> {
                 num_buf.clear();
                 toks.push(IntervalToken::Zulu);
             }
-            chr if chr.is_digit(10) => num_buf.push(chr),
+            chr if chr.is_ascii_digit() => num_buf.push(chr),
             chr if chr.is_ascii_alphabetic() => {
                 parse_num(&mut toks, &num_buf, split_nums, i)?;
                 let substring = &value[i..];
@@ -147,7 +147,7 @@ fn tokenize_timezone(value: &str) -> Result<Vec<IntervalToken>, ParserError> {
                 return Ok(toks);
             }
             chr => {
-                return Err(ParserError::TokenizerError(format!(
+                return Err(ParserError::new(format!(
                     "Error tokenizing timezone string ({}): invalid character {:?} at offset {}",
                     value, chr, i
                 )))
@@ -194,10 +194,7 @@ fn potential_interval_tokens(from: &DateTimeField) -> Vec<IntervalToken> {
     all_toks[offset..].to_vec()
 }
 
-fn build_timezone_offset_second(
-    tokens: &[IntervalToken],
-    value: &str,
-) -> Result<i64, ParserError> {
+fn build_timezone_offset_second(tokens: &[IntervalToken], value: &str) -> Result<i64, ParserError> {
     use IntervalToken::*;
     let all_formats = [
         vec![Plus, Num(0), Colon, Num(0)],
@@ -235,7 +232,7 @@ fn build_timezone_offset_second(
                         } else {
                             // We can return an error here because in all the formats with numbers
                             // we require the first number to be an hour and we require it to be <= 24
-                            return Err(ParserError::ParserError(format!(
+                            return Err(ParserError::new(format!(
                                 "Error parsing timezone string ({}): timezone hour invalid {}",
                                 value, val
                             )));
@@ -243,23 +240,23 @@ fn build_timezone_offset_second(
                         (Some(_), None) => if val <= 60 {
                             minute_offset = Some(val as i64);
                         } else {
-                            return Err(ParserError::ParserError(format!(
+                            return Err(ParserError::new(format!(
                                 "Error parsing timezone string ({}): timezone minute invalid {}",
                                 value, val
                             )));
                         },
                         // We've already seen an hour and a minute so we should never see another number
-                        (Some(_), Some(_)) => return Err(ParserError::ParserError(format!(
+                        (Some(_), Some(_)) => return Err(ParserError::new(format!(
                             "Error parsing timezone string ({}): invalid value {} at token index {}", value,
                             val, i
                         ))),
                         (None, Some(_)) => unreachable!("parsed a minute before an hour!"),
                     }
                 }
-                (Zulu, Zulu) => return Ok(0 as i64),
+                (Zulu, Zulu) => return Ok(0_i64),
                 (TzName(val), TzName(_)) => {
                     // For now, we don't support named timezones
-                    return Err(ParserError::ParserError(format!(
+                    return Err(ParserError::new(format!(
                         "Error parsing timezone string ({}): named timezones are not supported. \
                          Failed to parse {} at token index {}",
                         value, val, i
@@ -291,7 +288,7 @@ fn build_timezone_offset_second(
         }
     }
 
-    return Err(ParserError::ParserError(format!("It didnt work")));
+    Err(ParserError::new("It didnt work".to_string()))
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -315,7 +312,7 @@ pub(crate) fn build_parsed_datetime(
 ) -> Result<ParsedDateTime, ParserError> {
     use IntervalToken::*;
 
-    let expected = potential_interval_tokens(&leading_field);
+    let expected = potential_interval_tokens(leading_field);
     let mut actual = tokens.iter().peekable();
 
     let is_positive = match actual.peek() {
@@ -413,7 +410,7 @@ pub(crate) fn split_timestamp_string(value: &str) -> (&str, &str) {
             }
         }
 
-        return (value.trim(), "");
+        (value.trim(), "")
     } else {
         // We don't have a time, so the only formats available are
         // YYY-mm-dd<tz> or YYYY-MM-dd <tz>
@@ -425,13 +422,13 @@ pub(crate) fn split_timestamp_string(value: &str) -> (&str, &str) {
             return (first.trim(), second.trim());
         }
 
-        return (value.trim(), "");
+        (value.trim(), "")
     }
 }
 
 pub(crate) fn parse_timezone_offset_second(value: &str) -> Result<i64, ParserError> {
     let toks = tokenize_timezone(value)?;
-    Ok(build_timezone_offset_second(&toks, value)?)
+    build_timezone_offset_second(&toks, value)
 }
 
 #[cfg(test)]
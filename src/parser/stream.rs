@@ -0,0 +1,339 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Parsing statements one at a time from a reader, for SQL files that are
+//! too large to hold in memory, such as database dumps.
+
+use std::collections::VecDeque;
+use std::io::BufRead;
+
+use crate::ast::Statement;
+use crate::dialect::Dialect;
+use crate::parser::{Parser, ParserError, ParserErrorKind, ParserOptions};
+use crate::tokenizer::{Location, Span, Token, TokenWithSpan, Tokenizer};
+
+/// An iterator over the statements of the SQL read from `reader`, which
+/// parses one statement at a time, so that only the text of the statement
+/// being parsed is kept in memory.
+///
+/// The text is read a line at a time, and split into statements at each `;`
+/// outside of string literals, quoted identifiers and comments. The data of
+/// a `COPY ... FROM stdin;` statement is read up to the `\.` line that ends
+/// it, without tokenizing it. The spans of identifiers and errors are
+/// locations in the whole text, as with `Parser::parse_sql`.
+///
+/// A statement that fails to parse yields an error, after which the next
+/// statement is parsed. Errors reading the input or splitting it into tokens
+/// end the iteration, as the end of the statement is not known.
+pub struct StatementStream<'a, R> {
+    reader: R,
+    dialect: &'a dyn Dialect,
+    options: ParserOptions,
+    /// The text that has been read, whose first `start` bytes have been
+    /// parsed
+    buffer: String,
+    start: usize,
+    /// The location of `buffer[start..]` in the whole text
+    location: Location,
+    /// The tokens of `buffer[start..tokenized]`, located in the whole text,
+    /// of which the first `scanned` are known not to be a `;`
+    tokens: VecDeque<TokenWithSpan<'static>>,
+    tokenized: usize,
+    scanned: usize,
+    /// The location of `buffer[tokenized..]` in the whole text
+    tokenized_location: Location,
+    /// The byte offset in `buffer` up to which it is known not to contain a
+    /// complete statement
+    checked: usize,
+    /// The text of the line containing `buffer[start..]` that precedes it, to
+    /// display the location of errors
+    line_prefix: String,
+    /// A `COPY ... FROM stdin` statement whose data is being read
+    copy: Option<Statement>,
+    eof: bool,
+    done: bool,
+}
+
+impl<'a, R: BufRead> StatementStream<'a, R> {
+    /// Create a stream of the statements read from `reader`, which contains
+    /// SQL in `dialect`
+    pub fn new(dialect: &'a dyn Dialect, reader: R) -> Self {
+        StatementStream {
+            reader,
+            dialect,
+            options: ParserOptions::default(),
+            buffer: String::new(),
+            start: 0,
+            location: Location::start(),
+            tokens: VecDeque::new(),
+            tokenized: 0,
+            scanned: 0,
+            tokenized_location: Location::start(),
+            checked: 0,
+            line_prefix: String::new(),
+            copy: None,
+            eof: false,
+            done: false,
+        }
+    }
+
+    /// Set the options that control how each statement is parsed
+    pub fn with_options(mut self, options: ParserOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Read the next line into `buffer`, setting `eof` if there is none
+    fn read_line(&mut self) -> Result<(), ParserError> {
+        // Drop the text that has been parsed
+        self.buffer.drain(..self.start);
+        self.tokenized -= self.start;
+        self.checked -= self.start;
+        self.start = 0;
+        match self.reader.read_line(&mut self.buffer) {
+            Ok(0) => self.eof = true,
+            Ok(_) => (),
+            Err(e) => {
+                let mut err = ParserError::new(e.to_string());
+                err.kind = ParserErrorKind::Io;
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
+
+    /// Parse the next statement from `buffer`, or return `None` if it does
+    /// not contain a complete statement yet.
+    ///
+    /// The text is only tokenized once, when a line that may end a statement
+    /// has been read, and the tokens that follow a statement are kept for
+    /// the next one, so that parsing takes linear time even when a line holds
+    /// many statements.
+    fn parse_buffered(&mut self) -> Option<Result<Statement, ParserError>> {
+        loop {
+            let semicolon = self
+                .tokens
+                .iter()
+                .skip(self.scanned)
+                .position(|t| t.token == Token::SemiColon);
+            if let Some(i) = semicolon {
+                let end = self.tokens[self.scanned + i].span.end.offset;
+                let tokens = self.tokens.drain(..=self.scanned + i).collect();
+                let result = self.parse_tokens(tokens);
+                self.consume(end - self.location.offset);
+                // Skip empty statements
+                if result.is_some() {
+                    return result;
+                }
+                continue;
+            }
+            self.scanned = self.tokens.len();
+            if self.tokenized == self.buffer.len() {
+                if !self.eof || self.tokens.is_empty() {
+                    self.checked = self.buffer.len();
+                    return None;
+                }
+                // The rest of the text is the last statement
+                let tokens = self.tokens.drain(..).collect();
+                let result = self.parse_tokens(tokens);
+                self.consume(self.buffer.len() - self.start);
+                return result;
+            }
+            if !self.eof && !self.buffer[self.checked..].contains(';') {
+                self.checked = self.buffer.len();
+                return None;
+            }
+            let text = &self.buffer[self.tokenized..];
+            match Tokenizer::new(self.dialect, text).tokenize_with_span() {
+                // An unterminated string, quoted identifier or comment may be
+                // completed by the lines that follow
+                Err(e) if e.location.offset == text.len() && !self.eof => {
+                    self.checked = self.buffer.len();
+                    return None;
+                }
+                Err(e) => {
+                    self.done = true;
+                    let mut err = ParserError::from(e);
+                    err.span = relocate_span(err.span, self.tokenized_location);
+                    return Some(Err(self.with_source(err)));
+                }
+                Ok(tokens) => {
+                    let location = self.tokenized_location;
+                    self.tokens
+                        .extend(tokens.into_iter().map(|t| TokenWithSpan {
+                            token: t.token.into_owned(),
+                            span: relocate_span(t.span, location),
+                        }));
+                    self.tokenized_location.advance(text);
+                    self.tokenized = self.buffer.len();
+                    self.checked = self.buffer.len();
+                }
+            }
+        }
+    }
+
+    /// Parse `tokens`, which are those of the statement at the start of
+    /// `buffer[start..]`, and its `;` if any
+    fn parse_tokens(
+        &self,
+        tokens: Vec<TokenWithSpan<'static>>,
+    ) -> Option<Result<Statement, ParserError>> {
+        match Parser::new_with_spans(tokens, self.dialect)
+            .with_options(self.options.clone())
+            .parse_statements()
+        {
+            Ok(mut stmts) => stmts.pop().map(Ok),
+            Err(e) => Some(Err(self.with_source(e))),
+        }
+    }
+
+    /// Read a line of the data of the `COPY` statement in `copy`, returning
+    /// the statement once all of its data has been read
+    fn read_copy_data(&mut self) -> Result<Option<Statement>, ParserError> {
+        // Skip the previous line, i.e. the rest of the line containing the
+        // statement, after which the data starts, or the last line of data
+        self.consume(self.buffer.len() - self.start);
+        self.read_line()?;
+        let line = self.buffer.trim_end_matches(&['\n', '\r'][..]);
+        if self.eof {
+            return Ok(self.copy.take());
+        } else if line == "\\." {
+            self.consume(self.buffer.len() - self.start);
+            return Ok(self.copy.take());
+        }
+        if let Some(Statement::Copy { values, .. }) = &mut self.copy {
+            values.extend(line.split('\t').map(|v| match v {
+                "\\N" => None,
+                v => Some(v.to_string()),
+            }));
+        }
+        Ok(None)
+    }
+
+    /// Skip the first `len` bytes of `buffer[start..]`, along with their
+    /// tokens
+    fn consume(&mut self, len: usize) {
+        let consumed = &self.buffer[self.start..self.start + len];
+        self.location.advance(consumed);
+        match consumed.rfind(&['\n', '\r'][..]) {
+            Some(i) => {
+                self.line_prefix.clear();
+                self.line_prefix.push_str(&consumed[i + 1..]);
+            }
+            None => self.line_prefix.push_str(consumed),
+        }
+        self.start += len;
+        while matches!(self.tokens.front(), Some(t) if t.span.start.offset < self.location.offset) {
+            self.tokens.pop_front();
+        }
+        self.scanned = 0;
+        if self.tokenized < self.start {
+            self.tokenized = self.start;
+            self.tokenized_location = self.location;
+        }
+        self.checked = self.checked.max(self.start);
+    }
+
+    /// Set the `source_line` of `err`, which is located in `buffer`
+    fn with_source(&self, mut err: ParserError) -> ParserError {
+        if err.span.is_empty() {
+            return err;
+        }
+        let span = err.span;
+        err.span = Span::new(
+            unrelocate(span.start, self.location),
+            unrelocate(span.end, self.location),
+        );
+        let first_line = err.span.start.line == 1;
+        err = err.with_source(&self.buffer[self.start..]);
+        if first_line {
+            if let Some(line) = &mut err.source_line {
+                line.insert_str(0, &self.line_prefix);
+            }
+        }
+        err.span = span;
+        err
+    }
+}
+
+impl<'a, R: BufRead> Iterator for StatementStream<'a, R> {
+    type Item = Result<Statement, ParserError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            let result = if self.copy.is_some() {
+                match self.read_copy_data() {
+                    Ok(None) => continue,
+                    result => result.transpose(),
+                }
+            } else {
+                match self.parse_buffered() {
+                    Some(Ok(stmt @ Statement::Copy { .. })) => {
+                        self.copy = Some(stmt);
+                        continue;
+                    }
+                    None if self.eof => {
+                        self.done = true;
+                        continue;
+                    }
+                    None => match self.read_line() {
+                        Ok(()) => continue,
+                        Err(e) => Some(Err(e)),
+                    },
+                    result => result,
+                }
+            };
+            if let Some(Err(e)) = &result {
+                if e.kind == ParserErrorKind::Io {
+                    self.done = true;
+                }
+            }
+            return result;
+        }
+        None
+    }
+}
+
+/// Return the location in the whole text of `location`, a location in the
+/// text starting at `start`
+fn relocate(location: Location, start: Location) -> Location {
+    Location {
+        offset: start.offset + location.offset,
+        line: start.line + location.line - 1,
+        column: if location.line == 1 {
+            start.column + location.column - 1
+        } else {
+            location.column
+        },
+    }
+}
+
+/// The inverse of `relocate`
+fn unrelocate(location: Location, start: Location) -> Location {
+    Location {
+        offset: location.offset - start.offset,
+        line: location.line + 1 - start.line,
+        column: if location.line == start.line {
+            location.column + 1 - start.column
+        } else {
+            location.column
+        },
+    }
+}
+
+fn relocate_span(span: Span, start: Location) -> Span {
+    if span.is_empty() {
+        span
+    } else {
+        Span::new(relocate(span.start, start), relocate(span.end, start))
+    }
+}
@@ -0,0 +1,479 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Splitting SQL scripts into statements
+//!
+//! [`split`] finds where each statement of a script starts and ends without
+//! parsing it, so that a tool can run the statements one at a time, and
+//! report errors at their location in the script. Besides the statements, a
+//! script may contain commands for the client that runs it, which `split`
+//! recognizes if the dialect supports them:
+//!
+//! - `DELIMITER //` lines, which change the delimiter that ends statements,
+//!   e.g. to write procedures whose body contains `;`, as in the MySQL client
+//!   (`Dialect::supports_delimiter_command`);
+//! - `GO` lines, which end a batch of statements, as in the MS SQL tools
+//!   (`Dialect::supports_go_batch_separator`);
+//! - backslash meta-commands, such as `\connect db`, which extend to the end
+//!   of the line and end the statement before them, as `\g` does, and the
+//!   data of `COPY ... FROM stdin` statements, up to the `\.` line that ends
+//!   it, as in psql (`Dialect::supports_backslash_commands`).
+//!
+//! Delimiters are only recognized outside of string literals (including
+//! dollar-quoted ones), quoted identifiers and comments, which are found by
+//! the `Tokenizer`.
+
+use crate::dialect::keywords::Keyword;
+use crate::dialect::Dialect;
+use crate::tokenizer::{Location, Span, Token, TokenWithSpan, Tokenizer, TokenizerError, Word};
+
+/// A statement or client command of a script, as found by [`split`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptItem {
+    pub kind: ScriptItemKind,
+    /// The location of the item in the script, without the surrounding
+    /// whitespace and comments, or the delimiter ending a statement
+    pub span: Span,
+}
+
+impl ScriptItem {
+    /// The text of the item in `script`, the script it was found in
+    pub fn text<'a>(&self, script: &'a str) -> &'a str {
+        &script[self.span.start.offset..self.span.end.offset]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScriptItemKind {
+    /// A SQL statement
+    Statement,
+    /// A `DELIMITER` command, with the delimiter that ends the statements
+    /// after it
+    Delimiter(String),
+    /// A `GO` batch separator, with the number of times to run the batch,
+    /// e.g. 5 for `GO 5`
+    BatchSeparator(u64),
+    /// A psql meta-command, e.g. `\connect db`
+    MetaCommand,
+    /// The data of the preceding `COPY ... FROM stdin` statement, a line per
+    /// row, without the `\.` line that ends it
+    CopyData,
+}
+
+/// Split `script`, which contains SQL in `dialect`, into statements and
+/// client commands. Fails if the script can't be tokenized, e.g. because
+/// of an unterminated quoted identifier.
+pub fn split(dialect: &dyn Dialect, script: &str) -> Result<Vec<ScriptItem>, TokenizerError> {
+    let mut splitter = Splitter {
+        dialect,
+        script,
+        delimiter: ";".to_string(),
+        items: vec![],
+    };
+    let mut location = Location::start();
+    while let Some(next) = splitter.split_statement(location)? {
+        location = next;
+    }
+    Ok(splitter.items)
+}
+
+struct Splitter<'a> {
+    dialect: &'a dyn Dialect,
+    script: &'a str,
+    /// The delimiter that ends statements, as set by `DELIMITER` commands
+    delimiter: String,
+    items: Vec<ScriptItem>,
+}
+
+/// The statement being split, from its first token that is not whitespace
+/// or a comment to its last
+struct PendingStatement {
+    span: Option<Span>,
+    first_keyword: Keyword,
+    /// The keywords of the last two tokens, `Keyword::NoKeyword` if they are
+    /// not keywords, to recognize `COPY ... FROM stdin`
+    last_keywords: [Keyword; 2],
+}
+
+impl PendingStatement {
+    fn push(&mut self, token: &TokenWithSpan, end: Location) {
+        let keyword = match &token.token {
+            Token::Word(Word { keyword, .. }) => *keyword,
+            _ => Keyword::NoKeyword,
+        };
+        match &mut self.span {
+            Some(span) => span.end = end,
+            None => {
+                self.span = Some(Span::new(token.span.start, end));
+                self.first_keyword = keyword;
+            }
+        }
+        self.last_keywords = [self.last_keywords[1], keyword];
+    }
+
+    fn is_copy_from_stdin(&self) -> bool {
+        self.first_keyword == Keyword::Copy && self.last_keywords == [Keyword::From, Keyword::Stdin]
+    }
+}
+
+impl<'a> Splitter<'a> {
+    /// Split the statement starting at `start`, as well as the client
+    /// commands after it, returning the location after them, or `None` at
+    /// the end of the script
+    fn split_statement(&mut self, start: Location) -> Result<Option<Location>, TokenizerError> {
+        let mut statement = PendingStatement {
+            span: None,
+            first_keyword: Keyword::NoKeyword,
+            last_keywords: [Keyword::NoKeyword; 2],
+        };
+        let tokenizer = Tokenizer::new(self.dialect, self.script);
+        let mut tokens = tokenizer.tokens_from(start);
+        let mut boundary = start;
+        loop {
+            // Look for a command or delimiter before tokenizing what follows,
+            // as e.g. a `$$` delimiter would start a dollar-quoted string
+            if let Some((item, next)) = self.command_at(boundary, statement.span.is_none()) {
+                self.push_statement(&statement);
+                if let ScriptItemKind::Delimiter(delimiter) = &item.kind {
+                    self.delimiter = delimiter.clone();
+                }
+                self.items.push(item);
+                return Ok(Some(next));
+            }
+            if self.script[boundary.offset..].starts_with(&self.delimiter) {
+                return self.end_statement(&statement, boundary).map(Some);
+            }
+            let token = match tokens.next() {
+                Some(token) => token?,
+                None => {
+                    self.push_statement(&statement);
+                    return Ok(None);
+                }
+            };
+            if let Token::Whitespace(_) = token.token {
+                boundary = token.span.end;
+                continue;
+            }
+            // The delimiter may also start inside a token, e.g. in `END$$`,
+            // unless the token is a literal or a quoted identifier
+            let text = &self.script[token.span.start.offset..token.span.end.offset];
+            let is_literal = match &token.token {
+                Token::Word(w) => w.quote_style.is_some(),
+                Token::SingleQuotedString(_)
+                | Token::NationalStringLiteral(_)
+                | Token::HexStringLiteral(_)
+                | Token::DollarQuotedString { .. } => true,
+                _ => false,
+            };
+            let delimiter_offset = if is_literal {
+                None
+            } else {
+                text.char_indices()
+                    .skip(1)
+                    .map(|(i, _)| i)
+                    .find(|&i| text[i..].starts_with(&self.delimiter))
+            };
+            if let Some(i) = delimiter_offset {
+                let mut end = token.span.start;
+                end.advance(&text[..i]);
+                statement.push(&token, end);
+                return self.end_statement(&statement, end).map(Some);
+            }
+            statement.push(&token, token.span.end);
+            boundary = token.span.end;
+        }
+    }
+
+    /// End `statement` at the delimiter at `delimiter`, returning the location
+    /// after the delimiter, or after the data following it if the statement
+    /// is a `COPY ... FROM stdin`
+    fn end_statement(
+        &mut self,
+        statement: &PendingStatement,
+        delimiter: Location,
+    ) -> Result<Location, TokenizerError> {
+        self.push_statement(statement);
+        let mut next = delimiter;
+        next.advance(&self.delimiter);
+        if self.dialect.supports_backslash_commands() && statement.is_copy_from_stdin() {
+            next = self.split_copy_data(next);
+        }
+        Ok(next)
+    }
+
+    fn push_statement(&mut self, statement: &PendingStatement) {
+        if let Some(span) = statement.span {
+            self.items.push(ScriptItem {
+                kind: ScriptItemKind::Statement,
+                span,
+            });
+        }
+    }
+
+    /// Find the data of a `COPY ... FROM stdin` statement ending at `start`,
+    /// which starts on the next line, returning the location after it
+    fn split_copy_data(&mut self, start: Location) -> Location {
+        let mut location = start;
+        location.advance(line_at(self.script, start.offset));
+        location.advance(line_break_at(self.script, location.offset));
+        let data_start = location;
+        while location.offset < self.script.len() {
+            let line = line_at(self.script, location.offset);
+            if line == "\\." {
+                self.push_copy_data(data_start, location);
+                location.advance(line);
+                return location;
+            }
+            location.advance(line);
+            location.advance(line_break_at(self.script, location.offset));
+        }
+        self.push_copy_data(data_start, location);
+        location
+    }
+
+    fn push_copy_data(&mut self, start: Location, end: Location) {
+        self.items.push(ScriptItem {
+            kind: ScriptItemKind::CopyData,
+            span: Span::new(start, end),
+        });
+    }
+
+    /// Recognize a client command at `location`, returning it along with the
+    /// location after it. `DELIMITER` commands are only recognized at the
+    /// start of a statement.
+    fn command_at(
+        &self,
+        location: Location,
+        at_statement_start: bool,
+    ) -> Option<(ScriptItem, Location)> {
+        // Only look at the rest of the line where a command can start, as
+        // this is called at every token boundary.
+        let at_backslash = self.script.as_bytes().get(location.offset) == Some(&b'\\');
+        if location.column != 1 && !at_backslash {
+            return None;
+        }
+        let line = line_at(self.script, location.offset);
+        if at_backslash && self.dialect.supports_backslash_commands() {
+            let mut end = location;
+            end.advance(line.trim_end());
+            let item = ScriptItem {
+                kind: ScriptItemKind::MetaCommand,
+                span: Span::new(location, end),
+            };
+            return Some((item, end));
+        }
+        // The other commands take a line of their own
+        if location.column != 1 {
+            return None;
+        }
+        let command = line.trim();
+        let mut words = command.split_whitespace();
+        let kind = match (words.next(), words.next(), words.next()) {
+            (Some(go), count, None)
+                if go.eq_ignore_ascii_case("GO") && self.dialect.supports_go_batch_separator() =>
+            {
+                match count {
+                    None => ScriptItemKind::BatchSeparator(1),
+                    Some(count) => ScriptItemKind::BatchSeparator(count.parse().ok()?),
+                }
+            }
+            (Some(delimiter), Some(new_delimiter), None)
+                if delimiter.eq_ignore_ascii_case("DELIMITER")
+                    && at_statement_start
+                    && self.dialect.supports_delimiter_command() =>
+            {
+                ScriptItemKind::Delimiter(new_delimiter.to_string())
+            }
+            _ => return None,
+        };
+        let mut start = location;
+        start.advance(&line[..line.len() - line.trim_start().len()]);
+        let mut end = start;
+        end.advance(command);
+        Some((
+            ScriptItem {
+                kind,
+                span: Span::new(start, end),
+            },
+            end,
+        ))
+    }
+}
+
+/// The rest of the line of `script` starting at byte offset `offset`,
+/// without the line break
+fn line_at(script: &str, offset: usize) -> &str {
+    let rest = &script[offset..];
+    &rest[..rest.find(&['\n', '\r'][..]).unwrap_or(rest.len())]
+}
+
+/// The line break at byte offset `offset` of `script`, if any
+fn line_break_at(script: &str, offset: usize) -> &str {
+    let rest = &script[offset..];
+    if rest.starts_with("\r\n") {
+        &rest[..2]
+    } else if rest.starts_with('\n') || rest.starts_with('\r') {
+        &rest[..1]
+    } else {
+        ""
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::{GenericDialect, MsSqlDialect, MySqlDialect, PostgreSqlDialect};
+
+    /// Split `script`, returning the kind and text of each item
+    fn split_text<'a>(dialect: &dyn Dialect, script: &'a str) -> Vec<(ScriptItemKind, &'a str)> {
+        split(dialect, script)
+            .unwrap()
+            .into_iter()
+            .map(|item| (item.kind.clone(), item.text(script)))
+            .collect()
+    }
+
+    fn statements(texts: &[&'static str]) -> Vec<(ScriptItemKind, &'static str)> {
+        texts
+            .iter()
+            .map(|text| (ScriptItemKind::Statement, *text))
+            .collect()
+    }
+
+    #[test]
+    fn split_statements() {
+        let script = "SELECT 'a;b', \"c;\" -- d;\nFROM t;\n\n/* e; */ ;; SELECT $$f;$$;SELECT 1";
+        assert_eq!(
+            statements(&[
+                "SELECT 'a;b', \"c;\" -- d;\nFROM t",
+                "SELECT $$f;$$",
+                "SELECT 1"
+            ]),
+            split_text(&GenericDialect {}, script)
+        );
+        assert!(split(&GenericDialect {}, " -- nothing\n")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn split_spans() {
+        let items = split(&GenericDialect {}, "SELECT 1;\n  SELECT 2").unwrap();
+        assert_eq!(
+            Span::new(
+                Location {
+                    offset: 12,
+                    line: 2,
+                    column: 3
+                },
+                Location {
+                    offset: 20,
+                    line: 2,
+                    column: 11
+                }
+            ),
+            items[1].span
+        );
+    }
+
+    #[test]
+    fn split_tokenizer_error() {
+        assert!(split(&GenericDialect {}, "SELECT 1; SELECT \"a").is_err());
+    }
+
+    #[test]
+    fn split_mysql_delimiter() {
+        let script = "DELIMITER //\n\
+                      CREATE PROCEDURE p() BEGIN SELECT 1; SELECT '//'; END//\n\
+                      delimiter $$\n\
+                      SELECT 2$$ SELECT 3 $$\n\
+                      DELIMITER ;\n\
+                      SELECT 4;";
+        assert_eq!(
+            vec![
+                (ScriptItemKind::Delimiter("//".into()), "DELIMITER //"),
+                (
+                    ScriptItemKind::Statement,
+                    "CREATE PROCEDURE p() BEGIN SELECT 1; SELECT '//'; END"
+                ),
+                (ScriptItemKind::Delimiter("$$".into()), "delimiter $$"),
+                (ScriptItemKind::Statement, "SELECT 2"),
+                (ScriptItemKind::Statement, "SELECT 3"),
+                (ScriptItemKind::Delimiter(";".into()), "DELIMITER ;"),
+                (ScriptItemKind::Statement, "SELECT 4"),
+            ],
+            split_text(&MySqlDialect {}, script)
+        );
+        // Other dialects have no `DELIMITER` command
+        assert_eq!(
+            statements(&["DELIMITER //\nSELECT 1", "SELECT 2"]),
+            split_text(&MsSqlDialect {}, "DELIMITER //\nSELECT 1; SELECT 2")
+        );
+    }
+
+    #[test]
+    fn split_mssql_batches() {
+        let script = "SELECT 1\nSELECT 2\n  go\nSELECT 3; SELECT 4\nGO 5\nSELECT 'GO\nGO'\nGOTO";
+        assert_eq!(
+            vec![
+                (ScriptItemKind::Statement, "SELECT 1\nSELECT 2"),
+                (ScriptItemKind::BatchSeparator(1), "go"),
+                (ScriptItemKind::Statement, "SELECT 3"),
+                (ScriptItemKind::Statement, "SELECT 4"),
+                (ScriptItemKind::BatchSeparator(5), "GO 5"),
+                (ScriptItemKind::Statement, "SELECT 'GO\nGO'\nGOTO"),
+            ],
+            split_text(&MsSqlDialect {}, script)
+        );
+    }
+
+    #[test]
+    fn split_psql_commands() {
+        let script = "\\set ON_ERROR_STOP on\n\
+                      SELECT 1 \\gset\n\
+                      COPY t (a) FROM stdin;\n\
+                      1\n\
+                      2;'\n\
+                      \\.\n\
+                      \\connect db\n\
+                      SELECT 2;";
+        assert_eq!(
+            vec![
+                (ScriptItemKind::MetaCommand, "\\set ON_ERROR_STOP on"),
+                (ScriptItemKind::Statement, "SELECT 1"),
+                (ScriptItemKind::MetaCommand, "\\gset"),
+                (ScriptItemKind::Statement, "COPY t (a) FROM stdin"),
+                (ScriptItemKind::CopyData, "1\n2;'\n"),
+                (ScriptItemKind::MetaCommand, "\\connect db"),
+                (ScriptItemKind::Statement, "SELECT 2"),
+            ],
+            split_text(&PostgreSqlDialect {}, script)
+        );
+    }
+
+    #[test]
+    fn split_long_line() {
+        // mysqldump writes each table's rows as a single `INSERT` line.
+        let values = vec!["(1, 'a')"; 100_000].join(",");
+        let script = format!(
+            "INSERT INTO t VALUES {};INSERT INTO t VALUES (2, 'b');",
+            values
+        );
+        let items = split_text(&MySqlDialect {}, &script);
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].1.len(), script.find(';').unwrap());
+        assert_eq!(
+            items[1],
+            (ScriptItemKind::Statement, "INSERT INTO t VALUES (2, 'b')")
+        );
+    }
+}
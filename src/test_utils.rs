@@ -14,6 +14,7 @@ use std::fmt::Debug;
 
 use super::ast::*;
 use super::dialect::*;
+use super::formatter::{format_statement, CommaStyle, FormatOptions, KeywordCase};
 use super::parser::{Parser, ParserError};
 use super::tokenizer::Tokenizer;
 
@@ -26,9 +27,9 @@ pub struct TestedDialects {
 impl TestedDialects {
     /// Run the given function for all of `self.dialects`, assert that they
     /// return the same result, and return that result.
-    pub fn one_of_identical_results<F, T: Debug + PartialEq>(&self, f: F) -> T
+    pub fn one_of_identical_results<'a, F, T: Debug + PartialEq>(&'a self, f: F) -> T
     where
-        F: Fn(&dyn Dialect) -> T,
+        F: Fn(&'a dyn Dialect) -> T,
     {
         let parse_results = self.dialects.iter().map(|dialect| (dialect, f(&**dialect)));
         parse_results
@@ -46,14 +47,14 @@ impl TestedDialects {
             .1
     }
 
-    pub fn run_parser_method<F, T: Debug + PartialEq>(&self, sql: &str, f: F) -> T
+    pub fn run_parser_method<'a, F, T: Debug + PartialEq>(&'a self, sql: &'a str, f: F) -> T
     where
-        F: Fn(&mut Parser) -> T,
+        F: Fn(&mut Parser<'a>) -> T,
     {
         self.one_of_identical_results(|dialect| {
             let mut tokenizer = Tokenizer::new(dialect, sql);
-            let tokens = tokenizer.tokenize().unwrap();
-            f(&mut Parser::new(tokens))
+            let tokens = tokenizer.tokenize_with_span().unwrap();
+            f(&mut Parser::new_with_spans(tokens, dialect))
         })
     }
 
@@ -67,16 +68,52 @@ impl TestedDialects {
     /// that converting AST back to string equals to `canonical` (unless an
     /// empty canonical string is provided).
     pub fn one_statement_parses_to(&self, sql: &str, canonical: &str) -> Statement {
-        let mut statements = self.parse_sql_statements(&sql).unwrap();
+        let mut statements = self.parse_sql_statements(sql).unwrap();
         assert_eq!(statements.len(), 1);
+        self.assert_lossless(sql, &statements);
 
         let only_statement = statements.pop().unwrap();
         if !canonical.is_empty() {
-            assert_eq!(canonical, only_statement.to_string())
+            assert_eq!(canonical, only_statement.to_string());
+            self.assert_formatting_preserves(&only_statement);
         }
         only_statement
     }
 
+    /// Ensures that parsing `sql` losslessly yields `statements` along with a
+    /// concrete syntax tree that reproduces `sql`
+    pub fn assert_lossless(&self, sql: &str, statements: &[Statement]) {
+        let file = self.one_of_identical_results(|dialect| {
+            Parser::parse_sql_lossless(dialect, sql.to_string()).unwrap()
+        });
+        assert_eq!(statements, &file.statements[..]);
+        assert_eq!(sql, file.to_string());
+    }
+
+    /// Ensures that `statement` is not modified by pretty-printing it with
+    /// `format_statement` and parsing the result
+    pub fn assert_formatting_preserves(&self, statement: &Statement) {
+        let options = vec![
+            FormatOptions::default(),
+            FormatOptions {
+                indent: 2,
+                max_width: 20,
+                keyword_case: KeywordCase::Lower,
+                comma_style: CommaStyle::Leading,
+                clause_per_line: false,
+            },
+        ];
+        for options in options {
+            let formatted = format_statement(statement, &options);
+            assert_eq!(
+                vec![statement.clone()],
+                self.parse_sql_statements(&formatted).unwrap(),
+                "formatted as:\n{}",
+                formatted
+            );
+        }
+    }
+
     /// Ensures that `sql` parses as a single [Statement], and is not modified
     /// after a serialization round-trip.
     pub fn verified_stmt(&self, query: &str) -> Statement {
@@ -16,37 +16,50 @@
 //!
 //! The tokens then form the input for the parser, which outputs an Abstract Syntax Tree (AST).
 
+use std::borrow::Cow;
 use std::iter::Peekable;
 use std::str::Chars;
 
-use super::dialect::keywords::ALL_KEYWORDS;
+use super::dialect::keywords::Keyword;
 use super::dialect::Dialect;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::error::Error;
 use std::fmt;
 
 /// SQL Token enumeration
+///
+/// The text of a token is borrowed from the SQL it was tokenized from, unless
+/// it had to be unescaped; use `into_owned` to get a token that outlives
+/// the SQL.
 #[derive(Debug, Clone, PartialEq)]
-pub enum Token {
+pub enum Token<'a> {
     /// A keyword (like SELECT) or an optionally quoted SQL identifier
-    Word(Word),
+    Word(Word<'a>),
     /// An unsigned numeric literal
-    Number(String),
+    Number(Cow<'a, str>),
     /// A character that could not be tokenized
     Char(char),
     /// Single quoted string: i.e: 'string'
-    SingleQuotedString(String),
+    SingleQuotedString(Cow<'a, str>),
     /// "National" string literal: i.e: N'string'
-    NationalStringLiteral(String),
+    NationalStringLiteral(Cow<'a, str>),
     /// Hexadecimal string literal: i.e.: X'deadbeef'
-    HexStringLiteral(String),
+    HexStringLiteral(Cow<'a, str>),
+    /// Dollar-quoted string literal, as in PostgreSQL: i.e. $$string$$ or
+    /// $tag$string$tag$
+    DollarQuotedString {
+        tag: Cow<'a, str>,
+        value: Cow<'a, str>,
+    },
     /// An unsigned numeric literal representing positional
     /// parameters like $1, $2, etc. in prepared statements and
     /// function definitions
-    Parameter(String),
+    Parameter(Cow<'a, str>),
     /// Comma
     Comma,
     /// Whitespace (space, tab, etc)
-    Whitespace(Whitespace),
+    Whitespace(Whitespace<'a>),
     /// Equality operator `=`
     Eq,
     /// Not Equals operator `<>` (or `!=` in some dialects)
@@ -95,7 +108,7 @@ pub enum Token {
     RBrace,
 }
 
-impl fmt::Display for Token {
+impl<'a> fmt::Display for Token<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Token::Word(ref w) => write!(f, "{}", w),
@@ -104,6 +117,7 @@ impl fmt::Display for Token {
             Token::SingleQuotedString(ref s) => write!(f, "'{}'", s),
             Token::NationalStringLiteral(ref s) => write!(f, "N'{}'", s),
             Token::HexStringLiteral(ref s) => write!(f, "X'{}'", s),
+            Token::DollarQuotedString { tag, value } => write!(f, "${}${}${}$", tag, value, tag),
             Token::Parameter(n) => write!(f, "${}", n),
             Token::Comma => f.write_str(","),
             Token::Whitespace(ws) => write!(f, "{}", ws),
@@ -134,44 +148,87 @@ impl fmt::Display for Token {
     }
 }
 
-impl Token {
-    pub fn make_keyword(keyword: &str) -> Self {
+impl<'a> Token<'a> {
+    pub fn make_keyword(keyword: &'a str) -> Self {
         Token::make_word(keyword, None)
     }
-    pub fn make_word(word: &str, quote_style: Option<char>) -> Self {
-        let word_uppercase = word.to_uppercase();
-        //TODO: need to reintroduce FnvHashSet at some point .. iterating over keywords is
-        // not fast but I want the simplicity for now while I experiment with pluggable
-        // dialects
-        let is_keyword = quote_style == None && ALL_KEYWORDS.contains(&word_uppercase.as_str());
+    pub fn make_word(word: &'a str, quote_style: Option<char>) -> Self {
+        Token::make_word_cow(Cow::Borrowed(word), quote_style)
+    }
+    fn make_word_cow(word: Cow<'a, str>, quote_style: Option<char>) -> Self {
+        let keyword = match quote_style {
+            None => Keyword::lookup(&word),
+            Some(_) => Keyword::NoKeyword,
+        };
         Token::Word(Word {
-            value: word.to_string(),
+            value: word,
             quote_style,
-            keyword: if is_keyword {
-                word_uppercase
-            } else {
-                "".to_string()
-            },
+            keyword,
         })
     }
+
+    /// Return a copy of this token that does not borrow from the SQL it was
+    /// tokenized from
+    pub fn into_owned(self) -> Token<'static> {
+        match self {
+            Token::Word(w) => Token::Word(w.into_owned()),
+            Token::Number(s) => Token::Number(Cow::Owned(s.into_owned())),
+            Token::Char(c) => Token::Char(c),
+            Token::SingleQuotedString(s) => Token::SingleQuotedString(Cow::Owned(s.into_owned())),
+            Token::NationalStringLiteral(s) => {
+                Token::NationalStringLiteral(Cow::Owned(s.into_owned()))
+            }
+            Token::HexStringLiteral(s) => Token::HexStringLiteral(Cow::Owned(s.into_owned())),
+            Token::DollarQuotedString { tag, value } => Token::DollarQuotedString {
+                tag: Cow::Owned(tag.into_owned()),
+                value: Cow::Owned(value.into_owned()),
+            },
+            Token::Parameter(s) => Token::Parameter(Cow::Owned(s.into_owned())),
+            Token::Comma => Token::Comma,
+            Token::Whitespace(ws) => Token::Whitespace(ws.into_owned()),
+            Token::Eq => Token::Eq,
+            Token::Neq => Token::Neq,
+            Token::Lt => Token::Lt,
+            Token::Gt => Token::Gt,
+            Token::LtEq => Token::LtEq,
+            Token::GtEq => Token::GtEq,
+            Token::Plus => Token::Plus,
+            Token::Minus => Token::Minus,
+            Token::Mult => Token::Mult,
+            Token::Div => Token::Div,
+            Token::Mod => Token::Mod,
+            Token::LParen => Token::LParen,
+            Token::RParen => Token::RParen,
+            Token::Period => Token::Period,
+            Token::Colon => Token::Colon,
+            Token::DoubleColon => Token::DoubleColon,
+            Token::SemiColon => Token::SemiColon,
+            Token::Backslash => Token::Backslash,
+            Token::LBracket => Token::LBracket,
+            Token::RBracket => Token::RBracket,
+            Token::Ampersand => Token::Ampersand,
+            Token::LBrace => Token::LBrace,
+            Token::RBrace => Token::RBrace,
+        }
+    }
 }
 
 /// A keyword (like SELECT) or an optionally quoted SQL identifier
 #[derive(Debug, Clone, PartialEq)]
-pub struct Word {
+pub struct Word<'a> {
     /// The value of the token, without the enclosing quotes, and with the
     /// escape sequences (if any) processed (TODO: escapes are not handled)
-    pub value: String,
+    pub value: Cow<'a, str>,
     /// An identifier can be "quoted" (&lt;delimited identifier> in ANSI parlance).
     /// The standard and most implementations allow using double quotes for this,
     /// but some implementations support other quoting styles as well (e.g. \[MS SQL])
     pub quote_style: Option<char>,
     /// If the word was not quoted and it matched one of the known keywords,
-    /// this will have one of the values from dialect::keywords, otherwise empty
-    pub keyword: String,
+    /// this will be that keyword, otherwise `Keyword::NoKeyword`
+    pub keyword: Keyword,
 }
 
-impl fmt::Display for Word {
+impl<'a> fmt::Display for Word<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self.quote_style {
             Some(s) if s == '"' || s == '[' || s == '`' => {
@@ -182,7 +239,17 @@ impl fmt::Display for Word {
         }
     }
 }
-impl Word {
+impl<'a> Word<'a> {
+    /// Return a copy of this word that does not borrow from the SQL it was
+    /// tokenized from
+    pub fn into_owned(self) -> Word<'static> {
+        Word {
+            value: Cow::Owned(self.value.into_owned()),
+            quote_style: self.quote_style,
+            keyword: self.keyword,
+        }
+    }
+
     fn matching_end_quote(ch: char) -> char {
         match ch {
             '"' => '"', // ANSI and most dialects
@@ -194,15 +261,33 @@ impl Word {
 }
 
 #[derive(Debug, Clone, PartialEq)]
-pub enum Whitespace {
+pub enum Whitespace<'a> {
     Space,
     Newline,
     Tab,
-    SingleLineComment(String),
-    MultiLineComment(String),
+    SingleLineComment(Cow<'a, str>),
+    MultiLineComment(Cow<'a, str>),
 }
 
-impl fmt::Display for Whitespace {
+impl<'a> Whitespace<'a> {
+    /// Return a copy of this whitespace that does not borrow from the SQL it
+    /// was tokenized from
+    pub fn into_owned(self) -> Whitespace<'static> {
+        match self {
+            Whitespace::Space => Whitespace::Space,
+            Whitespace::Newline => Whitespace::Newline,
+            Whitespace::Tab => Whitespace::Tab,
+            Whitespace::SingleLineComment(s) => {
+                Whitespace::SingleLineComment(Cow::Owned(s.into_owned()))
+            }
+            Whitespace::MultiLineComment(s) => {
+                Whitespace::MultiLineComment(Cow::Owned(s.into_owned()))
+            }
+        }
+    }
+}
+
+impl<'a> fmt::Display for Whitespace<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Whitespace::Space => f.write_str(" "),
@@ -214,67 +299,237 @@ impl fmt::Display for Whitespace {
     }
 }
 
+/// A position in the SQL source text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Location {
+    /// Byte offset from the start of the source text
+    pub offset: usize,
+    /// Line number, starting from 1 (0 means the location is unknown)
+    pub line: u64,
+    /// Column number in characters, starting from 1
+    pub column: u64,
+}
+
+impl Location {
+    pub(crate) fn start() -> Self {
+        Location {
+            offset: 0,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    /// Move past `ch`, which is followed by `next`
+    fn advance_char(&mut self, ch: char, next: Option<char>) {
+        self.offset += ch.len_utf8();
+        match (ch, next) {
+            // `\r\n` counts as a single line break, which we account for
+            // when consuming the `\n`
+            ('\r', Some('\n')) => {}
+            ('\r', _) | ('\n', _) => {
+                self.line += 1;
+                self.column = 1;
+            }
+            _ => self.column += 1,
+        }
+    }
+
+    /// Move past `text`, which starts at this location
+    pub(crate) fn advance(&mut self, text: &str) {
+        let mut chars = text.chars().peekable();
+        while let Some(ch) = chars.next() {
+            self.advance_char(ch, chars.peek().copied());
+        }
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Line: {}, Column: {}", self.line, self.column)
+    }
+}
+
+/// A region of the SQL source text, from `start` (inclusive) to `end`
+/// (exclusive).
+///
+/// `Span::default()` is the empty span, used for tokens and AST nodes whose
+/// position is not known (e.g. because they were not produced by the parser).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Span {
+    pub start: Location,
+    pub end: Location,
+}
+
+impl Span {
+    pub fn new(start: Location, end: Location) -> Self {
+        Span { start, end }
+    }
+
+    /// Whether this is the empty span, i.e. the position is unknown
+    pub fn is_empty(&self) -> bool {
+        self.start.line == 0
+    }
+
+    /// Return the smallest span covering both `self` and `other`. Empty spans
+    /// are ignored.
+    pub fn union(&self, other: &Span) -> Span {
+        if self.is_empty() {
+            *other
+        } else if other.is_empty() {
+            *self
+        } else {
+            Span {
+                start: std::cmp::min(self.start, other.start),
+                end: std::cmp::max(self.end, other.end),
+            }
+        }
+    }
+
+    /// Return the smallest span covering all of `spans`
+    pub fn union_iter<I: IntoIterator<Item = Span>>(spans: I) -> Span {
+        spans
+            .into_iter()
+            .fold(Span::default(), |acc, span| acc.union(&span))
+    }
+
+    /// Return the slice of `source` covered by this span, `source` being the
+    /// text the span was computed from.
+    pub fn source<'s>(&self, source: &'s str) -> &'s str {
+        &source[self.start.offset..self.end.offset]
+    }
+}
+
+/// A token along with its location in the source text
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenWithSpan<'a> {
+    pub token: Token<'a>,
+    pub span: Span,
+}
+
+impl<'a> TokenWithSpan<'a> {
+    /// Return a copy of this token that does not borrow from the SQL it was
+    /// tokenized from
+    pub fn into_owned(self) -> TokenWithSpan<'static> {
+        TokenWithSpan {
+            token: self.token.into_owned(),
+            span: self.span,
+        }
+    }
+}
+
 /// Tokenizer error
 #[derive(Debug, PartialEq)]
-pub struct TokenizerError(String);
+pub struct TokenizerError {
+    pub message: String,
+    /// The location in the source text where the error was detected
+    pub location: Location,
+}
 
 impl fmt::Display for TokenizerError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.write_str(&self.0)
+        f.write_str(&self.message)
     }
 }
 
 impl Error for TokenizerError {}
 
+/// A peekable iterator over the source characters that keeps track of the
+/// location of the next character.
+struct State<'a> {
+    source: &'a str,
+    peekable: Peekable<Chars<'a>>,
+    location: Location,
+}
+
+impl<'a> State<'a> {
+    /// Start at `location` in `source`
+    fn new(source: &'a str, location: Location) -> Self {
+        State {
+            source,
+            peekable: source[location.offset..].chars().peekable(),
+            location,
+        }
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let ch = self.peekable.next()?;
+        self.location
+            .advance_char(ch, self.peekable.peek().copied());
+        Some(ch)
+    }
+
+    fn peek(&mut self) -> Option<&char> {
+        self.peekable.peek()
+    }
+
+    /// Return the source text from byte offset `start` up to the next
+    /// character
+    fn slice_from(&self, start: usize) -> &'a str {
+        &self.source[start..self.location.offset]
+    }
+}
+
 /// SQL Tokenizer
 pub struct Tokenizer<'a> {
     dialect: &'a dyn Dialect,
-    pub query: String,
-    pub line: u64,
-    pub col: u64,
+    pub query: &'a str,
 }
 
 impl<'a> Tokenizer<'a> {
     /// Create a new SQL tokenizer for the specified SQL statement
-    pub fn new(dialect: &'a dyn Dialect, query: &str) -> Self {
-        Self {
-            dialect,
-            query: query.to_string(),
-            line: 1,
-            col: 1,
-        }
+    pub fn new(dialect: &'a dyn Dialect, query: &'a str) -> Self {
+        Self { dialect, query }
     }
 
     /// Tokenize the statement and produce a vector of tokens
-    pub fn tokenize(&mut self) -> Result<Vec<Token>, TokenizerError> {
-        let mut peekable = self.query.chars().peekable();
+    pub fn tokenize(&mut self) -> Result<Vec<Token<'a>>, TokenizerError> {
+        Ok(self
+            .tokenize_with_span()?
+            .into_iter()
+            .map(|t| t.token)
+            .collect())
+    }
 
-        let mut tokens: Vec<Token> = vec![];
+    /// Tokenize the statement and produce a vector of tokens, each annotated
+    /// with its location in the source text
+    pub fn tokenize_with_span(&mut self) -> Result<Vec<TokenWithSpan<'a>>, TokenizerError> {
+        self.tokens_from(Location::start()).collect()
+    }
 
-        while let Some(token) = self.next_token(&mut peekable)? {
-            match &token {
-                Token::Whitespace(Whitespace::Newline) => {
-                    self.line += 1;
-                    self.col = 1;
+    /// Tokenize the statement lazily, starting at `start`, which must be the
+    /// location of the start of a token. Tokenizing stops after an error.
+    pub(crate) fn tokens_from<'t>(
+        &'t self,
+        start: Location,
+    ) -> impl Iterator<Item = Result<TokenWithSpan<'a>, TokenizerError>> + 't {
+        let mut state = State::new(self.query, start);
+        let mut failed = false;
+        std::iter::from_fn(move || {
+            if failed {
+                return None;
+            }
+            let start = state.location;
+            match self.next_token(&mut state) {
+                Ok(token) => token.map(|token| {
+                    Ok(TokenWithSpan {
+                        token,
+                        span: Span::new(start, state.location),
+                    })
+                }),
+                Err(e) => {
+                    failed = true;
+                    Some(Err(e))
                 }
-
-                Token::Whitespace(Whitespace::Tab) => self.col += 4,
-                Token::Word(w) if w.quote_style == None => self.col += w.value.len() as u64,
-                Token::Word(w) if w.quote_style != None => self.col += w.value.len() as u64 + 2,
-                Token::Number(s) => self.col += s.len() as u64,
-                Token::SingleQuotedString(s) => self.col += s.len() as u64,
-                Token::Parameter(s) => self.col += s.len() as u64,
-                _ => self.col += 1,
             }
-
-            tokens.push(token);
-        }
-        Ok(tokens)
+        })
     }
 
     /// Get the next token or return None
-    fn next_token(&self, chars: &mut Peekable<Chars<'_>>) -> Result<Option<Token>, TokenizerError> {
+    fn next_token(&self, chars: &mut State<'a>) -> Result<Option<Token<'a>>, TokenizerError> {
         //println!("next_token: {:?}", chars.peek());
+        let start = chars.location.offset;
         match chars.peek() {
             Some(&ch) => match ch {
                 ' ' => self.consume_and_return(chars, Token::Whitespace(Whitespace::Space)),
@@ -293,42 +548,42 @@ impl<'a> Tokenizer<'a> {
                     match chars.peek() {
                         Some('\'') => {
                             // N'...' - a <national character string literal>
-                            let s = self.tokenize_single_quoted_string(chars);
+                            let s = self.tokenize_single_quoted_string(chars)?;
                             Ok(Some(Token::NationalStringLiteral(s)))
                         }
                         _ => {
                             // regular identifier starting with an "N"
-                            let s = self.tokenize_word('N', chars);
-                            Ok(Some(Token::make_word(&s, None)))
+                            let s = self.tokenize_word(start, chars);
+                            Ok(Some(Token::make_word(s, None)))
                         }
                     }
                 }
                 // The spec only allows an uppercase 'X' to introduce a hex
                 // string, but PostgreSQL, at least, allows a lowercase 'x' too.
-                x @ 'x' | x @ 'X' => {
+                'x' | 'X' => {
                     chars.next(); // consume, to check the next char
                     match chars.peek() {
                         Some('\'') => {
                             // X'...' - a <binary string literal>
-                            let s = self.tokenize_single_quoted_string(chars);
+                            let s = self.tokenize_single_quoted_string(chars)?;
                             Ok(Some(Token::HexStringLiteral(s)))
                         }
                         _ => {
                             // regular identifier starting with an "X"
-                            let s = self.tokenize_word(x, chars);
-                            Ok(Some(Token::make_word(&s, None)))
+                            let s = self.tokenize_word(start, chars);
+                            Ok(Some(Token::make_word(s, None)))
                         }
                     }
                 }
                 // identifier or keyword
                 ch if self.dialect.is_identifier_start(ch) => {
                     chars.next(); // consume the first char
-                    let s = self.tokenize_word(ch, chars);
-                    Ok(Some(Token::make_word(&s, None)))
+                    let s = self.tokenize_word(start, chars);
+                    Ok(Some(Token::make_word(s, None)))
                 }
                 // string
                 '\'' => {
-                    let s = self.tokenize_single_quoted_string(chars);
+                    let s = self.tokenize_single_quoted_string(chars)?;
                     Ok(Some(Token::SingleQuotedString(s)))
                 }
                 // delimited (quoted) identifier
@@ -337,22 +592,22 @@ impl<'a> Tokenizer<'a> {
                     let quote_end = Word::matching_end_quote(quote_start);
                     let s = peeking_take_while(chars, |ch| ch != quote_end);
                     if chars.next() == Some(quote_end) {
-                        Ok(Some(Token::make_word(&s, Some(quote_start))))
+                        Ok(Some(Token::make_word(s, Some(quote_start))))
                     } else {
-                        Err(TokenizerError(format!(
-                            "Expected close delimiter '{}' before EOF.",
-                            quote_end
-                        )))
+                        Err(TokenizerError {
+                            message: format!(
+                                "Expected close delimiter '{}' before EOF.",
+                                quote_end
+                            ),
+                            location: chars.location,
+                        })
                     }
                 }
                 // numbers
                 '0'..='9' => {
                     // TODO: https://jakewheat.github.io/sql-overview/sql-2011-foundation-grammar.html#unsigned-numeric-literal
-                    let s = peeking_take_while(chars, |ch| match ch {
-                        '0'..='9' | '.' => true,
-                        _ => false,
-                    });
-                    Ok(Some(Token::Number(s)))
+                    let s = peeking_take_while(chars, |ch| matches!(ch, '0'..='9' | '.'));
+                    Ok(Some(Token::Number(Cow::Borrowed(s))))
                 }
                 // punctuation
                 '(' => self.consume_and_return(chars, Token::LParen),
@@ -364,12 +619,15 @@ impl<'a> Tokenizer<'a> {
                     match chars.peek() {
                         Some('-') => {
                             chars.next(); // consume the second '-', starting a single-line comment
-                            let mut s = peeking_take_while(chars, |ch| ch != '\n');
+                            let comment_start = chars.location.offset;
+                            peeking_take_while(chars, |ch| ch != '\n');
                             if let Some(ch) = chars.next() {
                                 assert_eq!(ch, '\n');
-                                s.push(ch);
                             }
-                            Ok(Some(Token::Whitespace(Whitespace::SingleLineComment(s))))
+                            let s = chars.slice_from(comment_start);
+                            Ok(Some(Token::Whitespace(Whitespace::SingleLineComment(
+                                Cow::Borrowed(s),
+                            ))))
                         }
                         // a regular '-' operator
                         _ => Ok(Some(Token::Minus)),
@@ -392,13 +650,17 @@ impl<'a> Tokenizer<'a> {
                 '=' => self.consume_and_return(chars, Token::Eq),
                 '.' => self.consume_and_return(chars, Token::Period),
                 '!' => {
+                    let start = chars.location;
                     chars.next(); // consume
                     match chars.peek() {
                         Some('=') => self.consume_and_return(chars, Token::Neq),
-                        _ => Err(TokenizerError(format!(
-                            "Tokenizer Error at Line: {}, Col: {}",
-                            self.line, self.col
-                        ))),
+                        _ => Err(TokenizerError {
+                            message: format!(
+                                "Tokenizer Error at Line: {}, Col: {}",
+                                start.line, start.column
+                            ),
+                            location: start,
+                        }),
                     }
                 }
                 '<' => {
@@ -437,70 +699,71 @@ impl<'a> Tokenizer<'a> {
         }
     }
 
-    /// Tokenize an identifier or keyword, after the first char is already consumed.
-    fn tokenize_word(&self, first_char: char, chars: &mut Peekable<Chars<'_>>) -> String {
-        let mut s = first_char.to_string();
-        s.push_str(&peeking_take_while(chars, |ch| {
-            self.dialect.is_identifier_part(ch)
-        }));
-        s
+    /// Tokenize an identifier or keyword starting at byte offset `start`,
+    /// after the first char is already consumed.
+    fn tokenize_word(&self, start: usize, chars: &mut State<'a>) -> &'a str {
+        peeking_take_while(chars, |ch| self.dialect.is_identifier_part(ch));
+        chars.slice_from(start)
     }
 
-    /// Read a single quoted string, starting with the opening quote.
-    fn tokenize_single_quoted_string(&self, chars: &mut Peekable<Chars<'_>>) -> String {
-        //TODO: handle escaped quotes in string
-        //TODO: handle newlines in string
-        //TODO: handle EOF before terminating quote
+    /// Read a single quoted string, starting with the opening quote. The
+    /// string is only copied if it contains escaped quotes.
+    fn tokenize_single_quoted_string(
+        &self,
+        chars: &mut State<'a>,
+    ) -> Result<Cow<'a, str>, TokenizerError> {
         //TODO: handle 'string' <white space> 'string continuation'
-        let mut s = String::new();
+        let mut s = Cow::Borrowed("");
         chars.next(); // consume the opening quote
+        let mut start = chars.location.offset;
         while let Some(&ch) = chars.peek() {
-            match ch {
-                '\'' => {
-                    chars.next(); // consume
-                    let escaped_quote = chars.peek().map(|c| *c == '\'').unwrap_or(false);
-                    if escaped_quote {
-                        s.push('\'');
-                        chars.next();
-                    } else {
-                        break;
-                    }
-                }
-                _ => {
-                    chars.next(); // consume
-                    s.push(ch);
+            if ch == '\'' {
+                let part = chars.slice_from(start);
+                chars.next(); // consume
+                let escaped_quote = chars.peek().map(|c| *c == '\'').unwrap_or(false);
+                if escaped_quote {
+                    // Keep the first quote of the pair
+                    s.to_mut().push_str(part);
+                    s.to_mut().push('\'');
+                    chars.next();
+                    start = chars.location.offset;
+                } else {
+                    return Ok(concat(s, part));
                 }
+            } else {
+                chars.next(); // consume
             }
         }
-        s
+        Err(TokenizerError {
+            message: "Unterminated string literal".to_string(),
+            location: chars.location,
+        })
     }
 
     fn tokenize_multiline_comment(
         &self,
-        chars: &mut Peekable<Chars<'_>>,
-    ) -> Result<Option<Token>, TokenizerError> {
-        let mut s = String::new();
+        chars: &mut State<'a>,
+    ) -> Result<Option<Token<'a>>, TokenizerError> {
+        let start = chars.location.offset;
         let mut maybe_closing_comment = false;
         // TODO: deal with nested comments
         loop {
             match chars.next() {
                 Some(ch) => {
-                    if maybe_closing_comment {
-                        if ch == '/' {
-                            break Ok(Some(Token::Whitespace(Whitespace::MultiLineComment(s))));
-                        } else {
-                            s.push('*');
-                        }
+                    if maybe_closing_comment && ch == '/' {
+                        // Leave out the closing `*/`
+                        let s = &chars.source[start..chars.location.offset - 2];
+                        break Ok(Some(Token::Whitespace(Whitespace::MultiLineComment(
+                            Cow::Borrowed(s),
+                        ))));
                     }
                     maybe_closing_comment = ch == '*';
-                    if !maybe_closing_comment {
-                        s.push(ch);
-                    }
                 }
                 None => {
-                    break Err(TokenizerError(
-                        "Unexpected EOF while in a multi-line comment".to_string(),
-                    ));
+                    break Err(TokenizerError {
+                        message: "Unexpected EOF while in a multi-line comment".to_string(),
+                        location: chars.location,
+                    });
                 }
             }
         }
@@ -508,56 +771,96 @@ impl<'a> Tokenizer<'a> {
 
     /// PostgreSQL supports positional parameters (like $1, $2, etc.) for
     /// prepared statements and function definitions.
-    /// Grab the positional argument following a $ to parse it.
+    /// Grab the positional argument following a $ to parse it, or the
+    /// dollar-quoted string starting with the $.
     fn tokenize_parameter(
         &self,
-        chars: &mut Peekable<Chars<'_>>,
-    ) -> Result<Option<Token>, TokenizerError> {
+        chars: &mut State<'a>,
+    ) -> Result<Option<Token<'a>>, TokenizerError> {
         assert_eq!(Some('$'), chars.next());
 
-        let n = peeking_take_while(chars, |ch| match ch {
-            '0'..='9' => true,
-            _ => false,
-        });
+        let n = peeking_take_while(chars, |ch| ch.is_ascii_digit());
 
         if n.is_empty() {
-            return Err(TokenizerError(
-                "parameter marker ($) was not followed by \
-                 at least one digit"
+            let tag = peeking_take_while(chars, |ch| ch.is_alphanumeric() || ch == '_');
+            if chars.peek() == Some(&'$') {
+                chars.next(); // consume the '$' ending the opening tag
+                return self.tokenize_dollar_quoted_string(tag, chars);
+            }
+            return Err(TokenizerError {
+                message: "parameter marker ($) was not followed by \
+                          at least one digit"
                     .into(),
-            ));
+                location: chars.location,
+            });
         }
 
-        Ok(Some(Token::Parameter(n)))
+        Ok(Some(Token::Parameter(Cow::Borrowed(n))))
+    }
+
+    /// Read a dollar-quoted string, after its opening `$tag$`
+    fn tokenize_dollar_quoted_string(
+        &self,
+        tag: &'a str,
+        chars: &mut State<'a>,
+    ) -> Result<Option<Token<'a>>, TokenizerError> {
+        let start = chars.location.offset;
+        let closing_tag = format!("${}$", tag);
+        match chars.source[start..].find(&closing_tag) {
+            Some(len) => {
+                while chars.location.offset < start + len + closing_tag.len() {
+                    chars.next();
+                }
+                Ok(Some(Token::DollarQuotedString {
+                    tag: Cow::Borrowed(tag),
+                    value: Cow::Borrowed(&chars.source[start..start + len]),
+                }))
+            }
+            None => {
+                while chars.next().is_some() {}
+                Err(TokenizerError {
+                    message: format!("Expected close delimiter '{}' before EOF.", closing_tag),
+                    location: chars.location,
+                })
+            }
+        }
     }
 
     fn consume_and_return(
         &self,
-        chars: &mut Peekable<Chars<'_>>,
-        t: Token,
-    ) -> Result<Option<Token>, TokenizerError> {
+        chars: &mut State<'a>,
+        t: Token<'a>,
+    ) -> Result<Option<Token<'a>>, TokenizerError> {
         chars.next();
         Ok(Some(t))
     }
 }
 
 /// Read from `chars` until `predicate` returns `false` or EOF is hit.
-/// Return the characters read as String, and keep the first non-matching
-/// char available as `chars.next()`.
-fn peeking_take_while(
-    chars: &mut Peekable<Chars<'_>>,
+/// Return the characters read as a slice of the source text, and keep the
+/// first non-matching char available as `chars.next()`.
+fn peeking_take_while<'a>(
+    chars: &mut State<'a>,
     mut predicate: impl FnMut(char) -> bool,
-) -> String {
-    let mut s = String::new();
+) -> &'a str {
+    let start = chars.location.offset;
     while let Some(&ch) = chars.peek() {
         if predicate(ch) {
             chars.next(); // consume
-            s.push(ch);
         } else {
             break;
         }
     }
-    s
+    chars.slice_from(start)
+}
+
+/// Append `part` to `s`, only copying it if `s` is not empty
+fn concat<'a>(s: Cow<'a, str>, part: &'a str) -> Cow<'a, str> {
+    if s.is_empty() {
+        Cow::Borrowed(part)
+    } else {
+        Cow::Owned(s.into_owned() + part)
+    }
 }
 
 #[cfg(test)]
@@ -567,7 +870,7 @@ mod tests {
 
     #[test]
     fn tokenize_select_1() {
-        let sql = String::from("SELECT 1");
+        let sql = "SELECT 1".to_string();
         let dialect = GenericDialect {};
         let mut tokenizer = Tokenizer::new(&dialect, &sql);
         let tokens = tokenizer.tokenize().unwrap();
@@ -575,7 +878,7 @@ mod tests {
         let expected = vec![
             Token::make_keyword("SELECT"),
             Token::Whitespace(Whitespace::Space),
-            Token::Number(String::from("1")),
+            Token::Number("1".into()),
         ];
 
         compare(expected, tokens);
@@ -583,7 +886,7 @@ mod tests {
 
     #[test]
     fn tokenize_scalar_function() {
-        let sql = String::from("SELECT sqrt(1)");
+        let sql = "SELECT sqrt(1)".to_string();
         let dialect = GenericDialect {};
         let mut tokenizer = Tokenizer::new(&dialect, &sql);
         let tokens = tokenizer.tokenize().unwrap();
@@ -593,7 +896,7 @@ mod tests {
             Token::Whitespace(Whitespace::Space),
             Token::make_word("sqrt", None),
             Token::LParen,
-            Token::Number(String::from("1")),
+            Token::Number("1".into()),
             Token::RParen,
         ];
 
@@ -602,7 +905,7 @@ mod tests {
 
     #[test]
     fn tokenize_simple_select() {
-        let sql = String::from("SELECT * FROM customer WHERE id = 1 LIMIT 5");
+        let sql = "SELECT * FROM customer WHERE id = 1 LIMIT 5".to_string();
         let dialect = GenericDialect {};
         let mut tokenizer = Tokenizer::new(&dialect, &sql);
         let tokens = tokenizer.tokenize().unwrap();
@@ -622,11 +925,11 @@ mod tests {
             Token::Whitespace(Whitespace::Space),
             Token::Eq,
             Token::Whitespace(Whitespace::Space),
-            Token::Number(String::from("1")),
+            Token::Number("1".into()),
             Token::Whitespace(Whitespace::Space),
             Token::make_keyword("LIMIT"),
             Token::Whitespace(Whitespace::Space),
-            Token::Number(String::from("5")),
+            Token::Number("5".into()),
         ];
 
         compare(expected, tokens);
@@ -634,7 +937,7 @@ mod tests {
 
     #[test]
     fn tokenize_string_predicate() {
-        let sql = String::from("SELECT * FROM customer WHERE salary != 'Not Provided'");
+        let sql = "SELECT * FROM customer WHERE salary != 'Not Provided'".to_string();
         let dialect = GenericDialect {};
         let mut tokenizer = Tokenizer::new(&dialect, &sql);
         let tokens = tokenizer.tokenize().unwrap();
@@ -654,7 +957,7 @@ mod tests {
             Token::Whitespace(Whitespace::Space),
             Token::Neq,
             Token::Whitespace(Whitespace::Space),
-            Token::SingleQuotedString(String::from("Not Provided")),
+            Token::SingleQuotedString("Not Provided".into()),
         ];
 
         compare(expected, tokens);
@@ -662,7 +965,7 @@ mod tests {
 
     #[test]
     fn tokenize_invalid_string() {
-        let sql = String::from("\nمصطفىh");
+        let sql = "\nمصطفىh".to_string();
 
         let dialect = GenericDialect {};
         let mut tokenizer = Tokenizer::new(&dialect, &sql);
@@ -682,7 +985,7 @@ mod tests {
 
     #[test]
     fn tokenize_invalid_string_cols() {
-        let sql = String::from("\n\nSELECT * FROM table\tمصطفىh");
+        let sql = "\n\nSELECT * FROM table\tمصطفىh".to_string();
 
         let dialect = GenericDialect {};
         let mut tokenizer = Tokenizer::new(&dialect, &sql);
@@ -711,7 +1014,7 @@ mod tests {
 
     #[test]
     fn tokenize_is_null() {
-        let sql = String::from("a IS NULL");
+        let sql = "a IS NULL".to_string();
         let dialect = GenericDialect {};
         let mut tokenizer = Tokenizer::new(&dialect, &sql);
         let tokens = tokenizer.tokenize().unwrap();
@@ -729,61 +1032,59 @@ mod tests {
 
     #[test]
     fn tokenize_comment() {
-        let sql = String::from("0--this is a comment\n1");
+        let sql = "0--this is a comment\n1".to_string();
 
         let dialect = GenericDialect {};
         let mut tokenizer = Tokenizer::new(&dialect, &sql);
         let tokens = tokenizer.tokenize().unwrap();
         let expected = vec![
-            Token::Number("0".to_string()),
-            Token::Whitespace(Whitespace::SingleLineComment(
-                "this is a comment\n".to_string(),
-            )),
-            Token::Number("1".to_string()),
+            Token::Number("0".into()),
+            Token::Whitespace(Whitespace::SingleLineComment("this is a comment\n".into())),
+            Token::Number("1".into()),
         ];
         compare(expected, tokens);
     }
 
     #[test]
     fn tokenize_comment_at_eof() {
-        let sql = String::from("--this is a comment");
+        let sql = "--this is a comment".to_string();
 
         let dialect = GenericDialect {};
         let mut tokenizer = Tokenizer::new(&dialect, &sql);
         let tokens = tokenizer.tokenize().unwrap();
         let expected = vec![Token::Whitespace(Whitespace::SingleLineComment(
-            "this is a comment".to_string(),
+            "this is a comment".into(),
         ))];
         compare(expected, tokens);
     }
 
     #[test]
     fn tokenize_multiline_comment() {
-        let sql = String::from("0/*multi-line\n* /comment*/1");
+        let sql = "0/*multi-line\n* /comment*/1".to_string();
 
         let dialect = GenericDialect {};
         let mut tokenizer = Tokenizer::new(&dialect, &sql);
         let tokens = tokenizer.tokenize().unwrap();
         let expected = vec![
-            Token::Number("0".to_string()),
+            Token::Number("0".into()),
             Token::Whitespace(Whitespace::MultiLineComment(
-                "multi-line\n* /comment".to_string(),
+                "multi-line\n* /comment".into(),
             )),
-            Token::Number("1".to_string()),
+            Token::Number("1".into()),
         ];
         compare(expected, tokens);
     }
 
     #[test]
     fn tokenize_multiline_comment_with_even_asterisks() {
-        let sql = String::from("\n/** Comment **/\n");
+        let sql = "\n/** Comment **/\n".to_string();
 
         let dialect = GenericDialect {};
         let mut tokenizer = Tokenizer::new(&dialect, &sql);
         let tokens = tokenizer.tokenize().unwrap();
         let expected = vec![
             Token::Whitespace(Whitespace::Newline),
-            Token::Whitespace(Whitespace::MultiLineComment("* Comment *".to_string())),
+            Token::Whitespace(Whitespace::MultiLineComment("* Comment *".into())),
             Token::Whitespace(Whitespace::Newline),
         ];
         compare(expected, tokens);
@@ -797,15 +1098,80 @@ mod tests {
         let mut tokenizer = Tokenizer::new(&dialect, &sql);
         assert_eq!(
             tokenizer.tokenize(),
-            Err(TokenizerError(
-                "Expected close delimiter '\"' before EOF.".to_string(),
-            ))
+            Err(TokenizerError {
+                message: "Expected close delimiter '\"' before EOF.".into(),
+                location: Location {
+                    offset: 4,
+                    line: 1,
+                    column: 5,
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn tokenize_unterminated_string() {
+        let dialect = GenericDialect {};
+        for sql in &["SELECT 'a;\nb", "SELECT N'a;\nb", "SELECT X'a;\nb"] {
+            let mut tokenizer = Tokenizer::new(&dialect, sql);
+            assert_eq!(
+                tokenizer.tokenize(),
+                Err(TokenizerError {
+                    message: "Unterminated string literal".into(),
+                    location: Location {
+                        offset: sql.len(),
+                        line: 2,
+                        column: 2,
+                    },
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn tokenize_dollar_quoted_string() {
+        let sql = String::from("SELECT $$it's$$, $fn$ $$; $x $fn$, $1");
+
+        let dialect = GenericDialect {};
+        let mut tokenizer = Tokenizer::new(&dialect, &sql);
+        let tokens = tokenizer.tokenize().unwrap();
+        let expected = vec![
+            Token::make_keyword("SELECT"),
+            Token::Whitespace(Whitespace::Space),
+            Token::DollarQuotedString {
+                tag: "".into(),
+                value: "it's".into(),
+            },
+            Token::Comma,
+            Token::Whitespace(Whitespace::Space),
+            Token::DollarQuotedString {
+                tag: "fn".into(),
+                value: " $$; $x ".into(),
+            },
+            Token::Comma,
+            Token::Whitespace(Whitespace::Space),
+            Token::Parameter("1".into()),
+        ];
+        compare(expected, tokens);
+
+        let sql = String::from("SELECT $a$b$");
+        let mut tokenizer = Tokenizer::new(&dialect, &sql);
+        assert_eq!(
+            tokenizer.tokenize(),
+            Err(TokenizerError {
+                message: "Expected close delimiter '$a$' before EOF.".into(),
+                location: Location {
+                    offset: 12,
+                    line: 1,
+                    column: 13,
+                },
+            })
         );
     }
 
     #[test]
     fn tokenize_newlines() {
-        let sql = String::from("line1\nline2\rline3\r\nline4\r");
+        let sql = "line1\nline2\rline3\r\nline4\r".to_string();
 
         let dialect = GenericDialect {};
         let mut tokenizer = Tokenizer::new(&dialect, &sql);
@@ -823,6 +1189,170 @@ mod tests {
         compare(expected, tokens);
     }
 
+    #[test]
+    fn tokenize_keywords() {
+        let sql = "select SeLeCt \"select\" selected ſelect";
+        let dialect = GenericDialect {};
+        let tokens = Tokenizer::new(&dialect, sql).tokenize().unwrap();
+        let keywords: Vec<Keyword> = tokens
+            .iter()
+            .filter_map(|t| match t {
+                Token::Word(w) => Some(w.keyword),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            vec![
+                Keyword::Select,
+                Keyword::Select,
+                Keyword::NoKeyword,
+                Keyword::NoKeyword,
+                Keyword::NoKeyword,
+            ],
+            keywords
+        );
+
+        assert_eq!(Keyword::EndExec, Keyword::lookup("end-exec"));
+        assert_eq!("END-EXEC", Keyword::EndExec.as_str());
+        assert_eq!(Keyword::NoKeyword, Keyword::lookup(&"A".repeat(100)));
+    }
+
+    #[test]
+    fn tokenize_borrows_from_query() {
+        let sql = "SELECT \"Foo\", 'bar', 'O''Brien', 1.5, $1 -- done".to_string();
+
+        let dialect = GenericDialect {};
+        let mut tokenizer = Tokenizer::new(&dialect, &sql);
+        let tokens = tokenizer.tokenize().unwrap();
+        let mut owned = 0;
+        for token in &tokens {
+            let text = match token {
+                Token::Word(w) => &w.value,
+                Token::SingleQuotedString(s) | Token::Number(s) | Token::Parameter(s) => s,
+                Token::Whitespace(Whitespace::SingleLineComment(s)) => s,
+                _ => continue,
+            };
+            if let Cow::Owned(_) = text {
+                owned += 1;
+            }
+        }
+        // Only the string with an escaped quote had to be copied
+        assert_eq!(1, owned);
+        assert_eq!(Token::SingleQuotedString("O'Brien".into()), tokens[8]);
+
+        // Tokens can outlive the query once they are owned
+        let tokens: Vec<Token<'static>> = tokens.into_iter().map(Token::into_owned).collect();
+        drop(sql);
+        assert_eq!(Token::make_keyword("SELECT"), tokens[0]);
+        assert_eq!(Token::make_word("Foo", Some('"')), tokens[2]);
+    }
+
+    #[test]
+    fn tokenize_with_span() {
+        let sql = String::from("SELECT a,\n  'b''c'\r\n/* é */ \"d\"");
+
+        let dialect = GenericDialect {};
+        let mut tokenizer = Tokenizer::new(&dialect, &sql);
+        let tokens = tokenizer.tokenize_with_span().unwrap();
+        let loc = |offset, line, column| Location {
+            offset,
+            line,
+            column,
+        };
+        let expected = vec![
+            (Token::make_keyword("SELECT"), loc(0, 1, 1), loc(6, 1, 7)),
+            (
+                Token::Whitespace(Whitespace::Space),
+                loc(6, 1, 7),
+                loc(7, 1, 8),
+            ),
+            (Token::make_word("a", None), loc(7, 1, 8), loc(8, 1, 9)),
+            (Token::Comma, loc(8, 1, 9), loc(9, 1, 10)),
+            (
+                Token::Whitespace(Whitespace::Newline),
+                loc(9, 1, 10),
+                loc(10, 2, 1),
+            ),
+            (
+                Token::Whitespace(Whitespace::Space),
+                loc(10, 2, 1),
+                loc(11, 2, 2),
+            ),
+            (
+                Token::Whitespace(Whitespace::Space),
+                loc(11, 2, 2),
+                loc(12, 2, 3),
+            ),
+            (
+                Token::SingleQuotedString("b'c".into()),
+                loc(12, 2, 3),
+                loc(18, 2, 9),
+            ),
+            (
+                Token::Whitespace(Whitespace::Newline),
+                loc(18, 2, 9),
+                loc(20, 3, 1),
+            ),
+            (
+                Token::Whitespace(Whitespace::MultiLineComment(" é ".into())),
+                loc(20, 3, 1),
+                loc(28, 3, 8),
+            ),
+            (
+                Token::Whitespace(Whitespace::Space),
+                loc(28, 3, 8),
+                loc(29, 3, 9),
+            ),
+            (
+                Token::make_word("d", Some('"')),
+                loc(29, 3, 9),
+                loc(32, 3, 12),
+            ),
+        ];
+        let expected: Vec<TokenWithSpan> = expected
+            .into_iter()
+            .map(|(token, start, end)| TokenWithSpan {
+                token,
+                span: Span::new(start, end),
+            })
+            .collect();
+        assert_eq!(expected, tokens);
+        assert_eq!("'b''c'", tokens[7].span.source(&sql));
+    }
+
+    #[test]
+    fn span_union() {
+        let a = Span::new(
+            Location {
+                offset: 0,
+                line: 1,
+                column: 1,
+            },
+            Location {
+                offset: 3,
+                line: 1,
+                column: 4,
+            },
+        );
+        let b = Span::new(
+            Location {
+                offset: 5,
+                line: 2,
+                column: 1,
+            },
+            Location {
+                offset: 8,
+                line: 2,
+                column: 4,
+            },
+        );
+        assert_eq!(Span::new(a.start, b.end), a.union(&b));
+        assert_eq!(Span::new(a.start, b.end), b.union(&a));
+        assert_eq!(a, a.union(&Span::default()));
+        assert_eq!(a, Span::default().union(&a));
+        assert!(Span::union_iter(vec![]).is_empty());
+    }
+
     fn compare(expected: Vec<Token>, actual: Vec<Token>) {
         //println!("------------------------------");
         //println!("tokens   = {:?}", actual);
@@ -21,8 +21,10 @@
 use matches::assert_matches;
 
 use sqlparser::ast::*;
+use sqlparser::dialect::{GenericDialect, PostgreSqlDialect};
 use sqlparser::parser::*;
 use sqlparser::test_utils::{all_dialects, expr_from_projection, number, only};
+use sqlparser::tokenizer::{Token, Tokenizer};
 
 #[test]
 fn parse_insert_values() {
@@ -88,10 +90,7 @@ fn parse_insert_values() {
 fn parse_insert_invalid() {
     let sql = "INSERT public.customer (id, name, active) VALUES (1, 2, 3)";
     let res = parse_sql_statements(sql);
-    assert_eq!(
-        ParserError::ParserError("Expected INTO, found: public".to_string()),
-        res.unwrap_err()
-    );
+    assert_eq!("Expected INTO, found: public", res.unwrap_err().message);
 }
 
 #[test]
@@ -131,16 +130,13 @@ fn parse_update() {
 
     let sql = "UPDATE t WHERE 1";
     let res = parse_sql_statements(sql);
-    assert_eq!(
-        ParserError::ParserError("Expected SET, found: WHERE".to_string()),
-        res.unwrap_err()
-    );
+    assert_eq!("Expected SET, found: WHERE", res.unwrap_err().message);
 
     let sql = "UPDATE t SET a = 1 extrabadstuff";
     let res = parse_sql_statements(sql);
     assert_eq!(
-        ParserError::ParserError("Expected end of statement, found: extrabadstuff".to_string()),
-        res.unwrap_err()
+        "Expected end of statement, found: extrabadstuff",
+        res.unwrap_err().message
     );
 }
 
@@ -208,7 +204,7 @@ fn parse_top_level() {
 fn parse_simple_select() {
     let sql = "SELECT id, fname, lname FROM customer WHERE id = 1 LIMIT 5";
     let select = verified_only_select(sql);
-    assert_eq!(false, select.distinct);
+    assert!(!select.distinct);
     assert_eq!(3, select.projection.len());
     let select = verified_query(sql);
     assert_eq!(Some(Expr::Value(number("5"))), select.limit);
@@ -228,7 +224,7 @@ fn parse_limit_is_not_an_alias() {
 fn parse_select_distinct() {
     let sql = "SELECT DISTINCT name FROM customer";
     let select = verified_only_select(sql);
-    assert_eq!(true, select.distinct);
+    assert!(select.distinct);
     assert_eq!(
         &SelectItem::UnnamedExpr(Expr::Identifier(Ident::new("name"))),
         only(&select.projection)
@@ -244,8 +240,8 @@ fn parse_select_all() {
 fn parse_select_all_distinct() {
     let result = parse_sql_statements("SELECT ALL DISTINCT name FROM customer");
     assert_eq!(
-        ParserError::ParserError("Cannot specify both ALL and DISTINCT in SELECT".to_string()),
-        result.unwrap_err(),
+        "Cannot specify both ALL and DISTINCT in SELECT",
+        result.unwrap_err().message,
     );
 }
 
@@ -276,7 +272,7 @@ fn parse_select_wildcard() {
 #[test]
 fn parse_count_wildcard() {
     verified_only_select(
-        "SELECT COUNT(Employee.*) FROM Order JOIN Employee ON Order.employee = Employee.id",
+        "SELECT COUNT(Employee.*) FROM Orders JOIN Employee ON Orders.employee = Employee.id",
     );
 }
 
@@ -299,21 +295,21 @@ fn parse_column_aliases() {
     }
 
     // alias without AS is parsed correctly:
-    one_statement_parses_to("SELECT a.col + 1 newname FROM foo AS a", &sql);
+    one_statement_parses_to("SELECT a.col + 1 newname FROM foo AS a", sql);
 }
 
 #[test]
 fn test_eof_after_as() {
     let res = parse_sql_statements("SELECT foo AS");
     assert_eq!(
-        ParserError::ParserError("Expected an identifier after AS, found: EOF".to_string()),
-        res.unwrap_err()
+        "Expected an identifier after AS, found: EOF",
+        res.unwrap_err().message
     );
 
     let res = parse_sql_statements("SELECT 1 FROM foo AS");
     assert_eq!(
-        ParserError::ParserError("Expected an identifier after AS, found: EOF".to_string()),
-        res.unwrap_err()
+        "Expected an identifier after AS, found: EOF",
+        res.unwrap_err().message
     );
 }
 
@@ -357,10 +353,8 @@ fn parse_select_count_distinct() {
     let sql = "SELECT COUNT(ALL DISTINCT + x) FROM customer";
     let res = parse_sql_statements(sql);
     assert_eq!(
-        ParserError::ParserError(
-            "Cannot specify both ALL and DISTINCT in function: COUNT".to_string()
-        ),
-        res.unwrap_err()
+        "Cannot specify both ALL and DISTINCT in function: COUNT",
+        res.unwrap_err().message
     );
 }
 
@@ -383,24 +377,20 @@ fn parse_parameters() {
 
     let res = parse_sql_statements("SELECT $q");
     assert_eq!(
-        ParserError::TokenizerError(
-            "parameter marker ($) was not followed by at least one digit".into()
-        ),
-        res.unwrap_err()
+        "parameter marker ($) was not followed by at least one digit",
+        res.unwrap_err().message
     );
 
     let res = parse_sql_statements("SELECT $1$2");
     assert_eq!(
-        ParserError::ParserError("Expected end of statement, found: $2".into()),
-        res.unwrap_err()
+        "Expected end of statement, found: $2",
+        res.unwrap_err().message
     );
 
-    let res = parse_sql_statements(&format!("SELECT $18446744073709551616"));
+    let res = parse_sql_statements("SELECT $18446744073709551616");
     assert_eq!(
-        ParserError::ParserError(
-            "unable to parse parameter: number too large to fit in target type".into(),
-        ),
-        res.unwrap_err()
+        "unable to parse parameter: number too large to fit in target type",
+        res.unwrap_err().message
     );
 }
 
@@ -415,8 +405,8 @@ fn parse_not() {
 fn parse_invalid_infix_not() {
     let res = parse_sql_statements("SELECT c FROM t WHERE c NOT (");
     assert_eq!(
-        ParserError::ParserError("Expected end of statement, found: NOT".to_string()),
-        res.unwrap_err(),
+        "Expected end of statement, found: NOT",
+        res.unwrap_err().message,
     );
 }
 
@@ -568,17 +558,23 @@ fn parse_is_not_null() {
 fn parse_not_precedence() {
     // NOT has higher precedence than OR/AND, so the following must parse as (NOT true) OR true
     let sql = "NOT true OR true";
-    assert_matches!(verified_expr(sql), Expr::BinaryOp {
-        op: BinaryOperator::Or,
-        ..
-    });
+    assert_matches!(
+        verified_expr(sql),
+        Expr::BinaryOp {
+            op: BinaryOperator::Or,
+            ..
+        }
+    );
 
     // But NOT has lower precedence than comparison operators, so the following parses as NOT (a IS NULL)
     let sql = "NOT a IS NULL";
-    assert_matches!(verified_expr(sql), Expr::UnaryOp {
-        op: UnaryOperator::Not,
-        ..
-    });
+    assert_matches!(
+        verified_expr(sql),
+        Expr::UnaryOp {
+            op: UnaryOperator::Not,
+            ..
+        }
+    );
 
     // NOT has lower precedence than BETWEEN, so the following parses as NOT (1 NOT BETWEEN 1 AND 2)
     let sql = "NOT 1 NOT BETWEEN 1 AND 2";
@@ -936,8 +932,8 @@ fn parse_extract() {
 
     let res = parse_sql_statements("SELECT EXTRACT(MILLISECOND FROM d)");
     assert_eq!(
-        ParserError::ParserError("Expected date/time field, found: MILLISECOND".to_string()),
-        res.unwrap_err()
+        "Expected date/time field, found: MILLISECOND",
+        res.unwrap_err().message
     );
 }
 
@@ -1085,7 +1081,7 @@ fn parse_create_external_table() {
                name VARCHAR(100) NOT NULL,\
                lat DOUBLE NULL,\
                lng DOUBLE)\
-               STORED AS TEXTFILE LOCATION '/tmp/example.csv";
+               STORED AS TEXTFILE LOCATION '/tmp/example.csv'";
     let ast = one_statement_parses_to(
         sql,
         "CREATE EXTERNAL TABLE uk_cities (\
@@ -1182,22 +1178,109 @@ fn parse_alter_table_constraints() {
     }
 }
 
+#[test]
+fn parse_alter_table_drop_constraint() {
+    match verified_stmt("ALTER TABLE tab DROP CONSTRAINT tab_pkey") {
+        Statement::AlterTable {
+            name,
+            operation: AlterTableOperation::DropConstraint { name: constraint },
+        } => {
+            assert_eq!("tab", name.to_string());
+            assert_eq!("tab_pkey", constraint.to_string());
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn parse_alter_table_columns() {
+    match verified_stmt("ALTER TABLE tab ADD COLUMN foo int NOT NULL DEFAULT 1") {
+        Statement::AlterTable {
+            name,
+            operation: AlterTableOperation::AddColumn(column),
+        } => {
+            assert_eq!("tab", name.to_string());
+            assert_eq!("foo int NOT NULL DEFAULT 1", column.to_string());
+        }
+        _ => unreachable!(),
+    }
+    one_statement_parses_to(
+        "ALTER TABLE tab ADD foo text",
+        "ALTER TABLE tab ADD COLUMN foo text",
+    );
+
+    match verified_stmt("ALTER TABLE tab DROP COLUMN foo") {
+        Statement::AlterTable {
+            operation: AlterTableOperation::DropColumn { name },
+            ..
+        } => assert_eq!("foo", name.to_string()),
+        _ => unreachable!(),
+    }
+    one_statement_parses_to(
+        "ALTER TABLE tab DROP foo",
+        "ALTER TABLE tab DROP COLUMN foo",
+    );
+
+    check_alter_column(
+        "SET DATA TYPE bigint",
+        AlterColumnOperation::SetDataType(DataType::BigInt),
+    );
+    check_alter_column(
+        "SET DEFAULT 'x'",
+        AlterColumnOperation::SetDefault(Expr::Value(Value::SingleQuotedString("x".into()))),
+    );
+    check_alter_column("DROP DEFAULT", AlterColumnOperation::DropDefault);
+    check_alter_column("SET NOT NULL", AlterColumnOperation::SetNotNull);
+    check_alter_column("DROP NOT NULL", AlterColumnOperation::DropNotNull);
+    one_statement_parses_to(
+        "ALTER TABLE tab ALTER foo TYPE text",
+        "ALTER TABLE tab ALTER COLUMN foo SET DATA TYPE text",
+    );
+
+    fn check_alter_column(operation_text: &str, expected: AlterColumnOperation) {
+        match verified_stmt(&format!(
+            "ALTER TABLE tab ALTER COLUMN foo {}",
+            operation_text
+        )) {
+            Statement::AlterTable {
+                operation: AlterTableOperation::AlterColumn { name, operation },
+                ..
+            } => {
+                assert_eq!("foo", name.to_string());
+                assert_eq!(expected, operation);
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[test]
+fn parse_bad_alter_table() {
+    let res = parse_sql_statements("ALTER TABLE tab RENAME TO foo");
+    assert_eq!(
+        "Expected ADD, DROP or ALTER after ALTER TABLE, found: RENAME",
+        res.unwrap_err().message
+    );
+
+    let res = parse_sql_statements("ALTER TABLE tab ALTER COLUMN foo SET");
+    assert_eq!(
+        "Expected SET, DROP or TYPE after ALTER COLUMN, found: SET",
+        res.unwrap_err().message
+    );
+}
+
 #[test]
 fn parse_bad_constraint() {
     let res = parse_sql_statements("ALTER TABLE tab ADD");
     assert_eq!(
-        ParserError::ParserError(
-            "Expected a constraint in ALTER TABLE .. ADD, found: EOF".to_string()
-        ),
-        res.unwrap_err()
+        "Expected a column or constraint in ALTER TABLE .. ADD, found: EOF",
+        res.unwrap_err().message
     );
 
     let res = parse_sql_statements("CREATE TABLE tab (foo int,");
     assert_eq!(
-        ParserError::ParserError(
-            "Expected column name or constraint definition, found: EOF".to_string()
-        ),
-        res.unwrap_err()
+        "Expected column name or constraint definition, found: EOF",
+        res.unwrap_err().message
     );
 }
 
@@ -1336,16 +1419,22 @@ fn parse_literal_date() {
     );
 
     assert_eq!(
-        ParserError::ParserError("Invalid Month 0 in 0-00-00".into()),
-        parse_sql_statements("SELECT DATE '0-00-00'").unwrap_err(),
+        "Invalid Month 0 in 0-00-00".to_string(),
+        parse_sql_statements("SELECT DATE '0-00-00'")
+            .unwrap_err()
+            .message,
     );
     assert_eq!(
-        ParserError::ParserError("Invalid Day 0 in 0-01-00".into()),
-        parse_sql_statements("SELECT DATE '0-01-00'").unwrap_err(),
+        "Invalid Day 0 in 0-01-00".to_string(),
+        parse_sql_statements("SELECT DATE '0-01-00'")
+            .unwrap_err()
+            .message,
     );
     assert_eq!(
-        ParserError::ParserError("Hours cannot be supplied for DATE, got 2 in '1-1-1 2'".into()),
-        parse_sql_statements("SELECT DATE '1-1-1 2").unwrap_err(),
+        "Hours cannot be supplied for DATE, got 2 in '1-1-1 2'".to_string(),
+        parse_sql_statements("SELECT DATE '1-1-1 2'")
+            .unwrap_err()
+            .message,
     );
 }
 
@@ -1449,7 +1538,7 @@ fn parse_literal_timestamptz() {
                 minute: test.5,
                 second: test.6,
                 nano: test.7,
-                timezone_offset_second: 0 as i64,
+                timezone_offset_second: 0_i64,
             };
 
             if *format == "TIMESTAMPTZ" || *format == "TIMESTAMP WITH TIME ZONE" {
@@ -1543,34 +1632,33 @@ fn parse_literal_interval_monthlike() {
 
 #[test]
 fn parse_literal_interval_error_messages() {
-    let result = parse_sql_statements("SELECT INTERVAL '1' SECOND TO SECOND");
+    // The first `TO` is taken for a column alias, except in the dialects
+    // that reserve it
+    let result = Parser::parse_sql(
+        &GenericDialect {},
+        "SELECT INTERVAL '1' SECOND TO SECOND".to_string(),
+    );
     assert_eq!(
-        ParserError::ParserError("Expected end of statement, found: SECOND".to_string()),
-        result.unwrap_err(),
+        "Expected end of statement, found: SECOND",
+        result.unwrap_err().message,
     );
 
     let result = parse_sql_statements("SELECT INTERVAL '10' HOUR (1) TO HOUR (2)");
     assert_eq!(
-        ParserError::ParserError("Expected end of statement, found: (".to_string()),
-        result.unwrap_err(),
+        "Expected end of statement, found: (",
+        result.unwrap_err().message,
     );
 
     let result = parse_sql_statements("SELECT INTERVAL '1 1-1' DAY");
     assert_eq!(
-        ParserError::ParserError(
-            "Invalid interval part at offset 3: '1 1-1' provided Dash but expected Colon"
-                .to_string()
-        ),
-        result.unwrap_err()
+        "Invalid interval part at offset 3: '1 1-1' provided Dash but expected Colon",
+        result.unwrap_err().message
     );
 
     let result = parse_sql_statements("SELECT INTERVAL '1 1:1' HOUR");
     assert_eq!(
-        ParserError::ParserError(
-            "Invalid interval part at offset 1: '1 1:1' provided Space but expected Colon"
-                .to_string(),
-        ),
-        result.unwrap_err()
+        "Invalid interval part at offset 1: '1 1:1' provided Space but expected Colon",
+        result.unwrap_err().message
     );
 }
 
@@ -1880,7 +1968,7 @@ fn parse_simple_math_expr_minus() {
 fn parse_delimited_identifiers() {
     // check that quoted identifiers in any position remain quoted after serialization
     let select = verified_only_select(
-        r#"SELECT "alias"."bar baz", "myfun"(), "simple id" AS "column alias" FROM "a table" AS "alias""#
+        r#"SELECT "alias"."bar baz", "myfun"(), "simple id" AS "column alias" FROM "a table" AS "alias""#,
     );
     // check FROM
     match only(select.from).relation {
@@ -2026,19 +2114,19 @@ fn parse_show_objects_with_like_regex() {
 fn parse_show_indexes() {
     let canonical_sql = "SHOW INDEXES FROM foo";
     assert_eq!(
-        verified_stmt(&canonical_sql),
+        verified_stmt(canonical_sql),
         Statement::ShowIndexes {
             table_name: ObjectName(vec!["foo".into()]),
             filter: None,
         }
     );
-    one_statement_parses_to("SHOW INDEXES IN foo", &canonical_sql);
+    one_statement_parses_to("SHOW INDEXES IN foo", canonical_sql);
     let index_alias = ["INDEX", "KEYS"];
     let from_alias = ["FROM", "IN"];
     for i in &index_alias {
         for f in &from_alias {
             let sql = format!("SHOW {} {} foo", i, f);
-            one_statement_parses_to(&sql, &canonical_sql);
+            one_statement_parses_to(&sql, canonical_sql);
         }
     }
 }
@@ -2062,14 +2150,14 @@ fn parse_show_indexes_with_where_expr() {
     }
     one_statement_parses_to(
         "SHOW INDEXES IN foo WHERE index_name = 'bar'",
-        &canonical_sql,
+        canonical_sql,
     );
     let index_alias = ["INDEX", "KEYS"];
     let from_alias = ["FROM", "IN"];
     for i in &index_alias {
         for f in &from_alias {
             let sql = format!("SHOW {} {} foo WHERE index_name = 'bar'", i, f);
-            one_statement_parses_to(&sql, &canonical_sql);
+            one_statement_parses_to(&sql, canonical_sql);
         }
     }
 }
@@ -2115,7 +2203,7 @@ fn parse_simple_case_expr() {
 
 #[test]
 fn parse_from_advanced() {
-    let sql = "SELECT * FROM fn(1, 2) AS foo, schema.bar AS bar WITH (NOLOCK)";
+    let sql = "SELECT * FROM fn(1, 2) AS foo, myschema.bar AS bar WITH (NOLOCK)";
     let _select = verified_only_select(sql);
 }
 
@@ -2349,8 +2437,8 @@ fn parse_natural_join() {
 
     let sql = "SELECT * FROM t1 natural";
     assert_eq!(
-        ParserError::ParserError("Expected a join type after NATURAL, found: EOF".to_string()),
-        parse_sql_statements(sql).unwrap_err(),
+        "Expected a join type after NATURAL, found: EOF",
+        parse_sql_statements(sql).unwrap_err().message,
     );
 }
 
@@ -2419,10 +2507,7 @@ fn parse_join_nesting() {
     );
 
     let res = parse_sql_statements("SELECT * FROM (a NATURAL JOIN (b))");
-    assert_eq!(
-        ParserError::ParserError("Expected joined table, found: )".to_string()),
-        res.unwrap_err()
-    );
+    assert_eq!("Expected joined table, found: )", res.unwrap_err().message);
 }
 
 #[test]
@@ -2445,10 +2530,7 @@ fn parse_join_syntax_variants() {
     );
 
     let res = parse_sql_statements("SELECT * FROM a OUTER JOIN b ON 1");
-    assert_eq!(
-        ParserError::ParserError("Expected APPLY, found: JOIN".to_string()),
-        res.unwrap_err()
-    );
+    assert_eq!("Expected APPLY, found: JOIN", res.unwrap_err().message);
 }
 
 #[test]
@@ -2460,8 +2542,7 @@ fn parse_ctes() {
     );
 
     fn assert_ctes_in_select(expected: &[&str], sel: &Query) {
-        let mut i = 0;
-        for exp in expected {
+        for (i, exp) in expected.iter().enumerate() {
             let Cte { alias, query } = &sel.ctes[i];
             assert_eq!(*exp, query.to_string());
             assert_eq!(
@@ -2473,7 +2554,6 @@ fn parse_ctes() {
                 alias.name
             );
             assert!(alias.columns.is_empty());
-            i += 1;
         }
     }
 
@@ -2566,10 +2646,7 @@ fn parse_derived_tables() {
     );
 
     let res = parse_sql_statements("SELECT * FROM ((SELECT 1) AS t)");
-    assert_eq!(
-        ParserError::ParserError("Expected joined table, found: )".to_string()),
-        res.unwrap_err()
-    );
+    assert_eq!("Expected joined table, found: )", res.unwrap_err().message);
 }
 
 #[test]
@@ -2606,7 +2683,7 @@ fn parse_multiple_statements() {
         let res = parse_sql_statements(&(sql1.to_owned() + ";" + sql2_kw + sql2_rest));
         assert_eq!(
             vec![
-                one_statement_parses_to(&sql1, ""),
+                one_statement_parses_to(sql1, ""),
                 one_statement_parses_to(&(sql2_kw.to_owned() + sql2_rest), ""),
             ],
             res.unwrap()
@@ -2616,8 +2693,8 @@ fn parse_multiple_statements() {
         // Check that forgetting the semicolon results in an error:
         let res = parse_sql_statements(&(sql1.to_owned() + " " + sql2_kw + sql2_rest));
         assert_eq!(
-            ParserError::ParserError("Expected end of statement, found: ".to_string() + sql2_kw),
-            res.unwrap_err()
+            "Expected end of statement, found: ".to_string() + sql2_kw,
+            res.unwrap_err().message
         );
     }
     test_with("SELECT foo", "SELECT", " bar");
@@ -2641,59 +2718,65 @@ fn parse_multiple_statements() {
 #[test]
 fn parse_scalar_subqueries() {
     let sql = "(SELECT 1) + (SELECT 2)";
-    assert_matches!(verified_expr(sql), Expr::BinaryOp {
-        op: BinaryOperator::Plus, ..
-        //left: box Subquery { .. },
-        //right: box Subquery { .. },
-    });
+    assert_matches!(
+        verified_expr(sql),
+        Expr::BinaryOp {
+            op: BinaryOperator::Plus,
+            .. //left: box Subquery { .. },
+               //right: box Subquery { .. },
+        }
+    );
 }
 
 #[test]
 fn parse_any_some_all() {
     let sql = "1 < ANY (SELECT 2)";
-    assert_matches!(verified_expr(sql), Expr::Any {
-        op: BinaryOperator::Lt, ..
-        //left: box Expr,
-        //right: box Query { .. },
-    });
+    assert_matches!(
+        verified_expr(sql),
+        Expr::Any {
+            op: BinaryOperator::Lt,
+            .. //left: box Expr,
+               //right: box Query { .. },
+        }
+    );
 
     let sql = "1 < SOME (SELECT 2)";
-    assert_matches!(verified_expr(sql), Expr::Any {
-        op: BinaryOperator::Lt, ..
-        //left: box Expr,
-        //right: box Query { .. },
-    });
+    assert_matches!(
+        verified_expr(sql),
+        Expr::Any {
+            op: BinaryOperator::Lt,
+            .. //left: box Expr,
+               //right: box Query { .. },
+        }
+    );
 
     let sql = "1 < ALL (SELECT 2)";
-    assert_matches!(verified_expr(sql), Expr::All {
-        op: BinaryOperator::Lt, ..
-        //left: box Expr,
-        //right: box Query { .. },
-    });
+    assert_matches!(
+        verified_expr(sql),
+        Expr::All {
+            op: BinaryOperator::Lt,
+            .. //left: box Expr,
+               //right: box Query { .. },
+        }
+    );
 
     let res = parse_sql_statements("SELECT 1 WHERE 1 < ANY SELECT 2");
-    assert_eq!(
-        ParserError::ParserError("Expected (, found: SELECT".to_string()),
-        res.unwrap_err()
-    );
+    assert_eq!("Expected (, found: SELECT", res.unwrap_err().message);
 
     let res = parse_sql_statements("SELECT 1 WHERE 1 < NONE (SELECT 2)");
     assert_eq!(
         // TODO this is a pretty unhelpful error - it started parsing "NONE (SELECT" as applying the function NONE to the argument SELECT
-        ParserError::ParserError("Expected ), found: 2".to_string()),
-        res.unwrap_err()
+        "Expected ), found: 2",
+        res.unwrap_err().message
     );
 
     let res = parse_sql_statements("SELECT 1 WHERE 1 < ANY (SELECT 2");
-    assert_eq!(
-        ParserError::ParserError("Expected ), found: EOF".to_string()),
-        res.unwrap_err()
-    );
+    assert_eq!("Expected ), found: EOF", res.unwrap_err().message);
 
     let res = parse_sql_statements("SELECT 1 WHERE 1 + ANY (SELECT 2)");
     assert_eq!(
-        ParserError::ParserError("Expected comparison operator, found: +".to_string()),
-        res.unwrap_err()
+        "Expected comparison operator, found: +",
+        res.unwrap_err().message
     );
 }
 
@@ -2722,18 +2805,14 @@ fn parse_exists_subquery() {
 
     let res = parse_sql_statements("SELECT EXISTS (");
     assert_eq!(
-        ParserError::ParserError(
-            "Expected SELECT, VALUES, or a subquery in the query body, found: EOF".to_string()
-        ),
-        res.unwrap_err(),
+        "Expected SELECT, VALUES, or a subquery in the query body, found: EOF",
+        res.unwrap_err().message,
     );
 
     let res = parse_sql_statements("SELECT EXISTS (NULL)");
     assert_eq!(
-        ParserError::ParserError(
-            "Expected SELECT, VALUES, or a subquery in the query body, found: NULL".to_string()
-        ),
-        res.unwrap_err(),
+        "Expected SELECT, VALUES, or a subquery in the query body, found: NULL",
+        res.unwrap_err().message,
     );
 }
 
@@ -2844,7 +2923,7 @@ fn parse_create_source_raw_schema() {
                 },]
             );
         }
-        _ => assert!(false),
+        _ => unreachable!(),
     }
 }
 
@@ -2866,7 +2945,7 @@ fn parse_create_source_registry() {
             );
             assert_eq!(with_options, vec![]);
         }
-        _ => assert!(false),
+        _ => unreachable!(),
     }
 }
 
@@ -2885,7 +2964,7 @@ fn parse_create_sources() {
             assert_eq!("http://foo.bar:8081", schema_registry);
             assert!(with_options.is_empty());
         }
-        _ => assert!(false),
+        _ => unreachable!(),
     }
 }
 
@@ -2907,7 +2986,7 @@ fn parse_create_sources_with_like_regex() {
             assert_eq!("http://foo.bar:8081", schema_registry);
             assert!(with_options.is_empty());
         }
-        _ => assert!(false),
+        _ => unreachable!(),
     }
 }
 
@@ -2932,7 +3011,7 @@ fn parse_create_sink() {
                 },]
             );
         }
-        _ => assert!(false),
+        _ => unreachable!(),
     }
 }
 
@@ -2955,7 +3034,7 @@ fn parse_create_index() {
                 ]
             );
         }
-        _ => assert!(false),
+        _ => unreachable!(),
     }
 
     let sql = "CREATE INDEX fizz ON baz (ascii(x), a IS NOT NULL, (EXISTS (SELECT y FROM boop WHERE boop.z = z)), delta)";
@@ -2975,11 +3054,11 @@ fn parse_create_index() {
             if let Expr::Nested(expr) = &key_parts[2] {
                 assert_matches!(**expr, Expr::Exists(..));
             } else {
-                assert!(false);
+                unreachable!();
             }
             assert_eq!(key_parts[3], Expr::Identifier(Ident::new("delta")));
         }
-        _ => assert!(false),
+        _ => unreachable!(),
     }
 
     let sql = "CREATE INDEX ind ON tab ((col + 1))";
@@ -2996,11 +3075,11 @@ fn parse_create_index() {
                 vec![Expr::Nested(Box::new(Expr::BinaryOp {
                     left: Box::new(Expr::Identifier(Ident::new("col"))),
                     op: BinaryOperator::Plus,
-                    right: Box::new(Expr::Value(Value::Number("1".to_string())))
+                    right: Box::new(Expr::Value(number("1")))
                 }))],
             );
         }
-        _ => assert!(false),
+        _ => unreachable!(),
     }
     let sql = "CREATE INDEX qualifiers ON no_parentheses (alpha.omega)";
     match verified_stmt(sql) {
@@ -3019,7 +3098,7 @@ fn parse_create_index() {
                 ])],
             );
         }
-        _ => assert!(false),
+        _ => unreachable!(),
     }
 }
 
@@ -3027,10 +3106,7 @@ fn parse_create_index() {
 fn parse_invalid_create_index() {
     // Index names should not have a schema in front of it
     let res = parse_sql_statements("CREATE INDEX myschema.ind ON foo(b)");
-    assert_eq!(
-        ParserError::ParserError("Expected ON, found: .".to_string()),
-        res.unwrap_err(),
-    );
+    assert_eq!("Expected ON, found: .", res.unwrap_err().message,);
 }
 
 #[test]
@@ -3043,13 +3119,13 @@ fn parse_drop_table() {
             names,
             cascade,
         } => {
-            assert_eq!(false, if_exists);
+            assert!(!if_exists);
             assert_eq!(ObjectType::Table, object_type);
             assert_eq!(
                 vec!["foo"],
                 names.iter().map(ToString::to_string).collect::<Vec<_>>()
             );
-            assert_eq!(false, cascade);
+            assert!(!cascade);
         }
         _ => unreachable!(),
     }
@@ -3062,27 +3138,27 @@ fn parse_drop_table() {
             names,
             cascade,
         } => {
-            assert_eq!(true, if_exists);
+            assert!(if_exists);
             assert_eq!(ObjectType::Table, object_type);
             assert_eq!(
                 vec!["foo", "bar"],
                 names.iter().map(ToString::to_string).collect::<Vec<_>>()
             );
-            assert_eq!(true, cascade);
+            assert!(cascade);
         }
         _ => unreachable!(),
     }
 
     let sql = "DROP TABLE";
     assert_eq!(
-        ParserError::ParserError("Expected identifier, found: EOF".to_string()),
-        parse_sql_statements(sql).unwrap_err(),
+        "Expected identifier, found: EOF",
+        parse_sql_statements(sql).unwrap_err().message,
     );
 
     let sql = "DROP TABLE IF EXISTS foo, bar CASCADE RESTRICT";
     assert_eq!(
-        ParserError::ParserError("Cannot specify both CASCADE and RESTRICT in DROP".to_string()),
-        parse_sql_statements(sql).unwrap_err(),
+        "Cannot specify both CASCADE and RESTRICT in DROP",
+        parse_sql_statements(sql).unwrap_err().message,
     );
 }
 
@@ -3113,13 +3189,13 @@ fn parse_drop_source() {
             names,
             cascade,
         } => {
-            assert_eq!(false, if_exists);
+            assert!(!if_exists);
             assert_eq!(ObjectType::Source, object_type);
             assert_eq!(
                 vec!["myschema.mydatasource"],
                 names.iter().map(|n| n.to_string()).collect::<Vec<_>>()
             );
-            assert_eq!(false, cascade);
+            assert!(!cascade);
         }
         _ => unreachable!(),
     }
@@ -3135,12 +3211,12 @@ fn parse_drop_index() {
             names,
             cascade,
         } => {
-            assert_eq!(true, if_exists);
+            assert!(if_exists);
             assert_eq!(
                 vec!["myschema.myindex"],
                 names.iter().map(|n| n.to_string()).collect::<Vec<_>>()
             );
-            assert_eq!(false, cascade);
+            assert!(!cascade);
             assert_eq!(ObjectType::Index, object_type);
         }
         _ => unreachable!(),
@@ -3155,7 +3231,7 @@ fn parse_peek() {
             assert_eq!("foo.bar", name.to_string());
             assert!(!immediate);
         }
-        _ => assert!(false),
+        _ => unreachable!(),
     }
 
     let sql = "PEEK IMMEDIATE foo.bar";
@@ -3164,7 +3240,7 @@ fn parse_peek() {
             assert_eq!("foo.bar", name.to_string());
             assert!(immediate);
         }
-        _ => assert!(false),
+        _ => unreachable!(),
     }
 }
 
@@ -3175,7 +3251,7 @@ fn parse_tail() {
         Statement::Tail { name } => {
             assert_eq!("foo.bar", name.to_string());
         }
-        _ => assert!(false),
+        _ => unreachable!(),
     }
 }
 
@@ -3183,9 +3259,122 @@ fn parse_tail() {
 fn parse_invalid_subquery_without_parens() {
     let res = parse_sql_statements("SELECT SELECT 1 FROM bar WHERE 1=1 FROM baz");
     assert_eq!(
-        ParserError::ParserError("Expected end of statement, found: 1".to_string()),
-        res.unwrap_err()
+        "Expected end of statement, found: 1",
+        res.unwrap_err().message
+    );
+}
+
+#[test]
+fn parse_error_details() {
+    let err = parse_sql_statements("SELECT foo(1 +) FROM t").unwrap_err();
+    assert_eq!(ParserErrorKind::Syntax, err.kind);
+    assert_eq!("Expected an expression, found: )", err.message);
+    assert_eq!(vec!["an expression"], err.expected);
+    assert_eq!(Some(Token::RParen), err.found);
+    assert_eq!(vec!["SELECT projection", "function arguments"], err.context);
+    assert_eq!((1, 15), (err.span.start.line, err.span.start.column));
+    assert_eq!(Some("SELECT foo(1 +) FROM t".to_string()), err.source_line);
+    assert_eq!(
+        "sql parser error: Expected an expression, found: ) at Line: 1, Column: 15, \
+         in SELECT projection, in function arguments\n\
+         SELECT foo(1 +) FROM t\n              ^",
+        err.to_string()
+    );
+
+    let err = parse_sql_statements("SELECT 1\nFROM t\nWHERE a = = b").unwrap_err();
+    assert_eq!(vec!["WHERE clause"], err.context);
+    assert_eq!((3, 11), (err.span.start.line, err.span.start.column));
+    assert_eq!(Some("WHERE a = = b".to_string()), err.source_line);
+
+    let err = parse_sql_statements("SELECT * FROM t WHERE").unwrap_err();
+    assert_eq!("Expected an expression, found: EOF", err.message);
+    assert_eq!(None, err.found);
+    assert_eq!((1, 22), (err.span.start.line, err.span.start.column));
+
+    let err = parse_sql_statements("SELECT \"foo").unwrap_err();
+    assert_eq!(ParserErrorKind::Tokenizer, err.kind);
+    assert_eq!((1, 12), (err.span.start.line, err.span.start.column));
+}
+
+#[test]
+fn parse_with_recovery() {
+    let sql = "SELECT 1; SELECT a FROM t WHERE a = = b GROUP BY c; INSERT foo; SELECT 2";
+    let (stmts, errors) = Parser::parse_sql_with_recovery(&GenericDialect {}, sql.to_string());
+    assert_eq!(
+        vec!["SELECT 1", "SELECT a FROM t GROUP BY c", "SELECT 2"],
+        stmts.iter().map(|s| s.to_string()).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![
+            "Expected an expression, found: =",
+            "Expected INTO, found: foo"
+        ],
+        errors
+            .iter()
+            .map(|e| e.message.as_str())
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(vec!["WHERE clause"], errors[0].context);
+    assert_eq!(
+        (1, 37),
+        (errors[0].span.start.line, errors[0].span.start.column)
+    );
+    assert!(errors.iter().all(|e| e.source_line.is_some()));
+
+    // A SELECT whose projection has an error is left out, after the errors
+    // in the rest of it are recorded, so that the statements that are
+    // returned can be parsed again
+    let sql = "SELECT 1 +, 2 FROM t WHERE a = = b; SELECT (SELECT +) AS x; \
+               SELECT a FROM t WHERE b IN (SELECT 1 +, 2 FROM u WHERE c = =) GROUP BY c";
+    let (stmts, errors) = Parser::parse_sql_with_recovery(&GenericDialect {}, sql.to_string());
+    assert_eq!(
+        vec!["SELECT a FROM t GROUP BY c"],
+        stmts.iter().map(|s| s.to_string()).collect::<Vec<_>>()
+    );
+    for stmt in &stmts {
+        one_statement_parses_to(&stmt.to_string(), &stmt.to_string());
+    }
+    assert_eq!(
+        vec![
+            "Expected an expression, found: ,",
+            "Expected an expression, found: =",
+            "Expected an expression, found: )",
+            "Expected an expression, found: ,",
+            "Expected an expression, found: =",
+        ],
+        errors
+            .iter()
+            .map(|e| e.message.as_str())
+            .collect::<Vec<_>>()
     );
+    assert_eq!(vec!["SELECT projection"], errors[0].context);
+    assert_eq!(vec!["WHERE clause"], errors[1].context);
+    assert_eq!(
+        vec!["SELECT projection", "subquery", "SELECT projection"],
+        errors[2].context
+    );
+    assert_eq!(vec!["WHERE clause", "SELECT projection"], errors[3].context);
+    assert_eq!(vec!["WHERE clause", "WHERE clause"], errors[4].context);
+
+    // Errors in a subquery are recovered from within the subquery, and keep
+    // the context of the enclosing query
+    let sql = "SELECT * FROM (SELECT a FROM t WHERE (b +) ORDER BY c) WHERE d";
+    let (stmts, errors) = Parser::parse_sql_with_recovery(&GenericDialect {}, sql.to_string());
+    assert_eq!(
+        vec!["SELECT * FROM (SELECT a FROM t ORDER BY c) WHERE d"],
+        stmts.iter().map(|s| s.to_string()).collect::<Vec<_>>()
+    );
+    assert_eq!(1, errors.len());
+    assert_eq!(vec!["FROM clause", "WHERE clause"], errors[0].context);
+
+    // Without errors, the result is the same as that of `parse_sql`
+    let sql = "SELECT a FROM t; DELETE FROM t";
+    let (stmts, errors) = Parser::parse_sql_with_recovery(&GenericDialect {}, sql.to_string());
+    assert_eq!(
+        Parser::parse_sql(&GenericDialect {}, sql.to_string()).unwrap(),
+        stmts
+    );
+    assert!(errors.is_empty());
 }
 
 #[test]
@@ -3328,7 +3517,7 @@ fn lateral_derived() {
         let lateral_str = if lateral_in { "LATERAL " } else { "" };
         let sql = format!(
             "SELECT * FROM customer LEFT JOIN {}\
-             (SELECT * FROM order WHERE order.customer = customer.id LIMIT 3) AS order ON true",
+             (SELECT * FROM orders WHERE orders.customer = customer.id LIMIT 3) AS orders ON true",
             lateral_str
         );
         let select = verified_only_select(&sql);
@@ -3346,10 +3535,10 @@ fn lateral_derived() {
         } = join.relation
         {
             assert_eq!(lateral_in, lateral);
-            assert_eq!(Ident::new("order"), alias.name);
+            assert_eq!(Ident::new("orders"), alias.name);
             assert_eq!(
                 subquery.to_string(),
-                "SELECT * FROM order WHERE order.customer = customer.id LIMIT 3"
+                "SELECT * FROM orders WHERE orders.customer = customer.id LIMIT 3"
             );
         } else {
             unreachable!()
@@ -3361,19 +3550,15 @@ fn lateral_derived() {
     let sql = "SELECT * FROM customer LEFT JOIN LATERAL generate_series(1, customer.id)";
     let res = parse_sql_statements(sql);
     assert_eq!(
-        ParserError::ParserError(
-            "Expected subquery after LATERAL, found: generate_series".to_string()
-        ),
-        res.unwrap_err()
+        "Expected subquery after LATERAL, found: generate_series",
+        res.unwrap_err().message
     );
 
     let sql = "SELECT * FROM a LEFT JOIN LATERAL (b CROSS JOIN c)";
     let res = parse_sql_statements(sql);
     assert_eq!(
-        ParserError::ParserError(
-            "Expected SELECT, VALUES, or a subquery in the query body, found: b".to_string()
-        ),
-        res.unwrap_err()
+        "Expected SELECT, VALUES, or a subquery in the query body, found: b",
+        res.unwrap_err().message
     );
 }
 
@@ -3420,20 +3605,20 @@ fn parse_start_transaction() {
 
     let res = parse_sql_statements("START TRANSACTION ISOLATION LEVEL BAD");
     assert_eq!(
-        ParserError::ParserError("Expected isolation level, found: BAD".to_string()),
-        res.unwrap_err()
+        "Expected isolation level, found: BAD",
+        res.unwrap_err().message
     );
 
     let res = parse_sql_statements("START TRANSACTION BAD");
     assert_eq!(
-        ParserError::ParserError("Expected transaction mode, found: BAD".to_string()),
-        res.unwrap_err()
+        "Expected transaction mode, found: BAD",
+        res.unwrap_err().message
     );
 
     let res = parse_sql_statements("START TRANSACTION READ ONLY,");
     assert_eq!(
-        ParserError::ParserError("Expected transaction mode, found: EOF".to_string()),
-        res.unwrap_err()
+        "Expected transaction mode, found: EOF",
+        res.unwrap_err().message
     );
 }
 
@@ -3541,30 +3726,276 @@ fn parse_flush() {
     );
 }
 
+#[test]
+fn parse_deeply_nested_expr() {
+    let nested = |depth| format!("SELECT {}1{}", "(".repeat(depth), ")".repeat(depth));
+    // Skip the serde round trip, as serde_json has a recursion limit of its
+    // own. The query and its projection take up two levels.
+    all_dialects().verified_stmt(&nested(Parser::DEFAULT_RECURSION_LIMIT - 2));
+    let err = parse_sql_statements(&nested(Parser::DEFAULT_RECURSION_LIMIT - 1)).unwrap_err();
+    assert_eq!(ParserErrorKind::RecursionLimitExceeded, err.kind);
+    assert_eq!("Exceeded the recursion limit of 50", err.message);
+    // The stack does not overflow, however deep the nesting
+    let err = parse_sql_statements(&nested(10_000)).unwrap_err();
+    assert_eq!(ParserErrorKind::RecursionLimitExceeded, err.kind);
+
+    let err = parse_sql_statements(&format!("SELECT {}1", "NOT -".repeat(10_000))).unwrap_err();
+    assert_eq!(ParserErrorKind::RecursionLimitExceeded, err.kind);
+}
+
+#[test]
+fn parse_deeply_nested_subqueries() {
+    let nested = |depth| {
+        let mut sql = "SELECT * FROM t".to_string();
+        for _ in 0..depth {
+            sql = format!("SELECT * FROM ({}) AS t", sql);
+        }
+        sql
+    };
+    all_dialects().verified_stmt(&nested(20));
+    let err = parse_sql_statements(&nested(10_000)).unwrap_err();
+    assert_eq!(ParserErrorKind::RecursionLimitExceeded, err.kind);
+
+    let nested = |depth| format!("{}SELECT 1{}", "(".repeat(depth), ")".repeat(depth));
+    all_dialects().verified_stmt(&nested(40));
+    let err = parse_sql_statements(&nested(10_000)).unwrap_err();
+    assert_eq!(ParserErrorKind::RecursionLimitExceeded, err.kind);
+}
+
+#[test]
+fn parse_deeply_nested_joins() {
+    let nested = |depth| {
+        format!(
+            "SELECT * FROM {}a NATURAL JOIN b{}",
+            "(".repeat(depth),
+            ")".repeat(depth)
+        )
+    };
+    all_dialects().verified_stmt(&nested(40));
+    // The error must not be taken for a sign that the parens do not start
+    // a derived table
+    let err = parse_sql_statements(&nested(10_000)).unwrap_err();
+    assert_eq!(ParserErrorKind::RecursionLimitExceeded, err.kind);
+}
+
+#[test]
+fn parse_with_recursion_limit() {
+    let sql = "SELECT (((1)))";
+    let dialect = GenericDialect {};
+    let parse = |limit| {
+        let tokens = Tokenizer::new(&dialect, sql).tokenize_with_span().unwrap();
+        Parser::new_with_spans(tokens, &dialect)
+            .with_recursion_limit(limit)
+            .parse_statements()
+    };
+    assert!(parse(5).is_ok());
+    let err = parse(4).unwrap_err();
+    assert_eq!(ParserErrorKind::RecursionLimitExceeded, err.kind);
+    assert_eq!("Exceeded the recursion limit of 4", err.message);
+    assert_eq!(10, err.span.start.offset);
+}
+
+fn parse_with_options(sql: &str, options: ParserOptions) -> Result<Vec<Statement>, ParserError> {
+    Parser::parse_sql_with_options(&GenericDialect {}, sql.to_string(), options)
+}
+
+#[test]
+fn parse_with_default_options() {
+    let sql = "SELECT a::int FROM t LIMIT 1";
+    assert_eq!(
+        parse_sql_statements(sql).unwrap(),
+        parse_with_options(sql, ParserOptions::default()).unwrap()
+    );
+}
+
+#[test]
+fn parse_with_recursion_limit_option() {
+    let options = ParserOptions {
+        recursion_limit: 4,
+        ..ParserOptions::default()
+    };
+    let err = parse_with_options("SELECT (((1)))", options).unwrap_err();
+    assert_eq!(ParserErrorKind::RecursionLimitExceeded, err.kind);
+}
+
+#[test]
+fn parse_strict_ansi() {
+    let strict = ParserOptions {
+        strict_ansi: true,
+        ..ParserOptions::default()
+    };
+    let err = parse_with_options("SELECT a::int FROM t", strict.clone()).unwrap_err();
+    assert_eq!("A `::` cast is not standard SQL", err.message);
+    assert_eq!(8, err.span.start.offset);
+    for (sql, message) in &[
+        ("SELECT a FROM t LIMIT 1", "LIMIT is not standard SQL"),
+        (
+            "SELECT a FROM t CROSS APPLY f(t.a)",
+            "CROSS APPLY is not standard SQL",
+        ),
+        (
+            "SELECT a FROM t OUTER APPLY f(t.a)",
+            "OUTER APPLY is not standard SQL",
+        ),
+        (
+            "SELECT a FROM t WITH (NOLOCK)",
+            "A table hint is not standard SQL",
+        ),
+        (
+            "SELECT a 'b' FROM t",
+            "A single-quoted alias is not standard SQL",
+        ),
+        (
+            "SELECT INTERVAL '1 day'",
+            "An interval with its units inside the string is not standard SQL",
+        ),
+    ] {
+        assert!(parse_with_options(sql, ParserOptions::default()).is_ok());
+        assert_eq!(
+            *message,
+            parse_with_options(sql, strict.clone()).unwrap_err().message
+        );
+    }
+    // The standard equivalents are accepted
+    parse_with_options(
+        "SELECT CAST(a AS INT) FROM t FETCH FIRST 1 ROWS ONLY",
+        strict.clone(),
+    )
+    .unwrap();
+    parse_with_options("SELECT INTERVAL '1' DAY", strict).unwrap();
+}
+
+#[test]
+fn parse_with_validated_intervals() {
+    let options = ParserOptions {
+        validate_intervals: true,
+        ..ParserOptions::default()
+    };
+    let sql = "SELECT INTERVAL '1:2:3' HOUR TO MINUTE";
+    assert!(parse_with_options(sql, ParserOptions::default()).is_ok());
+    let err = parse_with_options(sql, options.clone()).unwrap_err();
+    assert_eq!(16, err.span.start.offset);
+    parse_with_options("SELECT INTERVAL '1:2' HOUR TO MINUTE", options).unwrap();
+}
+
+#[test]
+fn parse_with_trailing_commas() {
+    let options = ParserOptions {
+        trailing_commas: true,
+        ..ParserOptions::default()
+    };
+    for (sql, canonical) in &[
+        ("SELECT a, b, FROM t", "SELECT a, b FROM t"),
+        ("SELECT a, FROM t ORDER BY a,", "SELECT a FROM t ORDER BY a"),
+        ("SELECT f(a, b,)", "SELECT f(a, b)"),
+        (
+            "INSERT INTO t (a, b,) VALUES (1, 2,)",
+            "INSERT INTO t (a, b) VALUES (1, 2)",
+        ),
+    ] {
+        let stmts = parse_with_options(sql, options.clone()).unwrap();
+        assert_eq!(*canonical, stmts[0].to_string());
+    }
+    assert!(parse_with_options("SELECT f(a, b,)", ParserOptions::default()).is_err());
+
+    // The keywords that the dialect reserves also end lists, unless they
+    // start an item.
+    let parse_postgres = |sql: &str| {
+        Parser::parse_sql_with_options(&PostgreSqlDialect {}, sql.to_string(), options.clone())
+    };
+    assert_eq!(
+        "SELECT a, NULL, CASE WHEN b THEN 1 END, current_date, left(c, 1) FROM t, LATERAL (SELECT 1) AS d",
+        parse_postgres(
+            "SELECT a, NULL, CASE WHEN b THEN 1 END, current_date, left(c, 1), FROM t, LATERAL (SELECT 1) AS d"
+        )
+        .unwrap()[0]
+            .to_string()
+    );
+    assert_eq!(
+        "SELECT a, into AS t FROM s",
+        parse_with_options("SELECT a, into t FROM s", options.clone()).unwrap()[0].to_string()
+    );
+    assert_eq!(
+        "Expected end of statement, found: into",
+        parse_postgres("SELECT a, into t FROM s")
+            .unwrap_err()
+            .message
+    );
+}
+
+#[test]
+fn parse_with_identifier_case() {
+    let sql = r#"SELECT Foo, "Bar" FROM MySchema.T AS x"#;
+    let with_case = |identifier_case| {
+        let options = ParserOptions {
+            identifier_case,
+            ..ParserOptions::default()
+        };
+        parse_with_options(sql, options).unwrap()[0].to_string()
+    };
+    assert_eq!(sql, with_case(IdentifierCase::Preserve));
+    assert_eq!(
+        r#"SELECT foo, "Bar" FROM myschema.t AS x"#,
+        with_case(IdentifierCase::Lower)
+    );
+    assert_eq!(
+        r#"SELECT FOO, "Bar" FROM MYSCHEMA.T AS X"#,
+        with_case(IdentifierCase::Upper)
+    );
+}
+
 fn parse_sql_statements(sql: &str) -> Result<Vec<Statement>, ParserError> {
-    all_dialects().parse_sql_statements(sql)
+    let result = all_dialects().parse_sql_statements(sql);
+    if let Ok(statements) = &result {
+        assert_serde_round_trip(statements);
+    }
+    result
 }
 
 fn one_statement_parses_to(sql: &str, canonical: &str) -> Statement {
-    all_dialects().one_statement_parses_to(sql, canonical)
+    let statement = all_dialects().one_statement_parses_to(sql, canonical);
+    assert_serde_round_trip(&statement);
+    statement
 }
 
 fn verified_stmt(query: &str) -> Statement {
-    all_dialects().verified_stmt(query)
+    let statement = all_dialects().verified_stmt(query);
+    assert_serde_round_trip(&statement);
+    statement
 }
 
 fn verified_query(query: &str) -> Query {
-    all_dialects().verified_query(query)
+    let query = all_dialects().verified_query(query);
+    assert_serde_round_trip(&query);
+    query
 }
 
 fn verified_only_select(query: &str) -> Select {
-    all_dialects().verified_only_select(query)
+    let select = all_dialects().verified_only_select(query);
+    assert_serde_round_trip(&select);
+    select
 }
 
 fn verified_expr(query: &str) -> Expr {
-    all_dialects().verified_expr(query)
+    let expr = all_dialects().verified_expr(query);
+    assert_serde_round_trip(&expr);
+    expr
 }
 
+/// Ensures that `node` is unchanged by serializing and deserializing it, if
+/// the `serde` feature is enabled
+#[cfg(feature = "serde")]
+fn assert_serde_round_trip<T>(node: &T)
+where
+    T: serde::Serialize + serde::de::DeserializeOwned + PartialEq + std::fmt::Debug,
+{
+    let json = serde_json::to_string(node).unwrap();
+    assert_eq!(node, &serde_json::from_str::<T>(&json).unwrap(), "{}", json);
+}
+
+#[cfg(not(feature = "serde"))]
+fn assert_serde_round_trip<T>(_node: &T) {}
+
 // interval test helpers
 
 fn dflt<T: Default>() -> T {
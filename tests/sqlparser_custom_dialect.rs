@@ -0,0 +1,336 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![warn(clippy::all)]
+//! Test the `Dialect` hooks and `Extension` AST nodes that let a dialect
+//! extend the grammar without changing the parser.
+
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use sqlparser::ast::visit::{self, Visit};
+use sqlparser::ast::visit_mut::{self, VisitMut};
+use sqlparser::ast::*;
+use sqlparser::dialect::{Dialect, GenericDialect};
+use sqlparser::parser::{Parser, ParserError};
+use sqlparser::test_utils::expr_from_projection;
+use sqlparser::tokenizer::Token;
+
+/// A dialect that accepts `ABORT` as an alias for `ROLLBACK`, `TODAY` as an
+/// alias for `current_date` and `&` as an alias for `AND`.
+#[derive(Debug)]
+struct AbbreviatingDialect {}
+
+impl Dialect for AbbreviatingDialect {
+    fn is_identifier_start(&self, ch: char) -> bool {
+        GenericDialect {}.is_identifier_start(ch)
+    }
+
+    fn is_identifier_part(&self, ch: char) -> bool {
+        GenericDialect {}.is_identifier_part(ch)
+    }
+
+    fn parse_statement(&self, parser: &mut Parser) -> Option<Result<Statement, ParserError>> {
+        if parse_word(parser, "ABORT") {
+            Some(Ok(Statement::Rollback { chain: false }))
+        } else {
+            None
+        }
+    }
+
+    fn parse_prefix(&self, parser: &mut Parser) -> Option<Result<Expr, ParserError>> {
+        if parse_word(parser, "TODAY") {
+            Some(Ok(Expr::Identifier(Ident::new("current_date"))))
+        } else {
+            None
+        }
+    }
+
+    fn parse_infix(
+        &self,
+        parser: &mut Parser,
+        expr: &Expr,
+        precedence: u8,
+    ) -> Option<Result<Expr, ParserError>> {
+        if parser.consume_token(&Token::Ampersand) {
+            Some(
+                parser
+                    .parse_subexpr(precedence)
+                    .map(|right| Expr::BinaryOp {
+                        left: Box::new(expr.clone()),
+                        op: BinaryOperator::And,
+                        right: Box::new(right),
+                    }),
+            )
+        } else {
+            None
+        }
+    }
+
+    fn get_next_precedence(&self, parser: &Parser) -> Option<Result<u8, ParserError>> {
+        match parser.peek_token() {
+            Some(Token::Ampersand) => Some(Ok(10)),
+            _ => None,
+        }
+    }
+}
+
+/// Like `Parser::parse_keyword`, for words that are not standard keywords
+fn parse_word(parser: &mut Parser, expected: &str) -> bool {
+    match parser.peek_token() {
+        Some(Token::Word(w)) if w.value.eq_ignore_ascii_case(expected) => {
+            parser.advance_token();
+            true
+        }
+        _ => false,
+    }
+}
+
+fn parse(sql: &str) -> Result<Vec<Statement>, ParserError> {
+    Parser::parse_sql(&AbbreviatingDialect {}, sql.to_string())
+}
+
+/// A dialect that accepts `VACUUM <table>` statements and `TRY(<expr>)`
+/// expressions, which are represented as extensions.
+#[derive(Debug)]
+struct ExtendedDialect {}
+
+impl Dialect for ExtendedDialect {
+    fn is_identifier_start(&self, ch: char) -> bool {
+        GenericDialect {}.is_identifier_start(ch)
+    }
+
+    fn is_identifier_part(&self, ch: char) -> bool {
+        GenericDialect {}.is_identifier_part(ch)
+    }
+
+    fn parse_statement(&self, parser: &mut Parser) -> Option<Result<Statement, ParserError>> {
+        if parse_word(parser, "VACUUM") {
+            Some(
+                parser
+                    .parse_object_name()
+                    .map(|table_name| Statement::Extension(Box::new(Vacuum { table_name }))),
+            )
+        } else {
+            None
+        }
+    }
+
+    fn parse_prefix(&self, parser: &mut Parser) -> Option<Result<Expr, ParserError>> {
+        if parse_word(parser, "TRY") {
+            Some((|| {
+                parser.expect_token(&Token::LParen)?;
+                let expr = parser.parse_expr()?;
+                parser.expect_token(&Token::RParen)?;
+                Ok(Expr::Extension(Box::new(Try { expr })))
+            })())
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Vacuum {
+    table_name: ObjectName,
+}
+
+impl fmt::Display for Vacuum {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "VACUUM {}", self.table_name)
+    }
+}
+
+impl Extension for Vacuum {
+    fn children(&self) -> Vec<visit::Node<'_>> {
+        vec![visit::Node::ObjectName(&self.table_name)]
+    }
+
+    fn children_mut(&mut self) -> Vec<visit_mut::Node<'_>> {
+        vec![visit_mut::Node::ObjectName(&mut self.table_name)]
+    }
+
+    fn span(&self) -> Span {
+        self.table_name.span()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Try {
+    expr: Expr,
+}
+
+impl fmt::Display for Try {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TRY({})", self.expr)
+    }
+}
+
+impl Extension for Try {
+    fn children(&self) -> Vec<visit::Node<'_>> {
+        vec![visit::Node::Expr(&self.expr)]
+    }
+
+    fn children_mut(&mut self) -> Vec<visit_mut::Node<'_>> {
+        vec![visit_mut::Node::Expr(&mut self.expr)]
+    }
+}
+
+fn parse_extended(sql: &str) -> Vec<Statement> {
+    let stmts = Parser::parse_sql(&ExtendedDialect {}, sql.to_string()).unwrap();
+    assert_eq!(
+        sql,
+        stmts
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<_>>()
+            .join("; ")
+    );
+    stmts
+}
+
+fn hash<T: Hash>(t: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    t.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn parse_custom_statement() {
+    assert_eq!(
+        vec![
+            Statement::Rollback { chain: false },
+            Statement::Rollback { chain: true }
+        ],
+        parse("ABORT; ROLLBACK AND CHAIN").unwrap()
+    );
+    assert_eq!(
+        "Expected a keyword at the beginning of a statement, found: ABORT",
+        Parser::parse_sql(&GenericDialect {}, "ABORT".to_string())
+            .unwrap_err()
+            .message
+    );
+}
+
+#[test]
+fn parse_custom_expressions() {
+    let stmts = parse("SELECT today, x FROM t WHERE a & b = 1 & c OR d").unwrap();
+    assert_eq!(
+        "SELECT current_date, x FROM t WHERE a AND b = 1 AND c OR d",
+        stmts[0].to_string()
+    );
+    // The standard operators keep their precedence relative to `&`
+    match &stmts[0] {
+        Statement::Query(query) => match &query.body {
+            SetExpr::Select(select) => match &select.selection {
+                Some(Expr::BinaryOp { op, .. }) => assert_eq!(&BinaryOperator::Or, op),
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        },
+        _ => unreachable!(),
+    }
+
+    assert_eq!(
+        "Expected an expression, found: EOF",
+        parse("SELECT a &").unwrap_err().message
+    );
+}
+
+#[test]
+fn parse_extension_nodes() {
+    let stmts = parse_extended("VACUUM foo.bar; SELECT TRY(a + 1) FROM t");
+    match &stmts[0] {
+        Statement::Extension(extension) => {
+            let vacuum = extension.as_any().downcast_ref::<Vacuum>().unwrap();
+            assert_eq!("foo.bar", vacuum.table_name.to_string());
+        }
+        _ => unreachable!(),
+    }
+    let start = stmts[0].span().start;
+    assert_eq!((1, 8), (start.line, start.column));
+    match &stmts[1] {
+        Statement::Query(query) => match &query.body {
+            SetExpr::Select(select) => match expr_from_projection(&select.projection[0]) {
+                Expr::Extension(extension) => assert!(extension.as_any().is::<Try>()),
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        },
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn extension_nodes_compare_and_hash_by_value() {
+    let stmts = parse_extended("VACUUM a; VACUUM a; VACUUM b; SELECT TRY(a)");
+    assert_eq!(stmts[0], stmts[1]);
+    assert_eq!(hash(&stmts[0]), hash(&stmts[1]));
+    assert_ne!(stmts[0], stmts[2]);
+    assert_ne!(hash(&stmts[0]), hash(&stmts[2]));
+
+    let cloned = stmts.clone();
+    assert_eq!(stmts, cloned);
+
+    // Extensions of different types are never equal, even if they display
+    // the same way
+    let vacuum = Expr::Extension(Box::new(Vacuum {
+        table_name: ObjectName(vec![Ident::new("x")]),
+    }));
+    let imposter = Expr::Extension(Box::new(Try {
+        expr: Expr::Identifier(Ident::new("x")),
+    }));
+    assert_ne!(vacuum, imposter);
+}
+
+#[test]
+fn visit_extension_nodes() {
+    struct Visitor<'a> {
+        idents: Vec<&'a str>,
+    }
+
+    impl<'a> Visit<'a> for Visitor<'a> {
+        fn visit_ident(&mut self, ident: &'a Ident) {
+            self.idents.push(&ident.value);
+        }
+    }
+
+    struct Renamer;
+
+    impl<'a> VisitMut<'a> for Renamer {
+        fn visit_ident(&mut self, ident: &'a mut Ident) {
+            ident.value = ident.value.to_uppercase();
+        }
+    }
+
+    let mut stmts = parse_extended("VACUUM foo.bar; SELECT TRY(a + b) FROM t");
+    let mut visitor = Visitor { idents: vec![] };
+    for stmt in &stmts {
+        visitor.visit_statement(stmt);
+    }
+    assert_eq!(vec!["foo", "bar", "a", "b", "t"], visitor.idents);
+
+    for stmt in &mut stmts {
+        Renamer.visit_statement(stmt);
+    }
+    assert_eq!("VACUUM FOO.BAR", stmts[0].to_string());
+    assert_eq!("SELECT TRY(A + B) FROM T", stmts[1].to_string());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn extension_nodes_are_not_serializable() {
+    let stmts = parse_extended("VACUUM a; SELECT TRY(a)");
+    assert!(serde_json::to_string(&stmts[0]).is_err());
+    assert!(serde_json::to_string(&stmts[1]).is_err());
+}
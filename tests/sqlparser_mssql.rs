@@ -68,6 +68,18 @@ fn parse_mssql_apply_join() {
     );
 }
 
+#[test]
+fn parse_mssql_reserved_keywords() {
+    assert_eq!(
+        "Expected identifier, found: schema",
+        ms().parse_sql_statements("SELECT * FROM schema.t")
+            .unwrap_err()
+            .message
+    );
+    let _ = ms().verified_stmt("SELECT * FROM [schema].t");
+    let _ = ms().one_statement_parses_to("SELECT * FROM t offsets", "SELECT * FROM t AS offsets");
+}
+
 fn ms() -> TestedDialects {
     TestedDialects {
         dialects: vec![Box::new(MsSqlDialect {})],
@@ -97,6 +97,22 @@ fn parse_show_columns() {
     }
 }
 
+#[test]
+fn parse_reserved_keywords() {
+    // Unlike PostgreSQL, MySQL does not reserve `user`...
+    mysql().verified_stmt("SELECT user.id FROM user");
+    mysql().one_statement_parses_to("SELECT * FROM t user", "SELECT * FROM t AS user");
+    // ...but it does reserve `rank`
+    assert_eq!(
+        "Expected identifier, found: rank",
+        mysql()
+            .parse_sql_statements("CREATE TABLE rank (a INT)")
+            .unwrap_err()
+            .message
+    );
+    mysql().verified_stmt(r#"CREATE TABLE "rank" (a int)"#);
+}
+
 fn mysql() -> TestedDialects {
     TestedDialects {
         dialects: vec![Box::new(MySqlDialect {})],
@@ -16,7 +16,6 @@
 
 use sqlparser::ast::*;
 use sqlparser::dialect::{GenericDialect, PostgreSqlDialect};
-use sqlparser::parser::ParserError;
 use sqlparser::test_utils::*;
 
 #[test]
@@ -260,6 +259,22 @@ PHP	₱ USD $
     //assert_eq!(sql, ast.to_string());
 }
 
+#[test]
+fn parse_dollar_quoted_string() {
+    let stmt = pg_and_generic()
+        .one_statement_parses_to("SELECT $$it's$$, $fn$ $$ $fn$", "SELECT 'it''s', ' $$ '");
+    match stmt {
+        Statement::Query(query) => match &query.body {
+            SetExpr::Select(select) => assert_eq!(
+                &Expr::Value(Value::SingleQuotedString("it's".into())),
+                expr_from_projection(&select.projection[0])
+            ),
+            _ => unreachable!(),
+        },
+        _ => unreachable!(),
+    }
+}
+
 #[test]
 fn parse_set() {
     let stmt = pg_and_generic().verified_stmt("SET a = b");
@@ -316,24 +331,27 @@ fn parse_set() {
     pg_and_generic().one_statement_parses_to("SET SESSION a = b", "SET a = b");
 
     assert_eq!(
-        pg_and_generic().parse_sql_statements("SET"),
-        Err(ParserError::ParserError(
-            "Expected identifier, found: EOF".to_string()
-        )),
+        pg_and_generic()
+            .parse_sql_statements("SET")
+            .unwrap_err()
+            .message,
+        "Expected identifier, found: EOF",
     );
 
     assert_eq!(
-        pg_and_generic().parse_sql_statements("SET a b"),
-        Err(ParserError::ParserError(
-            "Expected equals sign or TO, found: b".to_string()
-        )),
+        pg_and_generic()
+            .parse_sql_statements("SET a b")
+            .unwrap_err()
+            .message,
+        "Expected equals sign or TO, found: b",
     );
 
     assert_eq!(
-        pg_and_generic().parse_sql_statements("SET a ="),
-        Err(ParserError::ParserError(
-            "Expected variable value, found: EOF".to_string()
-        )),
+        pg_and_generic()
+            .parse_sql_statements("SET a =")
+            .unwrap_err()
+            .message,
+        "Expected variable value, found: EOF",
     );
 }
 
@@ -356,6 +374,28 @@ fn parse_show() {
     )
 }
 
+#[test]
+fn parse_reserved_keywords() {
+    assert_eq!(
+        "Expected identifier, found: user",
+        pg().parse_sql_statements("SELECT * FROM user")
+            .unwrap_err()
+            .message
+    );
+    pg().verified_stmt(r#"SELECT * FROM "user""#);
+    // Reserved keywords are not taken for aliases without `AS`...
+    assert_eq!(
+        "Expected end of statement, found: user",
+        pg().parse_sql_statements("SELECT * FROM t user")
+            .unwrap_err()
+            .message
+    );
+    // ...but any keyword can be a column alias after it
+    pg().verified_stmt("SELECT 1 AS offset, 2 AS user");
+    // Variable names are not identifiers
+    pg().verified_stmt("SET user = DEFAULT");
+}
+
 #[test]
 fn parse_array() {
     let expr = pg_and_generic().verified_expr("ARRAY[]");
@@ -367,7 +407,7 @@ fn parse_array() {
     assert_eq!(
         expr,
         Expr::Value(Value::Array(vec![
-            Value::Number("1".into()),
+            number("1"),
             Value::SingleQuotedString("foo".to_owned())
         ]))
     );
@@ -384,7 +424,7 @@ fn parse_array() {
     assert_eq!(
         expr_from_projection(only(&select.projection)),
         &Expr::Value(Value::Array(vec![
-            Value::Number("1".into()),
+            number("1"),
             Value::SingleQuotedString("foo".to_owned())
         ]))
     );
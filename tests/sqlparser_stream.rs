@@ -0,0 +1,218 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![warn(clippy::all)]
+//! Test `StatementStream`, which parses statements one at a time from a
+//! reader.
+
+use std::io::{self, BufRead, BufReader, Read};
+
+use sqlparser::ast::*;
+use sqlparser::dialect::{GenericDialect, PostgreSqlDialect};
+use sqlparser::parser::*;
+
+fn stream(sql: &str) -> Vec<Result<Statement, ParserError>> {
+    StatementStream::new(&GenericDialect {}, sql.as_bytes()).collect()
+}
+
+#[test]
+fn stream_parses_like_parse_sql() {
+    let sql = "SELECT 'a;b' AS \"c;\" FROM t; -- d; e\n\
+               INSERT INTO t\n  VALUES (1);;\n\
+               /* f;\n g */ UPDATE t SET a = 1; DELETE FROM\r\nt; \n\
+               SELECT z";
+    let expected = Parser::parse_sql(&GenericDialect {}, sql.to_string()).unwrap();
+    let actual: Vec<_> = stream(sql).into_iter().map(Result::unwrap).collect();
+    assert_eq!(expected, actual);
+    let spans = |stmts: &[Statement]| stmts.iter().map(|s| s.span()).collect::<Vec<_>>();
+    assert_eq!(spans(&expected), spans(&actual));
+}
+
+#[test]
+fn stream_long_lines() {
+    // Many statements on one line, as in dumps
+    let sql = "INSERT INTO t VALUES (1);".repeat(50_000);
+    let results = stream(&sql);
+    assert_eq!(50_000, results.len());
+    assert!(results.iter().all(Result::is_ok));
+
+    // A statement over many lines that contain `;`
+    let sql = format!("INSERT INTO t VALUES\n{}(0);", "('a;b'),\n".repeat(50_000));
+    let results = stream(&sql);
+    assert_eq!(1, results.len());
+    match &results[0] {
+        Ok(Statement::Insert { source, .. }) => match &source.body {
+            SetExpr::Values(values) => assert_eq!(50_001, values.0.len()),
+            _ => unreachable!(),
+        },
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn stream_empty_input() {
+    assert!(stream("").is_empty());
+    assert!(stream(" ;\n-- comment\n;").is_empty());
+}
+
+#[test]
+fn stream_copy_from_stdin() {
+    let sql = "COPY t (a, b) FROM stdin;\n\
+               1\tfoo\n\
+               2\t\\N\n\
+               \\.\n\
+               SELECT 1;\n\
+               COPY u FROM stdin;\n\
+               3;4\n";
+    let stmts: Vec<_> = StatementStream::new(&PostgreSqlDialect {}, sql.as_bytes())
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(3, stmts.len());
+    match &stmts[0] {
+        Statement::Copy {
+            table_name, values, ..
+        } => {
+            assert_eq!("t", table_name.to_string());
+            assert_eq!(
+                vec![
+                    Some("1".to_string()),
+                    Some("foo".to_string()),
+                    Some("2".to_string()),
+                    None
+                ],
+                *values
+            );
+        }
+        _ => unreachable!(),
+    }
+    assert_eq!("SELECT 1", stmts[1].to_string());
+    // The data ends at the end of the input if there is no `\.`
+    match &stmts[2] {
+        Statement::Copy { values, .. } => assert_eq!(vec![Some("3;4".to_string())], *values),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn stream_continues_after_syntax_error() {
+    let results = stream("SELECT 1;\nSELECT a b c; SELECT 2");
+    assert_eq!(3, results.len());
+    let err = results[1].as_ref().unwrap_err();
+    assert_eq!("Expected end of statement, found: c", err.message);
+    assert_eq!((2, 12), (err.span.start.line, err.span.start.column));
+    assert_eq!(Some("SELECT a b c; SELECT 2"), err.source_line.as_deref());
+    assert_eq!("SELECT 2", results[2].as_ref().unwrap().to_string());
+
+    // A statement that starts in the middle of a line is located in the
+    // whole line
+    let results = stream("SELECT 1; SELECT (");
+    let err = results[1].as_ref().unwrap_err();
+    assert_eq!((1, 19), (err.span.start.line, err.span.start.column));
+    assert_eq!(Some("SELECT 1; SELECT ("), err.source_line.as_deref());
+}
+
+#[test]
+fn stream_stops_after_tokenizer_error() {
+    let results = stream("SELECT 1;\nSELECT \"a;\nb; SELECT 2");
+    assert_eq!(2, results.len());
+    let err = results[1].as_ref().unwrap_err();
+    assert_eq!(ParserErrorKind::Tokenizer, err.kind);
+    assert_eq!(3, err.span.start.line);
+
+    // A quoted identifier may span several lines
+    let results = stream("SELECT \"a;\nb\"; SELECT 2");
+    assert_eq!("SELECT \"a;\nb\"", results[0].as_ref().unwrap().to_string());
+    assert_eq!(2, results.len());
+}
+
+#[test]
+fn stream_multi_line_strings() {
+    // A string may span several lines, and contain `;`
+    let sql = "INSERT INTO t VALUES ('a;\nb'), (N'c;\nd'), (X'e;\nf'); SELECT 1;";
+    let expected = Parser::parse_sql(&GenericDialect {}, sql.to_string()).unwrap();
+    let actual: Vec<_> = stream(sql).into_iter().map(Result::unwrap).collect();
+    assert_eq!(2, actual.len());
+    assert_eq!(expected, actual);
+
+    // A string that is never closed is reported at the end of the input
+    let results = stream(
+        "SELECT 1;
+SELECT 'a;
+b; SELECT 2",
+    );
+    assert_eq!(2, results.len());
+    let err = results[1].as_ref().unwrap_err();
+    assert_eq!(ParserErrorKind::Tokenizer, err.kind);
+    assert_eq!((3, 12), (err.span.start.line, err.span.start.column));
+}
+
+#[test]
+fn stream_read_error() {
+    struct FailingReader;
+
+    impl Read for FailingReader {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::other("disk on fire"))
+        }
+    }
+
+    let reader = io::Cursor::new("SELECT 1;\n").chain(BufReader::new(FailingReader));
+    let results: Vec<_> = StatementStream::new(&GenericDialect {}, reader).collect();
+    assert_eq!(2, results.len());
+    assert!(results[0].is_ok());
+    let err = results[1].as_ref().unwrap_err();
+    assert_eq!(ParserErrorKind::Io, err.kind);
+    assert_eq!("disk on fire", err.message);
+}
+
+#[test]
+fn stream_with_options() {
+    let options = ParserOptions {
+        strict_ansi: true,
+        ..ParserOptions::default()
+    };
+    let results: Vec<_> = StatementStream::new(&GenericDialect {}, "SELECT a::int".as_bytes())
+        .with_options(options)
+        .collect();
+    assert!(results[0].is_err());
+}
+
+#[test]
+fn stream_reads_one_statement_at_a_time() {
+    /// A reader that fails if it is read past `limit` bytes
+    struct Limited<R> {
+        inner: R,
+        read: usize,
+        limit: usize,
+    }
+
+    impl<R: BufRead> Read for Limited<R> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = self.inner.read(buf)?;
+            self.read += n;
+            assert!(self.read <= self.limit, "read too far ahead");
+            Ok(n)
+        }
+    }
+
+    let sql = "SELECT 1;\nSELECT 2;\n";
+    let reader = BufReader::with_capacity(
+        1,
+        Limited {
+            inner: sql.as_bytes(),
+            read: 0,
+            limit: 10,
+        },
+    );
+    let mut stream = StatementStream::new(&GenericDialect {}, reader);
+    assert_eq!("SELECT 1", stream.next().unwrap().unwrap().to_string());
+}